@@ -1,11 +1,19 @@
 #!/usr/bin/env node
 
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
 // 使用标准的 napi-rs 生成的文件
-const { generateProject, listTemplates, getTemplateInfo } = require('../dist/index.js');
+const {
+  generateProject,
+  generateProjectAsync,
+  listTemplates,
+  getTemplateInfo
+} = require('../dist/index.js');
 
 console.log('🦀 Testing napi-rs generated bindings...\n');
 
-try {
+async function main() {
   // 测试 1: 列出模板
   console.log('📋 Testing listTemplates("vue"):');
   const vueTemplates = listTemplates('vue');
@@ -28,8 +36,38 @@ try {
   console.log('Result:', result);
   console.log('');
 
+  // 测试 4: 通过 onPrompt 回答缺少的必填变量
+  console.log('💬 Testing generateProjectAsync with onPrompt:');
+  const workDir = fs.mkdtempSync(path.join(os.tmpdir(), 'napi-prompt-'));
+  const templateDir = path.join(workDir, 'template');
+  fs.mkdirSync(templateDir);
+  fs.writeFileSync(path.join(templateDir, 'template.json'), JSON.stringify({
+    name: 'prompt-demo', version: '1.0.0', description: '', author: '',
+    project_type: 'node-lib', dependencies: [], tags: [],
+    variables: [{ name: 'author', description: '作者', default: null, required: true, var_type: 'string' }]
+  }));
+  fs.writeFileSync(path.join(templateDir, 'README.md'), '{{author}}');
+  const prompts = [];
+  await generateProjectAsync(
+    { name: 'prompted', projectType: 'node-lib', template: templateDir, outputPath: workDir },
+    undefined,
+    async (prompt) => {
+      prompts.push(prompt.name);
+      return 'Ada';
+    }
+  );
+  const readme = fs.readFileSync(path.join(workDir, 'prompted', 'README.md'), 'utf8');
+  if (prompts.join() !== 'author' || readme !== 'Ada') {
+    throw new Error(`onPrompt was not used: prompts=${prompts}, README=${readme}`);
+  }
+  fs.rmSync(workDir, { recursive: true, force: true });
+  console.log('Result:', { prompts, readme });
+  console.log('');
+
   console.log('✅ All napi-rs tests passed!');
-} catch (error) {
+}
+
+main().catch((error) => {
   console.error('❌ Test failed:', error);
   process.exit(1);
-}
\ No newline at end of file
+});
\ No newline at end of file
@@ -0,0 +1,34 @@
+/*
+ * generator 的 C 接口，使用 `cargo build --release --features ffi` 构建动态库。
+ *
+ * 参数与返回值均为 UTF-8 JSON 字符串。函数失败时返回 NULL，
+ * 错误详情（code、message、template、path、cause、violations）由
+ * generator_last_error() 在同一线程上取得。
+ * 所有返回的非 NULL 字符串都必须用 generator_free_string() 释放。
+ *
+ * 在非 Node 宿主中加载时，napi 会在 stderr 提示找不到 Node-API 符号，不影响以下函数。
+ */
+#ifndef GENERATOR_H
+#define GENERATOR_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* 生成项目，options 为 GenerateOptions 的 JSON（如 {"name":"app","project_type":"vue"}），返回生成结果 */
+char *generator_generate(const char *options);
+
+/* 列出项目类型的模板，返回 JSON 字符串数组 */
+char *generator_list_templates(const char *project_type);
+
+/* 取出当前线程最近一次失败的错误详情，没有时返回 NULL */
+char *generator_last_error(void);
+
+/* 释放以上函数返回的字符串，传入 NULL 时什么也不做 */
+void generator_free_string(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GENERATOR_H */
@@ -0,0 +1,228 @@
+//! Android 环境模块
+//! 检测 Android SDK（`ANDROID_HOME`、`ANDROID_SDK_ROOT` 与各平台的默认安装位置），并为生成的项目写入指向 SDK 的 local.properties；JDK 检测沿用 java_cli
+
+use crate::{GeneratorError, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 记录 SDK 位置的文件，按惯例不提交到版本库
+pub const LOCAL_PROPERTIES_FILE: &str = "local.properties";
+
+/// Android Gradle 插件要求的 JDK 版本
+pub const REQUIRED_JAVA_VERSION: &str = "17+";
+
+/// Android SDK 信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AndroidSdk {
+    pub path: PathBuf,
+    /// 已安装的平台 API 级别（`platforms/android-34` 即 34），从低到高
+    pub platforms: Vec<u32>,
+    /// 已安装的 build-tools 版本，按名称排序
+    pub build_tools: Vec<String>,
+    /// SDK 目录的来源
+    pub source: AndroidSdkSource,
+}
+
+/// SDK 目录的发现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AndroidSdkSource {
+    /// 来自 ANDROID_HOME 环境变量
+    AndroidHome,
+    /// 来自已弃用的 ANDROID_SDK_ROOT 环境变量
+    SdkRoot,
+    /// Android Studio 的默认安装位置
+    Default,
+}
+
+impl AndroidSdkSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AndroidSdkSource::AndroidHome => "ANDROID_HOME",
+            AndroidSdkSource::SdkRoot => "ANDROID_SDK_ROOT",
+            AndroidSdkSource::Default => "default location",
+        }
+    }
+}
+
+impl AndroidSdk {
+    /// 读取 `path` 下的 SDK，目录中既没有 `platforms` 也没有 `platform-tools` 时不视为 SDK
+    pub fn at(path: &Path, source: AndroidSdkSource) -> Option<Self> {
+        if !path.join("platforms").is_dir() && !path.join("platform-tools").is_dir() {
+            return None;
+        }
+        let mut platforms: Vec<u32> = entry_names(&path.join("platforms"))
+            .iter()
+            .filter_map(|name| name.strip_prefix("android-")?.parse().ok())
+            .collect();
+        platforms.sort_unstable();
+        Some(Self {
+            path: path.to_path_buf(),
+            platforms,
+            build_tools: entry_names(&path.join("build-tools")),
+            source,
+        })
+    }
+
+    /// 最高的已安装平台
+    pub fn latest_platform(&self) -> Option<u32> {
+        self.platforms.last().copied()
+    }
+}
+
+/// 检测 Android SDK：ANDROID_HOME > ANDROID_SDK_ROOT > 默认安装位置
+pub fn detect_android_sdk() -> Result<AndroidSdk> {
+    for (name, source) in [
+        ("ANDROID_HOME", AndroidSdkSource::AndroidHome),
+        ("ANDROID_SDK_ROOT", AndroidSdkSource::SdkRoot),
+    ] {
+        if let Some(path) = env::var_os(name).filter(|path| !path.is_empty())
+            && let Some(sdk) = AndroidSdk::at(Path::new(&path), source)
+        {
+            return Ok(sdk);
+        }
+    }
+    default_locations()
+        .iter()
+        .find_map(|path| AndroidSdk::at(path, AndroidSdkSource::Default))
+        .ok_or_else(|| {
+            GeneratorError::Configuration(
+                "Android SDK not found. Install it with Android Studio or the command-line tools and set ANDROID_HOME.".to_string(),
+            )
+        })
+}
+
+/// Android Studio 在各平台的默认 SDK 目录
+fn default_locations() -> Vec<PathBuf> {
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    let mut locations = Vec::new();
+    if cfg!(windows) {
+        if let Some(local) = env::var_os("LOCALAPPDATA") {
+            locations.push(PathBuf::from(local).join("Android").join("Sdk"));
+        }
+    } else if cfg!(target_os = "macos") {
+        locations.extend(home.map(|home| home.join("Library/Android/sdk")));
+    } else {
+        locations.extend(home.map(|home| home.join("Android/Sdk")));
+    }
+    locations
+}
+
+/// 在项目目录写入 `sdk.dir`，返回写入的文件（相对项目根目录）
+pub fn write_local_properties(project_dir: &Path, sdk: &AndroidSdk) -> Result<PathBuf> {
+    // properties 格式中 `\` 与 `:` 需要转义（Windows 路径）
+    let dir = sdk
+        .path
+        .to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace(':', "\\:");
+    fs::write(
+        project_dir.join(LOCAL_PROPERTIES_FILE),
+        format!("sdk.dir={}\n", dir),
+    )?;
+    Ok(PathBuf::from(LOCAL_PROPERTIES_FILE))
+}
+
+fn entry_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateOptions;
+    use crate::build_wrapper::bootstrap_with;
+    use crate::command_audit::{CommandPolicy, with_audit};
+    use crate::templates::{generate_project_from_template, get_template_info};
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generate_android_project_with_sdk_and_wrapper() {
+        let sdk = tempdir().unwrap();
+        for dir in [
+            "platforms/android-34",
+            "platforms/android-35",
+            "build-tools/35.0.0",
+        ] {
+            fs::create_dir_all(sdk.path().join(dir)).unwrap();
+        }
+        assert!(AndroidSdk::at(&sdk.path().join("platforms"), AndroidSdkSource::Default).is_none());
+        let detected = AndroidSdk::at(sdk.path(), AndroidSdkSource::AndroidHome).unwrap();
+        assert_eq!(detected.latest_platform(), Some(35));
+        assert_eq!(detected.build_tools, ["35.0.0"]);
+
+        let assets = tempdir().unwrap();
+        fs::create_dir_all(assets.path().join("gradle/gradle/wrapper")).unwrap();
+        fs::write(assets.path().join("gradle/gradlew"), "#!/bin/sh\n").unwrap();
+        fs::write(
+            assets
+                .path()
+                .join("gradle/gradle/wrapper/gradle-wrapper.properties"),
+            "distributionUrl=gradle-8.10-bin.zip\n",
+        )
+        .unwrap();
+
+        // 审计模式下构建工具只记录不执行；本机是否装有 SDK 与 JDK 不影响生成
+        let output = tempdir().unwrap();
+        let (result, commands) = with_audit(CommandPolicy::default(), || {
+            generate_project_from_template(GenerateOptions {
+                name: "notes".to_string(),
+                project_type: "android".to_string(),
+                template: Some("compose".to_string()),
+                output_path: Some(output.path().to_string_lossy().to_string()),
+                variables: Some(HashMap::from([(
+                    "packageName".to_string(),
+                    "com.acme.notes".to_string(),
+                )])),
+                ..Default::default()
+            })
+        });
+        assert!(result.unwrap().success);
+        assert!(get_template_info("android", "compose").is_ok());
+
+        // 源码目录按包名展开
+        let project = output.path().join("notes");
+        let activity =
+            fs::read_to_string(project.join("app/src/main/java/com/acme/notes/MainActivity.kt"))
+                .unwrap();
+        assert!(activity.starts_with("package com.acme.notes\n"));
+        let build = fs::read_to_string(project.join("app/build.gradle.kts")).unwrap();
+        assert!(build.contains("applicationId = \"com.acme.notes\""));
+        assert!(build.contains("minSdk = 24"));
+
+        // Android 项目默认生成 Gradle wrapper，并写入本机 SDK 位置
+        assert!(
+            project.join("gradlew").is_file()
+                || commands
+                    .iter()
+                    .any(|command| command.program.starts_with("gradle"))
+        );
+        assert!(
+            bootstrap_with(&project, Some(assets.path()))
+                .unwrap()
+                .contains(&"gradlew".into())
+        );
+        assert!(project.join("gradlew").is_file());
+        assert_eq!(
+            write_local_properties(&project, &detected).unwrap(),
+            PathBuf::from(LOCAL_PROPERTIES_FILE)
+        );
+        assert_eq!(
+            fs::read_to_string(project.join(LOCAL_PROPERTIES_FILE)).unwrap(),
+            format!("sdk.dir={}\n", sdk.path().display())
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! 答案文件模块
+//! 预先提供交互问题的答案与模板变量，用于 CI 等非交互场景；也可把交互过程中的回答保存下来以便重放
+
+use crate::{GenerateOptions, GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 答案文件内容
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Answers {
+    /// 交互问题的答案，键为问题标识（如 `project_type`、`name`、`template`）
+    #[serde(default)]
+    pub prompts: BTreeMap<String, String>,
+    /// 模板变量
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// 启用的模板特性
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
+}
+
+impl Answers {
+    /// 读取答案文件
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            GeneratorError::FileOperation(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid answers file {}: {}", path.display(), e))
+        })
+    }
+
+    /// 写入答案文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// 获取问题的答案，没有答案时使用 `default`，两者都没有则报错
+    pub fn prompt(&self, key: &str, default: Option<&str>) -> Result<String> {
+        self.prompts
+            .get(key)
+            .cloned()
+            .or_else(|| default.map(str::to_string))
+            .ok_or_else(|| {
+                GeneratorError::Configuration(format!("Answers file has no answer for '{}'", key))
+            })
+    }
+}
+
+/// 用 `answers_file` 补全生成选项：显式传入的变量与特性优先
+pub fn apply_answers_file(mut options: GenerateOptions) -> Result<GenerateOptions> {
+    let Some(path) = options.answers_file.take() else {
+        return Ok(options);
+    };
+    let answers = Answers::load(Path::new(&path))?;
+
+    let mut variables: std::collections::HashMap<String, String> =
+        answers.variables.into_iter().collect();
+    variables.extend(options.variables.take().unwrap_or_default());
+    options.variables = Some(variables);
+
+    if options.template.is_none() {
+        options.template = answers.prompts.get("template").cloned();
+    }
+    if options.features.is_none() {
+        options.features = answers.features;
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_answers_roundtrip_and_apply() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("answers.json");
+
+        let mut answers = Answers::default();
+        answers
+            .prompts
+            .insert("template".to_string(), "basic".to_string());
+        answers
+            .variables
+            .insert("author".to_string(), "ci".to_string());
+        answers
+            .variables
+            .insert("license".to_string(), "MIT".to_string());
+        answers.save(&path).unwrap();
+
+        let options = apply_answers_file(GenerateOptions {
+            name: "app".to_string(),
+            project_type: "node-lib".to_string(),
+            variables: Some([("license".to_string(), "Apache-2.0".to_string())].into()),
+            answers_file: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let variables = options.variables.unwrap();
+        assert_eq!(variables["author"], "ci");
+        assert_eq!(variables["license"], "Apache-2.0");
+        assert_eq!(options.template.as_deref(), Some("basic"));
+
+        let loaded = Answers::load(&path).unwrap();
+        assert_eq!(loaded.prompt("confirm", Some("yes")).unwrap(), "yes");
+        assert!(loaded.prompt("name", None).is_err());
+    }
+}
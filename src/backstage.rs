@@ -0,0 +1,576 @@
+//! Backstage 模板导入模块
+//! 将 Backstage 软件模板（`scaffolder.backstage.io/v1beta3` 的 template.yaml）转换为本工具的模板目录：参数映射为变量，fetch 步骤的骨架复制为模板文件，执行命令的步骤映射为钩子
+
+use crate::computed::Expr;
+use crate::hooks::{Hook, HookKind};
+use crate::pipeline::{RawMatcher, TEMPLATE_METADATA_FILE};
+use crate::template_metadata::{
+    ComputedVariable, TemplateEngine, TemplateMetadata, TemplateVariable, VariableType,
+};
+use crate::{BackstageImportOptions, BackstageImportResult, GeneratorError, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+use walkdir::WalkDir;
+
+/// 支持的 Backstage 模板版本
+pub const API_VERSION: &str = "scaffolder.backstage.io/v1beta3";
+
+/// 未指定路径时在目录中查找的模板文件
+const TEMPLATE_FILE: &str = "template.yaml";
+
+/// `${{ parameters.x }}` 形式的直接引用
+static PARAMETER_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\$\{\{\s*parameters\.(\w+)\s*\}\}$").unwrap());
+/// Nunjucks 的输出与语句标签
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)\{\{.*?\}\}|\{%.*?%\}").unwrap());
+/// 标签中的 `values.`、`parameters.` 前缀，导入后变量直接按名称引用
+static SCOPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(values|parameters)\.").unwrap());
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    api_version: String,
+    kind: String,
+    metadata: DocumentMetadata,
+    spec: Spec,
+}
+
+#[derive(Deserialize)]
+struct DocumentMetadata {
+    name: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Spec {
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    parameters: Option<Parameters>,
+    #[serde(default)]
+    steps: Vec<Step>,
+}
+
+/// 参数可以是单个表单页或多页
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Parameters {
+    Pages(Vec<ParameterPage>),
+    Page(ParameterPage),
+}
+
+#[derive(Deserialize)]
+struct ParameterPage {
+    #[serde(default)]
+    required: Vec<String>,
+    /// 保持声明顺序
+    #[serde(default)]
+    properties: Mapping,
+}
+
+#[derive(Deserialize)]
+struct Property {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    default: Option<Value>,
+    #[serde(rename = "enum", default)]
+    options: Option<Vec<Value>>,
+    #[serde(rename = "ui:widget", default)]
+    widget: Option<String>,
+    #[serde(rename = "ui:field", default)]
+    field: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Step {
+    #[serde(default)]
+    id: Option<String>,
+    action: String,
+    #[serde(default)]
+    input: Mapping,
+}
+
+impl Step {
+    fn label(&self) -> String {
+        match &self.id {
+            Some(id) => format!("steps.{} ({})", id, self.action),
+            None => format!("steps ({})", self.action),
+        }
+    }
+
+    fn input(&self, key: &str) -> Option<&Value> {
+        self.input.get(key)
+    }
+
+    fn input_str(&self, key: &str) -> Option<&str> {
+        self.input(key).and_then(Value::as_str)
+    }
+
+    fn input_list(&self, key: &str) -> Vec<String> {
+        match self.input(key) {
+            Some(Value::Sequence(items)) => items.iter().filter_map(scalar).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 导入 Backstage 模板并写入新的模板目录
+///
+/// 无法转换的参数、values 与步骤（如 `publish:github`、`catalog:register`）不会中断导入，
+/// 而是列在结果的 `skipped` 中。
+pub fn import_template(options: &BackstageImportOptions) -> Result<BackstageImportResult> {
+    let mut source = PathBuf::from(&options.source);
+    if source.is_dir() {
+        source = source.join(TEMPLATE_FILE);
+    }
+    let content = fs::read_to_string(&source).map_err(|e| {
+        GeneratorError::FileOperation(format!("Failed to read {}: {}", source.display(), e))
+    })?;
+    let document: Document = serde_yaml::from_str(&content).map_err(|e| {
+        GeneratorError::Configuration(format!(
+            "Invalid Backstage template {}: {}",
+            source.display(),
+            e
+        ))
+    })?;
+    if document.api_version != API_VERSION || document.kind != "Template" {
+        return Err(GeneratorError::Configuration(format!(
+            "Unsupported Backstage template {}: expected apiVersion {} and kind Template, found {} {}",
+            source.display(),
+            API_VERSION,
+            document.api_version,
+            document.kind
+        )));
+    }
+    let base = source.parent().unwrap_or(Path::new("."));
+
+    let project_type = options
+        .project_type
+        .clone()
+        .or(document.spec.kind.clone())
+        .ok_or_else(|| {
+            GeneratorError::Configuration(format!(
+                "{} has no spec.type; specify the project type",
+                source.display()
+            ))
+        })?;
+    let template_dir = match &options.path {
+        Some(path) => PathBuf::from(path),
+        None => Path::new("templates")
+            .join(&project_type)
+            .join(&document.metadata.name),
+    };
+    if template_dir.exists() && fs::read_dir(&template_dir)?.next().is_some() {
+        return Err(GeneratorError::FileOperation(format!(
+            "Template directory already exists and is not empty: {}",
+            template_dir.display()
+        )));
+    }
+
+    let mut skipped = Vec::new();
+    let variables = import_parameters(document.spec.parameters, &mut skipped)?;
+    let mut metadata = TemplateMetadata {
+        name: document.metadata.name.clone(),
+        version: "0.1.0".to_string(),
+        description: document
+            .metadata
+            .description
+            .or(document.metadata.title)
+            .unwrap_or_default(),
+        author: document.spec.owner.unwrap_or_default(),
+        project_type,
+        variables,
+        tags: document.metadata.tags,
+        // Nunjucks 语法与 Tera 基本兼容
+        engine: TemplateEngine::Tera,
+        ..Default::default()
+    };
+
+    let mut files = Vec::new();
+    for step in &document.spec.steps {
+        match step.action.as_str() {
+            "fetch:template" | "fetch:plain" => {
+                let Some(url) = step.input_str("url") else {
+                    skipped.push(step.label());
+                    continue;
+                };
+                if url.contains("://") {
+                    // 远程骨架需先下载到本地再导入
+                    skipped.push(format!("{}: {}", step.label(), url));
+                    continue;
+                }
+                let target = step.input_str("targetPath").unwrap_or(".");
+                let render = step.action == "fetch:template";
+                let mut raw = step.input_list("copyWithoutTemplating");
+                raw.extend(step.input_list("copyWithoutRender"));
+                let raw = RawMatcher::new(&raw)?;
+                copy_skeleton(
+                    &base.join(url),
+                    &template_dir,
+                    target,
+                    |relative| render && !raw.is_raw(relative),
+                    &mut metadata,
+                    &mut files,
+                )?;
+                if render {
+                    import_values(step, &mut metadata.computed, &mut skipped);
+                }
+            }
+            _ => match step.input_str("command") {
+                Some(command) => metadata.hooks.push(command_hook(step, command)),
+                None => skipped.push(step.label()),
+            },
+        }
+    }
+
+    let mut metadata_json = serde_json::to_string_pretty(&metadata)?;
+    metadata_json.push('\n');
+    fs::create_dir_all(&template_dir)?;
+    fs::write(template_dir.join(TEMPLATE_METADATA_FILE), metadata_json)?;
+    files.push(TEMPLATE_METADATA_FILE.to_string());
+    files.sort();
+
+    Ok(BackstageImportResult {
+        path: template_dir.to_string_lossy().to_string(),
+        files,
+        skipped,
+    })
+}
+
+/// 表单页中的属性映射为变量；`name` 即项目名称，由生成器提供
+fn import_parameters(
+    parameters: Option<Parameters>,
+    skipped: &mut Vec<String>,
+) -> Result<Vec<TemplateVariable>> {
+    let pages = match parameters {
+        None => Vec::new(),
+        Some(Parameters::Pages(pages)) => pages,
+        Some(Parameters::Page(page)) => vec![page],
+    };
+    let mut variables = Vec::new();
+    for page in pages {
+        for (key, value) in page.properties {
+            let Some(name) = key.as_str().map(str::to_string) else {
+                continue;
+            };
+            if name == "name" {
+                continue;
+            }
+            let property: Property = serde_yaml::from_value(value).map_err(|e| {
+                GeneratorError::Configuration(format!("Invalid parameter '{}': {}", name, e))
+            })?;
+            let var_type = match (&property.options, property.kind.as_deref()) {
+                (Some(options), _) => VariableType::Choice {
+                    options: options.iter().filter_map(scalar).collect(),
+                },
+                (None, Some("boolean")) => VariableType::Boolean,
+                (None, Some("number" | "integer")) => VariableType::Number,
+                (None, None | Some("string")) => VariableType::String,
+                (None, Some(_)) => {
+                    // object、array 等结构化参数没有对应的变量类型
+                    skipped.push(format!("parameters.{}", name));
+                    continue;
+                }
+            };
+            variables.push(TemplateVariable {
+                description: property.description.or(property.title).unwrap_or_default(),
+                default: property.default.as_ref().and_then(scalar),
+                required: page.required.contains(&name),
+                var_type,
+                sensitive: property.field.as_deref() == Some("Secret")
+                    || property.widget.as_deref() == Some("password"),
+                name,
+            });
+        }
+    }
+    Ok(variables)
+}
+
+/// `fetch:template` 的 `values` 中与参数同名的引用无需转换，改名的引用与字面量成为派生变量
+fn import_values(step: &Step, computed: &mut Vec<ComputedVariable>, skipped: &mut Vec<String>) {
+    let Some(Value::Mapping(values)) = step.input("values") else {
+        return;
+    };
+    for (key, value) in values {
+        let Some(name) = key.as_str() else {
+            continue;
+        };
+        let expr = match value {
+            Value::String(value) => match PARAMETER_REF.captures(value) {
+                Some(captures) if &captures[1] == name => continue,
+                Some(captures) => Some(captures[1].to_string()),
+                None if value.contains("${{") => None,
+                None => quote(value),
+            },
+            value => scalar(value).and_then(|value| quote(&value)),
+        };
+        match expr.filter(|expr| Expr::parse(expr).is_ok()) {
+            Some(expr) => computed.push(ComputedVariable {
+                name: name.to_string(),
+                expr,
+            }),
+            None => skipped.push(format!("values.{}", name)),
+        }
+    }
+}
+
+/// 输入含 `command`（可选 `arguments`、`workingDirectory`）的步骤，如社区的命令执行 action
+fn command_hook(step: &Step, command: &str) -> Hook {
+    let args: Vec<String> = step
+        .input_list("arguments")
+        .iter()
+        .map(|arg| convert_expressions(arg))
+        .collect();
+    Hook {
+        // 有参数时直接执行程序，避免参数经过 shell 转义
+        kind: Some(if args.is_empty() {
+            HookKind::Shell
+        } else {
+            HookKind::Exec
+        }),
+        run: convert_expressions(command),
+        args,
+        cwd: step.input_str("workingDirectory").map(str::to_string),
+        ..Default::default()
+    }
+}
+
+/// 复制骨架目录；`render` 为 false 的文件原样复制并记入 `raw`
+fn copy_skeleton(
+    skeleton: &Path,
+    template_dir: &Path,
+    target: &str,
+    render: impl Fn(&Path) -> bool,
+    metadata: &mut TemplateMetadata,
+    files: &mut Vec<String>,
+) -> Result<()> {
+    if !skeleton.is_dir() {
+        return Err(GeneratorError::FileOperation(format!(
+            "Skeleton directory not found: {}",
+            skeleton.display()
+        )));
+    }
+    let target: PathBuf = Path::new(target)
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .collect();
+    for entry in WalkDir::new(skeleton).sort_by_file_name() {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(skeleton).unwrap_or(entry.path());
+        // 路径中的表达式在生成时渲染
+        let output =
+            convert_expressions(&target.join(relative).to_string_lossy().replace('\\', "/"));
+        let destination = template_dir.join(&output);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = fs::read(entry.path())?;
+        match String::from_utf8(content) {
+            Ok(text) if render(relative) => fs::write(&destination, convert_expressions(&text))?,
+            Ok(text) => {
+                fs::write(&destination, text)?;
+                metadata.raw.push(glob_escape(&output));
+            }
+            // 二进制文件不做渲染
+            Err(e) => fs::write(&destination, e.into_bytes())?,
+        }
+        files.push(output);
+    }
+    Ok(())
+}
+
+/// `${{ values.name }}` → `{{ name }}`，`{% if values.x %}` → `{% if x %}`
+fn convert_expressions(content: &str) -> String {
+    let content = content.replace("${{", "{{");
+    TAG.replace_all(&content, |captures: &regex::Captures| {
+        SCOPE.replace_all(&captures[0], "").into_owned()
+    })
+    .into_owned()
+}
+
+/// 标量 YAML 值的字符串形式
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// 派生变量表达式中的字符串字面量，不支持转义，同时包含两种引号时无法表示
+fn quote(value: &str) -> Option<String> {
+    if !value.contains('"') {
+        Some(format!("\"{}\"", value))
+    } else if !value.contains('\'') {
+        Some(format!("'{}'", value))
+    } else {
+        None
+    }
+}
+
+/// 按字面匹配的 glob
+fn glob_escape(path: &str) -> String {
+    path.chars()
+        .map(|c| match c {
+            '*' | '?' | '[' | ']' | '{' | '}' => format!("[{}]", c),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const TEMPLATE: &str = r#"
+apiVersion: scaffolder.backstage.io/v1beta3
+kind: Template
+metadata:
+  name: node-service
+  title: Node.js service
+  tags: [node]
+spec:
+  owner: platform-team
+  type: service
+  parameters:
+    - title: Service
+      required: [name, port]
+      properties:
+        name:
+          type: string
+        port:
+          type: number
+          default: 8080
+        db:
+          type: string
+          enum: [postgres, mysql]
+        token:
+          type: string
+          ui:field: Secret
+    - title: Repository
+      properties:
+        repoUrl:
+          type: string
+        labels:
+          type: object
+  steps:
+    - id: fetch
+      action: fetch:template
+      input:
+        url: ./skeleton
+        copyWithoutTemplating: ["assets/**"]
+        values:
+          name: ${{ parameters.name }}
+          port: ${{ parameters.port }}
+          database: ${{ parameters.db }}
+          license: MIT
+          owner: ${{ parameters.owner | upper }}
+    - id: install
+      action: roadiehq:utils:execute
+      input:
+        command: npm
+        arguments: [install]
+    - id: publish
+      action: publish:github
+      input:
+        repoUrl: ${{ parameters.repoUrl }}
+"#;
+
+    #[test]
+    fn test_import_backstage_template() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("backstage");
+        fs::create_dir_all(source.join("skeleton/assets")).unwrap();
+        fs::write(source.join(TEMPLATE_FILE), TEMPLATE).unwrap();
+        fs::write(
+            source.join("skeleton/${{ values.name }}.md"),
+            "# ${{ values.name }}\n{% if values.database %}db{% endif %}\n",
+        )
+        .unwrap();
+        fs::write(source.join("skeleton/assets/logo.txt"), "${{ raw }}").unwrap();
+
+        let target = dir.path().join("imported");
+        let result = import_template(&BackstageImportOptions {
+            source: source.to_string_lossy().to_string(),
+            project_type: Some("node-lib".to_string()),
+            path: Some(target.to_string_lossy().to_string()),
+        })
+        .unwrap();
+        assert_eq!(
+            result.files,
+            ["assets/logo.txt", TEMPLATE_METADATA_FILE, "{{ name }}.md"]
+        );
+        assert_eq!(
+            result.skipped,
+            [
+                "parameters.labels",
+                "values.owner",
+                "steps.publish (publish:github)"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("{{ name }}.md")).unwrap(),
+            "# {{ name }}\n{% if database %}db{% endif %}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("assets/logo.txt")).unwrap(),
+            "${{ raw }}"
+        );
+
+        let metadata: TemplateMetadata =
+            serde_json::from_str(&fs::read_to_string(target.join(TEMPLATE_METADATA_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(metadata.project_type, "node-lib");
+        assert_eq!(metadata.author, "platform-team");
+        assert_eq!(metadata.engine, TemplateEngine::Tera);
+        let names: Vec<_> = metadata.variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["port", "db", "token", "repoUrl"]);
+        assert!(metadata.variables[0].required);
+        assert_eq!(metadata.variables[0].default.as_deref(), Some("8080"));
+        assert!(matches!(
+            metadata.variables[1].var_type,
+            VariableType::Choice { .. }
+        ));
+        assert!(metadata.variables[2].sensitive);
+        let computed: Vec<_> = metadata
+            .computed
+            .iter()
+            .map(|c| (c.name.as_str(), c.expr.as_str()))
+            .collect();
+        assert_eq!(computed, [("database", "db"), ("license", "\"MIT\"")]);
+        assert_eq!(metadata.raw, ["assets/logo.txt"]);
+        assert_eq!(metadata.hooks[0].kind, Some(HookKind::Exec));
+        assert_eq!(metadata.hooks[0].args, ["install"]);
+
+        // 已导入的目录不会被覆盖
+        assert!(
+            import_template(&BackstageImportOptions {
+                source: source.join(TEMPLATE_FILE).to_string_lossy().to_string(),
+                project_type: None,
+                path: Some(target.to_string_lossy().to_string()),
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! 批量生成模块
+//! 一次生成多个相关项目（前端、后端、基础设施），支持共享变量以及失败即停 / 出错继续两种模式
+
+use crate::templates::generate_project_from_template;
+use crate::{BatchOptions, GenerateOptions, GenerateResult, GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// 批量生成清单文件内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchManifest {
+    #[serde(flatten)]
+    pub options: BatchOptions,
+    /// 要生成的项目
+    pub projects: Vec<GenerateOptions>,
+}
+
+impl BatchManifest {
+    /// 读取 JSON 清单文件
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            GeneratorError::FileOperation(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid manifest {}: {}", path.display(), e))
+        })
+    }
+}
+
+/// 依次生成多个项目
+///
+/// 默认遇到失败立即返回错误（已生成的项目保留）；`continue_on_error` 为真时
+/// 失败项目以 `success: false` 记录在结果中并继续生成后续项目。
+pub fn generate_projects(
+    projects: Vec<GenerateOptions>,
+    options: &BatchOptions,
+) -> Result<Vec<GenerateResult>> {
+    let continue_on_error = options.continue_on_error.unwrap_or(false);
+    let mut results = Vec::with_capacity(projects.len());
+
+    for project in projects {
+        let name = project.name.clone();
+        match generate_project_from_template(with_shared_options(project, options)) {
+            Ok(result) => results.push(result),
+            // 取消时不再继续后续项目
+            Err(e) if e.is_cancelled() => return Err(e),
+            Err(e) if continue_on_error => results.push(GenerateResult {
+                success: false,
+                files: vec![],
+                message: Some(format!("项目 {} 生成失败: {}", name, e)),
+                stats: None,
+                template_version: None,
+                warnings: vec![],
+                action: None,
+            }),
+            Err(e) => {
+                return Err(GeneratorError::TemplateProcessing(format!(
+                    "Project {} failed after {} succeeded: {}",
+                    name,
+                    results.len(),
+                    e
+                )));
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// 按清单文件批量生成
+pub fn generate_from_manifest(path: &Path) -> Result<Vec<GenerateResult>> {
+    let manifest = BatchManifest::load(path)?;
+    generate_projects(manifest.projects, &manifest.options)
+}
+
+/// 合并共享变量与默认输出路径，项目自身的设置优先
+fn with_shared_options(mut project: GenerateOptions, options: &BatchOptions) -> GenerateOptions {
+    if let Some(shared) = &options.variables {
+        let mut variables = shared.clone();
+        variables.extend(project.variables.take().unwrap_or_default());
+        project.variables = Some(variables);
+    }
+    if project.output_path.is_none() {
+        project.output_path = options.output_path.clone();
+    }
+    if project.frozen.is_none() {
+        project.frozen = options.frozen;
+    }
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generate_from_manifest_continue_on_error() {
+        let output_dir = tempdir().unwrap();
+        let manifest_path = output_dir.path().join("projects.json");
+        fs::write(
+            &manifest_path,
+            serde_json::json!({
+                "output_path": output_dir.path(),
+                "variables": {"org": "acme"},
+                "continue_on_error": true,
+                "projects": [
+                    {"name": "sdk", "project_type": "node-lib"},
+                    {"name": "broken", "project_type": "unknown"},
+                    {"name": "api", "project_type": "rust", "template": "bin"}
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let results = generate_from_manifest(&manifest_path).unwrap();
+        assert_eq!(
+            results.iter().map(|r| r.success).collect::<Vec<_>>(),
+            vec![true, false, true]
+        );
+        assert!(output_dir.path().join("sdk/package.json").exists());
+        assert!(output_dir.path().join("api/Cargo.toml").exists());
+    }
+
+    #[test]
+    fn test_generate_projects_fail_fast() {
+        let output_dir = tempdir().unwrap();
+        let project = |name: &str, project_type: &str| GenerateOptions {
+            name: name.to_string(),
+            project_type: project_type.to_string(),
+            variables: Some(HashMap::from([("org".to_string(), "own".to_string())])),
+            ..Default::default()
+        };
+        let options = BatchOptions {
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        let result = generate_projects(
+            vec![project("broken", "unknown"), project("sdk", "node-lib")],
+            &options,
+        );
+        assert!(result.is_err());
+        assert!(!output_dir.path().join("sdk").exists());
+    }
+}
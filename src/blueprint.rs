@@ -0,0 +1,361 @@
+//! 蓝图模块
+//! 读取声明式的 `generator.yaml` 蓝图（多个项目、模板、变量与后置步骤），幂等地生成或更新各项目
+
+use crate::command_audit::{self, CommandPolicy, describe};
+use crate::hooks::Hook;
+use crate::lockfile::{LOCK_FILE_NAME, ProjectLock};
+use crate::report::{HookRun, millis};
+use crate::template_registry::TemplateRegistryConfig;
+use crate::templates::{generate_with_report, resolve_timeouts};
+use crate::update::update_project;
+use crate::{
+    AuditedCommand, BlueprintProjectResult, GenerateOptions, GeneratorError, PhaseTimeouts, Result,
+    UpdateOptions, workspace,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 蓝图文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blueprint {
+    /// 蓝图名称
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 项目输出根目录，相对路径基于蓝图文件所在目录
+    #[serde(default)]
+    pub output_path: Option<String>,
+    /// 所有项目共享的变量
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// 项目列表
+    pub projects: Vec<BlueprintProject>,
+}
+
+/// 蓝图中的单个项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlueprintProject {
+    #[serde(flatten)]
+    pub options: GenerateOptions,
+    /// 生成后执行的命令
+    #[serde(default)]
+    pub post: Vec<PostStep>,
+}
+
+/// 后置步骤
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostStep {
+    /// 要执行的命令，默认经系统 shell 执行，可指定钩子类型与平台覆盖
+    #[serde(flatten)]
+    pub hook: Hook,
+    /// 每次 apply 都执行；默认只在项目首次创建时执行
+    #[serde(default)]
+    pub always: bool,
+}
+
+impl Blueprint {
+    /// 读取蓝图文件（YAML，兼容 JSON）
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            GeneratorError::FileOperation(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid blueprint {}: {}", path.display(), e))
+        })
+    }
+}
+
+/// 执行蓝图文件
+pub fn apply_blueprint(path: &Path) -> Result<Vec<BlueprintProjectResult>> {
+    let blueprint = Blueprint::load(path)?;
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    apply(&blueprint, base_dir)
+}
+
+/// 列出执行蓝图时会运行的外部命令：待创建项目的生成命令，以及按创建 / `always` 规则会执行的后置步骤
+pub fn audit_blueprint(path: &Path) -> Result<Vec<AuditedCommand>> {
+    let blueprint = Blueprint::load(path)?;
+    let output_root = output_root(&blueprint, path.parent().unwrap_or(Path::new(".")));
+    let policy = CommandPolicy::user()?;
+
+    let mut commands = Vec::new();
+    for project in &blueprint.projects {
+        let (options, project_dir) = project_options(&blueprint, project, &output_root);
+        let created = !project_dir.join(LOCK_FILE_NAME).is_file();
+        if created {
+            commands.extend(command_audit::audit_generation(options)?);
+        }
+        for step in project.post.iter().filter(|step| created || step.always) {
+            let mut command = describe(&step.hook.command(&project_dir));
+            command.denied_by = policy.denied_by(&command)?;
+            commands.push(command);
+        }
+    }
+    Ok(commands)
+}
+
+fn output_root(blueprint: &Blueprint, base_dir: &Path) -> PathBuf {
+    match &blueprint.output_path {
+        Some(output_path) => base_dir.join(workspace::expand_home(output_path)),
+        None => base_dir.to_path_buf(),
+    }
+}
+
+/// 合并蓝图共享变量并确定输出位置，返回生成选项与项目目录
+fn project_options(
+    blueprint: &Blueprint,
+    project: &BlueprintProject,
+    output_root: &Path,
+) -> (GenerateOptions, PathBuf) {
+    let mut options = project.options.clone();
+    let mut variables = blueprint.variables.clone();
+    variables.extend(options.variables.take().unwrap_or_default());
+    options.variables = Some(variables);
+
+    let output_dir = match &options.output_path {
+        Some(output_path) => output_root.join(output_path),
+        None => output_root.to_path_buf(),
+    };
+    options.output_path = Some(output_dir.to_string_lossy().to_string());
+    let project_dir = output_dir.join(&options.name);
+    (options, project_dir)
+}
+
+/// 依次处理蓝图中的项目：不存在则生成，已由生成器创建则按锁文件更新
+///
+/// 某个项目失败后停止处理后续项目，重新执行蓝图会从失败处继续。
+pub fn apply(blueprint: &Blueprint, base_dir: &Path) -> Result<Vec<BlueprintProjectResult>> {
+    let output_root = output_root(blueprint, base_dir);
+
+    let mut results = Vec::with_capacity(blueprint.projects.len());
+    for project in &blueprint.projects {
+        let result = apply_project(blueprint, project, &output_root).unwrap_or_else(|e| {
+            BlueprintProjectResult {
+                name: project.options.name.clone(),
+                action: "failed".to_string(),
+                files: vec![],
+                message: Some(e.to_string()),
+            }
+        });
+        let failed = result.action == "failed";
+        results.push(result);
+        if failed {
+            break;
+        }
+    }
+
+    Ok(results)
+}
+
+fn apply_project(
+    blueprint: &Blueprint,
+    project: &BlueprintProject,
+    output_root: &Path,
+) -> Result<BlueprintProjectResult> {
+    let (mut options, project_dir) = project_options(blueprint, project, output_root);
+    let config = TemplateRegistryConfig::user()?;
+    let variables = options.variables.clone().unwrap_or_default();
+
+    // 生成报告在后置步骤执行后写入，记录各步骤的退出码；相对路径同样基于蓝图目录
+    let report_path = options.report.take().map(|path| output_root.join(path));
+    let (action, files, message, mut report) = if project_dir.join(LOCK_FILE_NAME).is_file() {
+        let (action, files, message) = update_existing(&options, &project_dir, variables)?;
+        (action, files, message, None)
+    } else {
+        let (result, report) = generate_with_report(options.clone(), &config)?;
+        ("created", result.files, result.message, report)
+    };
+
+    // 后置步骤失败（含超时）时仍报告已生成的文件，便于排查与重新执行
+    let created = action == "created";
+    let hook_timeout = PhaseTimeouts::duration(resolve_timeouts(&options, &config).hook_secs);
+    let mut failure = None;
+    for step in project.post.iter().filter(|step| created || step.always) {
+        let started = Instant::now();
+        let hook = &step.hook;
+        let output = hook.output(&mut hook.command(&project_dir), hook_timeout);
+        let exit_code = output.as_ref().ok().and_then(|output| output.status.code());
+        let result = output.and_then(|output| hook.check(&output));
+        if let Some(report) = &mut report {
+            report.hooks.push(HookRun {
+                command: hook.run.clone(),
+                exit_code,
+                duration_ms: millis(started.elapsed()),
+                error: result.as_ref().err().map(ToString::to_string),
+            });
+        }
+        if let Err(e) = result {
+            failure = Some(e);
+            break;
+        }
+    }
+
+    if let (Some(path), Some(report)) = (&report_path, &report) {
+        report.write(path)?;
+    }
+
+    if let Some(e) = failure {
+        if e.is_cancelled() {
+            return Err(e);
+        }
+        return Ok(BlueprintProjectResult {
+            name: options.name,
+            action: "failed".to_string(),
+            files,
+            message: Some(format!("Project {} but {}", action, e)),
+        });
+    }
+
+    Ok(BlueprintProjectResult {
+        name: options.name,
+        action: action.to_string(),
+        files,
+        message,
+    })
+}
+
+/// 已存在的项目按锁文件做三方合并更新
+fn update_existing(
+    options: &GenerateOptions,
+    project_dir: &Path,
+    variables: HashMap<String, String>,
+) -> Result<(&'static str, Vec<String>, Option<String>)> {
+    let lock = ProjectLock::read(project_dir)?;
+    let template = options.template.clone().unwrap_or("basic".to_string());
+    if lock.project_type != options.project_type || lock.template != template {
+        return Err(GeneratorError::Conflict(format!(
+            "{} was generated from {}:{}, blueprint asks for {}:{}",
+            project_dir.display(),
+            lock.project_type,
+            lock.template,
+            options.project_type,
+            template
+        )));
+    }
+
+    let result = update_project(UpdateOptions {
+        project_path: project_dir.to_string_lossy().to_string(),
+        variables: Some(variables),
+        dry_run: None,
+    })?;
+
+    let action = if !result.conflicts.is_empty() {
+        "conflict"
+    } else if result.updated.is_empty() && result.created.is_empty() && result.deleted.is_empty() {
+        "unchanged"
+    } else {
+        "updated"
+    };
+    let files = result
+        .updated
+        .into_iter()
+        .chain(result.created)
+        .chain(result.deleted)
+        .collect();
+    Ok((action, files, result.message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_blueprint_is_idempotent() {
+        let dir = tempdir().unwrap();
+        let blueprint_path = dir.path().join("generator.yaml");
+        fs::write(
+            &blueprint_path,
+            r#"
+name: golden-path
+output_path: services
+variables:
+  org: acme
+projects:
+  - name: sdk
+    project_type: node-lib
+    post:
+      - run: echo created >> post.log
+      - run: echo applied >> post.log
+        always: true
+"#,
+        )
+        .unwrap();
+
+        let first = apply_blueprint(&blueprint_path).unwrap();
+        assert_eq!(first[0].action, "created");
+
+        let second = apply_blueprint(&blueprint_path).unwrap();
+        assert_eq!(second[0].action, "unchanged");
+
+        let log = fs::read_to_string(dir.path().join("services/sdk/post.log")).unwrap();
+        assert_eq!(log, "created\napplied\napplied\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_audit_blueprint_lists_post_steps_without_running() {
+        let dir = tempdir().unwrap();
+        let blueprint_path = dir.path().join("generator.yaml");
+        fs::write(
+            &blueprint_path,
+            r#"
+projects:
+  - name: web
+    project_type: node-lib
+    post:
+      - run: npm install
+        cwd: packages
+"#,
+        )
+        .unwrap();
+
+        let commands = audit_blueprint(&blueprint_path).unwrap();
+        let post = commands.last().unwrap();
+        assert_eq!(post.program, "sh");
+        assert_eq!(post.args, ["-c", "npm install"]);
+        assert_eq!(
+            Path::new(&post.cwd),
+            dir.path().join("web").join("packages")
+        );
+        assert!(!dir.path().join("web").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_post_step_timeout_reports_generated_files() {
+        let dir = tempdir().unwrap();
+        let blueprint_path = dir.path().join("generator.yaml");
+        fs::write(
+            &blueprint_path,
+            r#"
+projects:
+  - name: slow
+    project_type: node-lib
+    report: slow-report.json
+    timeouts:
+      hook_secs: 1
+    post:
+      - run: "true"
+      - run: sleep 10
+"#,
+        )
+        .unwrap();
+
+        let results = apply_blueprint(&blueprint_path).unwrap();
+        assert_eq!(results[0].action, "failed");
+        assert!(!results[0].files.is_empty());
+        assert!(results[0].message.as_deref().unwrap().contains("timed out"));
+
+        // 报告记录了每个后置步骤的退出码
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("slow-report.json")).unwrap())
+                .unwrap();
+        assert_eq!(report["hooks"][0]["exit_code"], 0);
+        assert!(report["hooks"][1]["exit_code"].is_null());
+        assert!(report["phases"].as_array().unwrap().len() >= 3);
+    }
+}
@@ -0,0 +1,237 @@
+//! 构建工具 wrapper 模块
+//! 为生成的 Java 项目补齐 Maven / Gradle wrapper（mvnw、gradlew），优先使用随包资源，否则调用本机构建工具生成
+
+use crate::utils::{copy_dir_recursive, get_exe_dir};
+use crate::{GeneratorError, Result, cancel};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+/// 指定 wrapper 资源目录的环境变量，目录下按 `maven/`、`gradle/` 存放 wrapper 文件
+pub const WRAPPER_ASSETS_ENV: &str = "GENERATOR_WRAPPER_ASSETS";
+
+/// Java 构建工具
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildTool {
+    #[napi(value = "maven")]
+    Maven,
+    #[napi(value = "gradle")]
+    Gradle,
+}
+
+impl BuildTool {
+    /// 根据项目中的构建文件识别构建工具
+    pub fn detect(project_dir: &Path) -> Option<Self> {
+        if project_dir.join("pom.xml").is_file() {
+            return Some(BuildTool::Maven);
+        }
+        [
+            "build.gradle",
+            "build.gradle.kts",
+            "settings.gradle",
+            "settings.gradle.kts",
+        ]
+        .iter()
+        .any(|file| project_dir.join(file).is_file())
+        .then_some(BuildTool::Gradle)
+    }
+
+    /// 名称，即 template.json 的 `build_tools` 与 `buildTool` 变量中的写法
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildTool::Maven => "maven",
+            BuildTool::Gradle => "gradle",
+        }
+    }
+
+    /// wrapper 生成的文件（相对项目根目录）
+    fn wrapper_files(&self) -> &'static [&'static str] {
+        match self {
+            BuildTool::Maven => &[
+                "mvnw",
+                "mvnw.cmd",
+                ".mvn/wrapper/maven-wrapper.properties",
+                ".mvn/wrapper/maven-wrapper.jar",
+            ],
+            BuildTool::Gradle => &[
+                "gradlew",
+                "gradlew.bat",
+                "gradle/wrapper/gradle-wrapper.properties",
+                "gradle/wrapper/gradle-wrapper.jar",
+            ],
+        }
+    }
+
+    fn script(&self) -> &'static str {
+        match self {
+            BuildTool::Maven => "mvnw",
+            BuildTool::Gradle => "gradlew",
+        }
+    }
+
+    /// 调用本机构建工具生成 wrapper 的命令
+    fn command(&self) -> Command {
+        let (program, args): (&str, &[&str]) = match self {
+            BuildTool::Maven => (
+                if cfg!(windows) { "mvn.cmd" } else { "mvn" },
+                &["-N", "-q", "wrapper:wrapper"],
+            ),
+            BuildTool::Gradle => (
+                if cfg!(windows) {
+                    "gradle.bat"
+                } else {
+                    "gradle"
+                },
+                &["wrapper", "-q"],
+            ),
+        };
+        let mut command = Command::new(program);
+        command.args(args);
+        command
+    }
+}
+
+impl FromStr for BuildTool {
+    type Err = GeneratorError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "maven" => Ok(BuildTool::Maven),
+            "gradle" => Ok(BuildTool::Gradle),
+            _ => Err(GeneratorError::Configuration(format!(
+                "Unknown build tool '{}': expected maven or gradle",
+                name
+            ))),
+        }
+    }
+}
+
+/// 生成或刷新项目的构建工具 wrapper，返回写入的文件（相对项目根目录）
+pub fn bootstrap(project_dir: &Path) -> Result<Vec<PathBuf>> {
+    let assets_root = match std::env::var_os(WRAPPER_ASSETS_ENV) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => get_exe_dir()
+            .ok()
+            .map(|dir| dir.join("assets").join("wrappers")),
+    };
+    bootstrap_with(project_dir, assets_root.as_deref())
+}
+
+/// 同 [`bootstrap`]，wrapper 资源取自 `assets_root`
+pub(crate) fn bootstrap_with(
+    project_dir: &Path,
+    assets_root: Option<&Path>,
+) -> Result<Vec<PathBuf>> {
+    let tool = BuildTool::detect(project_dir).ok_or_else(|| {
+        GeneratorError::Configuration(format!(
+            "No pom.xml or build.gradle found in {}",
+            project_dir.display()
+        ))
+    })?;
+
+    let bundled = assets_root
+        .map(|root| root.join(tool.name()))
+        .filter(|dir| dir.is_dir());
+    match bundled {
+        Some(assets) => copy_dir_recursive(&assets, project_dir)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?,
+        None => run_build_tool(tool, project_dir)?,
+    }
+
+    make_executable(&project_dir.join(tool.script()))?;
+
+    Ok(tool
+        .wrapper_files()
+        .iter()
+        .map(PathBuf::from)
+        .filter(|file| project_dir.join(file).exists())
+        .collect())
+}
+
+fn run_build_tool(tool: BuildTool, project_dir: &Path) -> Result<()> {
+    let mut command = tool.command();
+    let output = cancel::output(command.current_dir(project_dir)).map_err(|e| match e {
+        GeneratorError::Io(e) => GeneratorError::ExternalCommand(format!(
+            "No bundled {} wrapper and failed to run {:?}: {}",
+            tool.name(),
+            command.get_program(),
+            e
+        )),
+        e => e,
+    })?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(GeneratorError::ExternalCommand(format!(
+            "{:?} wrapper generation failed: {}",
+            command.get_program(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(script: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if script.is_file() {
+        let mut permissions = fs::metadata(script)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o755);
+        fs::set_permissions(script, permissions)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_script: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_bootstrap_copies_bundled_maven_wrapper() {
+        let assets = tempdir().unwrap();
+        let maven = assets.path().join("maven");
+        fs::create_dir_all(maven.join(".mvn/wrapper")).unwrap();
+        fs::write(maven.join("mvnw"), "#!/bin/sh\n").unwrap();
+        fs::write(
+            maven.join(".mvn/wrapper/maven-wrapper.properties"),
+            "distributionUrl=https://repo.maven.apache.org/maven2/",
+        )
+        .unwrap();
+
+        let project = tempdir().unwrap();
+        assert_eq!(BuildTool::detect(project.path()), None);
+        fs::write(project.path().join("pom.xml"), "<project/>").unwrap();
+        assert_eq!(BuildTool::detect(project.path()), Some(BuildTool::Maven));
+
+        let files = bootstrap_with(project.path(), Some(assets.path())).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("mvnw"),
+                PathBuf::from(".mvn/wrapper/maven-wrapper.properties")
+            ]
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(project.path().join("mvnw"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_ne!(mode & 0o111, 0);
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! 模板包模块
+//! 将模板目录打包为自包含的 `.gentpl` 文件（tar.zst，内含清单与逐文件 sha256），并校验后安装到本地注册表
+
+use crate::extract::Extractor;
+use crate::pipeline::{self, TEMPLATE_METADATA_FILE};
+use crate::template_lint;
+use crate::{GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 模板包扩展名
+pub const BUNDLE_EXTENSION: &str = "gentpl";
+/// 包内清单文件名，位于归档根目录
+pub const BUNDLE_MANIFEST_FILE: &str = "gentpl.json";
+/// 包内存放模板文件的目录
+const BUNDLE_TEMPLATE_DIR: &str = "template";
+/// 当前包格式版本
+const BUNDLE_FORMAT: u32 = 1;
+/// zstd 压缩级别
+const COMPRESSION_LEVEL: i32 = 19;
+/// 打包时跳过的目录
+const SKIPPED_DIRS: &[&str] = &[".git"];
+
+/// 模板包清单
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    /// 包格式版本
+    pub format: u32,
+    pub name: String,
+    pub version: String,
+    pub project_type: String,
+    #[serde(default)]
+    pub description: String,
+    /// 打包所用的生成器版本
+    pub generator_version: String,
+    /// 打包时间（RFC 3339）
+    pub created_at: String,
+    /// 模板内相对路径（`/` 分隔）到 sha256 的映射
+    pub files: BTreeMap<String, String>,
+}
+
+/// 打包模板目录，返回写入的包路径
+///
+/// `output` 为目录或未指定（当前目录）时写入 `<name>-<version>.gentpl`。
+/// 模板存在 error 级诊断时拒绝打包。
+pub fn pack_template(template_dir: &Path, output: Option<&Path>) -> Result<PathBuf> {
+    ensure_valid(template_dir)?;
+    let metadata = pipeline::load_template_metadata(template_dir)?;
+
+    let files = collect_files(template_dir)?;
+    let mut manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        name: metadata.name.clone(),
+        version: metadata.version.clone(),
+        project_type: metadata.project_type.clone(),
+        description: metadata.description.clone(),
+        generator_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        files: BTreeMap::new(),
+    };
+    for (relative, path) in &files {
+        manifest.files.insert(
+            relative.clone(),
+            hex::encode(Sha256::digest(fs::read(path)?)),
+        );
+    }
+
+    let file_name = format!(
+        "{}-{}.{}",
+        metadata.name, metadata.version, BUNDLE_EXTENSION
+    );
+    let bundle_path = match output {
+        Some(path) if path.is_dir() => path.join(file_name),
+        Some(path) => path.to_path_buf(),
+        None => PathBuf::from(file_name),
+    };
+    if let Some(parent) = bundle_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    // 清单放在最前，便于只读取开头即可查看包信息
+    let encoder = zstd::Encoder::new(fs::File::create(&bundle_path)?, COMPRESSION_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, BUNDLE_MANIFEST_FILE, manifest_json.as_slice())?;
+    for (relative, path) in &files {
+        builder.append_path_with_name(path, format!("{}/{}", BUNDLE_TEMPLATE_DIR, relative))?;
+    }
+    builder.into_inner()?.finish()?;
+
+    Ok(bundle_path)
+}
+
+/// 模板存在 error 级诊断时报错
+pub(crate) fn ensure_valid(template_dir: &Path) -> Result<()> {
+    let errors: Vec<String> = template_lint::validate_template(template_dir)?
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == "error")
+        .map(|diagnostic| diagnostic.message)
+        .collect();
+    if !errors.is_empty() {
+        return Err(GeneratorError::TemplateProcessing(format!(
+            "Template has errors and cannot be packed: {}",
+            errors.join("; ")
+        )));
+    }
+    Ok(())
+}
+
+/// 读取包内清单，不校验文件
+pub fn read_bundle_manifest(bundle: &Path) -> Result<BundleManifest> {
+    let decoder = zstd::Decoder::new(fs::File::open(bundle)?)?;
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.path()?.as_ref() == Path::new(BUNDLE_MANIFEST_FILE) {
+            return serde_json::from_reader(entry).map_err(|e| invalid_bundle(bundle, e));
+        }
+    }
+    Err(invalid_bundle(bundle, "manifest not found"))
+}
+
+/// 校验模板包并安装到 `registry_dir/<project_type>/<name>`，返回安装目录
+///
+/// 任一文件缺失、多余或哈希不符时报错且不写入注册表；目标已存在时需 `force` 覆盖。
+pub fn install_template(bundle: &Path, registry_dir: &Path, force: bool) -> Result<PathBuf> {
+    let archive = fs::read(bundle)?;
+    fs::create_dir_all(registry_dir)?;
+    // 解压到注册表内的临时目录，校验通过后可直接改名
+    let staging = tempfile::Builder::new()
+        .prefix(".gentpl-")
+        .tempdir_in(registry_dir)?;
+    Extractor::default().extract(&archive, staging.path())?;
+
+    let manifest: BundleManifest = serde_json::from_str(
+        &fs::read_to_string(staging.path().join(BUNDLE_MANIFEST_FILE))
+            .map_err(|_| invalid_bundle(bundle, "manifest not found"))?,
+    )
+    .map_err(|e| invalid_bundle(bundle, e))?;
+    if manifest.format > BUNDLE_FORMAT {
+        return Err(invalid_bundle(
+            bundle,
+            format!(
+                "format {} requires a newer generator (supported: {})",
+                manifest.format, BUNDLE_FORMAT
+            ),
+        ));
+    }
+    for part in [&manifest.name, &manifest.project_type] {
+        if part.is_empty() || part.contains(['/', '\\']) || part == "." || part == ".." {
+            return Err(invalid_bundle(
+                bundle,
+                format!("'{}' is not a valid directory name", part),
+            ));
+        }
+    }
+
+    let template_dir = staging.path().join(BUNDLE_TEMPLATE_DIR);
+    verify_files(bundle, &manifest, &template_dir)?;
+    let metadata = pipeline::load_template_metadata(&template_dir)?;
+    if metadata.name != manifest.name || metadata.project_type != manifest.project_type {
+        return Err(invalid_bundle(
+            bundle,
+            "manifest does not match template.json",
+        ));
+    }
+
+    let target = registry_dir
+        .join(&manifest.project_type)
+        .join(&manifest.name);
+    if target.exists() {
+        if !force {
+            return Err(GeneratorError::Conflict(format!(
+                "Template already installed: {} (use force to overwrite)",
+                target.display()
+            )));
+        }
+        fs::remove_dir_all(&target)?;
+    }
+    fs::create_dir_all(registry_dir.join(&manifest.project_type))?;
+    fs::rename(&template_dir, &target)?;
+    Ok(target)
+}
+
+/// 按相对路径排序收集模板文件
+fn collect_files(template_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(template_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        });
+    for entry in walker {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(template_dir)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        files.push((bundle_path(relative), entry.into_path()));
+    }
+    if !files
+        .iter()
+        .any(|(relative, _)| relative == TEMPLATE_METADATA_FILE)
+    {
+        return Err(GeneratorError::TemplateNotFound(format!(
+            "{} not found in {}",
+            TEMPLATE_METADATA_FILE,
+            template_dir.display()
+        )));
+    }
+    Ok(files)
+}
+
+/// 解压后的文件集合必须与清单完全一致
+fn verify_files(bundle: &Path, manifest: &BundleManifest, template_dir: &Path) -> Result<()> {
+    let mut remaining = manifest.files.clone();
+    for entry in WalkDir::new(template_dir) {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = bundle_path(
+            entry
+                .path()
+                .strip_prefix(template_dir)
+                .map_err(|e| GeneratorError::FileOperation(e.to_string()))?,
+        );
+        let Some(expected) = remaining.remove(&relative) else {
+            return Err(invalid_bundle(
+                bundle,
+                format!("'{}' is not listed in the manifest", relative),
+            ));
+        };
+        let actual = hex::encode(Sha256::digest(fs::read(entry.path())?));
+        if actual != expected {
+            return Err(invalid_bundle(
+                bundle,
+                format!("checksum mismatch for '{}'", relative),
+            ));
+        }
+    }
+    if let Some(missing) = remaining.keys().next() {
+        return Err(invalid_bundle(
+            bundle,
+            format!("'{}' is missing from the bundle", missing),
+        ));
+    }
+    Ok(())
+}
+
+/// 以 `/` 分隔的相对路径，保证清单跨平台一致
+fn bundle_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn invalid_bundle(bundle: &Path, reason: impl std::fmt::Display) -> GeneratorError {
+    GeneratorError::TemplateProcessing(format!(
+        "Invalid template bundle {}: {}",
+        bundle.display(),
+        reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_pack_and_install_template_bundle() {
+        let dir = tempdir().unwrap();
+        let template = dir.path().join("source");
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::create_dir_all(template.join(".git")).unwrap();
+        fs::write(
+            template.join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.2.0", "description": "", "author": "", "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        fs::write(template.join("src/{{name}}.txt"), "hello {{name}}").unwrap();
+        fs::write(template.join(".git/HEAD"), "ref").unwrap();
+
+        let bundle = pack_template(&template, Some(dir.path())).unwrap();
+        assert_eq!(bundle, dir.path().join("demo-1.2.0.gentpl"));
+        let manifest = read_bundle_manifest(&bundle).unwrap();
+        assert_eq!(manifest.project_type, "vue");
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["src/{{name}}.txt", "template.json"]
+        );
+
+        let registry = dir.path().join("templates");
+        let installed = install_template(&bundle, &registry, false).unwrap();
+        assert_eq!(installed, registry.join("vue/demo"));
+        assert_eq!(
+            fs::read_to_string(installed.join("src/{{name}}.txt")).unwrap(),
+            "hello {{name}}"
+        );
+        assert!(!installed.join(".git").exists());
+        assert!(install_template(&bundle, &registry, false).is_err());
+        assert!(install_template(&bundle, &registry, true).is_ok());
+
+        // 篡改文件内容后重新封包，哈希校验失败且不写入注册表
+        let unpacked = dir.path().join("unpacked");
+        Extractor::default()
+            .extract(&fs::read(&bundle).unwrap(), &unpacked)
+            .unwrap();
+        fs::write(unpacked.join("template/src/{{name}}.txt"), "tampered").unwrap();
+        let mut builder = tar::Builder::new(zstd::Encoder::new(Vec::new(), 0).unwrap());
+        builder.append_dir_all(".", &unpacked).unwrap();
+        let tampered = dir.path().join("tampered.gentpl");
+        fs::write(&tampered, builder.into_inner().unwrap().finish().unwrap()).unwrap();
+
+        let other = dir.path().join("other");
+        let err = install_template(&tampered, &other, false).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+        assert!(!other.join("vue/demo").exists());
+    }
+}
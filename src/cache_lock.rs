@@ -0,0 +1,65 @@
+//! 缓存目录锁模块
+//! 多个生成器进程（如 CI 矩阵任务）共享缓存目录时，用跨进程的咨询锁串行化对缓存的写入
+
+use crate::{GeneratorError, Result};
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+
+/// 锁文件名（位于被锁定的目录中）
+pub const LOCK_FILE: &str = ".cache.lock";
+
+/// 目录的独占锁，drop 时释放；进程异常退出时由操作系统释放
+#[derive(Debug)]
+pub struct CacheLock {
+    _file: File,
+}
+
+impl CacheLock {
+    /// 获取 `dir` 的独占锁，其他进程持有时阻塞等待
+    pub fn exclusive(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        file.lock().map_err(|e| {
+            GeneratorError::FileOperation(format!("Cannot lock {}: {}", path.display(), e))
+        })?;
+        Ok(Self { _file: file })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Barrier};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_exclusive_lock_serializes_holders() {
+        let dir = tempdir().unwrap();
+        let lock = CacheLock::exclusive(dir.path()).unwrap();
+        assert!(dir.path().join(LOCK_FILE).is_file());
+
+        // 另一个文件句柄的锁等价于另一个进程，必须等到第一个锁释放
+        let released = Arc::new(AtomicBool::new(false));
+        let barrier = Arc::new(Barrier::new(2));
+        let waiter = {
+            let (path, released, barrier) =
+                (dir.path().to_path_buf(), released.clone(), barrier.clone());
+            std::thread::spawn(move || {
+                barrier.wait();
+                let _lock = CacheLock::exclusive(&path).unwrap();
+                released.load(Ordering::SeqCst)
+            })
+        };
+        barrier.wait();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        released.store(true, Ordering::SeqCst);
+        drop(lock);
+        assert!(waiter.join().unwrap());
+    }
+}
@@ -0,0 +1,232 @@
+//! 取消令牌模块
+//! napi 层将 AbortSignal、CLI 将 Ctrl-C 绑定到令牌；渲染、下载与子进程在检查点发现取消后中止，已生成的部分由暂存目录回滚
+
+use crate::{GeneratorError, Result, command_audit};
+use std::cell::RefCell;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 轮询子进程与取消状态的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 取消令牌，克隆后共享同一状态
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 请求取消
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 已取消时返回 [`GeneratorError::Cancelled`]
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(GeneratorError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 等待取消（异步代码中与其他 future 一起 select）
+    pub async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// 进程级令牌，CLI 将 Ctrl-C 绑定到它
+pub fn global() -> &'static CancellationToken {
+    static GLOBAL: OnceLock<CancellationToken> = OnceLock::new();
+    GLOBAL.get_or_init(CancellationToken::new)
+}
+
+/// 当前线程生效的令牌：[`with_token`] 设置的令牌，否则为进程级令牌
+pub fn current() -> CancellationToken {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| global().clone())
+}
+
+/// 检查当前令牌是否已取消
+pub fn check() -> Result<()> {
+    current().check()
+}
+
+/// 在令牌作用域内执行 `f`
+pub fn with_token<R>(token: &CancellationToken, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(token.clone())));
+    let result = f();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// 类似 [`Command::output`]，但在当前令牌被取消时终止子进程
+pub fn output(command: &mut Command) -> Result<Output> {
+    output_with_timeout(command, None)
+}
+
+/// 同 [`output`]，超过 `timeout` 时终止子进程并返回 [`GeneratorError::Timeout`]，
+/// 错误信息中附带已收到的部分输出
+pub fn output_with_timeout(command: &mut Command, timeout: Option<Duration>) -> Result<Output> {
+    let token = current();
+    token.check()?;
+    if let Some(output) = command_audit::intercept(command)? {
+        return Ok(output);
+    }
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // 在独立线程中读取输出，避免管道写满导致子进程阻塞
+    let (stdout, stdout_reader) = read_pipe(child.stdout.take());
+    let (stderr, stderr_reader) = read_pipe(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if token.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(GeneratorError::Cancelled);
+        }
+        if let Some(limit) = timeout.filter(|limit| started.elapsed() >= *limit) {
+            let _ = child.kill();
+            let _ = child.wait();
+            // 子进程派生的进程可能仍持有管道，不等待读取线程，只取已收到的输出
+            return Err(GeneratorError::Timeout(format!(
+                "{:?} exceeded {}s{}",
+                command.get_program(),
+                limit.as_secs(),
+                partial_output(&snapshot(&stdout), &snapshot(&stderr))
+            )));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let _ = stdout_reader.join();
+    let _ = stderr_reader.join();
+    Ok(Output {
+        status,
+        stdout: snapshot(&stdout),
+        stderr: snapshot(&stderr),
+    })
+}
+
+/// 超时错误中附带的部分输出（各取末尾若干字节）
+pub(crate) fn partial_output(stdout: &[u8], stderr: &[u8]) -> String {
+    const TAIL: usize = 2000;
+    let tail = |bytes: &[u8]| {
+        let text = String::from_utf8_lossy(&bytes[bytes.len().saturating_sub(TAIL)..]);
+        text.trim().to_string()
+    };
+
+    let mut message = String::new();
+    for (label, text) in [("stdout", tail(stdout)), ("stderr", tail(stderr))] {
+        if !text.is_empty() {
+            message.push_str(&format!("; partial {}: {}", label, text));
+        }
+    }
+    message
+}
+
+type PipeBuffer = Arc<Mutex<Vec<u8>>>;
+
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> (PipeBuffer, thread::JoinHandle<()>) {
+    let buffer = PipeBuffer::default();
+    let shared = buffer.clone();
+    let reader = thread::spawn(move || {
+        let Some(mut pipe) = pipe else {
+            return;
+        };
+        let mut chunk = [0u8; 8 * 1024];
+        while let Ok(read) = pipe.read(&mut chunk) {
+            if read == 0 {
+                break;
+            }
+            shared.lock().unwrap().extend_from_slice(&chunk[..read]);
+        }
+    });
+    (buffer, reader)
+}
+
+fn snapshot(buffer: &PipeBuffer) -> Vec<u8> {
+    buffer.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_token_scopes_cancellation() {
+        let token = CancellationToken::new();
+        with_token(&token, || {
+            assert!(check().is_ok());
+            token.cancel();
+            assert!(matches!(check(), Err(GeneratorError::Cancelled)));
+        });
+        // 作用域外恢复为进程级令牌
+        assert!(check().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_kills_child_on_cancel() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let result = with_token(&token, || output(Command::new("sleep").arg("10")));
+        assert!(matches!(result, Err(GeneratorError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_timeout_reports_partial_output() {
+        let started = std::time::Instant::now();
+        let result = output_with_timeout(
+            Command::new("sh").args(["-c", "echo started; sleep 10"]),
+            Some(Duration::from_millis(300)),
+        );
+        match result {
+            Err(GeneratorError::Timeout(message)) => {
+                assert!(message.contains("partial stdout: started"), "{}", message);
+            }
+            other => panic!(
+                "expected timeout, got {:?}",
+                other.map(|output| output.status)
+            ),
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,239 @@
+//! 模板变更日志模块
+//! 读取模板源中的 CHANGELOG.md，没有时按 git 版本标签汇总提交记录，供更新项目时展示两个版本之间的变化
+
+use crate::template_registry::TemplateRegistryConfig;
+use crate::template_version::parse_version;
+use crate::templates::resolve_template_dir;
+use crate::{ChangelogEntry, GeneratorError, Result};
+use git2::{Oid, Repository};
+use semver::{Version, VersionReq};
+use std::fs;
+use std::path::Path;
+
+/// 模板自身的变更日志，在忽略规则中声明后不会被复制到输出目录
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// 读取模板的变更日志，按版本从新到旧排列
+///
+/// `range` 为 semver 版本要求（如 `">1.2.0, <=2.0.0"`），只返回满足要求的版本。
+pub fn template_changelog(
+    project_type: &str,
+    template: &str,
+    range: Option<&str>,
+) -> Result<Vec<ChangelogEntry>> {
+    let range = range
+        .map(|range| {
+            VersionReq::parse(range).map_err(|e| {
+                GeneratorError::Configuration(format!("Invalid version range '{}': {}", range, e))
+            })
+        })
+        .transpose()?;
+    let template_dir =
+        resolve_template_dir(&TemplateRegistryConfig::user()?, project_type, template)?;
+    let entries = read_changelog(&template_dir)?;
+    Ok(entries
+        .into_iter()
+        .filter(|(version, _)| range.as_ref().is_none_or(|range| range.matches(version)))
+        .map(|(_, entry)| entry)
+        .collect())
+}
+
+/// 版本 `from` 之后直到 `to`（含）的变更；版本号无法解析或读取失败时返回空列表
+pub fn changes_between(template_dir: &Path, from: &str, to: &str) -> Vec<ChangelogEntry> {
+    let (Some(from), Some(to)) = (parse_version(from), parse_version(to)) else {
+        return Vec::new();
+    };
+    read_changelog(template_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(version, _)| *version > from && *version <= to)
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+/// 模板目录中的 CHANGELOG.md，没有时取 git 历史
+pub fn read_changelog(template_dir: &Path) -> Result<Vec<(Version, ChangelogEntry)>> {
+    let path = template_dir.join(CHANGELOG_FILE);
+    let mut entries = if path.is_file() {
+        parse_changelog(&fs::read_to_string(&path)?)
+    } else {
+        git_changelog(template_dir).map_err(|e| {
+            GeneratorError::FileOperation(format!(
+                "Cannot read git history of {}: {}",
+                template_dir.display(),
+                e
+            ))
+        })?
+    };
+    entries.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(entries)
+}
+
+/// 解析 Keep a Changelog 风格的 `## [1.2.0] - 2024-05-01` 标题，无法识别版本号的小节（如 Unreleased）被跳过
+pub fn parse_changelog(content: &str) -> Vec<(Version, ChangelogEntry)> {
+    let mut entries = Vec::new();
+    let mut current: Option<(Version, ChangelogEntry)> = None;
+    let mut notes = Vec::new();
+
+    let mut flush = |current: Option<(Version, ChangelogEntry)>, notes: &mut Vec<&str>| {
+        if let Some((version, mut entry)) = current {
+            entry.notes = notes.join("\n").trim().to_string();
+            entries.push((version, entry));
+        }
+        notes.clear();
+    };
+
+    for line in content.lines() {
+        let Some(heading) = line.strip_prefix("## ") else {
+            notes.push(line);
+            continue;
+        };
+        flush(current.take(), &mut notes);
+
+        let (title, date) = match heading.split_once(" - ") {
+            Some((title, date)) => (title, Some(date.trim().to_string())),
+            None => (heading, None),
+        };
+        let title = title.trim().trim_start_matches('[').trim_end_matches(']');
+        current = parse_version(title).map(|version| {
+            let entry = ChangelogEntry {
+                version: version.to_string(),
+                date,
+                notes: String::new(),
+            };
+            (version, entry)
+        });
+    }
+    flush(current, &mut notes);
+    entries
+}
+
+/// 以版本标签划分的提交记录：每个版本列出上一个标签之后修改了模板目录的提交标题
+fn git_changelog(
+    template_dir: &Path,
+) -> std::result::Result<Vec<(Version, ChangelogEntry)>, git2::Error> {
+    let repo = Repository::discover(template_dir)?;
+    let prefix = match (repo.workdir(), template_dir.canonicalize()) {
+        (Some(workdir), Ok(dir)) => workdir
+            .canonicalize()
+            .ok()
+            .and_then(|workdir| dir.strip_prefix(workdir).ok().map(Path::to_path_buf))
+            .unwrap_or_default(),
+        _ => Default::default(),
+    };
+
+    let mut tags: Vec<(Version, Oid)> = Vec::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        let Some(version) = parse_version(name) else {
+            continue;
+        };
+        let commit = repo.revparse_single(name)?.peel_to_commit()?;
+        tags.push((version, commit.id()));
+    }
+    tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let touches = |commit: &git2::Commit| -> std::result::Result<bool, git2::Error> {
+        if prefix.as_os_str().is_empty() {
+            return Ok(true);
+        }
+        let entry = |commit: &git2::Commit| -> std::result::Result<Option<Oid>, git2::Error> {
+            Ok(commit
+                .tree()?
+                .get_path(&prefix)
+                .ok()
+                .map(|entry| entry.id()))
+        };
+        let ours = entry(commit)?;
+        match commit.parents().next() {
+            Some(parent) => Ok(entry(&parent)? != ours),
+            None => Ok(ours.is_some()),
+        }
+    };
+
+    let mut entries = Vec::new();
+    for (index, (version, oid)) in tags.iter().enumerate() {
+        let mut walk = repo.revwalk()?;
+        walk.push(*oid)?;
+        if let Some((_, previous)) = index.checked_sub(1).and_then(|i| tags.get(i)) {
+            walk.hide(*previous)?;
+        }
+        let mut notes = Vec::new();
+        for commit in walk {
+            let commit = repo.find_commit(commit?)?;
+            if touches(&commit)? {
+                notes.push(format!("- {}", commit.summary().unwrap_or_default()));
+            }
+        }
+        let time = repo.find_commit(*oid)?.time();
+        let date = chrono::DateTime::from_timestamp(time.seconds(), 0)
+            .map(|date| date.format("%Y-%m-%d").to_string());
+        entries.push((
+            version.clone(),
+            ChangelogEntry {
+                version: version.to_string(),
+                date,
+                notes: notes.join("\n"),
+            },
+        ));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_changelog_from_file_and_git_tags() {
+        let entries = parse_changelog(
+            "# Changelog\n\n## [Unreleased]\n- wip\n\n## [2.0.0] - 2024-05-01\n### Changed\n\
+             - Vite 5\n\n## 1.1.0\n- Add ESLint\n",
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].1.version, "2.0.0");
+        assert_eq!(entries[0].1.date.as_deref(), Some("2024-05-01"));
+        assert_eq!(entries[0].1.notes, "### Changed\n- Vite 5");
+        assert_eq!(entries[1].1.notes, "- Add ESLint");
+
+        // 没有 CHANGELOG.md 时按标签汇总修改了模板目录的提交
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let template = dir.path().join("vue/basic");
+        fs::create_dir_all(&template).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit = |file: &str, message: &str, tag: Option<&str>| {
+            fs::write(dir.path().join(file), message).unwrap();
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+            let parents: Vec<_> = parent.iter().collect();
+            let oid = repo
+                .commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    message,
+                    &tree,
+                    &parents,
+                )
+                .unwrap();
+            if let Some(tag) = tag {
+                let object = repo.find_object(oid, None).unwrap();
+                repo.tag_lightweight(tag, &object, false).unwrap();
+            }
+        };
+        commit("vue/basic/a.txt", "Initial template", Some("v1.0.0"));
+        commit("README.md", "Unrelated docs", None);
+        commit("vue/basic/b.txt", "Add router", Some("v1.1.0"));
+
+        let entries = read_changelog(&template).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].1.version, "1.1.0");
+        assert_eq!(entries[0].1.notes, "- Add router");
+        assert_eq!(entries[1].1.notes, "- Initial template");
+    }
+}
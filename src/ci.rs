@@ -0,0 +1,307 @@
+//! CI 脚手架模块
+//! 内置的 `ci:github`、`ci:gitlab` 特性：按项目的构建工具生成 lint、构建与测试任务，模板可通过 `ci_jobs` 追加或替换任务
+
+use crate::pipeline::RenderReport;
+use crate::template_registry::TemplateMetadata;
+use crate::toolchain::{self, DEFAULT_JAVA_VERSION, DEFAULT_NODE_VERSION, Toolchain};
+use crate::{GeneratorError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// CI 特性名称前缀，如 `ci:github`
+pub const CI_FEATURE_PREFIX: &str = "ci:";
+
+/// 支持的 CI 平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    GitHub,
+    GitLab,
+}
+
+impl CiProvider {
+    pub const ALL: [CiProvider; 2] = [CiProvider::GitHub, CiProvider::GitLab];
+
+    /// 解析 `ci:<provider>` 特性名
+    pub fn from_feature(feature: &str) -> Option<Self> {
+        match feature.strip_prefix(CI_FEATURE_PREFIX)? {
+            "github" => Some(CiProvider::GitHub),
+            "gitlab" => Some(CiProvider::GitLab),
+            _ => None,
+        }
+    }
+
+    pub fn feature_name(self) -> String {
+        match self {
+            CiProvider::GitHub => format!("{}github", CI_FEATURE_PREFIX),
+            CiProvider::GitLab => format!("{}gitlab", CI_FEATURE_PREFIX),
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            CiProvider::GitHub => "GitHub Actions 持续集成：lint、构建与测试（内置）",
+            CiProvider::GitLab => "GitLab CI 持续集成：lint、构建与测试（内置）",
+        }
+    }
+
+    /// 生成的配置文件（相对项目目录）
+    fn config_file(self) -> &'static str {
+        match self {
+            CiProvider::GitHub => ".github/workflows/ci.yml",
+            CiProvider::GitLab => ".gitlab-ci.yml",
+        }
+    }
+}
+
+/// CI 中的一个任务
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Job {
+    name: String,
+    run: Vec<String>,
+}
+
+impl Job {
+    fn new(name: &str, run: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            run,
+        }
+    }
+}
+
+/// 项目类型是否支持内置的 CI 特性
+pub fn supports(project_type: &str) -> bool {
+    matches!(
+        project_type,
+        "java" | "vue" | "react" | "node-lib" | "monorepo" | "rust"
+    )
+}
+
+/// 在生成的项目中写入 CI 配置
+pub fn scaffold(
+    project_dir: &Path,
+    provider: CiProvider,
+    metadata: &TemplateMetadata,
+    variables: &HashMap<String, String>,
+) -> Result<RenderReport> {
+    let toolchain = Toolchain::detect(project_dir, &metadata.project_type).ok_or_else(|| {
+        GeneratorError::Configuration(format!(
+            "Feature '{}' is not available for project type '{}'",
+            provider.feature_name(),
+            metadata.project_type
+        ))
+    })?;
+    let variable = |name: &str, default: &'static str| {
+        variables
+            .get(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| default.to_string())
+    };
+    let java = variable("javaVersion", DEFAULT_JAVA_VERSION);
+    let node = variable("nodeVersion", DEFAULT_NODE_VERSION);
+
+    // 模板声明的同名任务替换内置任务，其余追加在后
+    let mut jobs = default_jobs(&toolchain, project_dir);
+    for extra in &metadata.ci_jobs {
+        let job = Job {
+            name: extra.name.clone(),
+            run: extra.run.clone(),
+        };
+        match jobs.iter_mut().find(|existing| existing.name == job.name) {
+            Some(existing) => *existing = job,
+            None => jobs.push(job),
+        }
+    }
+
+    let content = match provider {
+        CiProvider::GitHub => github_workflow(&toolchain, &jobs, &java, &node),
+        CiProvider::GitLab => gitlab_pipeline(&toolchain, &jobs, &java, &node),
+    };
+    let path = project_dir.join(provider.config_file());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, &content)?;
+
+    Ok(RenderReport {
+        files: vec![PathBuf::from(provider.config_file())],
+        bytes_written: content.len() as u64,
+        ..Default::default()
+    })
+}
+
+/// 按构建工具生成的 lint、构建与测试任务
+fn default_jobs(toolchain: &Toolchain, project_dir: &Path) -> Vec<Job> {
+    match toolchain {
+        Toolchain::Node { manager } => {
+            let scripts = toolchain::node_scripts(project_dir, &["lint", "build", "test"])
+                .unwrap_or_else(|| vec!["build"]);
+            scripts
+                .into_iter()
+                .map(|script| {
+                    Job::new(
+                        script,
+                        vec![
+                            format!("{} install", manager),
+                            format!("{} run {}", manager, script),
+                        ],
+                    )
+                })
+                .collect()
+        }
+        Toolchain::Maven { command } => vec![
+            Job::new("build", vec![format!("{} -B package -DskipTests", command)]),
+            Job::new("test", vec![format!("{} -B test", command)]),
+        ],
+        Toolchain::Gradle { command } => vec![
+            Job::new("build", vec![format!("{} build -x test", command)]),
+            Job::new("test", vec![format!("{} test", command)]),
+        ],
+        Toolchain::Cargo => vec![
+            Job::new(
+                "lint",
+                vec![
+                    "cargo fmt --check".to_string(),
+                    "cargo clippy --all-targets -- -D warnings".to_string(),
+                ],
+            ),
+            Job::new("build", vec!["cargo build".to_string()]),
+            Job::new("test", vec!["cargo test".to_string()]),
+        ],
+    }
+}
+
+fn github_workflow(toolchain: &Toolchain, jobs: &[Job], java: &str, node: &str) -> String {
+    let setup = match toolchain {
+        Toolchain::Node { manager } => {
+            let mut setup = String::new();
+            if *manager == "pnpm" {
+                setup.push_str("      - uses: pnpm/action-setup@v4\n        with:\n          version: 9\n");
+            }
+            // 新项目还没有锁文件，不启用依赖缓存
+            setup.push_str(&format!(
+                "      - uses: actions/setup-node@v4\n        with:\n          node-version: {node}\n"
+            ));
+            setup
+        }
+        Toolchain::Maven { .. } | Toolchain::Gradle { .. } => {
+            let cache = match toolchain {
+                Toolchain::Maven { .. } => "maven",
+                _ => "gradle",
+            };
+            format!(
+                "      - uses: actions/setup-java@v4\n        with:\n          distribution: temurin\n          java-version: {java}\n          cache: {cache}\n"
+            )
+        }
+        Toolchain::Cargo => "      - uses: dtolnay/rust-toolchain@stable\n        with:\n          components: clippy, rustfmt\n".to_string(),
+    };
+
+    let mut workflow =
+        "name: CI\n\non:\n  push:\n    branches: [main]\n  pull_request:\n\njobs:\n".to_string();
+    for job in jobs {
+        workflow.push_str(&format!(
+            "  {}:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n{}",
+            job.name, setup
+        ));
+        for command in &job.run {
+            workflow.push_str(&format!("      - run: {}\n", yaml_scalar(command)));
+        }
+    }
+    workflow
+}
+
+fn gitlab_pipeline(toolchain: &Toolchain, jobs: &[Job], java: &str, node: &str) -> String {
+    let image = match toolchain {
+        Toolchain::Node { .. } => format!("node:{}", node),
+        Toolchain::Maven { .. } => format!("maven:3.9-eclipse-temurin-{}", java),
+        Toolchain::Gradle { .. } => format!("gradle:8-jdk{}", java),
+        Toolchain::Cargo => "rust:1".to_string(),
+    };
+
+    let mut pipeline = format!("image: {}\n\nstages:\n", image);
+    for job in jobs {
+        pipeline.push_str(&format!("  - {}\n", job.name));
+    }
+    for job in jobs {
+        pipeline.push_str(&format!("\n{}:\n  stage: {}\n", job.name, job.name));
+        if *toolchain == (Toolchain::Node { manager: "pnpm" }) {
+            pipeline.push_str("  before_script:\n    - corepack enable\n");
+        } else if *toolchain == Toolchain::Cargo && job.name == "lint" {
+            pipeline.push_str("  before_script:\n    - rustup component add clippy rustfmt\n");
+        }
+        pipeline.push_str("  script:\n");
+        for command in &job.run {
+            pipeline.push_str(&format!("    - {}\n", yaml_scalar(command)));
+        }
+    }
+    pipeline
+}
+
+/// 必要时给命令加上双引号，避免被 YAML 解析为映射或注释
+fn yaml_scalar(value: &str) -> String {
+    let needs_quotes = value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`',
+        ]);
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_registry::CiJob;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scaffold_ci() {
+        let project = tempdir().unwrap();
+        fs::write(
+            project.path().join("package.json"),
+            r#"{"scripts": {"build": "vite build", "lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        let metadata = TemplateMetadata {
+            project_type: "vue".to_string(),
+            ci_jobs: vec![CiJob {
+                name: "e2e".to_string(),
+                run: vec!["npm run e2e -- --reporter=dot: ci".to_string()],
+            }],
+            ..Default::default()
+        };
+
+        let provider = CiProvider::from_feature("ci:github").unwrap();
+        let report = scaffold(project.path(), provider, &metadata, &HashMap::new()).unwrap();
+        assert_eq!(
+            report.files,
+            vec![PathBuf::from(".github/workflows/ci.yml")]
+        );
+        let workflow = fs::read_to_string(project.path().join(".github/workflows/ci.yml")).unwrap();
+        assert!(workflow.contains("  lint:\n    runs-on: ubuntu-latest\n"));
+        assert!(workflow.contains("          node-version: 20\n"));
+        assert!(workflow.contains("      - run: npm run build\n"));
+        assert!(workflow.contains("      - run: \"npm run e2e -- --reporter=dot: ci\"\n"));
+        assert!(!workflow.contains("  test:"));
+
+        let rust = tempdir().unwrap();
+        fs::write(rust.path().join("Cargo.toml"), "[package]").unwrap();
+        let metadata = TemplateMetadata {
+            project_type: "rust".to_string(),
+            ..Default::default()
+        };
+        scaffold(rust.path(), CiProvider::GitLab, &metadata, &HashMap::new()).unwrap();
+        let pipeline = fs::read_to_string(rust.path().join(".gitlab-ci.yml")).unwrap();
+        assert!(pipeline.starts_with("image: rust:1\n\nstages:\n  - lint\n  - build\n  - test\n"));
+        assert!(pipeline.contains("    - cargo clippy --all-targets -- -D warnings\n"));
+
+        assert_eq!(CiProvider::from_feature("ci:jenkins"), None);
+    }
+}
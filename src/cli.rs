@@ -1,199 +1,700 @@
-use crate::{GenerateOptions, GenerateResult, GeneratorError, Result};
-use std::io::{self, Write};
+use crate::answers::Answers;
+use crate::build_wrapper::BuildTool;
+use crate::condition::is_truthy;
+use crate::crud;
+use crate::output;
+use crate::session::Session;
+use crate::template_registry::{TemplateMetadata, TemplateRegistryConfig, VariableType};
+use crate::variables::{REDACTED, TypedValue, coerce, is_sensitive};
+use crate::{
+    CliRunOptions, GenerateOptions, GenerateResult, GeneratorError, Result, features, i18n, say, t,
+};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, FuzzySelect, Input, MultiSelect, Password, Select};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub struct GenCli {
+    /// 预设答案，存在时不再交互询问
+    answers: Option<Answers>,
+    /// 本次运行中的回答
+    recorded: RefCell<Answers>,
+    /// 保存回答的路径
+    save_answers: Option<PathBuf>,
+    /// 生成报告路径
+    report: Option<String>,
+    /// 正在恢复中断的会话，缺少的敏感变量需要重新询问
+    resuming: bool,
+    /// 生成失败时保存会话的路径
+    session: PathBuf,
+    /// 冻结模式，模板必须位于锁文件记录的修订
+    frozen: bool,
+    /// 委托给官方初始化工具生成
+    delegate: bool,
+    /// python 项目生成后创建虚拟环境并安装依赖
+    python_venv: bool,
+}
 
-pub struct GenCli;
+impl Default for GenCli {
+    fn default() -> Self {
+        Self {
+            answers: None,
+            recorded: RefCell::new(Answers::default()),
+            save_answers: None,
+            report: None,
+            resuming: false,
+            session: Session::default_path(),
+            frozen: false,
+            delegate: false,
+            python_venv: false,
+        }
+    }
+}
 
 impl GenCli {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// 按运行选项创建，加载答案文件
+    pub fn with_options(options: CliRunOptions) -> Result<Self> {
+        if let Some(mode) = options.output {
+            output::set_mode(mode);
+        }
+        i18n::set_lang(i18n::detect(options.lang.as_deref())?);
+        let resuming = options.resume.unwrap_or(false);
+        let session = Session::default_path();
+        let answers = if resuming {
+            let interrupted = Session::load(&session)?.ok_or_else(|| {
+                GeneratorError::Configuration(format!(
+                    "No interrupted session to resume ({} not found)",
+                    session.display()
+                ))
+            })?;
+            say!("{}", t!("cli.resuming", error = interrupted.error));
+            Some(interrupted.answers)
+        } else {
+            match &options.answers_file {
+                Some(path) => Some(Answers::load(Path::new(path))?),
+                None => None,
+            }
+        };
+        Ok(Self {
+            answers,
+            save_answers: options.save_answers.map(PathBuf::from),
+            report: options.report,
+            resuming,
+            session,
+            frozen: options.frozen.unwrap_or(false),
+            delegate: options.delegate.unwrap_or(false),
+            python_venv: options.python_venv.unwrap_or(false),
+            ..Self::new()
+        })
+    }
+
+    /// 优先使用预设答案，否则交互询问；回答会被记录
+    fn answer(
+        &self,
+        key: &str,
+        default: Option<&str>,
+        ask: impl FnOnce() -> Result<String>,
+    ) -> Result<String> {
+        let value = match &self.answers {
+            Some(answers) => {
+                let value = answers.prompt(key, default)?;
+                say!("📄 {}: {}", key, value);
+                value
+            }
+            None => ask()?,
+        };
+        self.recorded
+            .borrow_mut()
+            .prompts
+            .insert(key.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// 本次的回答（连同变量与特性），去掉模板声明的敏感变量
+    fn recorded_answers(&self, metadata: Option<&TemplateMetadata>) -> Answers {
+        let mut recorded = self.recorded.borrow().clone();
+        if let Some(metadata) = metadata {
+            recorded
+                .variables
+                .retain(|name, _| !is_sensitive(metadata, name));
+        }
+        recorded
+    }
+
+    /// 保存本次回答，敏感变量不写入
+    fn save_recorded_answers(&self, metadata: Option<&TemplateMetadata>) -> Result<()> {
+        let Some(path) = &self.save_answers else {
+            return Ok(());
+        };
+        self.recorded_answers(metadata).save(path)?;
+        say!("{}", t!("cli.answers_saved", path = path.display()));
+        Ok(())
     }
 
     pub fn run_interactive(&self) -> Result<GenerateResult> {
         // 选择生成方式
-        let generation_mode = self.select_generation_mode("请选择生成模式")?;
-        println!("{}", generation_mode);
+        let generation_mode = self.answer("generation_mode", None, || {
+            self.select_generation_mode(&t!("cli.select_mode"))
+        })?;
+        say!("{}", generation_mode);
 
         // TODO 模版源加载
         match generation_mode.as_str() {
             "NOP" => {
-                println!("🎯 使用 NOP 平台模式");
+                say!("{}", t!("cli.using_nop"));
                 // NOP 模式的特定逻辑
-                println!("📋 NOP 平台将使用预定义的模板和配置");
+                say!("{}", t!("cli.using_nop_hint"));
             }
             "CUSTOM" => {
-                println!("🎯 使用自定义模式");
+                say!("{}", t!("cli.using_custom"));
                 // 自定义模式的逻辑
-                println!("📋 自定义模式允许您选择任意模板源");
+                say!("{}", t!("cli.using_custom_hint"));
             }
             _ => {
-                println!("❌ 未知的生成模式: {}", generation_mode);
+                let message = t!("cli.unknown_mode", mode = generation_mode);
+                say!("❌ {}", message);
                 return Ok(GenerateResult {
                     success: false,
                     files: vec![],
-                    message: Some(format!("未知的生成模式: {}", generation_mode)),
+                    message: Some(message),
+                    stats: None,
+                    template_version: None,
+                    warnings: vec![],
+                    action: None,
                 });
             }
         }
 
         // 2. 选择项目类型
-        let project_type = self.select_project_type()?;
+        let project_type = self.answer("project_type", None, || self.select_project_type())?;
         // 1. 获取项目名称
-        let project_name = self.get_input("请输入项目名称")?;
+        let project_name = self.answer("name", None, || self.get_input(&t!("cli.enter_name")))?;
 
-        // 3. 选择模板（简化）
-        let template = self.select_template(&project_type)?;
+        // 3. 选择模板
+        let template = self.answer("template", None, || self.select_template(&project_type))?;
+        let metadata = TemplateRegistryConfig::user()
+            .and_then(|config| {
+                crate::templates::resolve_template(&config, &project_type, &template)
+            })
+            .map(|template| template.metadata)
+            .ok();
+
+        // 4. 模板变量与特性：有答案文件时直接使用，否则按模板声明逐项询问
+        let (mut variables, features) = match (&self.answers, &metadata) {
+            (Some(answers), _) => (answers.variables.clone(), answers.features.clone()),
+            (None, Some(metadata)) => (self.ask_variables(metadata)?, self.ask_features(metadata)?),
+            (None, None) => (BTreeMap::new(), None),
+        };
+        // 会话文件不含敏感变量，恢复时重新询问
+        if let (true, Some(metadata)) = (self.resuming, &metadata) {
+            self.ask_missing_sensitive(metadata, &mut variables)?;
+        }
 
-        // 4. 确认生成
-        println!("\n📋 生成信息:");
-        println!("   项目名称: {}", project_name);
-        println!("   项目类型: {}", project_type);
-        println!("   模板: {}", template);
+        let build_tool = self.ask_build_tool(metadata.as_ref())?;
 
-        if self.confirm("确认生成项目?")? {
+        // java 项目可按数据库表结构生成 CRUD 代码，连接信息按敏感变量处理，不会写入答案文件
+        let mut metadata = metadata;
+        let database = if project_type == "java" {
+            self.ask_database(&mut variables)?
+        } else {
+            None
+        };
+        if database.is_some() {
+            crud::declare_connection_variables(metadata.get_or_insert_with(Default::default));
+        }
+        {
+            let mut recorded = self.recorded.borrow_mut();
+            recorded.variables = variables.clone();
+            recorded.features = features.clone();
+        }
+
+        // 5. 确认生成
+        say!("\n{}", t!("cli.summary"));
+        say!("{}", t!("cli.summary_name", value = project_name));
+        say!("{}", t!("cli.summary_type", value = project_type));
+        say!("{}", t!("cli.summary_template", value = template));
+        if !variables.is_empty() {
+            let variables = variables
+                .iter()
+                .map(|(name, value)| {
+                    let sensitive = metadata
+                        .as_ref()
+                        .is_some_and(|metadata| is_sensitive(metadata, name));
+                    format!("{}={}", name, if sensitive { REDACTED } else { value })
+                })
+                .collect::<Vec<_>>();
+            say!(
+                "{}",
+                t!("cli.summary_variables", value = variables.join(", "))
+            );
+        }
+        if let Some(features) = features.as_ref().filter(|features| !features.is_empty()) {
+            say!(
+                "{}",
+                t!("cli.summary_features", value = features.join(", "))
+            );
+        }
+
+        let confirmed = self.answer("confirm", Some("yes"), || {
+            self.confirm(&t!("cli.confirm_generate"))
+                .map(|confirmed| if confirmed { "yes" } else { "no" }.to_string())
+        })?;
+        self.save_recorded_answers(metadata.as_ref())?;
+
+        if is_truthy(&confirmed) {
             let options = GenerateOptions {
                 name: project_name,
                 project_type,
                 template: Some(template),
-                output_path: None,
-                variables: None,
+                variables: Some(variables.into_iter().collect()),
+                features,
+                report: self.report.clone(),
+                database,
+                build_tool,
+                frozen: Some(self.frozen),
+                delegate: Some(self.delegate),
+                python_venv: Some(self.python_venv),
+                ..Default::default()
             };
 
-            println!("🔄 正在生成项目...");
-            let result = crate::templates::generate_project_from_template(options)?;
+            say!("{}", t!("cli.generating"));
+            let result = match crate::templates::generate_project_from_template(options) {
+                Ok(result) => {
+                    Session::clear(&self.session)?;
+                    result
+                }
+                Err(e) => {
+                    // 保存回答，修复问题后可用 --resume 跳过所有问题重新生成
+                    let session = Session::new(self.recorded_answers(metadata.as_ref()), &e);
+                    if session.save(&self.session).is_ok() {
+                        say!("{}", t!("cli.session_saved"));
+                    }
+                    return Err(e);
+                }
+            };
 
             if result.success {
-                println!("✅ 项目生成成功!");
+                say!("{}", t!("cli.generated"));
+            }
+            for warning in &result.warnings {
+                say!("⚠️  {}", warning);
             }
 
             Ok(result)
         } else {
-            println!("❌ 用户取消生成");
+            say!("{}", t!("cli.cancelled"));
             Ok(GenerateResult {
                 success: false,
                 files: vec![],
-                message: Some("用户取消".to_string()),
+                message: Some(t!("cli.cancelled_message")),
+                stats: None,
+                template_version: None,
+                warnings: vec![],
+                action: None,
             })
         }
     }
 
-    fn select_generation_mode(&self, _prompt: &str) -> Result<String> {
-        let types = vec![("NOP", "NOP平台"), ("CUSTOM", "自定义")];
-
-        println!("\n🎯 请选择生成模式:");
-        for (i, (name, desc)) in types.iter().enumerate() {
-            println!("{}. {} - {}", i + 1, name, desc);
-        }
-
-        loop {
-            print!("请输入选项 (1-{}): ", types.len());
-            io::stdout().flush().map_err(|e| GeneratorError::Io(e))?;
-
-            let mut input = String::new();
-            io::stdin()
-                .read_line(&mut input)
-                .map_err(|e| GeneratorError::Io(e))?;
-
-            if let Ok(choice) = input.trim().parse::<usize>() {
-                if choice > 0 && choice <= types.len() {
-                    return Ok(types[choice - 1].0.to_string());
-                }
-            }
-
-            println!("❌ 无效选项，请输入 1-{}", types.len());
-        }
+    fn select_generation_mode(&self, prompt: &str) -> Result<String> {
+        let types = [
+            ("NOP", t!("cli.mode_nop")),
+            ("CUSTOM", t!("cli.mode_custom")),
+        ];
+        let index = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(&labels(&types))
+            .default(0)
+            .interact()
+            .map_err(interaction_error)?;
+        Ok(types[index].0.to_string())
     }
 
-    /// 获取用户输入
+    /// 获取用户输入，为空时在原位提示并重新输入
     fn get_input(&self, prompt: &str) -> Result<String> {
-        loop {
-            print!("{}: ", prompt);
-            io::stdout().flush().map_err(|e| GeneratorError::Io(e))?;
-
-            let mut input = String::new();
-            io::stdin()
-                .read_line(&mut input)
-                .map_err(|e| GeneratorError::Io(e))?;
-
-            let input = input.trim().to_string();
-            if !input.is_empty() {
-                return Ok(input);
-            }
-
-            println!("❌ 输入不能为空，请重新输入");
-        }
+        Input::<String>::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .validate_with(|input: &String| {
+                if input.trim().is_empty() {
+                    Err(t!("cli.empty_input"))
+                } else {
+                    Ok(())
+                }
+            })
+            .interact_text()
+            .map(|input| input.trim().to_string())
+            .map_err(interaction_error)
     }
 
     /// 选择项目类型
     fn select_project_type(&self) -> Result<String> {
-        let types = vec![
-            ("vue", "Vue.js 项目"),
-            ("react", "React 项目"),
-            ("java", "Java 项目"),
+        let types = [
+            ("vue", t!("cli.type_vue")),
+            ("react", t!("cli.type_react")),
+            ("java", t!("cli.type_java")),
+            ("android", t!("cli.type_android")),
+            ("python", t!("cli.type_python")),
+            ("node-lib", t!("cli.type_node_lib")),
+            ("rust", t!("cli.type_rust")),
+            ("monorepo", t!("cli.type_monorepo")),
         ];
+        let index = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("cli.select_project_type"))
+            .items(&labels(&types))
+            .default(0)
+            .interact()
+            .map_err(interaction_error)?;
+        Ok(types[index].0.to_string())
+    }
 
-        println!("\n🎯 请选择项目类型:");
-        for (i, (name, desc)) in types.iter().enumerate() {
-            println!("{}. {} - {}", i + 1, name, desc);
+    /// 从该类型的可用模板中模糊搜索选择，列不出模板时使用默认模板
+    fn select_template(&self, project_type: &str) -> Result<String> {
+        let fallback = match project_type {
+            "vue" => "basic",
+            "react" => "basic",
+            "java" => "spring-boot",
+            "android" => "compose",
+            "python" => "uv",
+            "rust" => "bin",
+            "monorepo" => "pnpm",
+            _ => "basic",
+        };
+        let templates = crate::templates::list_templates_by_type(project_type).unwrap_or_default();
+        if templates.is_empty() {
+            say!("\n{}", t!("cli.template_used", template = fallback));
+            return Ok(fallback.to_string());
         }
 
-        loop {
-            print!("请输入选项 (1-{}): ", types.len());
-            io::stdout().flush().map_err(|e| GeneratorError::Io(e))?;
-
-            let mut input = String::new();
-            io::stdin()
-                .read_line(&mut input)
-                .map_err(|e| GeneratorError::Io(e))?;
+        let index = FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("cli.select_template"))
+            .items(&templates)
+            .default(
+                templates
+                    .iter()
+                    .position(|template| template == fallback)
+                    .unwrap_or(0),
+            )
+            .interact()
+            .map_err(interaction_error)?;
+        Ok(templates[index].clone())
+    }
 
-            if let Ok(choice) = input.trim().parse::<usize>() {
-                if choice > 0 && choice <= types.len() {
-                    return Ok(types[choice - 1].0.to_string());
+    /// 按模板声明询问变量：显示默认值，按类型在原位校验，敏感变量隐藏输入
+    ///
+    /// 留空的可选变量不返回，由生成时的默认值补全。
+    fn ask_variables(&self, metadata: &TemplateMetadata) -> Result<BTreeMap<String, String>> {
+        let theme = ColorfulTheme::default();
+        let mut variables = BTreeMap::new();
+        for variable in &metadata.variables {
+            let prompt = if variable.description.is_empty() {
+                variable.name.clone()
+            } else {
+                format!("{} ({})", variable.description, variable.name)
+            };
+            let default = variable.default.as_deref();
+            let value = match &variable.var_type {
+                VariableType::Boolean => {
+                    let default = default.and_then(|value| coerce(&variable.var_type, value).ok());
+                    Confirm::with_theme(&theme)
+                        .with_prompt(prompt)
+                        .default(default == Some(TypedValue::Boolean(true)))
+                        .interact()
+                        .map(|value| value.to_string())
+                }
+                VariableType::Choice { options } => Select::with_theme(&theme)
+                    .with_prompt(prompt)
+                    .items(options)
+                    .default(
+                        default
+                            .and_then(|default| options.iter().position(|option| option == default))
+                            .unwrap_or(0),
+                    )
+                    .interact()
+                    .map(|index| options[index].clone()),
+                var_type if variable.sensitive => Password::with_theme(&theme)
+                    .with_prompt(prompt)
+                    .allow_empty_password(!variable.required || default.is_some())
+                    .validate_with(|input: &String| validate_input(var_type, input))
+                    .interact(),
+                var_type => {
+                    let mut input = Input::<String>::with_theme(&theme)
+                        .with_prompt(prompt)
+                        .allow_empty(!variable.required);
+                    if let Some(default) = default {
+                        input = input.default(default.to_string());
+                    }
+                    input
+                        .validate_with(|input: &String| validate_input(var_type, input))
+                        .interact_text()
                 }
             }
+            .map_err(interaction_error)?;
 
-            println!("❌ 无效选项，请输入 1-{}", types.len());
+            if !value.is_empty() {
+                variables.insert(variable.name.clone(), value);
+            }
         }
+        Ok(variables)
     }
 
-    /// 选择模板
-    fn select_template(&self, project_type: &str) -> Result<String> {
-        // 简化实现：根据项目类型返回默认模板
-        let template = match project_type {
-            "vue" => "basic",
-            "react" => "basic",
-            "java" => "spring-boot",
-            _ => "basic",
-        };
+    /// 询问 CRUD 代码的表结构来源，JDBC 连接信息写入 `variables`，返回 `database` 生成选项
+    /// 模板支持多种构建工具时选择其一，第一个为默认值
+    fn ask_build_tool(&self, metadata: Option<&TemplateMetadata>) -> Result<Option<BuildTool>> {
+        let tools = metadata
+            .map(|metadata| metadata.build_tools.clone())
+            .unwrap_or_default();
+        if tools.len() < 2 {
+            return Ok(None);
+        }
+        self.answer("build_tool", Some(&tools[0]), || {
+            let index = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(t!("cli.select_build_tool"))
+                .items(&tools)
+                .default(0)
+                .interact()
+                .map_err(interaction_error)?;
+            Ok(tools[index].clone())
+        })
+        .and_then(|tool| tool.parse().map(Some))
+    }
 
-        println!("\n🎨 使用模板: {}", template);
-        Ok(template.to_string())
+    fn ask_database(&self, variables: &mut BTreeMap<String, String>) -> Result<Option<String>> {
+        let source = self.answer("crud_source", Some("none"), || {
+            let sources = [
+                ("none", t!("cli.crud_none")),
+                ("jdbc", t!("cli.crud_jdbc")),
+                ("ddl", t!("cli.crud_ddl")),
+            ];
+            let index = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(t!("cli.select_crud_source"))
+                .items(&labels(&sources))
+                .default(0)
+                .interact()
+                .map_err(interaction_error)?;
+            Ok(sources[index].0.to_string())
+        })?;
+
+        match source.as_str() {
+            "jdbc" => {
+                // 使用答案文件时连接信息取自其中的变量，恢复会话时重新询问
+                if self.answers.is_none() || self.resuming {
+                    let theme = ColorfulTheme::default();
+                    let url = self.get_input(&t!("cli.enter_db_url"))?;
+                    let username = Input::<String>::with_theme(&theme)
+                        .with_prompt(t!("cli.enter_db_username"))
+                        .allow_empty(true)
+                        .interact_text()
+                        .map_err(interaction_error)?;
+                    let password = Password::with_theme(&theme)
+                        .with_prompt(t!("cli.enter_db_password"))
+                        .allow_empty_password(true)
+                        .interact()
+                        .map_err(interaction_error)?;
+                    variables.insert(crud::URL_VARIABLE.to_string(), url);
+                    for (name, value) in [
+                        (crud::USERNAME_VARIABLE, username.trim().to_string()),
+                        (crud::PASSWORD_VARIABLE, password),
+                    ] {
+                        if !value.is_empty() {
+                            variables.insert(name.to_string(), value);
+                        }
+                    }
+                }
+                match variables.get(crud::URL_VARIABLE) {
+                    Some(url) => Ok(Some(url.clone())),
+                    None => Err(GeneratorError::Configuration(format!(
+                        "Variable '{}' is required to generate CRUD code from a JDBC connection",
+                        crud::URL_VARIABLE
+                    ))),
+                }
+            }
+            "ddl" => self
+                .answer("ddl_file", None, || {
+                    self.get_input(&t!("cli.enter_ddl_file"))
+                })
+                .map(Some),
+            _ => Ok(None),
+        }
     }
 
-    /// 确认操作
-    fn confirm(&self, message: &str) -> Result<bool> {
-        print!("{} (Y/n): ", message);
-        io::stdout().flush().map_err(|e| GeneratorError::Io(e))?;
+    /// 询问尚未提供值的敏感变量
+    fn ask_missing_sensitive(
+        &self,
+        metadata: &TemplateMetadata,
+        variables: &mut BTreeMap<String, String>,
+    ) -> Result<()> {
+        for variable in &metadata.variables {
+            if !variable.sensitive || variables.contains_key(&variable.name) {
+                continue;
+            }
+            let prompt = if variable.description.is_empty() {
+                variable.name.clone()
+            } else {
+                format!("{} ({})", variable.description, variable.name)
+            };
+            let value = Password::with_theme(&ColorfulTheme::default())
+                .with_prompt(prompt)
+                .allow_empty_password(!variable.required || variable.default.is_some())
+                .validate_with(|input: &String| validate_input(&variable.var_type, input))
+                .interact()
+                .map_err(interaction_error)?;
+            if !value.is_empty() {
+                variables.insert(variable.name.clone(), value);
+            }
+        }
+        Ok(())
+    }
 
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .map_err(|e| GeneratorError::Io(e))?;
+    /// 多选模板特性，模板没有特性时不询问
+    fn ask_features(&self, metadata: &TemplateMetadata) -> Result<Option<Vec<String>>> {
+        let features = features::available_features(metadata);
+        if features.is_empty() {
+            return Ok(None);
+        }
+        let items: Vec<String> = features
+            .iter()
+            .map(|feature| {
+                if feature.description.is_empty() {
+                    feature.name.clone()
+                } else {
+                    format!("{} - {}", feature.name, feature.description)
+                }
+            })
+            .collect();
+        let selected = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("cli.select_features"))
+            .items(&items)
+            .interact()
+            .map_err(interaction_error)?;
+        Ok(Some(
+            selected
+                .into_iter()
+                .map(|index| features[index].name.clone())
+                .collect(),
+        ))
+    }
 
-        let input = input.trim().to_lowercase();
-        Ok(matches!(input.as_str(), "" | "y" | "yes"))
+    /// 确认操作
+    fn confirm(&self, message: &str) -> Result<bool> {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(message)
+            .default(true)
+            .interact()
+            .map_err(interaction_error)
     }
 
     /// 显示帮助信息
     pub fn show_help(&self) {
-        println!("🚀 项目生成器 CLI");
-        println!("================");
-        println!();
-        println!("支持的项目类型:");
-        println!("  - vue: Vue.js 项目");
-        println!("  - react: React 项目");
-        println!("  - java: Java 项目");
-        println!();
-        println!("使用方法:");
-        println!("  运行交互模式，按提示输入信息即可生成项目");
+        println!("{}", t!("help.text"));
+    }
+}
+
+/// 选项的显示文本：`名称 - 描述`
+fn labels(items: &[(&str, String)]) -> Vec<String> {
+    items
+        .iter()
+        .map(|(name, desc)| format!("{} - {}", name, desc))
+        .collect()
+}
+
+/// 按变量类型校验输入，空输入交由是否必填决定
+fn validate_input(var_type: &VariableType, input: &str) -> std::result::Result<(), String> {
+    if input.is_empty() {
+        return Ok(());
+    }
+    coerce(var_type, input)
+        .map(|_| ())
+        .map_err(|(_, expected)| t!("cli.invalid_value", expected = expected))
+}
+
+fn interaction_error(error: dialoguer::Error) -> GeneratorError {
+    match error {
+        dialoguer::Error::IO(e) => GeneratorError::Io(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_validate_input_checks_variable_type() {
+        assert!(validate_input(&VariableType::Number, "").is_ok());
+        assert!(validate_input(&VariableType::Number, "8080").is_ok());
+        assert!(
+            validate_input(&VariableType::Number, "eighty")
+                .unwrap_err()
+                .contains("a number")
+        );
+        let choice = VariableType::Choice {
+            options: vec!["MIT".to_string(), "Apache-2.0".to_string()],
+        };
+        assert!(validate_input(&choice, "mit").is_ok());
+        assert!(
+            validate_input(&choice, "GPL")
+                .unwrap_err()
+                .contains("[MIT, Apache-2.0]")
+        );
+    }
+
+    #[test]
+    fn test_run_with_answers_records_variables_and_features() {
+        let template_dir = tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "vue", "dependencies": [], "tags": [],
+                "variables": [
+                    {"name": "port", "description": "", "default": "8080", "required": false,
+                     "var_type": "number"},
+                    {"name": "apiKey", "description": "", "default": null, "required": false,
+                     "var_type": "string", "sensitive": true}],
+                "features": [{"name": "eslint"}]}"#,
+        )
+        .unwrap();
+        let saved = template_dir.path().join("answers.json");
+        let answers = Answers {
+            prompts: BTreeMap::from([
+                ("generation_mode".to_string(), "CUSTOM".to_string()),
+                ("project_type".to_string(), "vue".to_string()),
+                ("name".to_string(), "demo-app".to_string()),
+                (
+                    "template".to_string(),
+                    template_dir.path().to_string_lossy().to_string(),
+                ),
+                ("confirm".to_string(), "no".to_string()),
+            ]),
+            variables: BTreeMap::from([
+                ("port".to_string(), "3000".to_string()),
+                ("apiKey".to_string(), "secret".to_string()),
+            ]),
+            features: Some(vec!["eslint".to_string()]),
+        };
+        let cli = GenCli {
+            answers: Some(answers),
+            save_answers: Some(saved.clone()),
+            ..GenCli::new()
+        };
+
+        // 未确认时不生成，只保存回答
+        let result = cli.run_interactive().unwrap();
+        assert!(!result.success);
+
+        let recorded = Answers::load(&saved).unwrap();
+        assert_eq!(
+            recorded.prompts.get("name").map(String::as_str),
+            Some("demo-app")
+        );
+        assert_eq!(
+            recorded.variables,
+            BTreeMap::from([("port".to_string(), "3000".to_string())])
+        );
+        assert_eq!(recorded.features, Some(vec!["eslint".to_string()]));
     }
 }
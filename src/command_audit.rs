@@ -0,0 +1,285 @@
+//! 外部命令审计模块
+//! 生成器执行的外部命令（后置步骤、java、git、构建工具）都经过这里：按策略拒绝匹配黑名单的命令，审计模式下只记录命令、参数与工作目录而不执行
+
+use crate::templates::generate_project_from_template;
+use crate::utils::generator_home;
+use crate::{AuditedCommand, GenerateOptions, GeneratorError, Result, cancel, workspace};
+use globset::GlobBuilder;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Output};
+
+/// 命令策略文件名（位于 `~/.generator`）
+pub const POLICY_FILE: &str = "policy.json";
+
+/// 外部命令策略
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandPolicy {
+    /// 禁止执行的命令模式（glob，`*` 可跨越空格与路径分隔符），匹配完整命令行或 shell 步骤的脚本
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl CommandPolicy {
+    /// 读取 `dir` 下的策略文件，文件不存在时不做限制
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(POLICY_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e))
+        })
+    }
+
+    /// 用户级策略（`~/.generator/policy.json`）
+    pub fn user() -> Result<Self> {
+        Self::load(&generator_home())
+    }
+
+    /// 命令匹配的第一个禁止模式
+    pub fn denied_by(&self, command: &AuditedCommand) -> Result<Option<String>> {
+        let candidates = [Some(command_line(command)), shell_script(command)];
+        for pattern in &self.deny {
+            let matcher = GlobBuilder::new(pattern)
+                .literal_separator(false)
+                .build()
+                .map_err(|e| {
+                    GeneratorError::Configuration(format!(
+                        "Invalid command pattern '{}': {}",
+                        pattern, e
+                    ))
+                })?
+                .compile_matcher();
+            if candidates
+                .iter()
+                .flatten()
+                .any(|text| matcher.is_match(text))
+            {
+                return Ok(Some(pattern.clone()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 审计作用域
+struct Scope {
+    policy: CommandPolicy,
+    commands: Vec<AuditedCommand>,
+}
+
+thread_local! {
+    static SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+/// 在审计模式下执行 `f`：外部命令只记录不执行，返回 `f` 的结果与记录的命令
+pub fn with_audit<R>(policy: CommandPolicy, f: impl FnOnce() -> R) -> (R, Vec<AuditedCommand>) {
+    let scope = Scope {
+        policy,
+        commands: Vec::new(),
+    };
+    let previous = SCOPE.with(|current| current.replace(Some(scope)));
+    let result = f();
+    let scope = SCOPE.with(|current| current.replace(previous));
+    (
+        result,
+        scope.map(|scope| scope.commands).unwrap_or_default(),
+    )
+}
+
+/// 审计模式下记录取得模板所需的远程操作：HTTP 下载记为 `http GET <url>`，libgit2 拉取记为 `git fetch <url> <refspec>`
+///
+/// 远程操作照常执行：审计需要真实的模板内容才能列出之后的命令，跳过或伪造结果会让审计失败或列出错误的命令。
+pub fn record_remote(program: &str, args: &[&str]) -> Result<()> {
+    let mut command = Command::new(program);
+    command.args(args);
+    let mut audited = describe(&command);
+    SCOPE.with(|current| {
+        if let Some(scope) = current.borrow_mut().as_mut() {
+            audited.denied_by = scope.policy.denied_by(&audited)?;
+            scope.commands.push(audited);
+        }
+        Ok(())
+    })
+}
+
+/// 执行取得模板的 git 命令（如稀疏检出）：审计模式下记录后照常执行，而不是返回伪造的空输出
+pub fn remote_output(command: &mut Command) -> Result<Output> {
+    let scope = SCOPE.with(|current| current.borrow_mut().take());
+    let Some(mut scope) = scope else {
+        return cancel::output(command);
+    };
+    let mut audited = describe(command);
+    let result = scope.policy.denied_by(&audited).and_then(|denied_by| {
+        audited.denied_by = denied_by;
+        scope.commands.push(audited);
+        cancel::output(command)
+    });
+    SCOPE.with(|current| *current.borrow_mut() = Some(scope));
+    result
+}
+
+/// 执行外部命令前调用：被策略禁止时返回错误；审计模式下记录命令并返回代替执行结果的空输出
+pub fn intercept(command: &Command) -> Result<Option<Output>> {
+    let mut audited = describe(command);
+    let recorded = SCOPE.with(|current| -> Result<bool> {
+        let mut current = current.borrow_mut();
+        let Some(scope) = current.as_mut() else {
+            return Ok(false);
+        };
+        audited.denied_by = scope.policy.denied_by(&audited)?;
+        scope.commands.push(audited.clone());
+        Ok(true)
+    })?;
+    if recorded {
+        return Ok(Some(Output {
+            status: ExitStatus::default(),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        }));
+    }
+
+    match CommandPolicy::user()?.denied_by(&audited)? {
+        Some(pattern) => Err(GeneratorError::Configuration(format!(
+            "Command '{}' is denied by policy pattern '{}'",
+            command_line(&audited),
+            pattern
+        ))),
+        None => Ok(None),
+    }
+}
+
+/// 命令的程序、参数与工作目录
+pub fn describe(command: &Command) -> AuditedCommand {
+    let cwd = command
+        .get_current_dir()
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    AuditedCommand {
+        program: command.get_program().to_string_lossy().to_string(),
+        args: command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect(),
+        cwd: cwd.display().to_string(),
+        denied_by: None,
+    }
+}
+
+/// 审计按 `options` 生成项目时会执行的外部命令
+///
+/// 项目生成到临时目录，命令只记录不执行；取得模板的远程操作照常执行并一同列出。命令中的临时路径替换为实际的输出位置。
+pub fn audit_generation(options: GenerateOptions) -> Result<Vec<AuditedCommand>> {
+    let base_dir = workspace::resolve_output_dir(
+        options.output_path.as_deref(),
+        options.workspace_root.as_deref(),
+    )?;
+    let project_dir = if options.into_existing.unwrap_or(false) {
+        base_dir.clone()
+    } else {
+        base_dir.join(&options.name)
+    };
+
+    let staging = tempfile::tempdir()?;
+    let name = options.name.clone();
+    let (result, commands) = with_audit(CommandPolicy::user()?, || {
+        generate_project_from_template(GenerateOptions {
+            output_path: Some(staging.path().to_string_lossy().to_string()),
+            workspace_root: None,
+            into_existing: None,
+            report: None,
+            frozen: None,
+            ..options
+        })
+    });
+    result?;
+
+    // 生成时先写入 `.<name>.generating-<pid>` 暂存目录，再移动到 `<name>`
+    let staging_root = staging.path().to_string_lossy().to_string();
+    let staged = format!(
+        "{}{}.{}.generating-{}",
+        staging_root,
+        std::path::MAIN_SEPARATOR,
+        name,
+        std::process::id()
+    );
+    let project = format!("{}{}{}", staging_root, std::path::MAIN_SEPARATOR, name);
+    let relocate = |text: &str| {
+        text.replace(&staged, &project_dir.display().to_string())
+            .replace(&project, &project_dir.display().to_string())
+            .replace(&staging_root, &base_dir.display().to_string())
+    };
+    Ok(commands
+        .into_iter()
+        .map(|command| AuditedCommand {
+            program: command.program,
+            args: command.args.iter().map(|arg| relocate(arg)).collect(),
+            cwd: relocate(&command.cwd),
+            denied_by: command.denied_by,
+        })
+        .collect())
+}
+
+/// 以空格连接的命令行
+pub fn command_line(command: &AuditedCommand) -> String {
+    std::iter::once(command.program.as_str())
+        .chain(command.args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `sh -c <script>` / `cmd /C <script>` 形式的后置步骤中的脚本
+fn shell_script(command: &AuditedCommand) -> Option<String> {
+    match (command.program.as_str(), command.args.as_slice()) {
+        ("sh" | "cmd", [flag, script]) if flag == "-c" || flag == "/C" => Some(script.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_records_commands_and_policy_matches() {
+        let policy = CommandPolicy {
+            deny: vec!["npm install*".to_string(), "*rm -rf*".to_string()],
+        };
+        let ((), commands) = with_audit(policy, || {
+            for script in ["npm install --no-audit", "echo ok"] {
+                let mut command = Command::new("sh");
+                command.args(["-c", script]).current_dir("/tmp");
+                let output = crate::cancel::output(&mut command).unwrap();
+                assert!(output.status.success());
+            }
+            let mut command = Command::new("rm");
+            command.args(["-rf", "build"]);
+            assert!(intercept(&command).unwrap().is_some());
+
+            // 取得模板的远程操作只记录，git 命令照常执行并返回真实输出
+            record_remote("http", &["GET", "https://example.com/t.tgz"]).unwrap();
+            let mut command = Command::new("sh");
+            command.args(["-c", "echo fetched"]);
+            let output = remote_output(&mut command).unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), "fetched\n");
+        });
+
+        assert_eq!(commands.len(), 5);
+        assert_eq!(commands[3].program, "http");
+        assert_eq!(commands[3].args, ["GET", "https://example.com/t.tgz"]);
+        assert_eq!(commands[4].args, ["-c", "echo fetched"]);
+        assert_eq!(commands[0].program, "sh");
+        assert_eq!(commands[0].args, ["-c", "npm install --no-audit"]);
+        assert_eq!(commands[0].cwd, "/tmp");
+        assert_eq!(commands[0].denied_by.as_deref(), Some("npm install*"));
+        assert_eq!(commands[1].denied_by, None);
+        assert_eq!(commands[2].denied_by.as_deref(), Some("*rm -rf*"));
+
+        // 作用域外恢复执行
+        assert!(intercept(&Command::new("true")).unwrap().is_none());
+    }
+}
@@ -0,0 +1,134 @@
+//! 模板兼容性模块
+//! 按当前环境（Java、Node.js 引擎、操作系统）检查模板能否使用，用于过滤模板列表并说明不兼容的原因
+
+use crate::java_cli::{self, JavaVersionRequirement};
+use crate::template_registry::{TemplateMetadata, TemplateRegistryConfig};
+use crate::templates::{list_templates_by_type, resolve_template};
+use crate::{NodeEnvironmentInfo, Result, TemplateCompatibility, node_env};
+
+/// 检查兼容性所用的环境信息
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    /// 已安装的 Java 主版本号
+    pub java: Option<u32>,
+    pub node: NodeEnvironmentInfo,
+    /// 操作系统（`std::env::consts::OS` 的取值）
+    pub os: String,
+}
+
+impl Environment {
+    /// 探测当前环境
+    pub fn detect() -> Self {
+        Self {
+            java: java_cli::detect_java()
+                .ok()
+                .and_then(|java| java.major_version()),
+            node: node_env::detect_node_environment(),
+            os: std::env::consts::OS.to_string(),
+        }
+    }
+}
+
+/// 模板在 `environment` 中不可用的原因，兼容时为空
+pub fn incompatibilities(metadata: &TemplateMetadata, environment: &Environment) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if !metadata.os.is_empty() && !metadata.os.contains(&environment.os) {
+        reasons.push(format!(
+            "Supports {} only, current OS is {}",
+            metadata.os.join(", "),
+            environment.os
+        ));
+    }
+    if let Some(requirement) = &metadata.required_java_version {
+        match (JavaVersionRequirement::parse(requirement), environment.java) {
+            (Err(e), _) => reasons.push(e.to_string()),
+            (Ok(_), None) => reasons.push(format!(
+                "Java {} is required but not installed",
+                requirement
+            )),
+            (Ok(required), Some(major)) if !required.matches(major) => {
+                reasons.push(format!("Java {} is required, found {}", requirement, major))
+            }
+            _ => {}
+        }
+    }
+    reasons.extend(node_env::check_engines(
+        &metadata.engines,
+        &environment.node,
+    ));
+    reasons
+}
+
+/// 列出项目类型的模板及其在当前环境中的兼容性，`include_incompatible` 为 false 时只返回兼容的模板
+pub fn list_compatible_templates(
+    project_type: &str,
+    include_incompatible: bool,
+) -> Result<Vec<TemplateCompatibility>> {
+    let environment = Environment::detect();
+    let config = TemplateRegistryConfig::user()?;
+    let mut templates = Vec::new();
+    for name in list_templates_by_type(project_type)? {
+        // 没有模板目录的内置模板不声明环境要求
+        let reasons = match resolve_template(&config, project_type, &name) {
+            Ok(template) => incompatibilities(&template.metadata, &environment),
+            Err(_) => Vec::new(),
+        };
+        if include_incompatible || reasons.is_empty() {
+            templates.push(TemplateCompatibility {
+                name,
+                compatible: reasons.is_empty(),
+                reasons,
+            });
+        }
+    }
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_incompatibility_reasons() {
+        let metadata = TemplateMetadata {
+            required_java_version: Some("17+".to_string()),
+            engines: HashMap::from([("node".to_string(), ">=18".to_string())]),
+            os: vec!["linux".to_string(), "macos".to_string()],
+            ..Default::default()
+        };
+        let environment = Environment {
+            java: Some(11),
+            node: NodeEnvironmentInfo {
+                node: Some("20.11.0".to_string()),
+                ..Default::default()
+            },
+            os: "windows".to_string(),
+        };
+        assert_eq!(
+            incompatibilities(&metadata, &environment),
+            [
+                "Supports linux, macos only, current OS is windows",
+                "Java 17+ is required, found 11",
+            ]
+        );
+
+        let environment = Environment {
+            java: Some(21),
+            os: "linux".to_string(),
+            ..environment
+        };
+        assert!(incompatibilities(&metadata, &environment).is_empty());
+        let bare = Environment {
+            os: "macos".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            incompatibilities(&metadata, &bare),
+            [
+                "Java 17+ is required but not installed",
+                "node >=18 is required but not installed",
+            ]
+        );
+    }
+}
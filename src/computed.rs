@@ -0,0 +1,260 @@
+//! 派生变量模块
+//! 解析并求值 template.json 中 `computed` 声明的表达式，例如 `replace(package, ".", "/")`、`pascalCase(name)`
+
+use crate::template_helpers;
+use crate::template_metadata::TemplateMetadata;
+use crate::{GeneratorError, Result};
+use std::collections::HashMap;
+
+/// 派生变量表达式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// 变量引用
+    Var(String),
+    /// 字符串字面量
+    Literal(String),
+    /// 函数调用：`replace`、`concat`、`trim`、`default` 或内置 helper
+    Call(String, Vec<Expr>),
+}
+
+/// 表达式中可用的函数（内置 helper 之外）
+const FUNCTIONS: &[&str] = &["replace", "concat", "trim", "default"];
+
+impl Expr {
+    /// 解析表达式
+    pub fn parse(expr: &str) -> Result<Self> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser {
+            expr,
+            tokens,
+            pos: 0,
+        };
+        let parsed = parser.parse_expr()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(invalid(expr, "unexpected trailing tokens"));
+        }
+        Ok(parsed)
+    }
+
+    /// 基于变量求值，引用未定义的变量时报错
+    pub fn evaluate(&self, variables: &HashMap<String, String>) -> Result<String> {
+        match self {
+            Expr::Var(name) => variables.get(name).cloned().ok_or_else(|| {
+                GeneratorError::TemplateProcessing(format!("Variable '{}' is not defined", name))
+            }),
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Call(function, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(variables))
+                    .collect::<Result<Vec<_>>>()?;
+                call(function, &args)
+            }
+        }
+    }
+
+    /// 表达式引用的变量名
+    pub fn variables(&self) -> Vec<&str> {
+        match self {
+            Expr::Var(name) => vec![name.as_str()],
+            Expr::Literal(_) => Vec::new(),
+            Expr::Call(_, args) => args.iter().flat_map(Expr::variables).collect(),
+        }
+    }
+}
+
+/// 按声明顺序求值派生变量并写入 `variables`，后声明的可以引用先声明的
+///
+/// 调用方已显式提供的同名变量不会被覆盖。
+pub fn apply_computed(
+    metadata: &TemplateMetadata,
+    variables: &mut HashMap<String, String>,
+) -> Result<()> {
+    for computed in &metadata.computed {
+        if variables.contains_key(&computed.name) {
+            continue;
+        }
+        let value = Expr::parse(&computed.expr)
+            .and_then(|expr| expr.evaluate(variables))
+            .map_err(|e| {
+                GeneratorError::TemplateProcessing(format!(
+                    "Cannot compute variable '{}': {}",
+                    computed.name, e
+                ))
+            })?;
+        variables.insert(computed.name.clone(), value);
+    }
+    Ok(())
+}
+
+fn call(function: &str, args: &[String]) -> Result<String> {
+    let result = match (function, args) {
+        ("replace", [value, from, to]) => Some(value.replace(from.as_str(), to)),
+        ("concat", args) => Some(args.concat()),
+        ("trim", [value]) => Some(value.trim().to_string()),
+        ("default", [value, fallback]) => Some(if value.is_empty() {
+            fallback.clone()
+        } else {
+            value.clone()
+        }),
+        (function, args) => template_helpers::call_helper(function, args),
+    };
+    result.ok_or_else(|| {
+        GeneratorError::TemplateProcessing(format!(
+            "Function '{}' does not accept {} argument(s)",
+            function,
+            args.len()
+        ))
+    })
+}
+
+fn invalid(expr: &str, reason: &str) -> GeneratorError {
+    GeneratorError::TemplateProcessing(format!("Invalid expression '{}': {}", expr, reason))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(String),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| invalid(expr, "unterminated string"))?;
+                tokens.push(Token::Literal(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            other => return Err(invalid(expr, &format!("unexpected character '{}'", other))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(Expr::Literal(value)),
+            Some(Token::Ident(name)) if self.tokens.get(self.pos) == Some(&Token::LParen) => {
+                self.pos += 1;
+                if !FUNCTIONS.contains(&name.as_str()) && !template_helpers::is_helper(&name) {
+                    return Err(invalid(self.expr, &format!("unknown function '{}'", name)));
+                }
+                let args = self.parse_args()?;
+                Ok(Expr::Call(name, args))
+            }
+            Some(Token::Ident(name)) => Ok(Expr::Var(name)),
+            _ => Err(invalid(
+                self.expr,
+                "expected a variable, string or function call",
+            )),
+        }
+    }
+
+    /// 解析到右括号为止的逗号分隔参数
+    fn parse_args(&mut self) -> Result<Vec<Expr>> {
+        let mut args = Vec::new();
+        if self.tokens.get(self.pos) == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_expr()?);
+            match self.next() {
+                Some(Token::Comma) => {}
+                Some(Token::RParen) => return Ok(args),
+                _ => return Err(invalid(self.expr, "expected ',' or ')'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_computed_in_order() {
+        let metadata: TemplateMetadata = serde_json::from_value(serde_json::json!({
+            "name": "demo", "version": "1.0.0", "description": "", "author": "",
+            "project_type": "java", "variables": [], "dependencies": [], "tags": [],
+            "computed": [
+                {"name": "packagePath", "expr": "replace(package, \".\", \"/\")"},
+                {"name": "className", "expr": "pascalCase(name)"},
+                {"name": "mainFile", "expr": "concat(packagePath, '/', className, \".java\")"},
+                {"name": "title", "expr": "upper(trim(default(title, name)))"}
+            ]
+        }))
+        .unwrap();
+        let mut variables = HashMap::from([
+            ("name".to_string(), "order-service".to_string()),
+            ("package".to_string(), "com.example.order".to_string()),
+            ("title".to_string(), "Orders".to_string()),
+        ]);
+
+        apply_computed(&metadata, &mut variables).unwrap();
+        assert_eq!(variables["packagePath"], "com/example/order");
+        assert_eq!(variables["className"], "OrderService");
+        assert_eq!(variables["mainFile"], "com/example/order/OrderService.java");
+        // 调用方显式提供的值优先
+        assert_eq!(variables["title"], "Orders");
+
+        assert!(Expr::parse("shout(name)").is_err());
+        assert!(Expr::parse("replace(name, \"-\"").is_err());
+        assert_eq!(
+            Expr::parse("concat(a, lower(b))").unwrap().variables(),
+            vec!["a", "b"]
+        );
+        assert!(
+            Expr::parse("missing")
+                .unwrap()
+                .evaluate(&variables)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! 条件表达式模块
+//! 解析并求值 template.json 中的 `when` 条件，例如 `useRouter == true && style != "css"`
+
+use crate::{GeneratorError, Result};
+use std::collections::HashMap;
+
+/// 条件表达式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// 变量为真值
+    Truthy(String),
+    /// 变量等于给定值
+    Eq(String, String),
+    /// 变量不等于给定值
+    Ne(String, String),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    /// 解析条件表达式
+    pub fn parse(expr: &str) -> Result<Self> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let condition = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(invalid(expr, "unexpected trailing tokens"));
+        }
+        Ok(condition)
+    }
+
+    /// 基于变量求值
+    pub fn evaluate(&self, variables: &HashMap<String, String>) -> bool {
+        match self {
+            Condition::Truthy(name) => variables.get(name).is_some_and(|v| is_truthy(v)),
+            Condition::Eq(name, value) => compare(variables.get(name), value),
+            Condition::Ne(name, value) => !compare(variables.get(name), value),
+            Condition::Not(inner) => !inner.evaluate(variables),
+            Condition::And(lhs, rhs) => lhs.evaluate(variables) && rhs.evaluate(variables),
+            Condition::Or(lhs, rhs) => lhs.evaluate(variables) || rhs.evaluate(variables),
+        }
+    }
+
+    /// 表达式引用的变量名
+    pub fn variables(&self) -> Vec<&str> {
+        match self {
+            Condition::Truthy(name) | Condition::Eq(name, _) | Condition::Ne(name, _) => {
+                vec![name.as_str()]
+            }
+            Condition::Not(inner) => inner.variables(),
+            Condition::And(lhs, rhs) | Condition::Or(lhs, rhs) => {
+                let mut names = lhs.variables();
+                names.extend(rhs.variables());
+                names
+            }
+        }
+    }
+}
+
+/// 解析并求值条件表达式
+pub fn evaluate(expr: &str, variables: &HashMap<String, String>) -> Result<bool> {
+    Ok(Condition::parse(expr)?.evaluate(variables))
+}
+
+/// 判断字符串是否为真值（true/yes/y/on/1）
+pub fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "true" | "yes" | "y" | "on" | "1"
+    )
+}
+
+fn compare(actual: Option<&String>, expected: &str) -> bool {
+    match (actual, expected) {
+        (Some(actual), "true") => is_truthy(actual),
+        (Some(actual), "false") => !is_truthy(actual),
+        (None, "false") => true,
+        (Some(actual), expected) => actual == expected,
+        (None, _) => false,
+    }
+}
+
+fn invalid(expr: &str, reason: &str) -> GeneratorError {
+    GeneratorError::TemplateProcessing(format!("Invalid condition '{}': {}", expr, reason))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(String),
+    Eq,
+    Ne,
+    Not,
+    And,
+    Or,
+    LParen,
+    RParen,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Eq);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ne);
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| invalid(expr, "unterminated string"))?;
+                tokens.push(Token::Literal(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '-' | '.'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            other => return Err(invalid(expr, &format!("unexpected character '{}'", other))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, reason: &str) -> GeneratorError {
+        GeneratorError::TemplateProcessing(format!("Invalid condition: {}", reason))
+    }
+
+    fn parse_or(&mut self) -> Result<Condition> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = Condition::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            lhs = Condition::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Condition> {
+        match self.next() {
+            Some(Token::Not) => Ok(Condition::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err(self.error("missing ')'")),
+                }
+            }
+            Some(Token::Ident(name)) => match self.peek() {
+                Some(Token::Eq) | Some(Token::Ne) => {
+                    let op = self.next();
+                    let value = match self.next() {
+                        Some(Token::Ident(value)) | Some(Token::Literal(value)) => value,
+                        _ => return Err(self.error("expected value after comparison")),
+                    };
+                    if op == Some(Token::Eq) {
+                        Ok(Condition::Eq(name, value))
+                    } else {
+                        Ok(Condition::Ne(name, value))
+                    }
+                }
+                _ => Ok(Condition::Truthy(name)),
+            },
+            _ => Err(self.error("expected variable name")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_conditions() {
+        let variables = vars(&[("useRouter", "true"), ("style", "scss"), ("usePinia", "no")]);
+
+        assert!(evaluate("useRouter == true", &variables).unwrap());
+        assert!(evaluate("useRouter", &variables).unwrap());
+        assert!(!evaluate("usePinia", &variables).unwrap());
+        assert!(evaluate("!usePinia && style == 'scss'", &variables).unwrap());
+        assert!(evaluate("style != css || missing", &variables).unwrap());
+        assert!(evaluate("missing == false", &variables).unwrap());
+        assert!(!evaluate("(useRouter && usePinia) || style == \"less\"", &variables).unwrap());
+    }
+
+    #[test]
+    fn test_parse_invalid_condition() {
+        assert!(Condition::parse("useRouter ==").is_err());
+        assert!(Condition::parse("(useRouter").is_err());
+        assert!(Condition::parse("a && && b").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! 内容寻址缓存模块
+//! 远程模板归档按 sha256 存放并解压一次，多个注册表引用同一归档时只下载、解压一份；总大小超出上限时按最近使用时间淘汰
+
+use crate::cache_lock::CacheLock;
+use crate::{CacheStats, GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 索引文件名（位于缓存根目录）
+pub const CACHE_INDEX_FILE: &str = "cache-index.json";
+/// 解压内容所在的子目录
+const CONTENT_DIR: &str = "content";
+
+/// 单个缓存条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// 解压后的字节数
+    pub size: u64,
+    /// 最近一次使用的 Unix 时间（秒）
+    pub last_used: u64,
+}
+
+/// 缓存索引：内容哈希到条目，以及别名（校验和、npm integrity、包版本）到内容哈希
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheIndex {
+    #[serde(default)]
+    pub entries: BTreeMap<String, CacheEntry>,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// 内容寻址缓存
+#[derive(Debug, Clone)]
+pub struct ContentCache {
+    pub root: PathBuf,
+    /// 总大小上限（字节）
+    pub max_size: Option<u64>,
+}
+
+impl ContentCache {
+    pub fn new(root: impl Into<PathBuf>, max_size: Option<u64>) -> Self {
+        Self {
+            root: root.into(),
+            max_size,
+        }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join(CACHE_INDEX_FILE)
+    }
+
+    fn content_dir(&self, hash: &str) -> PathBuf {
+        self.root.join(CONTENT_DIR).join(hash)
+    }
+
+    fn load_index(&self) -> Result<CacheIndex> {
+        let path = self.index_path();
+        if !path.is_file() {
+            return Ok(CacheIndex::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid cache index {}: {}", path.display(), e))
+        })
+    }
+
+    /// 先写临时文件再替换，避免中断时留下损坏的索引
+    fn save_index(&self, index: &CacheIndex) -> Result<()> {
+        fs::create_dir_all(&self.root)?;
+        let staging = tempfile::NamedTempFile::new_in(&self.root)?;
+        fs::write(staging.path(), serde_json::to_string_pretty(index)?)?;
+        staging
+            .persist(self.index_path())
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 按别名查找已解压的内容目录，命中时更新使用时间
+    pub fn lookup(&self, alias: &str) -> Result<Option<PathBuf>> {
+        let _lock = CacheLock::exclusive(&self.root)?;
+        let mut index = self.load_index()?;
+        let Some(hash) = index.aliases.get(alias).cloned() else {
+            return Ok(None);
+        };
+        let dir = self.content_dir(&hash);
+        let Some(entry) = index.entries.get_mut(&hash).filter(|_| dir.is_dir()) else {
+            // 内容已被手动删除，清理失效的别名
+            index.aliases.retain(|_, target| *target != hash);
+            index.entries.remove(&hash);
+            self.save_index(&index)?;
+            return Ok(None);
+        };
+        entry.last_used = now();
+        self.save_index(&index)?;
+        Ok(Some(dir))
+    }
+
+    /// 存入归档内容并记录别名，返回解压目录；相同内容已存在时直接复用
+    ///
+    /// `extract` 将归档解压到给定的临时目录，成功后才移入缓存。整个过程持有缓存目录锁，
+    /// 并发的进程不会同时改写索引或重复解压同一内容。
+    pub fn insert(
+        &self,
+        content: &[u8],
+        aliases: &[String],
+        extract: impl FnOnce(&[u8], &Path) -> Result<()>,
+    ) -> Result<PathBuf> {
+        let hash = hex::encode(Sha256::digest(content));
+        let dir = self.content_dir(&hash);
+        let _lock = CacheLock::exclusive(&self.root)?;
+        let mut index = self.load_index()?;
+
+        if !(dir.is_dir() && index.entries.contains_key(&hash)) {
+            let staging = tempfile::tempdir_in(&self.root)?;
+            extract(content, staging.path())?;
+            if dir.exists() {
+                fs::remove_dir_all(&dir)?;
+            }
+            fs::create_dir_all(self.root.join(CONTENT_DIR))?;
+            fs::rename(staging.keep(), &dir)?;
+            index.entries.insert(
+                hash.clone(),
+                CacheEntry {
+                    size: dir_size(&dir),
+                    last_used: 0,
+                },
+            );
+        }
+
+        if let Some(entry) = index.entries.get_mut(&hash) {
+            entry.last_used = now();
+        }
+        index
+            .aliases
+            .insert(format!("sha256:{}", hash), hash.clone());
+        for alias in aliases {
+            index.aliases.insert(alias.clone(), hash.clone());
+        }
+        self.evict(&mut index, &hash)?;
+        self.save_index(&index)?;
+        Ok(dir)
+    }
+
+    /// 总大小超出上限时按最近使用时间从旧到新删除，`keep` 不会被删除
+    fn evict(&self, index: &mut CacheIndex, keep: &str) -> Result<()> {
+        let Some(max_size) = self.max_size else {
+            return Ok(());
+        };
+        let mut total: u64 = index.entries.values().map(|entry| entry.size).sum();
+        let mut candidates: Vec<(String, u64)> = index
+            .entries
+            .iter()
+            .filter(|(hash, _)| hash.as_str() != keep)
+            .map(|(hash, entry)| (hash.clone(), entry.last_used))
+            .collect();
+        candidates.sort_by_key(|(_, last_used)| *last_used);
+
+        for (hash, _) in candidates {
+            if total <= max_size {
+                break;
+            }
+            let dir = self.content_dir(&hash);
+            if dir.exists() {
+                fs::remove_dir_all(&dir)?;
+            }
+            if let Some(entry) = index.entries.remove(&hash) {
+                total -= entry.size;
+            }
+            index.aliases.retain(|_, target| *target != hash);
+        }
+        Ok(())
+    }
+
+    /// 缓存统计
+    pub fn stats(&self) -> Result<CacheStats> {
+        let index = self.load_index()?;
+        Ok(CacheStats {
+            path: self.root.display().to_string(),
+            entries: index.entries.len() as u32,
+            aliases: index.aliases.len() as u32,
+            total_bytes: index.entries.values().map(|entry| entry.size).sum::<u64>() as i64,
+            max_bytes: self.max_size.map(|size| size as i64),
+        })
+    }
+}
+
+/// 归档中的模板根目录：解压目录本身，或唯一的顶层目录（如 npm 的 `package/`、GitHub 的 `repo-main/`）
+pub fn archive_root(dir: &Path, marker: &str) -> Option<PathBuf> {
+    if dir.join(marker).is_file() {
+        return Some(dir.to_path_buf());
+    }
+    let mut entries = fs::read_dir(dir).ok()?.filter_map(|entry| entry.ok());
+    let only = entries.next()?;
+    if entries.next().is_some() {
+        return None;
+    }
+    let root = only.path();
+    root.join(marker).is_file().then_some(root)
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_content(content: &[u8], dir: &Path) -> Result<()> {
+        fs::write(dir.join("data"), content)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_by_content_and_lru_eviction() {
+        let root = tempdir().unwrap();
+        let cache = ContentCache::new(root.path(), Some(10));
+
+        let first = cache
+            .insert(b"aaaaaa", &["npm:a@1.0.0".to_string()], write_content)
+            .unwrap();
+        // 同一内容经另一个注册表引用时不再解压
+        let mut extracted = false;
+        let again = cache
+            .insert(b"aaaaaa", &["https://mirror/a.tgz".to_string()], |_, _| {
+                extracted = true;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(first, again);
+        assert!(!extracted);
+        assert_eq!(cache.lookup("npm:a@1.0.0").unwrap(), Some(first.clone()));
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.total_bytes, 6);
+
+        // 超出 10 字节上限，淘汰最久未使用的内容
+        let second = cache
+            .insert(b"bbbbbb", &["npm:b@1.0.0".to_string()], write_content)
+            .unwrap();
+        assert!(second.is_dir());
+        assert!(!first.exists());
+        assert_eq!(cache.lookup("npm:a@1.0.0").unwrap(), None);
+        assert_eq!(cache.stats().unwrap().entries, 1);
+    }
+}
@@ -0,0 +1,190 @@
+//! Cookiecutter 兼容模块
+//! 没有 template.json 而有 `cookiecutter.json` 的目录按 cookiecutter 模板加载：变量取自 cookiecutter.json，用 Tera 引擎渲染 `{{cookiecutter.x}}`
+//!
+//! Tera 与 Jinja2 大体兼容，但不支持 Python 方法调用（如 `.lower()`），需改用过滤器（`| lower`）。
+//! cookiecutter 的 Python 钩子不会执行。
+
+use crate::pipeline::TEMPLATE_METADATA_FILE;
+use crate::render::COOKIECUTTER_NAMESPACE;
+use crate::template_metadata::{TemplateEngine, TemplateMetadata, TemplateVariable, VariableType};
+use crate::{GeneratorError, Result};
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// cookiecutter 的变量文件
+pub const COOKIECUTTER_FILE: &str = "cookiecutter.json";
+
+/// 目录是否为 cookiecutter 模板；同时存在 template.json 时以 template.json 为准
+pub fn is_cookiecutter_template(dir: &Path) -> bool {
+    !dir.join(TEMPLATE_METADATA_FILE).is_file() && dir.join(COOKIECUTTER_FILE).is_file()
+}
+
+/// 由 cookiecutter.json 构造模板元数据
+///
+/// 字符串、布尔值与数字成为带默认值的变量，列表成为以第一项为默认值的可选值，
+/// `_copy_without_render` 映射为 `raw`；其他以 `_` 开头的设置与字典变量被忽略。
+/// 默认值中的表达式（如 `{{ cookiecutter.project_name | slugify }}`）在生成时按先声明的变量渲染。
+pub fn load_metadata(dir: &Path) -> Result<TemplateMetadata> {
+    let path = dir.join(COOKIECUTTER_FILE);
+    // 按 YAML 解析以保留键的声明顺序，JSON 是 YAML 的子集
+    let context: serde_yaml::Mapping = serde_yaml::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e)))?;
+
+    let mut metadata = TemplateMetadata {
+        name: file_name(dir),
+        version: "0.0.0".to_string(),
+        project_type: dir.parent().map(file_name).unwrap_or_default(),
+        engine: TemplateEngine::Tera,
+        ..Default::default()
+    };
+    for (key, value) in context {
+        let Some(name) = key.as_str() else {
+            continue;
+        };
+        if name == "_copy_without_render" {
+            if let Value::Sequence(patterns) = value {
+                metadata.raw.extend(
+                    patterns
+                        .iter()
+                        .filter_map(|p| p.as_str().map(str::to_string)),
+                );
+            }
+            continue;
+        }
+        // `__` 开头的是不询问的私有变量，仍可在模板中引用
+        if name.starts_with('_') && !name.starts_with("__") {
+            continue;
+        }
+        let (var_type, default) = match value {
+            Value::String(value) => (VariableType::String, value),
+            Value::Bool(value) => (VariableType::Boolean, value.to_string()),
+            Value::Number(value) => (VariableType::Number, value.to_string()),
+            Value::Sequence(options) => {
+                let options: Vec<String> = options
+                    .iter()
+                    .filter_map(|option| match option {
+                        Value::String(option) => Some(option.clone()),
+                        Value::Bool(option) => Some(option.to_string()),
+                        Value::Number(option) => Some(option.to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                let Some(first) = options.first().cloned() else {
+                    continue;
+                };
+                (VariableType::Choice { options }, first)
+            }
+            _ => continue,
+        };
+        metadata.variables.push(TemplateVariable {
+            name: name.to_string(),
+            description: String::new(),
+            default: Some(default),
+            required: false,
+            var_type,
+            sensitive: false,
+        });
+    }
+    Ok(metadata)
+}
+
+/// 模板文件所在的目录，即名称中引用 cookiecutter 变量的子目录（如 `{{cookiecutter.project_slug}}`）
+pub fn project_dir(dir: &Path) -> Result<PathBuf> {
+    fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && file_name(path).contains("{{")
+                && file_name(path).contains(COOKIECUTTER_NAMESPACE)
+        })
+        .ok_or_else(|| {
+            GeneratorError::Configuration(format!(
+                "Cookiecutter template {} has no {{{{cookiecutter.*}}}} directory",
+                dir.display()
+            ))
+        })
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateOptions;
+    use crate::templates::generate_project_from_template;
+    use crate::variables::resolve_variables;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cookiecutter_template_renders_with_tera() {
+        let dir = tempdir().unwrap();
+        let template = dir.path().join("python").join("package");
+        let project = template.join("{{cookiecutter.project_slug}}");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            template.join(COOKIECUTTER_FILE),
+            r#"{
+                "project_name": "My Package",
+                "project_slug": "{{ cookiecutter.project_name | lower | replace(from=' ', to='_') }}",
+                "license": ["MIT", "BSD-3-Clause"],
+                "use_docker": false,
+                "_copy_without_render": ["*.html"],
+                "_extensions": ["jinja2_time.TimeExtension"]
+            }"#,
+        )
+        .unwrap();
+        assert!(is_cookiecutter_template(&template));
+        assert_eq!(project_dir(&template).unwrap(), project);
+
+        let metadata = load_metadata(&template).unwrap();
+        assert_eq!(
+            (metadata.name.as_str(), metadata.project_type.as_str()),
+            ("package", "python")
+        );
+        let names: Vec<_> = metadata.variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["project_name", "project_slug", "license", "use_docker"]
+        );
+        assert_eq!(metadata.raw, ["*.html"]);
+
+        let provided = HashMap::from([("license".to_string(), "bsd-3-clause".to_string())]);
+        let variables = resolve_variables(&metadata, &provided).unwrap();
+        assert_eq!(variables["project_slug"], "my_package");
+        assert_eq!(variables["license"], "BSD-3-Clause");
+
+        // 作为模板路径直接生成，只输出 `{{cookiecutter.*}}` 目录中的文件
+        fs::write(
+            project.join("{{ cookiecutter.project_slug }}.py"),
+            "# {{ cookiecutter.project_name }}{% if cookiecutter.use_docker %} docker{% endif %}",
+        )
+        .unwrap();
+        fs::write(project.join("index.html"), "{{ raw }}").unwrap();
+        let output = tempdir().unwrap();
+        generate_project_from_template(GenerateOptions {
+            name: "demo".to_string(),
+            project_type: "python".to_string(),
+            template: Some(template.to_string_lossy().to_string()),
+            output_path: Some(output.path().to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let generated = output.path().join("demo");
+        assert_eq!(
+            fs::read_to_string(generated.join("my_package.py")).unwrap(),
+            "# My Package"
+        );
+        assert_eq!(
+            fs::read_to_string(generated.join("index.html")).unwrap(),
+            "{{ raw }}"
+        );
+        assert!(!generated.join(COOKIECUTTER_FILE).exists());
+    }
+}
@@ -0,0 +1,351 @@
+//! 凭据存储模块
+//! 注册表通过凭据 ID 引用认证信息，依次从环境变量、系统钥匙串与权限受限的凭据文件中查找，配置文件中不再保存明文密钥
+
+use crate::utils::generator_home;
+use crate::{GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 环境变量凭据前缀：`GENERATOR_CREDENTIAL_<ID>` 为密钥，`..._USERNAME` 为用户名
+pub const CREDENTIAL_ENV_PREFIX: &str = "GENERATOR_CREDENTIAL_";
+/// 系统钥匙串中的服务名
+pub const KEYRING_SERVICE: &str = "generator";
+/// 凭据文件名（位于 `~/.generator`）
+pub const CREDENTIALS_FILE: &str = "credentials.json";
+
+/// 认证凭据
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Credential {
+    /// 用户名（Git / HTTP Basic 认证）
+    #[serde(default)]
+    pub username: Option<String>,
+    /// 令牌或密码
+    pub secret: String,
+}
+
+/// 调试输出中隐藏密钥
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credential")
+            .field("username", &self.username)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// 凭据来源
+pub trait CredentialProvider {
+    /// 来源名称，用于错误信息
+    fn name(&self) -> &'static str;
+
+    /// 按 ID 查找凭据，不存在时返回 None
+    fn get(&self, id: &str) -> Result<Option<Credential>>;
+}
+
+/// 从 `GENERATOR_CREDENTIAL_<ID>` 环境变量读取凭据
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvCredentials;
+
+impl EnvCredentials {
+    /// 凭据 ID 对应的环境变量名，非字母数字字符替换为 `_`
+    pub fn variable(id: &str) -> String {
+        let id: String = id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}{}", CREDENTIAL_ENV_PREFIX, id)
+    }
+
+    /// 用 `var` 读取凭据 ID 对应的变量及其 `_USERNAME` 变量
+    pub fn lookup(id: &str, var: impl Fn(&str) -> Option<String>) -> Option<Credential> {
+        let variable = Self::variable(id);
+        var(&variable).map(|secret| Credential {
+            username: var(&format!("{}_USERNAME", variable)),
+            secret,
+        })
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn name(&self) -> &'static str {
+        "environment"
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Credential>> {
+        Ok(Self::lookup(id, |name| std::env::var(name).ok()))
+    }
+}
+
+/// 系统钥匙串（macOS Keychain、Windows 凭据管理器、Linux 内核密钥环）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyringCredentials;
+
+impl KeyringCredentials {
+    fn entry(id: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(KEYRING_SERVICE, id).map_err(|e| {
+            GeneratorError::Configuration(format!("Keyring unavailable for '{}': {}", id, e))
+        })
+    }
+
+    /// 保存凭据
+    pub fn store(&self, id: &str, credential: &Credential) -> Result<()> {
+        Self::entry(id)?
+            .set_password(&serde_json::to_string(credential)?)
+            .map_err(|e| {
+                GeneratorError::Configuration(format!("Cannot store credential '{}': {}", id, e))
+            })
+    }
+
+    /// 删除凭据，不存在时返回 false
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        match Self::entry(id)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(GeneratorError::Configuration(format!(
+                "Cannot delete credential '{}': {}",
+                id, e
+            ))),
+        }
+    }
+}
+
+impl CredentialProvider for KeyringCredentials {
+    fn name(&self) -> &'static str {
+        "keyring"
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Credential>> {
+        let password = match Self::entry(id)?.get_password() {
+            Ok(password) => password,
+            // 没有可用的钥匙串时继续查找其他来源
+            Err(
+                keyring::Error::NoEntry
+                | keyring::Error::NoStorageAccess(_)
+                | keyring::Error::PlatformFailure(_),
+            ) => return Ok(None),
+            Err(e) => {
+                return Err(GeneratorError::Configuration(format!(
+                    "Cannot read credential '{}' from keyring: {}",
+                    id, e
+                )));
+            }
+        };
+
+        // 兼容直接保存的令牌字符串
+        Ok(Some(serde_json::from_str(&password).unwrap_or(
+            Credential {
+                username: None,
+                secret: password,
+            },
+        )))
+    }
+}
+
+/// JSON 凭据文件（`{"<id>": {"username": ..., "secret": ...}}`），Unix 下要求权限为 600
+#[derive(Debug, Clone)]
+pub struct FileCredentials {
+    pub path: PathBuf,
+}
+
+impl Default for FileCredentials {
+    fn default() -> Self {
+        Self {
+            path: generator_home().join(CREDENTIALS_FILE),
+        }
+    }
+}
+
+impl FileCredentials {
+    fn load(&self) -> Result<BTreeMap<String, Credential>> {
+        if !self.path.is_file() {
+            return Ok(BTreeMap::new());
+        }
+        check_permissions(&self.path)?;
+        let content = fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            GeneratorError::Configuration(format!(
+                "Invalid credentials file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+
+    fn save(&self, credentials: &BTreeMap<String, Credential>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&self.path)?;
+        file.write_all(serde_json::to_string_pretty(credentials)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// 保存凭据
+    pub fn store(&self, id: &str, credential: &Credential) -> Result<()> {
+        let mut credentials = self.load()?;
+        credentials.insert(id.to_string(), credential.clone());
+        self.save(&credentials)
+    }
+
+    /// 删除凭据，不存在时返回 false
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let mut credentials = self.load()?;
+        let removed = credentials.remove(id).is_some();
+        if removed {
+            self.save(&credentials)?;
+        }
+        Ok(removed)
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Credential>> {
+        Ok(self.load()?.remove(id))
+    }
+}
+
+/// 凭据文件可被其他用户读取时拒绝使用
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)?.permissions().mode();
+    if mode & 0o077 != 0 {
+        return Err(GeneratorError::Configuration(format!(
+            "Credentials file {} is accessible by other users (mode {:o}); run chmod 600",
+            path.display(),
+            mode & 0o777
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// 按顺序查询的凭据来源
+pub struct CredentialStore {
+    providers: Vec<Box<dyn CredentialProvider + Send + Sync>>,
+}
+
+/// 默认顺序：环境变量、系统钥匙串、凭据文件
+impl Default for CredentialStore {
+    fn default() -> Self {
+        Self::new(vec![
+            Box::new(EnvCredentials),
+            Box::new(KeyringCredentials),
+            Box::new(FileCredentials::default()),
+        ])
+    }
+}
+
+impl CredentialStore {
+    pub fn new(providers: Vec<Box<dyn CredentialProvider + Send + Sync>>) -> Self {
+        Self { providers }
+    }
+
+    /// 查找凭据，所有来源都没有时返回错误
+    pub fn resolve(&self, id: &str) -> Result<Credential> {
+        for provider in &self.providers {
+            if let Some(credential) = provider.get(id)? {
+                return Ok(credential);
+            }
+        }
+
+        let searched: Vec<&str> = self.providers.iter().map(|p| p.name()).collect();
+        Err(GeneratorError::Configuration(format!(
+            "Credential '{}' not found (searched: {}); set {} or store it with `generator-cli credential set {}`",
+            id,
+            searched.join(", "),
+            EnvCredentials::variable(id),
+            id
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// 以固定的变量代替进程环境，测试之间互不影响
+    struct FixedEnv(BTreeMap<String, String>);
+
+    impl CredentialProvider for FixedEnv {
+        fn name(&self) -> &'static str {
+            "environment"
+        }
+
+        fn get(&self, id: &str) -> Result<Option<Credential>> {
+            Ok(EnvCredentials::lookup(id, |name| self.0.get(name).cloned()))
+        }
+    }
+
+    #[test]
+    fn test_store_resolves_in_provider_order() {
+        let dir = tempdir().unwrap();
+        let file = FileCredentials {
+            path: dir.path().join(CREDENTIALS_FILE),
+        };
+        let credential = Credential {
+            username: Some("ci".to_string()),
+            secret: "file-token".to_string(),
+        };
+        file.store("corp-git", &credential).unwrap();
+
+        let env = FixedEnv(BTreeMap::from([(
+            "GENERATOR_CREDENTIAL_CORP_HTTP".to_string(),
+            "env-token".to_string(),
+        )]));
+        assert_eq!(
+            EnvCredentials::variable("corp-http"),
+            "GENERATOR_CREDENTIAL_CORP_HTTP"
+        );
+
+        let store = CredentialStore::new(vec![Box::new(env), Box::new(file.clone())]);
+        assert_eq!(store.resolve("corp-git").unwrap(), credential);
+        assert_eq!(store.resolve("corp-http").unwrap().secret, "env-token");
+        assert!(store.resolve("missing").is_err());
+        assert!(!format!("{:?}", credential).contains("file-token"));
+
+        assert!(file.delete("corp-git").unwrap());
+        assert!(!file.delete("corp-git").unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_world_readable_credentials_file_is_rejected() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CREDENTIALS_FILE);
+        fs::write(&path, r#"{"corp": {"secret": "token"}}"#).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let file = FileCredentials { path };
+        assert!(matches!(
+            file.get("corp"),
+            Err(GeneratorError::Configuration(_))
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! 数据库 CRUD 生成模块
+//! 按 JDBC 连接或 DDL 文件中的表结构，委托 Java CLI 为 java 项目生成 entity、repository 与 controller
+
+use crate::java_cli::{self, DatabaseSource, JavaCliConfig, JavaCrudOptions};
+use crate::template_registry::{TemplateMetadata, TemplateVariable, VariableType};
+use crate::{GeneratorError, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// JDBC 连接串
+pub const URL_VARIABLE: &str = "dbUrl";
+/// 数据库用户名
+pub const USERNAME_VARIABLE: &str = "dbUsername";
+/// 数据库密码
+pub const PASSWORD_VARIABLE: &str = "dbPassword";
+
+/// 声明数据库连接变量；连接串中可能带有凭据，三者均按敏感变量处理，不写入锁文件、报告与答案文件
+///
+/// 模板已声明的同名变量同样标记为敏感。
+pub fn declare_connection_variables(metadata: &mut TemplateMetadata) {
+    for (name, description) in [
+        (URL_VARIABLE, "数据库 JDBC 连接串"),
+        (USERNAME_VARIABLE, "数据库用户名"),
+        (PASSWORD_VARIABLE, "数据库密码"),
+    ] {
+        match metadata.variables.iter_mut().find(|v| v.name == name) {
+            Some(variable) => variable.sensitive = true,
+            None => metadata.variables.push(TemplateVariable {
+                name: name.to_string(),
+                description: description.to_string(),
+                default: None,
+                required: false,
+                var_type: VariableType::String,
+                sensitive: true,
+            }),
+        }
+    }
+}
+
+/// 一次 CRUD 代码生成
+#[derive(Debug, Clone)]
+pub struct CrudScaffold {
+    pub source: DatabaseSource,
+    /// 只生成这些表，为空时生成全部表
+    pub tables: Vec<String>,
+    pub java_cli: JavaCliConfig,
+}
+
+impl CrudScaffold {
+    /// 解析 `database` 选项：`jdbc:` 开头为连接串，用户名与密码取自连接变量；否则为 DDL 文件路径
+    pub fn new(
+        project_type: &str,
+        database: &str,
+        tables: Vec<String>,
+        variables: &HashMap<String, String>,
+        java_cli: JavaCliConfig,
+    ) -> Result<Self> {
+        if project_type != "java" {
+            return Err(GeneratorError::Configuration(format!(
+                "CRUD generation from a database schema is only available for java projects, not '{}'",
+                project_type
+            )));
+        }
+        let variable = |name: &str| {
+            variables
+                .get(name)
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+
+        let database = database.trim();
+        let source = if database.starts_with("jdbc:") {
+            DatabaseSource::Jdbc {
+                url: database.to_string(),
+                username: variable(USERNAME_VARIABLE),
+                password: variable(PASSWORD_VARIABLE),
+            }
+        } else {
+            let path = Path::new(database);
+            if !path.is_file() {
+                return Err(GeneratorError::FileOperation(format!(
+                    "DDL file not found: {}",
+                    database
+                )));
+            }
+            DatabaseSource::Ddl(path.canonicalize()?)
+        };
+
+        Ok(Self {
+            source,
+            tables: tables
+                .into_iter()
+                .map(|table| table.trim().to_string())
+                .filter(|table| !table.is_empty())
+                .collect(),
+            java_cli,
+        })
+    }
+
+    /// 生成 CRUD 代码，返回写入的文件（相对项目目录）
+    pub fn scaffold(
+        &self,
+        project_dir: &Path,
+        variables: &HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let generated = java_cli::block_on(java_cli::generate_crud(
+            self.options(project_dir, variables.get("package").cloned()),
+        ))?;
+
+        Ok(generated
+            .into_iter()
+            .map(PathBuf::from)
+            .map(|path| match path.strip_prefix(project_dir) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => path,
+            })
+            .collect())
+    }
+
+    fn options(&self, project_dir: &Path, package_name: Option<String>) -> JavaCrudOptions {
+        JavaCrudOptions {
+            source: self.source.clone(),
+            tables: self.tables.clone(),
+            package_name,
+            output_path: project_dir.to_path_buf(),
+            cli: self.java_cli.clone(),
+            progress: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variables::without_sensitive;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_crud_source_keeps_credentials_out_of_args_and_lock() {
+        let variables = HashMap::from([
+            (
+                URL_VARIABLE.to_string(),
+                "jdbc:mysql://db:3306/shop".to_string(),
+            ),
+            (USERNAME_VARIABLE.to_string(), "root".to_string()),
+            (PASSWORD_VARIABLE.to_string(), "s3cret".to_string()),
+            ("package".to_string(), "com.example.shop".to_string()),
+        ]);
+        let crud = CrudScaffold::new(
+            "java",
+            "jdbc:mysql://db:3306/shop",
+            vec!["orders".to_string(), " ".to_string(), "items".to_string()],
+            &variables,
+            JavaCliConfig::default(),
+        )
+        .unwrap();
+
+        let (args, envs) = crud
+            .options(Path::new("/out"), variables.get("package").cloned())
+            .command();
+        assert_eq!(
+            args,
+            [
+                "crud",
+                "--jdbc-url",
+                "jdbc:mysql://db:3306/shop",
+                "--username",
+                "root",
+                "--password-env",
+                java_cli::DB_PASSWORD_ENV,
+                "--tables",
+                "orders,items",
+                "--package",
+                "com.example.shop",
+                "--output",
+                "/out",
+            ]
+        );
+        assert_eq!(
+            envs,
+            vec![(java_cli::DB_PASSWORD_ENV.to_string(), "s3cret".to_string())]
+        );
+
+        let mut metadata = TemplateMetadata::default();
+        declare_connection_variables(&mut metadata);
+        let kept = without_sensitive(&metadata, &variables);
+        assert_eq!(kept.keys().collect::<Vec<_>>(), vec!["package"]);
+
+        let dir = tempdir().unwrap();
+        let ddl = dir.path().join("schema.sql");
+        std::fs::write(&ddl, "CREATE TABLE orders (id BIGINT PRIMARY KEY);").unwrap();
+        let crud = CrudScaffold::new(
+            "java",
+            ddl.to_str().unwrap(),
+            Vec::new(),
+            &HashMap::new(),
+            JavaCliConfig::default(),
+        )
+        .unwrap();
+        assert!(matches!(crud.source, DatabaseSource::Ddl(_)));
+        assert!(
+            CrudScaffold::new(
+                "vue",
+                "schema.sql",
+                Vec::new(),
+                &variables,
+                JavaCliConfig::default()
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,401 @@
+//! 守护进程模块
+//! `generator-cli serve` 在 stdio 上按行收发 JSON-RPC 2.0 消息，模板索引常驻内存，IDE 插件列出与搜索模板时无需重新启动进程
+
+use crate::events::{self, Event, Listener};
+use crate::job_history::{JobRecord, JobState, JobStore};
+use crate::template_index::{IndexEntry, TemplateIndex};
+use crate::template_registry::{TemplateRegistryConfig, TemplateVariable};
+use crate::templates::with_builtin_templates;
+use crate::{ErrorDetails, GenerateOptions, GeneratorError, IndexStats, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+/// JSON-RPC 标准错误码
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// 生成器错误，`data` 为结构化错误详情
+const GENERATOR_ERROR: i64 = -32000;
+
+/// 守护进程的输出通道，响应与通知共用
+pub type Output = Arc<Mutex<dyn Write + Send>>;
+
+/// 搜索命中的模板
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TemplateMatch {
+    pub project_type: String,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Request {
+    /// 没有 id 的请求是通知，不回复
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct ListParams {
+    project_type: String,
+}
+
+#[derive(Deserialize)]
+struct JobsParams {
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct JobParams {
+    id: u64,
+    /// rerun 时补充或覆盖的变量
+    #[serde(default)]
+    variables: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    query: String,
+    #[serde(default)]
+    project_type: Option<String>,
+}
+
+/// JSON-RPC 错误对象
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn generator(error: GeneratorError) -> Self {
+        Self::details(error.details(None, None))
+    }
+
+    fn details(details: ErrorDetails) -> Self {
+        Self {
+            code: GENERATOR_ERROR,
+            message: details.message.clone(),
+            data: serde_json::to_value(details).ok(),
+        }
+    }
+}
+
+/// 常驻内存的模板索引与请求处理
+///
+/// 方法：`list`、`search`、`generate`、`refresh`（重新扫描注册表）、`jobs` / `job`（生成历史）、
+/// `rerun`（按历史参数重新生成）与 `shutdown`。
+pub struct Daemon {
+    index: TemplateIndex,
+    entries: Vec<IndexEntry>,
+    jobs: JobStore,
+    running: bool,
+}
+
+impl Daemon {
+    /// 构建索引并载入内存，生成任务记录到 `jobs`
+    pub fn new(index: TemplateIndex, jobs: JobStore) -> Result<Self> {
+        let (entries, _) = index.refresh(false)?;
+        Ok(Self {
+            index,
+            entries,
+            jobs,
+            running: true,
+        })
+    }
+
+    /// 使用用户级注册表配置与默认的任务记录位置
+    pub fn from_config() -> Result<Self> {
+        Self::new(
+            TemplateIndex::from_config(&TemplateRegistryConfig::user()?),
+            JobStore::default(),
+        )
+    }
+
+    /// 收到 `shutdown` 之前为真
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// 项目类型的模板，内置模板在前
+    pub fn list(&self, project_type: &str) -> Vec<String> {
+        let entries = self
+            .entries
+            .iter()
+            .filter(|entry| entry.project_type == project_type)
+            .cloned()
+            .collect();
+        with_builtin_templates(project_type, entries)
+    }
+
+    /// 名称、描述或标签包含 `query`（不区分大小写）的模板
+    pub fn search(&self, query: &str, project_type: Option<&str>) -> Vec<TemplateMatch> {
+        let query = query.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                project_type.is_none_or(|project_type| entry.project_type == project_type)
+            })
+            .filter(|entry| {
+                let metadata = &entry.metadata;
+                metadata.name.to_lowercase().contains(&query)
+                    || metadata.description.to_lowercase().contains(&query)
+                    || metadata
+                        .tags
+                        .iter()
+                        .any(|tag| tag.to_lowercase().contains(&query))
+            })
+            .map(|entry| TemplateMatch {
+                project_type: entry.project_type.clone(),
+                name: entry.metadata.name.clone(),
+                description: entry.metadata.description.clone(),
+                tags: entry.metadata.tags.clone(),
+            })
+            .collect()
+    }
+
+    /// 重新扫描注册表，只解析变化的模板
+    pub fn refresh(&mut self) -> Result<IndexStats> {
+        let (entries, stats) = self.index.refresh(false)?;
+        self.entries = entries;
+        Ok(stats)
+    }
+
+    /// 处理一行请求，返回要写回的响应；通知没有响应
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Request>(line) {
+            Ok(request) => {
+                let result = self.call(&request.method, request.params);
+                request.id.map(|id| response(id, result))
+            }
+            Err(e) => Some(response(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, e.to_string())),
+            )),
+        };
+        response.map(|response| response.to_string())
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        match method {
+            "list" => {
+                let params: ListParams = parse_params(params)?;
+                Ok(json!(self.list(&params.project_type)))
+            }
+            "search" => {
+                let params: SearchParams = parse_params(params)?;
+                Ok(json!(
+                    self.search(&params.query, params.project_type.as_deref())
+                ))
+            }
+            "generate" => {
+                let options: GenerateOptions = parse_params(params)?;
+                let record = self
+                    .jobs
+                    .create(&options, None)
+                    .and_then(|record| self.jobs.run(record, options))
+                    .map_err(RpcError::generator)?;
+                job_result(record)
+            }
+            "refresh" => self
+                .refresh()
+                .map(|stats| json!(stats))
+                .map_err(RpcError::generator),
+            "jobs" => {
+                let params: JobsParams = parse_params(params)?;
+                self.jobs
+                    .list(params.limit)
+                    .map(|records| json!(records))
+                    .map_err(RpcError::generator)
+            }
+            "job" => {
+                let params: JobParams = parse_params(params)?;
+                self.jobs
+                    .get(params.id)
+                    .map(|record| json!(record))
+                    .map_err(RpcError::generator)
+            }
+            "rerun" => {
+                let params: JobParams = parse_params(params)?;
+                self.jobs
+                    .rerun(params.id, params.variables)
+                    .map(|record| json!(record))
+                    .map_err(RpcError::generator)
+            }
+            "shutdown" => {
+                self.running = false;
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {}", method),
+            )),
+        }
+    }
+}
+
+/// 从 `input` 逐行读取请求直到输入结束或收到 `shutdown`
+///
+/// 处理期间的日志与阶段进度以 `log`、`progress` 通知写入 `output`。
+pub fn serve(daemon: &mut Daemon, input: impl BufRead, output: Output) -> Result<()> {
+    let notifier: Arc<dyn Listener> = Arc::new(Notifier(output.clone()));
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = events::with_listener(notifier.clone(), || daemon.handle(&line)) {
+            write_line(&output, &response)?;
+        }
+        if !daemon.is_running() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// 把生成事件转为 JSON-RPC 通知；守护进程不交互，缺少的变量按缺少报错
+struct Notifier(Output);
+
+impl Listener for Notifier {
+    fn event(&self, event: Event) {
+        let (method, params) = match event {
+            Event::Progress { phase } => ("progress", json!({ "phase": phase })),
+            Event::Log { message } => ("log", json!({ "message": message })),
+        };
+        let notification = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        // 通知写失败时客户端已断开，由下一次响应报告错误
+        let _ = write_line(&self.0, &notification.to_string());
+    }
+
+    fn prompt(&self, _variable: &TemplateVariable) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// generate 的响应：成功时为生成结果，失败时为带错误详情的错误
+fn job_result(record: JobRecord) -> std::result::Result<Value, RpcError> {
+    match (record.status, record.result, record.error) {
+        (JobState::Succeeded, Some(result), _) => Ok(json!(result)),
+        (_, _, Some(details)) => Err(RpcError::details(details)),
+        _ => Ok(Value::Null),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> std::result::Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn response(id: Value, result: std::result::Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+fn write_line(output: &Output, line: &str) -> Result<()> {
+    let mut output = output.lock().unwrap_or_else(|e| e.into_inner());
+    writeln!(output, "{}", line)?;
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use crate::template_index::INDEX_FILE;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_json_rpc_requests() {
+        let root = tempdir().unwrap();
+        let cache = tempdir().unwrap();
+        let dir = root.path().join("vue/admin");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(TEMPLATE_METADATA_FILE),
+            json!({
+                "name": "admin",
+                "version": "1.0.0",
+                "description": "Admin dashboard",
+                "author": "test",
+                "project_type": "vue",
+                "variables": [],
+                "dependencies": [],
+                "tags": ["Element-Plus"],
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let mut daemon = Daemon::new(
+            TemplateIndex {
+                path: cache.path().join(INDEX_FILE),
+                roots: vec![root.path().to_path_buf()],
+            },
+            JobStore {
+                dir: cache.path().join("jobs"),
+            },
+        )
+        .unwrap();
+
+        let input = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"list","params":{"project_type":"vue"}}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"search","params":{"query":"element"}}"#,
+            r#"{"jsonrpc":"2.0","method":"refresh"}"#,
+            r#"{"jsonrpc":"2.0","id":3,"method":"list"}"#,
+            r#"{"jsonrpc":"2.0","id":4,"method":"render"}"#,
+            "not json",
+            r#"{"jsonrpc":"2.0","id":5,"method":"generate","params":{"name":"app","project_type":"cobol"}}"#,
+            r#"{"jsonrpc":"2.0","id":6,"method":"jobs","params":{"limit":5}}"#,
+            r#"{"jsonrpc":"2.0","id":7,"method":"shutdown"}"#,
+            r#"{"jsonrpc":"2.0","id":8,"method":"list","params":{"project_type":"vue"}}"#,
+        ]
+        .join("\n");
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        serve(&mut daemon, input.as_bytes(), buffer.clone()).unwrap();
+
+        let output = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        // 生成过程中的 progress 通知没有 id
+        let responses: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .filter(|message| message.get("id").is_some())
+            .collect();
+        assert_eq!(responses.len(), 8);
+        assert_eq!(responses[0]["result"], json!(["nop", "admin"]));
+        assert_eq!(responses[1]["result"][0]["name"], "admin");
+        assert_eq!(responses[2]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(responses[3]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(responses[4]["error"]["code"], PARSE_ERROR);
+        assert_eq!(responses[5]["error"]["code"], GENERATOR_ERROR);
+        assert_eq!(responses[5]["error"]["data"]["code"], "TEMPLATE_NOT_FOUND");
+        assert_eq!(responses[6]["result"][0]["status"], "failed");
+        assert_eq!(
+            responses[6]["result"][0]["options"]["project_type"],
+            "cobol"
+        );
+        assert_eq!(responses[7]["id"], 7);
+        assert!(!daemon.is_running());
+    }
+}
@@ -0,0 +1,622 @@
+//! 委托生成模块
+//! 已知项目类型可以改用官方初始化工具生成（vue / react 使用 `npm create vite@latest`，java 使用 Spring Initializr）：规范化生成选项、在暂存目录中执行工具，再叠加覆盖模板并补充锁文件与生成报告
+
+use crate::build_wrapper::BuildTool;
+use crate::{cancel, command_audit};
+use crate::extract::Extractor;
+use crate::lockfile::{self, LockSource, ProjectLock};
+use crate::network::NetworkConfig;
+use crate::pipeline::{self, RenderReport};
+use crate::report::{GenerationReport, PhaseTimer, ReportTemplate};
+use crate::template_registry::TemplateRegistryConfig;
+use crate::templates::{
+    BUILD_TOOL_VARIABLE, build_render_options, build_variables, project_dir, resolve_template,
+    resolve_timeouts, select_build_tool,
+};
+use crate::transaction::{CommitMode, StagedOutput};
+use crate::variables::{redact_sensitive, resolve_variables, without_sensitive};
+use crate::workspace;
+use crate::{
+    GenerateOptions, GenerateResult, GenerationStats, GeneratorError, PhaseTimeouts, Result,
+};
+use std::collections::HashMap;
+use std::env;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+use walkdir::WalkDir;
+
+/// java 项目的 Spring Initializr 模板名称：选择它即委托给 Spring Initializr，模板本身作为覆盖层
+pub const SPRING_INITIALIZR_TEMPLATE: &str = "spring-initializr";
+
+/// Spring Initializr 服务地址，默认为 [`SPRING_INITIALIZR_URL`]，可指向私有部署
+pub const SPRING_INITIALIZR_URL_ENV: &str = "GENERATOR_SPRING_INITIALIZR_URL";
+
+/// 公共的 Spring Initializr 服务
+pub const SPRING_INITIALIZR_URL: &str = "https://start.spring.io";
+
+/// 官方初始化工具
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Initializer {
+    /// `npm create vite@latest . -- --template <template>`
+    Vite { template: String },
+    /// Spring Initializr 的 `starter.zip`
+    SpringInitializr(SpringProject),
+}
+
+/// Spring Initializr 的项目参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpringProject {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub name: String,
+    /// 未设置时由 Initializr 按 groupId 与 artifactId 推导
+    pub package_name: Option<String>,
+    /// 依赖 id，如 `web`、`data-jpa`
+    pub dependencies: Vec<String>,
+    pub java_version: Option<String>,
+    /// 未设置时使用 Initializr 的默认版本
+    pub boot_version: Option<String>,
+    /// Maven 或 Gradle（Kotlin DSL）项目
+    pub build_tool: BuildTool,
+    /// Initializr 服务地址，不带末尾的 `/`
+    pub service_url: String,
+}
+
+impl SpringProject {
+    /// 由变量 `groupId`、`artifactId`、`packageName`、`dependencies`（逗号或空白分隔）、`javaVersion`、`bootVersion`、`buildTool` 构造，
+    /// 从 `service_url` 下载项目
+    pub fn from_variables(
+        name: &str,
+        variables: &HashMap<String, String>,
+        service_url: &str,
+    ) -> Result<Self> {
+        let value = |key: &str| {
+            variables
+                .get(key)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        Ok(Self {
+            group_id: value("groupId").unwrap_or("com.example".to_string()),
+            artifact_id: value("artifactId").unwrap_or(name.to_string()),
+            name: name.to_string(),
+            package_name: value("packageName"),
+            dependencies: value("dependencies")
+                .map(|dependencies| {
+                    dependencies
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|dependency| !dependency.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            java_version: value("javaVersion"),
+            boot_version: value("bootVersion"),
+            build_tool: match value(BUILD_TOOL_VARIABLE) {
+                Some(tool) => tool.parse()?,
+                None => BuildTool::Maven,
+            },
+            service_url: service_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// `starter.zip` 的查询参数
+    pub fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![
+            (
+                "type",
+                match self.build_tool {
+                    BuildTool::Maven => "maven-project",
+                    BuildTool::Gradle => "gradle-project-kotlin",
+                }
+                .to_string(),
+            ),
+            ("language", "java".to_string()),
+            ("groupId", self.group_id.clone()),
+            ("artifactId", self.artifact_id.clone()),
+            ("name", self.name.clone()),
+        ];
+        if let Some(package_name) = &self.package_name {
+            query.push(("packageName", package_name.clone()));
+        }
+        if !self.dependencies.is_empty() {
+            query.push(("dependencies", self.dependencies.join(",")));
+        }
+        if let Some(java_version) = &self.java_version {
+            query.push(("javaVersion", java_version.clone()));
+        }
+        if let Some(boot_version) = &self.boot_version {
+            query.push(("bootVersion", boot_version.clone()));
+        }
+        query
+    }
+}
+
+/// 是否委托生成：设置了 `delegate`，或 java 项目选择了 [`SPRING_INITIALIZR_TEMPLATE`]
+pub fn requested(options: &GenerateOptions) -> bool {
+    options.delegate.unwrap_or(false)
+        || (options.project_type == "java"
+            && options.template.as_deref() == Some(SPRING_INITIALIZR_TEMPLATE))
+}
+
+/// 环境变量中的 Spring Initializr 服务地址，默认为公共服务
+fn spring_initializr_url() -> String {
+    env::var(SPRING_INITIALIZR_URL_ENV)
+        .ok()
+        .filter(|url| !url.is_empty())
+        .unwrap_or(SPRING_INITIALIZR_URL.to_string())
+}
+
+impl Initializer {
+    /// 按项目类型与变量选择初始化工具；不支持委托的项目类型返回错误
+    ///
+    /// vite 的模板为 `vue` / `react`，`typescript` 变量不为 `false` 时使用 `-ts` 变体；
+    /// 也可以用 `template` 直接指定 vite 模板（如 `react-swc-ts`）。java 项目的参数见 [`SpringProject::from_variables`]，
+    /// 服务地址为 `spring_url`。
+    pub fn for_options(
+        options: &GenerateOptions,
+        variables: &HashMap<String, String>,
+        spring_url: &str,
+    ) -> Result<Self> {
+        match options.project_type.as_str() {
+            "vue" | "react" => {
+                let typescript = variables
+                    .get("typescript")
+                    .is_none_or(|value| value != "false");
+                let template = match options.template.as_deref() {
+                    Some(template) if template != "basic" => template.to_string(),
+                    _ if typescript => format!("{}-ts", options.project_type),
+                    _ => options.project_type.clone(),
+                };
+                Ok(Initializer::Vite { template })
+            }
+            "java" => Ok(Initializer::SpringInitializr(
+                SpringProject::from_variables(&options.name, variables, spring_url)?,
+            )),
+            project_type => Err(GeneratorError::Configuration(format!(
+                "Project type '{}' has no official initializer to delegate to",
+                project_type
+            ))),
+        }
+    }
+
+    /// 锁文件与报告中的模板名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Initializer::Vite { .. } => "create-vite",
+            Initializer::SpringInitializr(_) => SPRING_INITIALIZR_TEMPLATE,
+        }
+    }
+
+    /// 锁文件与报告中的模板版本
+    pub fn version(&self) -> String {
+        match self {
+            Initializer::Vite { template } => format!("latest ({})", template),
+            Initializer::SpringInitializr(project) => {
+                project.boot_version.clone().unwrap_or("latest".to_string())
+            }
+        }
+    }
+
+    /// 叠加在工具输出之上的本地模板（`<project_type>/<name>`），不存在时只使用工具的输出
+    pub fn overlay(project_type: &str) -> Option<&'static str> {
+        match project_type {
+            "java" => Some(SPRING_INITIALIZR_TEMPLATE),
+            _ => None,
+        }
+    }
+
+    /// 模板来源，`kind` 为 `initializer`
+    pub fn source(&self) -> LockSource {
+        LockSource {
+            kind: "initializer".to_string(),
+            location: match self {
+                Initializer::Vite { .. } => "npm create vite@latest".to_string(),
+                Initializer::SpringInitializr(project) => project.service_url.clone(),
+            },
+            revision: None,
+        }
+    }
+
+    /// 在 `dir` 中生成项目：vite 执行命令，Spring Initializr 按 `network` 下载并解压 `starter.zip`
+    pub fn run(&self, dir: &Path, timeouts: &PhaseTimeouts, network: &NetworkConfig) -> Result<()> {
+        match self {
+            Initializer::Vite { template } => {
+                let mut command = Command::new(if cfg!(windows) { "npm.cmd" } else { "npm" });
+                command
+                    .args(["create", "vite@latest", ".", "--", "--template", template])
+                    .current_dir(dir);
+                self.run_command(command, PhaseTimeouts::duration(timeouts.hook_secs))
+            }
+            Initializer::SpringInitializr(project) => {
+                let archive = download_starter(
+                    project,
+                    network,
+                    PhaseTimeouts::duration(timeouts.http_download_secs),
+                )?;
+                // 归档中的文件位于 `<artifactId>/` 下
+                Extractor {
+                    strip_components: 1,
+                    ..Default::default()
+                }
+                .extract(&archive, dir)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// 执行命令，非零退出码视为失败
+    fn run_command(
+        &self,
+        mut command: Command,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<()> {
+        let output = cancel::output_with_timeout(&mut command, timeout).map_err(|e| match e {
+            GeneratorError::Io(e) => {
+                GeneratorError::ExternalCommand(format!("{}: {}", self.name(), e))
+            }
+            e => e,
+        })?;
+        if !output.status.success() {
+            return Err(GeneratorError::ExternalCommand(format!(
+                "{} failed: {}",
+                self.name(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 下载 Spring Initializr 生成的项目归档
+fn download_starter(
+    project: &SpringProject,
+    network: &NetworkConfig,
+    timeout: Option<std::time::Duration>,
+) -> Result<Vec<u8>> {
+    cancel::check()?;
+    let url = format!("{}/starter.zip", project.service_url);
+    let network = network.clone().resolve();
+    let agent = network.http_agent(&url, timeout)?;
+    let query = project.query();
+    command_audit::record_remote("http", &["GET", &url])?;
+    let request = || {
+        query.iter().fold(agent.get(&url), |request, (key, value)| {
+            request.query(key, value)
+        })
+    };
+    let response = network.retry_policy().call(&url, request, |e| match e {
+        // 依赖 id 或版本无效时 Initializr 返回 400 与说明
+        ureq::Error::Status(400, response) => GeneratorError::Configuration(format!(
+            "Spring Initializr rejected the project options: {}",
+            response.into_string().unwrap_or_default()
+        )),
+        e => GeneratorError::FileOperation(format!("Failed to download {}: {}", url, e)),
+    })?;
+    let mut body = Vec::new();
+    response.into_reader().read_to_end(&mut body)?;
+    Ok(body)
+}
+
+/// 用官方初始化工具生成项目，返回结果与生成报告
+///
+/// 只支持新建项目；工具的输出与覆盖模板渲染的文件作为基线快照与文件清单写入锁文件，后续更新可以与之三方合并。
+pub(crate) fn generate(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> Result<(GenerateResult, GenerationReport)> {
+    generate_with(options, config, &spring_initializr_url())
+}
+
+/// 与 [`generate`] 相同，java 项目从 `spring_url` 的 Spring Initializr 服务下载
+pub(crate) fn generate_with(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+    spring_url: &str,
+) -> Result<(GenerateResult, GenerationReport)> {
+    if options.into_existing.unwrap_or(false) {
+        return Err(GeneratorError::Configuration(
+            "Delegated generation cannot merge into an existing directory".to_string(),
+        ));
+    }
+    let mut timer = PhaseTimer::default();
+    let started = Instant::now();
+
+    // 覆盖模板声明的变量（如 groupId、dependencies）同样按默认值补全并校验
+    let provided = build_variables(&options)?;
+    let overlay = match Initializer::overlay(&options.project_type) {
+        Some(name) => match timer.time("resolve", || {
+            resolve_template(config, &options.project_type, name)
+        }) {
+            Ok(template) => Some(template),
+            Err(GeneratorError::TemplateNotFound(_)) => None,
+            Err(e) => return Err(e),
+        },
+        None => None,
+    };
+    let metadata = overlay
+        .as_ref()
+        .map(|template| template.metadata.clone())
+        .unwrap_or_default();
+    let mut variables = match &overlay {
+        Some(template) => resolve_variables(&template.metadata, &provided)?,
+        None => provided,
+    };
+    select_build_tool(&options, &metadata, &mut variables)?;
+    let initializer = Initializer::for_options(&options, &variables, spring_url)?;
+
+    let base_dir = workspace::resolve_output_dir(
+        options.output_path.as_deref(),
+        options.workspace_root.as_deref(),
+    )?;
+    let output_dir = project_dir(&base_dir, &options.name)?;
+    workspace::ensure_writable(&output_dir)?;
+    let staged = StagedOutput::begin(
+        &output_dir,
+        CommitMode::Create,
+        options.keep_partial.unwrap_or(false),
+    )?;
+
+    let timeouts = resolve_timeouts(&options, config);
+    let mut stage = || -> Result<_> {
+        timer.time("initializer", || {
+            initializer.run(staged.path(), &timeouts, &config.network)
+        })?;
+        if let Some(template) = &overlay {
+            let render_options = build_render_options(&options)?;
+            timer.time("render", || {
+                pipeline::render_template_dir(
+                    &template.dir,
+                    staged.path(),
+                    &template.metadata,
+                    &variables,
+                    &render_options,
+                )
+            })?;
+        }
+        timer.time("scan", || scan(staged.path()))
+    };
+    let report = match stage() {
+        Ok(report) => report,
+        Err(e) => return Err(staged.fail(e)),
+    };
+
+    let lock = ProjectLock {
+        name: options.name.clone(),
+        project_type: options.project_type.clone(),
+        template: initializer.name().to_string(),
+        template_version: initializer.version(),
+        variables: without_sensitive(&metadata, &variables),
+        features: Vec::new(),
+        source: initializer.source(),
+        generator_version: String::new(),
+        generated_at: String::new(),
+        openapi: None,
+        files: Default::default(),
+    }
+    .stamp();
+    let written = timer.time("lockfile", || -> Result<()> {
+        lockfile::write_base_snapshot(staged.path(), &report.files)?;
+        let mut lock = lock.clone();
+        lock.files = lockfile::file_manifest(staged.path(), &report.files)?;
+        lock.write(staged.path())
+    });
+    if let Err(e) = written {
+        return Err(staged.fail(e));
+    }
+    timer.time("commit", || staged.commit())?;
+
+    let files: Vec<String> = report
+        .files
+        .iter()
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    let report_files = files.clone();
+    let result = GenerateResult {
+        success: true,
+        files: files
+            .into_iter()
+            .map(|path| format!("{}/{}", options.name, path))
+            .collect(),
+        message: Some(format!(
+            "项目 {} 生成成功 (委托给: {})",
+            options.name,
+            initializer.source().location
+        )),
+        stats: Some(GenerationStats::from_report(&report, started.elapsed())),
+        template_version: Some(initializer.version()),
+        warnings: Vec::new(),
+        action: Some("created".to_string()),
+    };
+    let report = GenerationReport {
+        project: options.name.clone(),
+        output_dir: output_dir.display().to_string(),
+        template: ReportTemplate {
+            project_type: options.project_type.clone(),
+            name: initializer.name().to_string(),
+            version: initializer.version(),
+            source: initializer.source(),
+        },
+        variables: redact_sensitive(&metadata, &variables),
+        features: Vec::new(),
+        files: report_files,
+        provenance: Default::default(),
+        hooks: Vec::new(),
+        phases: timer.phases,
+        warnings: Vec::new(),
+        retries: Vec::new(),
+        generator_version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    };
+    Ok((result, report))
+}
+
+/// 工具生成的文件（相对项目目录），不含依赖目录与 git 仓库
+fn scan(dir: &Path) -> Result<RenderReport> {
+    let mut report = RenderReport::default();
+    let walker = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !matches!(entry.file_name().to_str(), Some("node_modules" | ".git")));
+    for entry in walker {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if entry.file_type().is_file() {
+            let relative: PathBuf = entry
+                .path()
+                .strip_prefix(dir)
+                .unwrap_or(entry.path())
+                .into();
+            report.bytes_written += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            report.files.push(relative);
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_audit::{CommandPolicy, with_audit};
+    use crate::templates::generate_project_from_template;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_delegate_to_official_initializer() {
+        let options = |project_type: &str, variables: &[(&str, &str)]| GenerateOptions {
+            name: "app".to_string(),
+            project_type: project_type.to_string(),
+            variables: Some(
+                variables
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            ..Default::default()
+        };
+        let initializer = |options: GenerateOptions| {
+            let variables = options.variables.clone().unwrap_or_default();
+            Initializer::for_options(&options, &variables, SPRING_INITIALIZR_URL)
+        };
+        assert_eq!(
+            initializer(options("vue", &[])).unwrap(),
+            Initializer::Vite {
+                template: "vue-ts".to_string()
+            }
+        );
+        assert_eq!(
+            initializer(options("react", &[("typescript", "false")])).unwrap(),
+            Initializer::Vite {
+                template: "react".to_string()
+            }
+        );
+        assert!(initializer(options("cobol", &[])).is_err());
+
+        // 审计模式下只记录命令，生成流程照常写入锁文件
+        let output = tempdir().unwrap();
+        let (result, commands) = with_audit(CommandPolicy::default(), || {
+            generate_project_from_template(GenerateOptions {
+                output_path: Some(output.path().to_string_lossy().to_string()),
+                delegate: Some(true),
+                ..options("react", &[])
+            })
+        });
+        let result = result.unwrap();
+        assert_eq!(result.action.as_deref(), Some("created"));
+        assert_eq!(commands.len(), 1);
+        assert_eq!(
+            commands[0].args,
+            ["create", "vite@latest", ".", "--", "--template", "react-ts"]
+        );
+        let lock = ProjectLock::read(&output.path().join("app")).unwrap();
+        assert_eq!(lock.template, "create-vite");
+        assert_eq!(lock.source.kind, "initializer");
+    }
+
+    /// 返回固定的 starter.zip，并把请求行发送到通道
+    fn serve_starter() -> (String, mpsc::Receiver<String>) {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for (path, content) in [
+            ("shop/pom.xml", "<project/>"),
+            (
+                "shop/src/main/java/com/acme/shop/ShopApplication.java",
+                "class ShopApplication {}",
+            ),
+        ] {
+            writer.start_file(path, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        let body = writer.finish().unwrap().into_inner();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut stream = listener.incoming().next().unwrap().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            sender.send(line.clone()).unwrap();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).unwrap();
+            stream.write_all(&body).unwrap();
+        });
+        (url, receiver)
+    }
+
+    #[test]
+    fn test_spring_initializr_with_overlay_template() {
+        let (url, requests) = serve_starter();
+        let output = tempdir().unwrap();
+        let options = GenerateOptions {
+            name: "shop".to_string(),
+            project_type: "java".to_string(),
+            template: Some(SPRING_INITIALIZR_TEMPLATE.to_string()),
+            output_path: Some(output.path().to_string_lossy().to_string()),
+            variables: Some(HashMap::from([
+                ("groupId".to_string(), "com.acme".to_string()),
+                ("dependencies".to_string(), "web, actuator".to_string()),
+            ])),
+            ..Default::default()
+        };
+        assert!(requested(&options));
+        let (result, _) = generate_with(
+            options,
+            &TemplateRegistryConfig::default(),
+            &format!("{}/", url),
+        )
+        .unwrap();
+        assert!(result.files.contains(&"shop/pom.xml".to_string()));
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /starter.zip?type=maven-project&"));
+        assert!(request.contains("groupId=com.acme&artifactId=shop"));
+        assert!(request.contains("dependencies=web%2Cactuator&javaVersion=17"));
+
+        // 覆盖模板的文件叠加在 Initializr 的输出之上
+        let project = output.path().join("shop");
+        assert!(
+            project
+                .join("src/main/java/com/acme/shop/ShopApplication.java")
+                .is_file()
+        );
+        let readme = std::fs::read_to_string(project.join("README.md")).unwrap();
+        assert!(readme.starts_with("# shop"));
+        assert!(readme.contains("`com.acme`"));
+        let lock = ProjectLock::read(&project).unwrap();
+        assert_eq!(lock.template, SPRING_INITIALIZR_TEMPLATE);
+        assert_eq!(lock.source.location, url);
+    }
+}
@@ -0,0 +1,286 @@
+//! 模板开发模块
+//! 监听模板目录并将变化实时渲染到预览目录：普通文件只重新渲染变化的部分，template.json、忽略规则或特性片段变化时整体重新渲染
+
+use crate::env_defaults::env_defaults;
+use crate::features::FEATURES_DIR;
+use crate::pipeline::{self, HOOKS_DIR, RenderOptions, RenderReport, TEMPLATE_METADATA_FILE};
+use crate::render::TemplateRenderer;
+use crate::template_helpers::PARTIALS_DIR;
+use crate::template_ignore::IGNORE_FILE_NAME;
+use crate::template_registry::{TemplateMetadata, TemplateRegistryConfig};
+use crate::variables::resolve_variables;
+use crate::{GeneratorError, Result, cancel, templates, workspace};
+use notify::{RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// 合并连续文件事件的等待时间（编辑器保存时常触发多次写入）
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// 一次预览渲染的结果
+#[derive(Debug, Clone, Default)]
+pub struct DevRender {
+    /// 是否为整体重新渲染
+    pub full: bool,
+    /// 本次写入的文件（相对预览目录）
+    pub written: Vec<PathBuf>,
+    /// 因模板文件删除或不再生成而移除的文件
+    pub removed: Vec<PathBuf>,
+}
+
+/// 模板开发会话
+#[derive(Debug)]
+pub struct DevSession {
+    pub template_dir: PathBuf,
+    pub output_dir: PathBuf,
+    /// 覆盖模板默认值的变量
+    pub overrides: HashMap<String, String>,
+    /// 启用的特性
+    pub features: Vec<String>,
+    metadata: TemplateMetadata,
+    /// 模板叠加了基础模板或覆盖层，变化时整体重新渲染
+    layered: bool,
+    variables: HashMap<String, String>,
+    renderer: TemplateRenderer,
+    /// 预览目录中由本会话生成的文件
+    rendered: BTreeSet<PathBuf>,
+}
+
+impl DevSession {
+    pub fn new(
+        template_dir: PathBuf,
+        output_dir: PathBuf,
+        overrides: HashMap<String, String>,
+        features: Vec<String>,
+    ) -> Self {
+        Self {
+            template_dir,
+            output_dir,
+            overrides,
+            features,
+            metadata: TemplateMetadata::default(),
+            layered: false,
+            variables: HashMap::new(),
+            renderer: TemplateRenderer::default(),
+            rendered: BTreeSet::new(),
+        }
+    }
+
+    /// 整体渲染，并移除上次渲染过、本次不再生成的文件
+    pub fn render_all(&mut self) -> Result<DevRender> {
+        let template = TemplateRegistryConfig::default().resolve_layers(&self.template_dir)?;
+        self.layered = template.dir != template.source_dir;
+        self.metadata = template.metadata.clone();
+        let name = self
+            .output_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "preview".to_string());
+        let mut variables = templates::default_variables(&self.metadata, &name);
+        variables.extend(env_defaults()?);
+        variables.extend(self.overrides.clone());
+        self.variables = resolve_variables(&self.metadata, &variables)?;
+        self.renderer = TemplateRenderer::load(&template.dir, &self.metadata)?;
+
+        let report = templates::render_to_dir(
+            &template.dir,
+            &self.metadata,
+            &self.output_dir,
+            &self.variables,
+            &RenderOptions::default(),
+            &self.features,
+        )?;
+        let current: BTreeSet<PathBuf> = report.files.iter().cloned().collect();
+        let stale: Vec<PathBuf> = self.rendered.difference(&current).cloned().collect();
+        for file in &stale {
+            remove_output(&self.output_dir, file)?;
+        }
+        self.rendered = current;
+
+        Ok(DevRender {
+            full: true,
+            written: report.files,
+            removed: stale,
+        })
+    }
+
+    /// 按变化的模板文件（绝对路径或相对模板目录）增量渲染
+    pub fn apply_changes(&mut self, changed: &[PathBuf]) -> Result<DevRender> {
+        let relative: BTreeSet<PathBuf> = changed
+            .iter()
+            .filter_map(|path| match path.strip_prefix(&self.template_dir) {
+                Ok(relative) => Some(relative.to_path_buf()),
+                Err(_) if path.is_relative() => Some(path.clone()),
+                Err(_) => None,
+            })
+            .filter(|relative| !relative.as_os_str().is_empty())
+            .collect();
+        if relative.is_empty() {
+            return Ok(DevRender::default());
+        }
+        if self.layered || relative.iter().any(|path| requires_full_render(path)) {
+            return self.render_all();
+        }
+
+        let (existing, deleted): (Vec<PathBuf>, Vec<PathBuf>) = relative
+            .into_iter()
+            .partition(|path| self.template_dir.join(path).exists());
+
+        // 删除的可能是整个目录，移除其下所有已渲染的文件
+        let mut removed = Vec::new();
+        for path in deleted {
+            let output = pipeline::render_relative_path(&path, &self.variables, &self.renderer)?;
+            let stale: Vec<PathBuf> = self
+                .rendered
+                .iter()
+                .filter(|file| file.starts_with(&output))
+                .cloned()
+                .collect();
+            for file in stale {
+                remove_output(&self.output_dir, &file)?;
+                self.rendered.remove(&file);
+                removed.push(file);
+            }
+        }
+
+        let RenderReport { files, .. } = pipeline::render_template_files(
+            &self.template_dir,
+            &self.output_dir,
+            &self.metadata,
+            &self.variables,
+            &RenderOptions::default(),
+            &existing,
+        )?;
+        self.rendered.extend(files.iter().cloned());
+
+        Ok(DevRender {
+            full: false,
+            written: files,
+            removed,
+        })
+    }
+}
+
+/// 元数据、忽略规则、特性片段、partial 与钩子影响整个输出，变化时整体重新渲染
+fn requires_full_render(relative: &Path) -> bool {
+    relative == Path::new(TEMPLATE_METADATA_FILE)
+        || relative == Path::new(IGNORE_FILE_NAME)
+        || relative.starts_with(FEATURES_DIR)
+        || relative.starts_with(PARTIALS_DIR)
+        || relative.starts_with(HOOKS_DIR)
+}
+
+fn remove_output(output_dir: &Path, relative: &Path) -> Result<()> {
+    match fs::remove_file(workspace::safe_join(output_dir, relative)?) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 先整体渲染一次，然后监听模板目录直到取消（Ctrl-C）
+///
+/// 每次渲染（含失败）都会回调 `on_render`，渲染错误不会终止监听。
+pub fn watch(session: &mut DevSession, mut on_render: impl FnMut(Result<DevRender>)) -> Result<()> {
+    on_render(session.render_all());
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+    watcher
+        .watch(&session.template_dir, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+    // 预览目录位于模板目录内时忽略其中的事件，避免自触发
+    let output_dir = fs::canonicalize(&session.output_dir).unwrap_or(session.output_dir.clone());
+    let template_dir = fs::canonicalize(&session.template_dir)?;
+
+    loop {
+        cancel::check()?;
+        let first = match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(event) => event,
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+
+        let mut changed = BTreeSet::new();
+        let mut pending = Some(first);
+        while let Some(event) = pending.take() {
+            let event = event.map_err(watch_error)?;
+            if !event.kind.is_access() {
+                changed.extend(
+                    event
+                        .paths
+                        .into_iter()
+                        .filter(|path| !path.starts_with(&output_dir))
+                        .filter_map(|path| {
+                            path.strip_prefix(&template_dir).ok().map(Path::to_path_buf)
+                        }),
+                );
+            }
+            pending = rx.recv_timeout(DEBOUNCE).ok();
+        }
+
+        let changed: Vec<PathBuf> = changed.into_iter().collect();
+        if !changed.is_empty() {
+            on_render(session.apply_changes(&changed));
+        }
+    }
+}
+
+fn watch_error(e: notify::Error) -> GeneratorError {
+    GeneratorError::FileOperation(format!("Cannot watch template directory: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_incremental_render_and_removal() {
+        let template = tempdir().unwrap();
+        let preview = tempdir().unwrap();
+        let root = template.path();
+        fs::write(
+            root.join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "dev", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "vue", "variables": [
+                  {"name": "title", "description": "", "default": "Hello", "required": false, "var_type": "string"}
+                ], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        fs::write(root.join("a.txt"), "{{title}} a").unwrap();
+        fs::write(root.join("b.txt"), "b").unwrap();
+
+        let mut session = DevSession::new(
+            root.to_path_buf(),
+            preview.path().join("out"),
+            HashMap::from([("title".to_string(), "Hi".to_string())]),
+            Vec::new(),
+        );
+        let first = session.render_all().unwrap();
+        assert_eq!(first.written.len(), 2);
+        let out = preview.path().join("out");
+        assert_eq!(fs::read_to_string(out.join("a.txt")).unwrap(), "Hi a");
+
+        // 只重新渲染变化的文件
+        fs::write(root.join("a.txt"), "{{title}} changed").unwrap();
+        fs::write(out.join("b.txt"), "untouched").unwrap();
+        let render = session.apply_changes(&[root.join("a.txt")]).unwrap();
+        assert_eq!(render.written, vec![PathBuf::from("a.txt")]);
+        assert_eq!(fs::read_to_string(out.join("a.txt")).unwrap(), "Hi changed");
+        assert_eq!(fs::read_to_string(out.join("b.txt")).unwrap(), "untouched");
+
+        fs::remove_file(root.join("b.txt")).unwrap();
+        let render = session.apply_changes(&[PathBuf::from("b.txt")]).unwrap();
+        assert_eq!(render.removed, vec![PathBuf::from("b.txt")]);
+        assert!(!out.join("b.txt").exists());
+
+        let render = session
+            .apply_changes(&[root.join(TEMPLATE_METADATA_FILE)])
+            .unwrap();
+        assert!(render.full);
+    }
+}
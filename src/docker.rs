@@ -0,0 +1,242 @@
+//! Docker 脚手架模块
+//! 内置的 `docker` 特性：按项目类型生成多阶段 Dockerfile、.dockerignore 与可选的 docker-compose.yml
+
+use crate::build_wrapper::BuildTool;
+use crate::pipeline::RenderReport;
+use crate::toolchain::{DEFAULT_JAVA_VERSION, DEFAULT_NODE_VERSION};
+use crate::{GeneratorError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 内置特性名称；模板自行声明同名特性时以模板为准
+pub const DOCKER_FEATURE: &str = "docker";
+
+/// 宿主机映射端口，默认与容器端口相同
+pub const PORT_VARIABLE: &str = "dockerPort";
+/// 镜像仓库前缀，如 `registry.example.com/team`
+pub const REGISTRY_VARIABLE: &str = "dockerRegistry";
+/// 为 `true` 时生成 docker-compose.yml
+pub const COMPOSE_VARIABLE: &str = "dockerCompose";
+
+/// 镜像的构建方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DockerKind {
+    /// JDK 构建、JRE 运行
+    Java(BuildTool),
+    /// Node 构建、nginx 托管静态资源
+    Web,
+    /// Rust 编译为单个可执行文件
+    Rust,
+}
+
+impl DockerKind {
+    fn for_project(project_type: &str, project_dir: &Path) -> Option<Self> {
+        match project_type {
+            "java" => Some(DockerKind::Java(
+                BuildTool::detect(project_dir).unwrap_or(BuildTool::Maven),
+            )),
+            "vue" | "react" => Some(DockerKind::Web),
+            "rust" if project_dir.join("src/main.rs").exists() => Some(DockerKind::Rust),
+            _ => None,
+        }
+    }
+
+    /// 容器内监听的端口
+    fn container_port(&self) -> Option<u16> {
+        match self {
+            DockerKind::Java(_) => Some(8080),
+            DockerKind::Web => Some(80),
+            DockerKind::Rust => None,
+        }
+    }
+}
+
+/// 项目类型是否支持内置的 docker 特性
+pub fn supports(project_type: &str) -> bool {
+    matches!(project_type, "java" | "vue" | "react" | "rust")
+}
+
+/// 在生成的项目中写入 Docker 相关文件
+pub fn scaffold(
+    project_dir: &Path,
+    project_type: &str,
+    variables: &HashMap<String, String>,
+) -> Result<RenderReport> {
+    let kind = DockerKind::for_project(project_type, project_dir).ok_or_else(|| {
+        GeneratorError::Configuration(format!(
+            "Feature '{}' is not available for project type '{}'",
+            DOCKER_FEATURE, project_type
+        ))
+    })?;
+    let variable = |name: &str| {
+        variables
+            .get(name)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let name = variable("name").unwrap_or("app");
+
+    let mut files = vec![
+        ("Dockerfile", dockerfile(kind, project_dir, name, &variable)),
+        (".dockerignore", dockerignore(kind).to_string()),
+    ];
+    if variable(COMPOSE_VARIABLE) == Some("true") {
+        let port = match variable(PORT_VARIABLE) {
+            Some(port) => Some(port.parse::<u16>().map_err(|_| {
+                GeneratorError::Configuration(format!(
+                    "{} must be a port number, got '{}'",
+                    PORT_VARIABLE, port
+                ))
+            })?),
+            None => kind.container_port(),
+        };
+        files.push((
+            "docker-compose.yml",
+            compose(kind, name, variable(REGISTRY_VARIABLE), port),
+        ));
+    }
+
+    let mut report = RenderReport::default();
+    for (file, content) in files {
+        fs::write(project_dir.join(file), &content)?;
+        report.files.push(PathBuf::from(file));
+        report.bytes_written += content.len() as u64;
+    }
+    Ok(report)
+}
+
+fn dockerfile<'a>(
+    kind: DockerKind,
+    project_dir: &Path,
+    name: &str,
+    variable: &impl Fn(&str) -> Option<&'a str>,
+) -> String {
+    match kind {
+        DockerKind::Java(tool) => {
+            let jdk = variable("javaVersion").unwrap_or(DEFAULT_JAVA_VERSION);
+            let build = match tool {
+                BuildTool::Maven => format!(
+                    "FROM maven:3.9-eclipse-temurin-{jdk} AS build\n\
+                     WORKDIR /app\n\
+                     COPY pom.xml .\n\
+                     RUN mvn -B dependency:go-offline\n\
+                     COPY src ./src\n\
+                     RUN mvn -B package -DskipTests\n"
+                ),
+                BuildTool::Gradle => format!(
+                    "FROM gradle:8-jdk{jdk} AS build\n\
+                     WORKDIR /app\n\
+                     COPY . .\n\
+                     RUN gradle build -x test --no-daemon\n"
+                ),
+            };
+            let jar = match tool {
+                BuildTool::Maven => "target/*.jar",
+                BuildTool::Gradle => "build/libs/*.jar",
+            };
+            format!(
+                "{build}\n\
+                 FROM eclipse-temurin:{jdk}-jre\n\
+                 WORKDIR /app\n\
+                 COPY --from=build /app/{jar} app.jar\n\
+                 EXPOSE 8080\n\
+                 ENTRYPOINT [\"java\", \"-jar\", \"/app/app.jar\"]\n"
+            )
+        }
+        DockerKind::Web => {
+            let node = variable("nodeVersion").unwrap_or(DEFAULT_NODE_VERSION);
+            // create-react-app 输出到 build/，vite 输出到 dist/
+            let package = fs::read_to_string(project_dir.join("package.json")).unwrap_or_default();
+            let output = if package.contains("react-scripts") {
+                "build"
+            } else {
+                "dist"
+            };
+            format!(
+                "FROM node:{node}-alpine AS build\n\
+                 WORKDIR /app\n\
+                 COPY package*.json ./\n\
+                 RUN npm install\n\
+                 COPY . .\n\
+                 RUN npm run build\n\
+                 \n\
+                 FROM nginx:alpine\n\
+                 COPY --from=build /app/{output} /usr/share/nginx/html\n\
+                 EXPOSE 80\n\
+                 CMD [\"nginx\", \"-g\", \"daemon off;\"]\n"
+            )
+        }
+        DockerKind::Rust => format!(
+            "FROM rust:1-slim AS build\n\
+             WORKDIR /app\n\
+             COPY . .\n\
+             RUN cargo build --release\n\
+             \n\
+             FROM debian:bookworm-slim\n\
+             COPY --from=build /app/target/release/{name} /usr/local/bin/{name}\n\
+             CMD [\"{name}\"]\n"
+        ),
+    }
+}
+
+fn dockerignore(kind: DockerKind) -> &'static str {
+    match kind {
+        DockerKind::Java(_) => ".git\ntarget/\nbuild/\n.gradle/\n.idea/\n*.log\n",
+        DockerKind::Web => ".git\nnode_modules/\ndist/\nbuild/\nnpm-debug.log*\n",
+        DockerKind::Rust => ".git\ntarget/\n",
+    }
+}
+
+fn compose(kind: DockerKind, name: &str, registry: Option<&str>, port: Option<u16>) -> String {
+    let image = match registry {
+        Some(registry) => format!("{}/{}", registry.trim_end_matches('/'), name),
+        None => name.to_string(),
+    };
+    let mut compose = format!("services:\n  {name}:\n    image: {image}:latest\n    build: .\n");
+    if let (Some(host), Some(container)) = (port, kind.container_port()) {
+        compose.push_str(&format!("    ports:\n      - \"{host}:{container}\"\n"));
+    }
+    compose
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scaffold_per_project_type() {
+        let java = tempdir().unwrap();
+        fs::write(java.path().join("pom.xml"), "<project/>").unwrap();
+        let variables = HashMap::from([
+            ("name".to_string(), "orders".to_string()),
+            ("javaVersion".to_string(), "21".to_string()),
+            (COMPOSE_VARIABLE.to_string(), "true".to_string()),
+            (PORT_VARIABLE.to_string(), "9000".to_string()),
+            (REGISTRY_VARIABLE.to_string(), "ghcr.io/acme/".to_string()),
+        ]);
+        let report = scaffold(java.path(), "java", &variables).unwrap();
+        assert_eq!(report.files.len(), 3);
+
+        let read = |dir: &Path, file: &str| fs::read_to_string(dir.join(file)).unwrap();
+        let dockerfile = read(java.path(), "Dockerfile");
+        assert!(dockerfile.starts_with("FROM maven:3.9-eclipse-temurin-21 AS build\n"));
+        assert!(dockerfile.contains("FROM eclipse-temurin:21-jre\n"));
+        assert_eq!(
+            read(java.path(), "docker-compose.yml"),
+            "services:\n  orders:\n    image: ghcr.io/acme/orders:latest\n    build: .\n    \
+             ports:\n      - \"9000:8080\"\n"
+        );
+
+        let web = tempdir().unwrap();
+        let variables = HashMap::from([("name".to_string(), "site".to_string())]);
+        let report = scaffold(web.path(), "vue", &variables).unwrap();
+        assert_eq!(report.files.len(), 2);
+        let dockerfile = read(web.path(), "Dockerfile");
+        assert!(dockerfile.starts_with("FROM node:20-alpine AS build\n"));
+        assert!(dockerfile.contains("COPY --from=build /app/dist /usr/share/nginx/html\n"));
+
+        assert!(scaffold(web.path(), "node-lib", &variables).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! 环境诊断模块
+//! 检查 Java、Android SDK、Python、Node.js/npm、git、缓存目录、模板注册表与 Java CLI jar 包，汇总为 pass / warn / fail 报告
+
+use crate::android_env;
+use crate::java_cli::{self, JavaCliConfig};
+use crate::network::NetworkConfig;
+use crate::node_env::detect_node_environment;
+use crate::python_env;
+use crate::template_registry::{TemplateRegistry, TemplateRegistryConfig, TemplateSource};
+use crate::{DoctorCheck, DoctorReport};
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+
+/// 检查注册表可达性的超时
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(5);
+/// 未指定 registry 的 npm 源
+const DEFAULT_NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// 按用户级注册表配置（`~/.generator/registries.json`）诊断当前环境
+///
+/// 配置文件无效时记为 fail，其余检查按默认配置进行。
+pub fn run_diagnostics() -> DoctorReport {
+    match TemplateRegistryConfig::user() {
+        Ok(config) => diagnose(&config),
+        Err(e) => {
+            let mut report = diagnose(&TemplateRegistryConfig::default());
+            report.checks.push(check(
+                "registry-config",
+                "fail",
+                e.to_string(),
+                Some("Fix or remove ~/.generator/registries.json"),
+            ));
+            report.ok = false;
+            report
+        }
+    }
+}
+
+/// 按 `config` 诊断当前环境；只有影响所有生成的问题（缓存目录不可写）记为 fail
+pub fn diagnose(config: &TemplateRegistryConfig) -> DoctorReport {
+    let mut checks = vec![
+        check_java(),
+        check_java_cli_jar(),
+        check_android_sdk(),
+        check_python(),
+    ];
+    checks.extend(check_node());
+    checks.push(check_git());
+    checks.push(check_cache_dir(config));
+    checks.extend(
+        config
+            .registries
+            .iter()
+            .filter(|registry| registry.enabled)
+            .map(|registry| check_registry(registry, &config.network)),
+    );
+
+    DoctorReport {
+        ok: checks.iter().all(|check| check.status != "fail"),
+        checks,
+    }
+}
+
+fn check(name: &str, status: &str, message: String, hint: Option<&str>) -> DoctorCheck {
+    DoctorCheck {
+        name: name.to_string(),
+        status: status.to_string(),
+        message,
+        hint: hint.map(str::to_string),
+    }
+}
+
+fn check_java() -> DoctorCheck {
+    match java_cli::detect_java() {
+        Ok(java) => check(
+            "java",
+            "pass",
+            format!(
+                "{} ({}, via {})",
+                java.version,
+                java.vendor.as_deref().unwrap_or("unknown vendor"),
+                java.source.as_str()
+            ),
+            None,
+        ),
+        Err(e) => check(
+            "java",
+            "warn",
+            e.to_string(),
+            Some("Install a JDK and set JAVA_HOME; only Java templates need it"),
+        ),
+    }
+}
+
+fn check_android_sdk() -> DoctorCheck {
+    match android_env::detect_android_sdk() {
+        Ok(sdk) => check(
+            "android-sdk",
+            "pass",
+            format!(
+                "{} (platforms: {}, via {})",
+                sdk.path.display(),
+                sdk.latest_platform()
+                    .map(|api| format!("up to API {}", api))
+                    .unwrap_or("none".to_string()),
+                sdk.source.as_str()
+            ),
+            None,
+        ),
+        Err(e) => check(
+            "android-sdk",
+            "warn",
+            e.to_string(),
+            Some("Set ANDROID_HOME; only Android templates need it"),
+        ),
+    }
+}
+
+fn check_python() -> DoctorCheck {
+    match python_env::detect_python() {
+        Ok(python) => check(
+            "python",
+            "pass",
+            format!(
+                "Python {} ({}, via {})",
+                python.version,
+                python.python_path,
+                python.source.as_str()
+            ),
+            None,
+        ),
+        Err(e) => check(
+            "python",
+            "warn",
+            e.to_string(),
+            Some("Install Python 3, uv or Poetry; only Python templates need it"),
+        ),
+    }
+}
+
+fn check_java_cli_jar() -> DoctorCheck {
+    let config = JavaCliConfig::default();
+    match java_cli::local_java_cli_jar(&config) {
+        Ok(Some(path)) => check("java-cli-jar", "pass", path.display().to_string(), None),
+        Ok(None)
+            if config
+                .clone()
+                .resolve()
+                .is_ok_and(|c| c.download_url.is_some()) =>
+        {
+            check(
+                "java-cli-jar",
+                "warn",
+                "Java CLI jar is not cached yet and will be downloaded on first use".to_string(),
+                None,
+            )
+        }
+        Ok(None) => check(
+            "java-cli-jar",
+            "warn",
+            "Java CLI jar not found".to_string(),
+            Some(
+                "Set GENERATOR_JAVA_CLI_JAR or configure jar_path/download_url in config/java_cli.json",
+            ),
+        ),
+        Err(e) => check("java-cli-jar", "fail", e.to_string(), None),
+    }
+}
+
+fn check_node() -> Vec<DoctorCheck> {
+    let node = detect_node_environment();
+    [("node", node.node), ("npm", node.npm)]
+        .into_iter()
+        .map(|(name, version)| match version {
+            Some(version) => check(name, "pass", version, None),
+            None => check(
+                name,
+                "warn",
+                format!("{} not found in PATH", name),
+                Some("Install Node.js; needed for Node templates and npm template sources"),
+            ),
+        })
+        .collect()
+}
+
+fn check_git() -> DoctorCheck {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => check(
+            "git",
+            "pass",
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            None,
+        ),
+        _ => check(
+            "git",
+            "warn",
+            "git not found in PATH".to_string(),
+            Some("Install git; needed to initialize repositories and run hooks that call git"),
+        ),
+    }
+}
+
+/// 在缓存目录中实际创建并删除一个文件
+fn check_cache_dir(config: &TemplateRegistryConfig) -> DoctorCheck {
+    let dir = &config.cache_dir;
+    let probe = dir.join(".doctor-probe");
+    let result = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => check("cache-dir", "pass", dir.display().to_string(), None),
+        Err(e) => check(
+            "cache-dir",
+            "fail",
+            format!("{} is not writable: {}", dir.display(), e),
+            Some("Change cache_dir in the registry config or fix the directory permissions"),
+        ),
+    }
+}
+
+fn check_registry(registry: &TemplateRegistry, network: &NetworkConfig) -> DoctorCheck {
+    let name = format!("registry:{}", registry.name);
+    let url = match &registry.source {
+        TemplateSource::Local { path } => {
+            return if path.is_dir() {
+                check(&name, "pass", path.display().to_string(), None)
+            } else {
+                check(
+                    &name,
+                    "warn",
+                    format!("{} does not exist", path.display()),
+                    None,
+                )
+            };
+        }
+        TemplateSource::Git { url, .. } | TemplateSource::Http { url, .. } => url.clone(),
+        TemplateSource::Npm { registry, .. } => registry
+            .clone()
+            .unwrap_or_else(|| DEFAULT_NPM_REGISTRY.to_string()),
+    };
+
+    // 非 HTTP 地址（如 ssh 形式的 git 仓库）无法在这里探测
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return check(&name, "pass", format!("{} (not checked)", url), None);
+    }
+
+    let network = network.clone().resolve();
+    let reachable = network
+        .http_agent(&url, Some(REACHABILITY_TIMEOUT))
+        .map_err(|e| e.to_string())
+        .and_then(|agent| match agent.head(&url).call() {
+            // 服务器有响应即视为可达，认证与路径问题留给实际拉取时报告
+            Ok(_) | Err(ureq::Error::Status(..)) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        });
+    match reachable {
+        Ok(()) => check(&name, "pass", url, None),
+        Err(e) => check(
+            &name,
+            "warn",
+            format!("{} is not reachable: {}", url, e),
+            Some(
+                "Check the network, proxy (HTTPS_PROXY) and CA bundle (GENERATOR_CA_BUNDLE) settings",
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diagnose_reports_cache_and_local_registries() {
+        let dir = tempdir().unwrap();
+        let local = |name: &str, path: std::path::PathBuf| TemplateRegistry {
+            name: name.to_string(),
+            source: TemplateSource::Local { path },
+            enabled: true,
+            priority: 0,
+        };
+        let config = TemplateRegistryConfig {
+            registries: vec![
+                local("present", dir.path().to_path_buf()),
+                local("missing", dir.path().join("missing")),
+            ],
+            cache_dir: dir.path().join("cache"),
+            ..Default::default()
+        };
+
+        let report = diagnose(&config);
+        let status = |name: &str| {
+            report
+                .checks
+                .iter()
+                .find(|check| check.name == name)
+                .map(|check| check.status.as_str())
+                .unwrap()
+        };
+        assert_eq!(status("cache-dir"), "pass");
+        assert_eq!(status("registry:present"), "pass");
+        assert_eq!(status("registry:missing"), "warn");
+        assert!(["pass", "warn"].contains(&status("java")));
+        assert!(report.ok);
+        assert!(!dir.path().join("cache/.doctor-probe").exists());
+
+        // 缓存路径被普通文件占用时无法创建目录
+        fs::write(dir.path().join("blocked"), "").unwrap();
+        let report = diagnose(&TemplateRegistryConfig {
+            cache_dir: dir.path().join("blocked"),
+            registries: Vec::new(),
+            ..Default::default()
+        });
+        assert!(!report.ok);
+    }
+}
@@ -0,0 +1,170 @@
+//! 环境默认变量模块
+//! 从 git 配置、环境变量与运行环境自动提供 author、email、year 等变量，来源可在 `~/.generator/defaults.json` 中调整
+
+use crate::utils::generator_home;
+use crate::{GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// 来源配置文件名，位于 `~/.generator` 下
+pub const DEFAULTS_FILE: &str = "defaults.json";
+
+/// 内置的默认变量及其来源
+pub const BUILTIN_SOURCES: &[(&str, &str)] = &[
+    ("author", "git:user.name"),
+    ("email", "git:user.email"),
+    ("year", "year"),
+    ("os", "os"),
+    ("generatorVersion", "version"),
+];
+
+/// 随时间变化的来源，重复生成时不据此判断输入是否变化
+const VOLATILE_SOURCES: &[&str] = &["year"];
+
+/// 默认变量的来源设置（`~/.generator/defaults.json`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvDefaultsConfig {
+    /// 关闭全部自动默认变量
+    #[serde(default)]
+    pub disabled: bool,
+    /// 变量名到来源的映射，与内置来源合并；来源为空字符串时不提供该变量
+    ///
+    /// 来源格式：`git:<配置项>`、`env:<环境变量>`、`year`、`os`、`version`
+    #[serde(default)]
+    pub sources: BTreeMap<String, String>,
+}
+
+impl EnvDefaultsConfig {
+    /// 读取 `dir` 下的来源设置，文件不存在时使用内置来源
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(DEFAULTS_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e))
+        })
+    }
+
+    /// 求值所有来源，取不到值的变量（如未配置 git 用户名）不出现在结果中
+    pub fn resolve(&self) -> Result<HashMap<String, String>> {
+        self.resolve_with(|name| std::env::var(name).ok())
+    }
+
+    /// 同 [`EnvDefaultsConfig::resolve`]，`env:` 来源从 `env` 读取
+    pub fn resolve_with(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<HashMap<String, String>> {
+        let mut variables = HashMap::new();
+        for (name, source) in self.sources() {
+            if let Some(value) = source_value(source, &env)? {
+                variables.insert(name.to_string(), value);
+            }
+        }
+        Ok(variables)
+    }
+
+    /// 来源随时间变化的变量（如 `year`）
+    pub fn volatile(&self) -> Vec<String> {
+        self.sources()
+            .into_iter()
+            .filter(|(_, source)| VOLATILE_SOURCES.contains(source))
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// 生效的来源：内置来源与用户设置合并，去掉关闭的变量
+    fn sources(&self) -> BTreeMap<&str, &str> {
+        if self.disabled {
+            return BTreeMap::new();
+        }
+        let mut sources: BTreeMap<&str, &str> = BUILTIN_SOURCES.iter().copied().collect();
+        sources.extend(
+            self.sources
+                .iter()
+                .map(|(name, source)| (name.as_str(), source.as_str())),
+        );
+        sources.retain(|_, source| !source.is_empty());
+        sources
+    }
+}
+
+/// 按用户设置求值默认变量
+pub fn env_defaults() -> Result<HashMap<String, String>> {
+    EnvDefaultsConfig::load(&generator_home())?.resolve()
+}
+
+/// 按用户设置，来源随时间变化的默认变量
+pub fn volatile_defaults() -> Result<Vec<String>> {
+    Ok(EnvDefaultsConfig::load(&generator_home())?.volatile())
+}
+
+fn source_value(source: &str, env: impl Fn(&str) -> Option<String>) -> Result<Option<String>> {
+    let value = match source.split_once(':') {
+        Some(("git", key)) => git2::Config::open_default()
+            .and_then(|config| config.get_string(key))
+            .ok(),
+        Some(("env", name)) => env(name),
+        None if source == "year" => Some(chrono::Local::now().format("%Y").to_string()),
+        None if source == "os" => Some(std::env::consts::OS.to_string()),
+        None if source == "version" => Some(env!("CARGO_PKG_VERSION").to_string()),
+        _ => {
+            return Err(GeneratorError::Configuration(format!(
+                "Unknown default variable source '{}'",
+                source
+            )));
+        }
+    };
+    Ok(value.filter(|value| !value.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_env_defaults_sources_are_configurable() {
+        let dir = tempdir().unwrap();
+        let defaults = EnvDefaultsConfig::load(dir.path())
+            .unwrap()
+            .resolve()
+            .unwrap();
+        assert_eq!(defaults["os"], std::env::consts::OS);
+        assert_eq!(defaults["generatorVersion"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(defaults["year"].len(), 4);
+
+        fs::write(
+            dir.path().join(DEFAULTS_FILE),
+            r#"{"sources": {"os": "", "team": "env:GENERATOR_TEAM", "author": "version",
+                "copyrightYear": "year"}}"#,
+        )
+        .unwrap();
+        let config = EnvDefaultsConfig::load(dir.path()).unwrap();
+        let defaults = config
+            .resolve_with(|name| (name == "GENERATOR_TEAM").then(|| "platform".to_string()))
+            .unwrap();
+        assert!(!defaults.contains_key("os"));
+        assert_eq!(defaults["team"], "platform");
+        assert_eq!(defaults["author"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(defaults["copyrightYear"], defaults["year"]);
+        assert_eq!(config.volatile(), ["copyrightYear", "year"]);
+        // 环境变量未设置时不提供
+        assert!(!config.resolve_with(|_| None).unwrap().contains_key("team"));
+
+        fs::write(
+            dir.path().join(DEFAULTS_FILE),
+            r#"{"sources": {"team": "ldap:cn"}}"#,
+        )
+        .unwrap();
+        assert!(
+            EnvDefaultsConfig::load(dir.path())
+                .unwrap()
+                .resolve()
+                .is_err()
+        );
+    }
+}
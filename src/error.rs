@@ -1,6 +1,10 @@
 //! 错误处理模块
 //! 定义项目中使用的错误类型
 
+use crate::{ErrorDetails, VariableViolation};
+use std::cell::RefCell;
+use std::fmt;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// 项目生成器错误类型
@@ -27,6 +31,9 @@ pub enum GeneratorError {
     #[error("Configuration error: {0}")]
     Configuration(String),
 
+    #[error("File conflict: {0}")]
+    Conflict(String),
+
     #[error("External command failed: {0}")]
     ExternalCommand(String),
 
@@ -36,16 +43,158 @@ pub enum GeneratorError {
     #[error("Template engine error: {0}")]
     TemplateEngine(#[from] handlebars::RenderError),
 
+    #[error("Invalid variables: {}", describe_violations(.0))]
+    InvalidVariables(Vec<VariableViolation>),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Operation timed out: {0}")]
+    Timeout(String),
+
     #[error("Unknown error: {0}")]
     Unknown(String),
+
+    #[error("{source} (partial output kept at {})", path.display())]
+    PartialOutput {
+        path: PathBuf,
+        #[source]
+        source: Box<GeneratorError>,
+    },
+}
+
+fn describe_violations(violations: &[VariableViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| violation.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// 项目生成器结果类型
 pub type Result<T> = std::result::Result<T, GeneratorError>;
 
+/// 跨 napi 边界的稳定错误码，作为 JS 错误对象的 `code`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode(pub &'static str);
+
+impl AsRef<str> for ErrorCode {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Debug for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+thread_local! {
+    /// 最近一次抛给 JS 的错误详情
+    static LAST_ERROR: RefCell<Option<ErrorDetails>> = const { RefCell::new(None) };
+}
+
+impl GeneratorError {
+    /// 稳定错误码，如 `TEMPLATE_NOT_FOUND`
+    pub fn code(&self) -> &'static str {
+        match self {
+            GeneratorError::Io(_) => "IO_ERROR",
+            GeneratorError::TemplateNotFound(_) => "TEMPLATE_NOT_FOUND",
+            GeneratorError::InvalidProjectName(_) => "INVALID_PROJECT_NAME",
+            GeneratorError::JavaEnvironment(_) => "JAVA_ENVIRONMENT",
+            GeneratorError::TemplateProcessing(_) => "TEMPLATE_PROCESSING",
+            GeneratorError::FileOperation(_) => "FILE_OPERATION",
+            GeneratorError::Configuration(_) => "CONFIGURATION",
+            GeneratorError::Conflict(_) => "CONFLICT",
+            GeneratorError::ExternalCommand(_) => "EXTERNAL_COMMAND",
+            GeneratorError::Serialization(_) => "SERIALIZATION",
+            GeneratorError::TemplateEngine(_) => "TEMPLATE_ENGINE",
+            GeneratorError::InvalidVariables(_) => "INVALID_VARIABLES",
+            GeneratorError::Cancelled => "CANCELLED",
+            GeneratorError::Timeout(_) => "TIMEOUT",
+            GeneratorError::Unknown(_) => "UNKNOWN",
+            GeneratorError::PartialOutput { source, .. } => source.code(),
+        }
+    }
+
+    /// 是否由取消引起（包括保留了部分输出的取消）
+    pub fn is_cancelled(&self) -> bool {
+        match self {
+            GeneratorError::Cancelled => true,
+            GeneratorError::PartialOutput { source, .. } => source.is_cancelled(),
+            _ => false,
+        }
+    }
+
+    /// 结构化错误详情，附带调用方已知的模板与路径
+    pub fn details(&self, template: Option<&str>, path: Option<&str>) -> ErrorDetails {
+        ErrorDetails {
+            code: self.code().to_string(),
+            message: self.to_string(),
+            template: template.map(str::to_string),
+            path: path.map(str::to_string),
+            cause: std::error::Error::source(self).map(|cause| cause.to_string()),
+            violations: match self {
+                GeneratorError::InvalidVariables(violations) => violations.clone(),
+                GeneratorError::PartialOutput { source, .. } => {
+                    source.details(None, None).violations
+                }
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// 转换为带错误码的 napi 错误，并记录详情供 `take_last_error` 读取
+    pub fn into_napi(self, template: Option<&str>, path: Option<&str>) -> napi::Error<ErrorCode> {
+        set_last_error(self.details(template, path));
+        napi::Error::new(ErrorCode(self.code()), self.to_string())
+    }
+}
+
+/// 记录错误详情，供 `take_last_error` 读取
+pub fn set_last_error(details: ErrorDetails) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(details));
+}
+
+/// 取出最近一次抛给 JS 的错误详情
+pub fn take_last_error() -> Option<ErrorDetails> {
+    LAST_ERROR.with(|last| last.borrow_mut().take())
+}
+
 /// 将GeneratorError转换为napi::Error
 impl From<GeneratorError> for napi::Error {
     fn from(err: GeneratorError) -> Self {
         napi::Error::from_reason(err.to_string())
     }
-}
\ No newline at end of file
+}
+
+/// 将GeneratorError转换为带错误码的napi::Error
+impl From<GeneratorError> for napi::Error<ErrorCode> {
+    fn from(err: GeneratorError) -> Self {
+        err.into_napi(None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_napi_records_details() {
+        let err = GeneratorError::TemplateNotFound("vue/missing".to_string());
+        let napi_err = err.into_napi(Some("missing"), Some("./app"));
+        assert_eq!(napi_err.status.as_ref(), "TEMPLATE_NOT_FOUND");
+
+        let details = take_last_error().unwrap();
+        assert_eq!(details.code, "TEMPLATE_NOT_FOUND");
+        assert_eq!(details.template.as_deref(), Some("missing"));
+        assert_eq!(details.path.as_deref(), Some("./app"));
+        assert!(take_last_error().is_none());
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        let details = GeneratorError::Io(io).details(None, None);
+        assert_eq!(details.code, "IO_ERROR");
+        assert_eq!(details.cause.as_deref(), Some("gone"));
+    }
+}
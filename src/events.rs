@@ -0,0 +1,123 @@
+//! 生成事件模块
+//! 调用线程注册了监听者（napi 的 GeneratorSession）时，生成过程中的阶段进度、日志与缺少的必填变量转发给它，否则照常输出或报错
+
+use crate::Result;
+use crate::template_metadata::TemplateVariable;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// 生成事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// 进入名为 `phase` 的阶段（resolve、render、commit 等）
+    Progress { phase: String },
+    /// 一行日志
+    Log { message: String },
+}
+
+/// 生成事件的监听者
+pub trait Listener: Send + Sync {
+    fn event(&self, event: Event);
+
+    /// 询问没有提供值的必填变量，返回 None 表示不回答（按缺少变量报错）
+    fn prompt(&self, variable: &TemplateVariable) -> Result<Option<String>>;
+}
+
+thread_local! {
+    static LISTENER: RefCell<Option<Arc<dyn Listener>>> = const { RefCell::new(None) };
+}
+
+/// 在监听者作用域内执行 `f`
+pub fn with_listener<R>(listener: Arc<dyn Listener>, f: impl FnOnce() -> R) -> R {
+    let previous = LISTENER.with(|current| current.replace(Some(listener)));
+    let result = f();
+    LISTENER.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// 调用线程上当前的监听者，供需要包装它的监听者转发事件
+pub fn current() -> Option<Arc<dyn Listener>> {
+    LISTENER.with(|current| current.borrow().clone())
+}
+
+/// 发送事件，返回是否有监听者接收
+pub fn emit(event: Event) -> bool {
+    match current() {
+        Some(listener) => {
+            listener.event(event);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 向监听者询问变量值，没有监听者时返回 None
+pub fn prompt(variable: &TemplateVariable) -> Result<Option<String>> {
+    match current() {
+        Some(listener) => listener.prompt(variable),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_metadata::{TemplateMetadata, VariableType};
+    use crate::variables::resolve_variables;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl Listener for Recorder {
+        fn event(&self, event: Event) {
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn prompt(&self, variable: &TemplateVariable) -> Result<Option<String>> {
+            Ok((variable.name == "author").then(|| "Ada".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_listener_receives_events_and_answers_prompts() {
+        let metadata = TemplateMetadata {
+            variables: vec![TemplateVariable {
+                name: "author".to_string(),
+                description: String::new(),
+                default: None,
+                required: true,
+                var_type: VariableType::String,
+                sensitive: false,
+            }],
+            ..Default::default()
+        };
+        assert!(resolve_variables(&metadata, &HashMap::new()).is_err());
+
+        let recorder = Arc::new(Recorder::default());
+        let variables = with_listener(recorder.clone(), || {
+            crate::say!("hello");
+            crate::report::PhaseTimer::default().time("render", || ());
+            resolve_variables(&metadata, &HashMap::new()).unwrap()
+        });
+        assert_eq!(variables["author"], "Ada");
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            [
+                Event::Log {
+                    message: "hello".to_string()
+                },
+                Event::Progress {
+                    phase: "render".to_string()
+                },
+            ]
+        );
+        // 作用域外不再转发
+        assert!(!emit(Event::Log {
+            message: "ignored".to_string()
+        }));
+    }
+}
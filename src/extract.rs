@@ -0,0 +1,340 @@
+//! 归档解压模块
+//! 远程模板源（HTTP、npm）与模板包共用的 tar / tar.gz / tar.zst / zip 解压，拒绝 `..`、绝对路径与链接条目，并限制条目数与解压后大小以防压缩炸弹
+
+use crate::{GeneratorError, Result};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// 解压限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractLimits {
+    /// 最多条目数
+    pub max_entries: u64,
+    /// 单个文件解压后的最大字节数
+    pub max_file_size: u64,
+    /// 全部文件解压后的最大字节数
+    pub max_total_size: u64,
+}
+
+impl Default for ExtractLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 20_000,
+            max_file_size: 100 * 1024 * 1024,
+            max_total_size: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// 归档格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Tar,
+    TarGz,
+    TarZst,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// 按文件头识别格式，无法识别时按 tar 处理
+    pub fn detect(archive: &[u8]) -> Self {
+        match archive {
+            [0x1f, 0x8b, ..] => Self::TarGz,
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Self::TarZst,
+            [b'P', b'K', 0x03, 0x04, ..] | [b'P', b'K', 0x05, 0x06, ..] => Self::Zip,
+            _ => Self::Tar,
+        }
+    }
+}
+
+/// 归档解压器
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Extractor {
+    pub limits: ExtractLimits,
+    /// 去掉条目路径开头的层级数（如 npm 包的 `package/`）
+    pub strip_components: usize,
+}
+
+/// 解压过程中累计的用量
+#[derive(Default)]
+struct Usage {
+    entries: u64,
+    bytes: u64,
+}
+
+impl Extractor {
+    /// 解压到 `dest`，返回写入的文件（相对 `dest`）
+    ///
+    /// 任一条目越出 `dest` 或超出限制时报错，调用方应解压到临时目录并在失败时丢弃。
+    pub fn extract(&self, archive: &[u8], dest: &Path) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dest)?;
+        let mut usage = Usage::default();
+        let mut files = Vec::new();
+        match ArchiveFormat::detect(archive) {
+            ArchiveFormat::TarGz => {
+                self.extract_tar(GzDecoder::new(archive), dest, &mut usage, &mut files)?
+            }
+            ArchiveFormat::TarZst => {
+                self.extract_tar(zstd::Decoder::new(archive)?, dest, &mut usage, &mut files)?
+            }
+            ArchiveFormat::Tar => self.extract_tar(archive, dest, &mut usage, &mut files)?,
+            ArchiveFormat::Zip => self.extract_zip(archive, dest, &mut usage, &mut files)?,
+        }
+        Ok(files)
+    }
+
+    fn extract_tar(
+        &self,
+        reader: impl Read,
+        dest: &Path,
+        usage: &mut Usage,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let raw = entry.path_bytes().into_owned();
+            let name = String::from_utf8_lossy(&raw).into_owned();
+            let kind = match entry.header().entry_type() {
+                tar::EntryType::Directory => EntryKind::Directory,
+                tar::EntryType::Regular | tar::EntryType::Continuous => EntryKind::File,
+                // pax / GNU 长文件名等元数据条目由 tar 库处理
+                tar::EntryType::XGlobalHeader | tar::EntryType::XHeader => continue,
+                _ => EntryKind::Other,
+            };
+            let mode = entry.header().mode().ok();
+            if let Some(file) = self.write_entry(&name, kind, mode, &mut entry, dest, usage)? {
+                files.push(file);
+            }
+        }
+        Ok(())
+    }
+
+    fn extract_zip(
+        &self,
+        archive: &[u8],
+        dest: &Path,
+        usage: &mut Usage,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(archive)).map_err(zip_error)?;
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(zip_error)?;
+            let name = entry.name().to_string();
+            let mode = entry.unix_mode();
+            let kind = if entry.is_dir() {
+                EntryKind::Directory
+            } else if mode.is_some_and(|mode| mode & 0o170000 == 0o120000) {
+                EntryKind::Other
+            } else {
+                EntryKind::File
+            };
+            if let Some(file) = self.write_entry(&name, kind, mode, &mut entry, dest, usage)? {
+                files.push(file);
+            }
+        }
+        Ok(())
+    }
+
+    /// 校验并写入单个条目，返回写入的文件路径
+    fn write_entry(
+        &self,
+        name: &str,
+        kind: EntryKind,
+        mode: Option<u32>,
+        reader: &mut impl Read,
+        dest: &Path,
+        usage: &mut Usage,
+    ) -> Result<Option<PathBuf>> {
+        usage.entries += 1;
+        if usage.entries > self.limits.max_entries {
+            return Err(GeneratorError::FileOperation(format!(
+                "Archive has more than {} entries",
+                self.limits.max_entries
+            )));
+        }
+
+        let Some(relative) = sanitize_entry_path(name, self.strip_components)? else {
+            return Ok(None);
+        };
+        let target = dest.join(&relative);
+        match kind {
+            EntryKind::Directory => {
+                fs::create_dir_all(&target)?;
+                Ok(None)
+            }
+            EntryKind::File => {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let mut file = fs::File::create(&target)?;
+                // 按实际解压字节计数，不信任归档头中声明的大小
+                let written = io::copy(&mut reader.take(self.limits.max_file_size + 1), &mut file)?;
+                if written > self.limits.max_file_size {
+                    return Err(GeneratorError::FileOperation(format!(
+                        "Archive entry '{}' exceeds the {} byte file size limit",
+                        name, self.limits.max_file_size
+                    )));
+                }
+                usage.bytes += written;
+                if usage.bytes > self.limits.max_total_size {
+                    return Err(GeneratorError::FileOperation(format!(
+                        "Archive exceeds the {} byte total size limit",
+                        self.limits.max_total_size
+                    )));
+                }
+                set_executable(&target, mode)?;
+                Ok(Some(relative))
+            }
+            // 模板包中不应出现链接、设备等特殊条目
+            EntryKind::Other => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    File,
+    Directory,
+    Other,
+}
+
+/// 去掉前 `strip` 层后的相对路径；绝对路径与 `..` 报错，剥离后为空时返回 None
+fn sanitize_entry_path(name: &str, strip: usize) -> Result<Option<PathBuf>> {
+    let escapes = || {
+        GeneratorError::FileOperation(format!(
+            "Archive entry '{}' escapes the extraction directory",
+            name
+        ))
+    };
+
+    let mut relative = PathBuf::new();
+    let mut stripped = 0;
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(_) if stripped < strip => stripped += 1,
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(escapes());
+            }
+        }
+    }
+    Ok((!relative.as_os_str().is_empty()).then_some(relative))
+}
+
+/// 保留可执行位（如 mvnw、gradlew）
+#[cfg(unix)]
+fn set_executable(path: &Path, mode: Option<u32>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if mode.is_some_and(|mode| mode & 0o111 != 0) {
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path, _mode: Option<u32>) -> Result<()> {
+    Ok(())
+}
+
+fn zip_error(e: zip::result::ZipError) -> GeneratorError {
+    GeneratorError::FileOperation(format!("Invalid zip archive: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    /// 直接写入 tar 头中的路径，绕过 tar 库对 `..` 的校验
+    fn tarball(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, content) in entries {
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn zip(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        for (path, content) in entries {
+            writer.start_file(*path, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_strips_components() {
+        let dest = tempdir().unwrap();
+        let extractor = Extractor {
+            strip_components: 1,
+            ..Default::default()
+        };
+
+        let files = extractor
+            .extract(
+                &tarball(&[("package/template.json", "{}"), ("package/src/a.ts", "a")]),
+                dest.path(),
+            )
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            fs::read_to_string(dest.path().join("src/a.ts")).unwrap(),
+            "a"
+        );
+
+        extractor
+            .extract(&zip(&[("repo-main/README.md", "hi")]), dest.path())
+            .unwrap();
+        assert!(dest.path().join("README.md").is_file());
+    }
+
+    #[test]
+    fn test_extract_rejects_traversal_and_bombs() {
+        let dest = tempdir().unwrap();
+        let target = dest.path().join("out");
+        let extractor = Extractor::default();
+
+        for archive in [
+            tarball(&[("../evil.sh", "x")]),
+            tarball(&[("/tmp/evil.sh", "x")]),
+            zip(&[("safe/../../evil.sh", "x")]),
+            zip(&[("/etc/evil", "x")]),
+        ] {
+            assert!(extractor.extract(&archive, &target).is_err());
+        }
+        assert!(!dest.path().join("evil.sh").exists());
+
+        let small = Extractor {
+            limits: ExtractLimits {
+                max_entries: 10,
+                max_file_size: 4,
+                max_total_size: 6,
+            },
+            strip_components: 0,
+        };
+        assert!(small.extract(&zip(&[("big", "12345")]), &target).is_err());
+        assert!(
+            small
+                .extract(&zip(&[("a", "1234"), ("b", "1234")]), &target)
+                .is_err()
+        );
+        assert!(small.extract(&zip(&[("a", "1234")]), &target).is_ok());
+    }
+}
@@ -0,0 +1,234 @@
+//! 模板特性组合模块
+//! 将可选特性片段（eslint、docker、tailwind、CI 等）叠加到基础模板之上，并对已有文件打补丁
+
+use crate::ci::{self, CiProvider};
+use crate::docker::{self, DOCKER_FEATURE};
+use crate::kubernetes::{self, HELM_FEATURE, K8S_FEATURE};
+use crate::pipeline::{self, RenderOptions, RenderReport};
+use crate::render::TemplateRenderer;
+use crate::template_registry::{FeaturePatch, PatchOperation, TemplateFeature, TemplateMetadata};
+use crate::{GeneratorError, Result, TemplateFeatureInfo};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 模板中存放特性片段的目录
+pub const FEATURES_DIR: &str = "features";
+
+/// 按选择顺序应用特性，返回新写入或被修改的文件
+pub fn apply_features(
+    template_dir: &Path,
+    output_dir: &Path,
+    metadata: &TemplateMetadata,
+    selected: &[String],
+    variables: &HashMap<String, String>,
+    options: &RenderOptions,
+) -> Result<RenderReport> {
+    let mut report = RenderReport::default();
+    let renderer = TemplateRenderer::load(template_dir, metadata)?;
+
+    for name in selected {
+        // 模板没有声明同名特性时使用内置的 Docker、CI 与 Kubernetes 脚手架
+        if !metadata.features.iter().any(|f| f.name == *name) {
+            if name == K8S_FEATURE || name == HELM_FEATURE {
+                report.merge(kubernetes::scaffold(
+                    output_dir,
+                    name,
+                    &metadata.project_type,
+                    variables,
+                )?);
+                continue;
+            }
+            if name == DOCKER_FEATURE {
+                report.merge(docker::scaffold(
+                    output_dir,
+                    &metadata.project_type,
+                    variables,
+                )?);
+                continue;
+            }
+            if let Some(provider) = CiProvider::from_feature(name) {
+                report.merge(ci::scaffold(output_dir, provider, metadata, variables)?);
+                continue;
+            }
+        }
+        let feature = find_feature(metadata, name)?;
+
+        let overlay_dir = feature_dir(template_dir, feature);
+        if overlay_dir.is_dir() {
+            report.merge(pipeline::render_overlay_dir(
+                &overlay_dir,
+                output_dir,
+                metadata,
+                variables,
+                options,
+                &renderer,
+            )?);
+        }
+
+        for patch in &feature.patches {
+            let bytes_written = apply_patch(output_dir, patch, variables, &renderer)?;
+            report.merge(RenderReport {
+                files: vec![PathBuf::from(renderer.render(&patch.file, variables)?)],
+                bytes_written,
+                ..Default::default()
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// 模板可选的特性：模板声明的特性，以及项目类型支持的内置特性
+pub fn available_features(metadata: &TemplateMetadata) -> Vec<TemplateFeatureInfo> {
+    let mut features: Vec<TemplateFeatureInfo> = metadata
+        .features
+        .iter()
+        .map(|feature| TemplateFeatureInfo {
+            name: feature.name.clone(),
+            description: feature.description.clone(),
+        })
+        .collect();
+    let mut builtin = Vec::new();
+    if docker::supports(&metadata.project_type) {
+        builtin.push((
+            DOCKER_FEATURE.to_string(),
+            "Docker 多阶段构建镜像（内置，可选 docker-compose.yml）",
+        ));
+    }
+    if kubernetes::supports(&metadata.project_type) {
+        builtin.push((
+            K8S_FEATURE.to_string(),
+            "Kubernetes 部署清单：Deployment、Service、Ingress（内置）",
+        ));
+        builtin.push((
+            HELM_FEATURE.to_string(),
+            "Helm chart：Deployment、Service、Ingress（内置）",
+        ));
+    }
+    if ci::supports(&metadata.project_type) {
+        builtin.extend(
+            CiProvider::ALL
+                .iter()
+                .map(|provider| (provider.feature_name(), provider.description())),
+        );
+    }
+    for (name, description) in builtin {
+        if !features.iter().any(|feature| feature.name == name) {
+            features.push(TemplateFeatureInfo {
+                name,
+                description: description.to_string(),
+            });
+        }
+    }
+    features
+}
+
+/// 查找模板声明的特性
+pub fn find_feature<'a>(metadata: &'a TemplateMetadata, name: &str) -> Result<&'a TemplateFeature> {
+    metadata
+        .features
+        .iter()
+        .find(|feature| feature.name == name)
+        .ok_or_else(|| {
+            GeneratorError::Configuration(format!(
+                "Feature '{}' is not provided by template '{}'",
+                name, metadata.name
+            ))
+        })
+}
+
+/// 特性片段目录，默认为 `features/<name>`
+fn feature_dir(template_dir: &Path, feature: &TemplateFeature) -> PathBuf {
+    match &feature.path {
+        Some(path) => template_dir.join(path),
+        None => template_dir.join(FEATURES_DIR).join(&feature.name),
+    }
+}
+
+/// 对输出目录中的文件应用补丁，返回补丁后文件的字节数
+fn apply_patch(
+    output_dir: &Path,
+    patch: &FeaturePatch,
+    variables: &HashMap<String, String>,
+    renderer: &TemplateRenderer,
+) -> Result<u64> {
+    let render = |text: &str| renderer.render(text, variables);
+    let target = output_dir.join(render(&patch.file)?);
+
+    let patched = match &patch.operation {
+        PatchOperation::JsonMerge { value } => {
+            let mut document: serde_json::Value = if target.exists() {
+                serde_json::from_str(&fs::read_to_string(&target)?)?
+            } else {
+                serde_json::Value::Object(Default::default())
+            };
+            let rendered = render(&value.to_string())?;
+            merge_json(&mut document, serde_json::from_str(&rendered)?);
+            let mut content = serde_json::to_string_pretty(&document)?;
+            content.push('\n');
+            content
+        }
+        PatchOperation::Append { content } => {
+            let mut existing = fs::read_to_string(&target).unwrap_or_default();
+            if !existing.is_empty() && !existing.ends_with('\n') {
+                existing.push('\n');
+            }
+            existing + &render(content)?
+        }
+        PatchOperation::Replace { find, replace } => {
+            let existing = fs::read_to_string(&target).map_err(|e| {
+                GeneratorError::FileOperation(format!("Cannot patch {}: {}", target.display(), e))
+            })?;
+            existing.replace(find, &render(replace)?)
+        }
+    };
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, &patched)?;
+    Ok(patched.len() as u64)
+}
+
+/// 深度合并 JSON 对象，数组与标量直接覆盖
+fn merge_json(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_json() {
+        let mut base = json!({
+            "scripts": { "dev": "vite" },
+            "devDependencies": { "vite": "^5.0.0" }
+        });
+        merge_json(
+            &mut base,
+            json!({
+                "scripts": { "lint": "eslint ." },
+                "devDependencies": { "eslint": "^9.0.0" }
+            }),
+        );
+
+        assert_eq!(base["scripts"]["dev"], "vite");
+        assert_eq!(base["scripts"]["lint"], "eslint .");
+        assert_eq!(base["devDependencies"]["eslint"], "^9.0.0");
+    }
+}
@@ -0,0 +1,152 @@
+//! C 接口模块
+//! `ffi` 特性下导出 `extern "C"` 函数，供 JNI、Python ctypes 等非 Node 宿主调用生成核心，宿主无需 Node.js 运行时
+
+use crate::error::set_last_error;
+use crate::templates::{generate_project_from_template, list_templates_by_type};
+use crate::{ErrorDetails, GenerateOptions, GeneratorError, Result};
+use serde::Serialize;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+/// 生成项目
+///
+/// `options` 为 UTF-8 JSON 编码的 [`GenerateOptions`]（字段名与 Rust 结构体相同），
+/// 成功时返回 JSON 编码的生成结果，失败时返回 NULL，错误详情由 [`generator_last_error`] 取得。
+/// 返回的字符串须用 [`generator_free_string`] 释放。
+///
+/// # Safety
+///
+/// `options` 必须是有效的、以 NUL 结尾的 C 字符串。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn generator_generate(options: *const c_char) -> *mut c_char {
+    call(|| {
+        let options = unsafe { read_str(options) }
+            .and_then(|options| Ok(serde_json::from_str::<GenerateOptions>(options)?))
+            .map_err(|e| Box::new(e.details(None, None)))?;
+        let template = options.template.clone();
+        let path = options.output_path.clone();
+        generate_project_from_template(options)
+            .map_err(|e| Box::new(e.details(template.as_deref(), path.as_deref())))
+    })
+}
+
+/// 列出项目类型的模板，返回 JSON 字符串数组；失败时返回 NULL
+///
+/// # Safety
+///
+/// `project_type` 必须是有效的、以 NUL 结尾的 C 字符串。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn generator_list_templates(project_type: *const c_char) -> *mut c_char {
+    call(|| {
+        unsafe { read_str(project_type) }
+            .and_then(list_templates_by_type)
+            .map_err(|e| Box::new(e.details(None, None)))
+    })
+}
+
+/// 取出调用线程上最近一次失败的错误详情（JSON），没有时返回 NULL
+#[unsafe(no_mangle)]
+pub extern "C" fn generator_last_error() -> *mut c_char {
+    crate::error::take_last_error()
+        .and_then(|details| serde_json::to_string(&details).ok())
+        .map_or(ptr::null_mut(), into_c_string)
+}
+
+/// 释放本模块返回的字符串，传入 NULL 时什么也不做
+///
+/// # Safety
+///
+/// `value` 必须是本模块返回且尚未释放的指针。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn generator_free_string(value: *mut c_char) {
+    if !value.is_null() {
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+/// 执行 `f` 并把结果编码为 JSON；错误与 panic 不会越过 C 边界，而是记录详情后返回 NULL
+fn call<T: Serialize>(
+    f: impl FnOnce() -> std::result::Result<T, Box<ErrorDetails>>,
+) -> *mut c_char {
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        let e = GeneratorError::Unknown(format!("Panicked: {}", message));
+        Err(Box::new(e.details(None, None)))
+    });
+    let json = result.and_then(|value| {
+        serde_json::to_string(&value)
+            .map_err(|e| Box::new(GeneratorError::from(e).details(None, None)))
+    });
+    match json {
+        Ok(json) => into_c_string(json),
+        Err(details) => {
+            set_last_error(*details);
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe fn read_str<'a>(value: *const c_char) -> Result<&'a str> {
+    if value.is_null() {
+        return Err(GeneratorError::Configuration(
+            "Argument is NULL".to_string(),
+        ));
+    }
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|e| GeneratorError::Configuration(format!("Argument is not UTF-8: {}", e)))
+}
+
+fn into_c_string(value: String) -> *mut c_char {
+    // JSON 中的 NUL 总是被转义，不会出现在编码结果里
+    CString::new(value).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 取回并释放返回的字符串
+    fn take(value: *mut c_char) -> Option<String> {
+        if value.is_null() {
+            return None;
+        }
+        let string = unsafe { CStr::from_ptr(value) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { generator_free_string(value) };
+        Some(string)
+    }
+
+    fn last_error_code() -> String {
+        let details = take(generator_last_error()).unwrap();
+        serde_json::from_str::<ErrorDetails>(&details).unwrap().code
+    }
+
+    #[test]
+    fn test_c_api_results_and_errors() {
+        let rust = CString::new("rust").unwrap();
+        let templates = take(unsafe { generator_list_templates(rust.as_ptr()) }).unwrap();
+        let templates: Vec<String> = serde_json::from_str(&templates).unwrap();
+        assert!(templates.contains(&"lib".to_string()));
+        assert!(take(generator_last_error()).is_none());
+
+        assert!(take(unsafe { generator_list_templates(ptr::null()) }).is_none());
+        assert_eq!(last_error_code(), "CONFIGURATION");
+
+        let options = CString::new("{ not json").unwrap();
+        assert!(take(unsafe { generator_generate(options.as_ptr()) }).is_none());
+        assert_eq!(last_error_code(), "SERIALIZATION");
+
+        let options = CString::new(r#"{ "name": "app", "project_type": "cobol" }"#).unwrap();
+        assert!(take(unsafe { generator_generate(options.as_ptr()) }).is_none());
+        let details = take(generator_last_error()).unwrap();
+        assert!(details.contains("TEMPLATE_NOT_FOUND"), "{}", details);
+    }
+}
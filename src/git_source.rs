@@ -0,0 +1,693 @@
+//! Git 模板源模块
+//! 缓存中为每个仓库保留一个裸仓库，缓存过期后只 fetch 请求的分支，并按树对象的差异增量更新已检出的目录；
+//! 指定子目录时优先用 git 命令行做部分克隆与稀疏检出，只下载子目录中的文件；固定到提交时本地已有该提交则无需联网
+
+use crate::cache_lock::CacheLock;
+use crate::credentials::Credential;
+use crate::network::NetworkConfig;
+use crate::{GeneratorError, Result, command_audit};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use git2::{Cred, CredentialType, ErrorClass, FetchOptions, ObjectType, Oid, RemoteCallbacks};
+use git2::{Repository, Tree};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// 缓存目录下存放 git 模板源的子目录
+pub const GIT_CACHE_DIR: &str = "git";
+/// 每个仓库目录下的裸仓库
+const BARE_REPO_DIR: &str = "repo.git";
+/// 每个仓库目录下的检出目录，按子目录分别检出
+const CHECKOUTS_DIR: &str = "checkouts";
+/// 每个仓库目录下按子目录分别存放的稀疏克隆
+const SPARSE_DIR: &str = "sparse";
+
+/// Git 模板获取器
+#[derive(Debug, Clone)]
+pub struct GitFetcher {
+    /// 代理、CA 证书与重试策略
+    pub network: NetworkConfig,
+    /// 缓存根目录，仓库位于其 `git/<url 哈希>/` 下
+    pub cache_dir: PathBuf,
+    /// 指定子目录时使用 git 命令行的部分克隆与稀疏检出（需要 git 2.25+），不可用时退回完整 fetch
+    pub sparse: bool,
+}
+
+impl GitFetcher {
+    /// 拉取 `branch`（默认远程 HEAD）或固定的 `commit`，并返回 `subfolder` 检出后的目录
+    ///
+    /// 再次拉取时只下载新增的对象；检出目录中树对象未变化的子目录不会重写。
+    pub fn fetch(
+        &self,
+        url: &str,
+        branch: Option<&str>,
+        commit: Option<&str>,
+        subfolder: Option<&str>,
+        credential: Option<&Credential>,
+    ) -> Result<PathBuf> {
+        let commit = commit.map(parse_commit).transpose()?;
+        let root = self
+            .cache_dir
+            .join(GIT_CACHE_DIR)
+            .join(short_hash(url.as_bytes()));
+        let _lock = CacheLock::exclusive(&root)?;
+
+        if let Some(subfolder) = subfolder
+            .map(|s| s.trim_matches('/'))
+            .filter(|s| !s.is_empty())
+            && self.sparse
+            && git_cli_available()
+        {
+            return self.fetch_sparse(&root, url, branch, commit, subfolder, credential);
+        }
+
+        let bare = root.join(BARE_REPO_DIR);
+        let repo = match Repository::open_bare(&bare) {
+            Ok(repo) => repo,
+            Err(_) => Repository::init_bare(&bare).map_err(|e| git_error("init", url, e))?,
+        };
+        let tree_id = self.fetch_tree(&repo, url, branch, commit, subfolder, credential)?;
+
+        let subfolder = subfolder.map(|s| s.trim_matches('/')).unwrap_or_default();
+        let checkout = root
+            .join(CHECKOUTS_DIR)
+            .join(short_hash(subfolder.as_bytes()));
+        update_checkout(&repo, &checkout, tree_id).map_err(|e| git_error("checkout", url, e))?;
+        Ok(checkout)
+    }
+
+    /// fetch 请求的分支（或固定的提交）并返回子目录的树对象
+    fn fetch_tree(
+        &self,
+        repo: &Repository,
+        url: &str,
+        branch: Option<&str>,
+        commit: Option<Oid>,
+        subfolder: Option<&str>,
+        credential: Option<&Credential>,
+    ) -> Result<Oid> {
+        let commit = match commit {
+            // 提交不可变，本地已有时无需联网
+            Some(commit) => {
+                if repo.find_commit(commit).is_err() {
+                    let refspec = format!("+{0}:refs/pinned/{0}", commit);
+                    self.fetch_refspec(repo, url, &refspec, credential)?;
+                }
+                repo.find_commit(commit).map_err(|_| {
+                    GeneratorError::TemplateNotFound(format!("{} has no commit {}", url, commit))
+                })?
+            }
+            None => {
+                let (refspec, local_ref) = match branch {
+                    Some(branch) => (
+                        format!("+refs/heads/{0}:refs/remotes/origin/{0}", branch),
+                        format!("refs/remotes/origin/{}", branch),
+                    ),
+                    None => (
+                        "+HEAD:refs/remotes/origin/HEAD".to_string(),
+                        "refs/remotes/origin/HEAD".to_string(),
+                    ),
+                };
+                self.fetch_refspec(repo, url, &refspec, credential)?;
+                repo.find_reference(&local_ref)
+                    .and_then(|reference| reference.peel_to_commit())
+                    .map_err(|e| git_error("fetch", url, e))?
+            }
+        };
+        let tree = commit.tree().map_err(|e| git_error("fetch", url, e))?;
+        match subfolder
+            .map(|s| s.trim_matches('/'))
+            .filter(|s| !s.is_empty())
+        {
+            Some(subfolder) => {
+                let entry = tree.get_path(Path::new(subfolder)).map_err(|_| {
+                    GeneratorError::TemplateNotFound(format!(
+                        "{} has no folder '{}' at {}",
+                        url,
+                        subfolder,
+                        commit.id()
+                    ))
+                })?;
+                Ok(entry.id())
+            }
+            None => Ok(tree.id()),
+        }
+    }
+
+    fn fetch_refspec(
+        &self,
+        repo: &Repository,
+        url: &str,
+        refspec: &str,
+        credential: Option<&Credential>,
+    ) -> Result<()> {
+        command_audit::record_remote("git", &["fetch", url, refspec])?;
+        let mut options = self.fetch_options(url, credential)?;
+        let mut remote = repo
+            .remote_anonymous(url)
+            .map_err(|e| git_error("fetch", url, e))?;
+        self.network
+            .retry_policy()
+            .run(url, is_retryable, || {
+                remote.fetch(&[refspec], Some(&mut options), None)
+            })?
+            .map_err(|e| git_error("fetch", url, e))
+    }
+
+    /// 只下载子目录所需的对象：`--filter=blob:none` 的浅克隆只取提交与树，
+    /// cone 模式的稀疏检出再按需批量下载子目录中的文件；再次拉取时 checkout 只改写变化的文件
+    fn fetch_sparse(
+        &self,
+        root: &Path,
+        url: &str,
+        branch: Option<&str>,
+        commit: Option<Oid>,
+        subfolder: &str,
+        credential: Option<&Credential>,
+    ) -> Result<PathBuf> {
+        let dir = root.join(SPARSE_DIR).join(short_hash(subfolder.as_bytes()));
+        if !dir.join(".git").is_dir() {
+            if dir.exists() {
+                fs::remove_dir_all(&dir)?;
+            }
+            fs::create_dir_all(&dir)?;
+            for args in [
+                vec!["init", "--quiet"],
+                vec!["remote", "add", "origin", url],
+                vec!["config", "remote.origin.promisor", "true"],
+                vec!["config", "remote.origin.partialclonefilter", "blob:none"],
+                vec!["sparse-checkout", "set", "--cone", subfolder],
+            ] {
+                self.git(&dir, url, &args, None)?;
+            }
+        }
+
+        let pinned = commit.map(|commit| commit.to_string());
+        let reference = pinned.as_deref().or(branch).unwrap_or("HEAD");
+        let cached = pinned.as_deref().is_some_and(|commit| {
+            let object = format!("{}^{{commit}}", commit);
+            self.git(&dir, url, &["cat-file", "-e", &object], None)
+                .is_ok()
+        });
+        if !cached {
+            let fetch = [
+                "fetch",
+                "--quiet",
+                "--depth=1",
+                "--filter=blob:none",
+                "origin",
+                reference,
+            ];
+            self.network.retry_policy().run(url, is_transient, || {
+                self.git(&dir, url, &fetch, credential)
+            })??;
+        }
+        let target = pinned.as_deref().unwrap_or("FETCH_HEAD");
+        self.git(
+            &dir,
+            url,
+            &["checkout", "--quiet", "--force", target],
+            credential,
+        )?;
+
+        let template = dir.join(subfolder);
+        if !template.is_dir() {
+            return Err(GeneratorError::TemplateNotFound(format!(
+                "{} has no folder '{}' on {}",
+                url, subfolder, reference
+            )));
+        }
+        Ok(template)
+    }
+
+    /// 在 `dir` 中执行 git 命令；代理、CA 证书与认证头经 `GIT_CONFIG_*` 环境变量传入，不出现在命令行参数中
+    fn git(
+        &self,
+        dir: &Path,
+        url: &str,
+        args: &[&str],
+        credential: Option<&Credential>,
+    ) -> Result<()> {
+        let mut config = Vec::new();
+        if let Some(proxy) = self.network.proxy_for(url) {
+            config.push(("http.proxy", proxy.to_string()));
+        }
+        if let Some(ca_bundle) = &self.network.ca_bundle {
+            config.push(("http.sslCAInfo", ca_bundle.display().to_string()));
+        }
+        if let Some(credential) = credential {
+            let token = BASE64.encode(format!(
+                "{}:{}",
+                credential.username.as_deref().unwrap_or("x-access-token"),
+                credential.secret
+            ));
+            config.push((
+                "http.extraHeader",
+                format!("Authorization: Basic {}", token),
+            ));
+        }
+
+        let mut command = Command::new("git");
+        command
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_CONFIG_COUNT", config.len().to_string());
+        for (index, (key, value)) in config.iter().enumerate() {
+            command
+                .env(format!("GIT_CONFIG_KEY_{}", index), key)
+                .env(format!("GIT_CONFIG_VALUE_{}", index), value);
+        }
+
+        let output = command_audit::remote_output(&mut command)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GeneratorError::ExternalCommand(format!(
+                "git {} failed for {}: {}",
+                args[0],
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    /// 代理与 CA 证书之外再加上认证：带凭据时按用户名/令牌认证，SSH 地址使用 ssh-agent
+    fn fetch_options(
+        &self,
+        url: &str,
+        credential: Option<&Credential>,
+    ) -> Result<FetchOptions<'static>> {
+        let credential = credential.cloned();
+        let mut tried = false;
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |_, username, allowed| {
+            // 认证失败时 libgit2 会反复调用，只提供一次凭据
+            if std::mem::replace(&mut tried, true) {
+                return Err(git2::Error::from_str("authentication failed"));
+            }
+            match &credential {
+                Some(credential) if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) => {
+                    Cred::userpass_plaintext(
+                        credential.username.as_deref().unwrap_or("x-access-token"),
+                        &credential.secret,
+                    )
+                }
+                _ if allowed.contains(CredentialType::SSH_KEY) => {
+                    Cred::ssh_key_from_agent(username.unwrap_or("git"))
+                }
+                _ => Cred::default(),
+            }
+        });
+
+        let mut options = self.network.git_fetch_options(url)?;
+        options.remote_callbacks(callbacks);
+        Ok(options)
+    }
+}
+
+/// 网络类错误视为暂时性故障，认证失败等不重试
+fn is_retryable(error: &git2::Error) -> bool {
+    error.code() != git2::ErrorCode::Auth
+        && matches!(
+            error.class(),
+            ErrorClass::Net | ErrorClass::Http | ErrorClass::Ssl | ErrorClass::Os
+        )
+}
+
+/// 固定的提交必须是完整的 40 位 SHA-1，缩写可能随仓库增长产生歧义
+fn parse_commit(commit: &str) -> Result<Oid> {
+    let commit = commit.trim();
+    if commit.len() != 40 || !commit.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(GeneratorError::Configuration(format!(
+            "Pinned commit '{}' must be a full 40-character SHA-1",
+            commit
+        )));
+    }
+    Oid::from_str(commit).map_err(|e| GeneratorError::Configuration(e.to_string()))
+}
+
+/// git 命令行的网络类失败视为暂时性故障
+fn is_transient(error: &GeneratorError) -> bool {
+    const MARKERS: [&str; 7] = [
+        "Could not resolve host",
+        "timed out",
+        "Connection reset",
+        "Connection refused",
+        "early EOF",
+        "RPC failed",
+        "returned error: 5",
+    ];
+    let message = error.to_string();
+    MARKERS.iter().any(|marker| message.contains(marker))
+}
+
+/// PATH 中是否有支持稀疏检出的 git 命令行
+fn git_cli_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        Command::new("git")
+            .args(["sparse-checkout", "-h"])
+            .output()
+            // `-h` 以退出码 129 打印用法，不支持该子命令时报错退出码为 1
+            .is_ok_and(|output| output.status.code() == Some(129))
+    })
+}
+
+fn git_error(operation: &str, url: &str, error: git2::Error) -> GeneratorError {
+    GeneratorError::ExternalCommand(format!("git {} failed for {}: {}", operation, url, error))
+}
+
+fn short_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))[..16].to_string()
+}
+
+/// 检出目录旁记录已检出树对象的文件
+fn state_file(checkout: &Path) -> PathBuf {
+    checkout.with_extension("tree")
+}
+
+/// 把检出目录更新为 `tree_id`：与上次检出的树对比，只重写变化的文件与子目录
+///
+/// 上次的记录缺失（首次检出或中途失败）时清空目录重新检出。
+fn update_checkout(
+    repo: &Repository,
+    checkout: &Path,
+    tree_id: Oid,
+) -> std::result::Result<(), git2::Error> {
+    let state = state_file(checkout);
+    let previous = fs::read_to_string(&state)
+        .ok()
+        .and_then(|id| Oid::from_str(id.trim()).ok())
+        .filter(|_| checkout.is_dir())
+        .and_then(|id| repo.find_tree(id).ok());
+    if previous.as_ref().is_some_and(|tree| tree.id() == tree_id) {
+        return Ok(());
+    }
+
+    let io = |e: std::io::Error| git2::Error::from_str(&e.to_string());
+    let _ = fs::remove_file(&state);
+    if previous.is_none() && checkout.exists() {
+        fs::remove_dir_all(checkout).map_err(io)?;
+    }
+    materialize(repo, previous.as_ref(), &repo.find_tree(tree_id)?, checkout).map_err(io)?;
+    fs::write(&state, tree_id.to_string()).map_err(io)
+}
+
+/// 将 `tree` 写入 `dir`；与 `previous` 中相同的条目跳过，`previous` 中已删除的条目移除
+fn materialize(
+    repo: &Repository,
+    previous: Option<&Tree>,
+    tree: &Tree,
+    dir: &Path,
+) -> std::io::Result<()> {
+    let other = |e: git2::Error| std::io::Error::other(e.to_string());
+    fs::create_dir_all(dir)?;
+
+    for entry in tree.iter() {
+        let Some(name) = entry.name() else {
+            continue;
+        };
+        let path = dir.join(name);
+        let old = previous.and_then(|previous| previous.get_name(name));
+        if old
+            .as_ref()
+            .is_some_and(|old| old.id() == entry.id() && old.filemode() == entry.filemode())
+            && fs::symlink_metadata(&path).is_ok()
+        {
+            continue;
+        }
+
+        match entry.kind() {
+            Some(ObjectType::Tree) => {
+                if path.is_file() || path.is_symlink() {
+                    fs::remove_file(&path)?;
+                }
+                let old_tree = old
+                    .filter(|old| old.kind() == Some(ObjectType::Tree))
+                    .and_then(|old| repo.find_tree(old.id()).ok());
+                let subtree = repo.find_tree(entry.id()).map_err(other)?;
+                materialize(repo, old_tree.as_ref(), &subtree, &path)?;
+            }
+            Some(ObjectType::Blob) => {
+                remove_path(&path)?;
+                let blob = repo.find_blob(entry.id()).map_err(other)?;
+                write_blob(&path, blob.content(), entry.filemode())?;
+            }
+            // 子模块不检出
+            _ => {}
+        }
+    }
+
+    if let Some(previous) = previous {
+        for entry in previous.iter() {
+            if let Some(name) = entry.name()
+                && tree.get_name(name).is_none()
+            {
+                remove_path(&dir.join(name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(_) => Ok(()),
+    }
+}
+
+/// 按 git 文件模式写入：可执行文件保留执行权限，符号链接在 unix 上还原为链接
+fn write_blob(path: &Path, content: &[u8], filemode: i32) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::fs::PermissionsExt;
+        if filemode == 0o120000 {
+            return std::os::unix::fs::symlink(std::ffi::OsStr::from_bytes(content), path);
+        }
+        fs::write(path, content)?;
+        if filemode == 0o100755 {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = filemode;
+        fs::write(path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_audit::{CommandPolicy, with_audit};
+    use tempfile::tempdir;
+
+    fn commit(repo: &Repository, files: &[(&str, Option<&str>)], message: &str) -> Oid {
+        let workdir = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (file, content) in files {
+            let path = workdir.join(file);
+            match content {
+                Some(content) => {
+                    fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    fs::write(&path, content).unwrap();
+                    index.add_path(Path::new(file)).unwrap();
+                }
+                None => {
+                    fs::remove_file(&path).unwrap();
+                    index.remove_path(Path::new(file)).unwrap();
+                }
+            }
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parent.iter().collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fetch_updates_only_changed_folders() {
+        let upstream_dir = tempdir().unwrap();
+        let upstream = Repository::init(upstream_dir.path()).unwrap();
+        let initial = commit(
+            &upstream,
+            &[
+                ("templates/vue/template.json", Some("{\"name\":\"vue\"}")),
+                (
+                    "templates/react/template.json",
+                    Some("{\"name\":\"react\"}"),
+                ),
+                ("templates/react/old.txt", Some("old")),
+                ("README.md", Some("readme")),
+            ],
+            "initial",
+        );
+
+        let cache = tempdir().unwrap();
+        let fetcher = GitFetcher {
+            network: NetworkConfig::default(),
+            cache_dir: cache.path().to_path_buf(),
+            sparse: false,
+        };
+        let url = upstream_dir.path().to_str().unwrap();
+        let dir = fetcher
+            .fetch(url, None, None, Some("templates/"), None)
+            .unwrap();
+        assert!(dir.join("vue/template.json").is_file());
+        assert!(!dir.join("README.md").exists());
+
+        // 未变化的 vue 目录不会被重写，本地改动得以保留
+        fs::write(dir.join("vue/template.json"), "local").unwrap();
+        commit(
+            &upstream,
+            &[
+                (
+                    "templates/react/template.json",
+                    Some("{\"name\":\"react2\"}"),
+                ),
+                ("templates/react/old.txt", None),
+            ],
+            "update react",
+        );
+        let again = fetcher
+            .fetch(url, None, None, Some("templates"), None)
+            .unwrap();
+        assert_eq!(again, dir);
+        assert_eq!(
+            fs::read_to_string(dir.join("react/template.json")).unwrap(),
+            "{\"name\":\"react2\"}"
+        );
+        assert!(!dir.join("react/old.txt").exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("vue/template.json")).unwrap(),
+            "local"
+        );
+
+        // 固定到提交时检出该提交的内容；新缓存中按提交 ID 直接 fetch
+        let initial = initial.to_string();
+        let pinned = fetcher
+            .fetch(url, None, Some(&initial), Some("templates"), None)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(pinned.join("react/template.json")).unwrap(),
+            "{\"name\":\"react\"}"
+        );
+        let fresh_cache = tempdir().unwrap();
+        let fresh = GitFetcher {
+            cache_dir: fresh_cache.path().to_path_buf(),
+            ..fetcher.clone()
+        };
+        let pinned = fresh
+            .fetch(url, Some("main"), Some(&initial), Some("templates"), None)
+            .unwrap();
+        assert!(pinned.join("react/old.txt").is_file());
+        assert!(matches!(
+            fetcher.fetch(url, None, Some(&initial[..12]), None, None),
+            Err(GeneratorError::Configuration(_))
+        ));
+
+        assert!(matches!(
+            fetcher.fetch(url, None, None, Some("missing"), None),
+            Err(GeneratorError::TemplateNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_sparse_fetch_downloads_only_subfolder() {
+        if !git_cli_available() {
+            return;
+        }
+        let upstream_dir = tempdir().unwrap();
+        let upstream = Repository::init(upstream_dir.path()).unwrap();
+        upstream
+            .config()
+            .unwrap()
+            .set_bool("uploadpack.allowFilter", true)
+            .unwrap();
+        let initial = commit(
+            &upstream,
+            &[
+                ("templates/vue/template.json", Some("{\"name\":\"vue\"}")),
+                (
+                    "templates/react/template.json",
+                    Some("{\"name\":\"react\"}"),
+                ),
+                ("assets/large.bin", Some("large")),
+            ],
+            "initial",
+        );
+
+        let cache = tempdir().unwrap();
+        let fetcher = GitFetcher {
+            network: NetworkConfig::default(),
+            cache_dir: cache.path().to_path_buf(),
+            sparse: true,
+        };
+        let url = format!("file://{}", upstream_dir.path().display());
+        // 审计模式下稀疏检出照常执行并列出每条 git 命令
+        let (dir, commands) = with_audit(CommandPolicy::default(), || {
+            fetcher.fetch(&url, None, None, Some("/templates/vue/"), None)
+        });
+        let dir = dir.unwrap();
+        assert!(dir.join("template.json").is_file());
+        assert!(commands.iter().all(|command| command.program == "git"));
+        assert!(
+            commands
+                .iter()
+                .any(|command| command.args[..2] == ["sparse-checkout", "set"])
+        );
+        let clone = dir.parent().unwrap().parent().unwrap();
+        assert!(!clone.join("templates/react").exists());
+        assert!(!clone.join("assets").exists());
+
+        // 子目录之外的文件内容没有下载
+        let objects = Command::new("git")
+            .args(["rev-list", "--objects", "--missing=print", "--all"])
+            .current_dir(clone)
+            .output()
+            .unwrap();
+        let objects = String::from_utf8_lossy(&objects.stdout);
+        assert!(objects.lines().any(|line| line.starts_with('?')));
+
+        commit(
+            &upstream,
+            &[("templates/vue/template.json", Some("{\"name\":\"vue2\"}"))],
+            "update vue",
+        );
+        let again = fetcher
+            .fetch(&url, None, None, Some("templates/vue"), None)
+            .unwrap();
+        assert_eq!(again, dir);
+        assert_eq!(
+            fs::read_to_string(dir.join("template.json")).unwrap(),
+            "{\"name\":\"vue2\"}"
+        );
+
+        // 已下载过的固定提交直接检出
+        let initial = initial.to_string();
+        fetcher
+            .fetch(&url, None, Some(&initial), Some("templates/vue"), None)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("template.json")).unwrap(),
+            "{\"name\":\"vue\"}"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! 钩子模块
+//! 模板 `hooks` 与蓝图后置步骤共用的跨平台命令：按钩子类型与当前平台的覆盖配置选择解释器
+
+#[cfg(feature = "native")]
+use crate::cancel;
+use crate::{GeneratorError, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+#[cfg(feature = "native")]
+use std::time::Duration;
+
+/// 传给钩子的模板目录环境变量，便于引用模板 `hooks/` 目录中的脚本
+pub const TEMPLATE_DIR_ENV: &str = "GENERATOR_TEMPLATE_DIR";
+
+/// 钩子类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HookKind {
+    /// 经系统 shell 执行的命令（Unix 为 `sh -c`，Windows 为 `cmd /C`）
+    Shell,
+    /// 用 `node` 执行的脚本
+    Node,
+    /// 用 PowerShell 执行的脚本，优先使用 `pwsh`
+    Powershell,
+    /// 直接执行的程序，不经过 shell
+    Exec,
+}
+
+/// 钩子在特定平台上的覆盖配置，未设置的字段沿用通用配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HookOverride {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<HookKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<String>>,
+}
+
+/// 生成后执行的钩子
+///
+/// ```json
+/// { "type": "shell", "run": "./scripts/setup.sh",
+///   "windows": { "type": "powershell", "run": "scripts/setup.ps1" } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Hook {
+    /// 钩子类型，省略时按脚本扩展名推断（`.js` → node，`.ps1` → powershell），否则为 shell
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<HookKind>,
+    /// shell 类型为命令行，其他类型为脚本或程序路径
+    pub run: String,
+    /// 传给脚本或程序的参数，shell 类型不使用
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// 工作目录（相对项目目录）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows: Option<HookOverride>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub macos: Option<HookOverride>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linux: Option<HookOverride>,
+}
+
+/// 合并平台覆盖后的钩子
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedHook {
+    pub kind: HookKind,
+    pub run: String,
+    pub args: Vec<String>,
+}
+
+impl Hook {
+    /// 按平台（`std::env::consts::OS` 的取值）合并覆盖配置并确定类型
+    pub fn resolve(&self, os: &str) -> ResolvedHook {
+        let platform = match os {
+            "windows" => self.windows.as_ref(),
+            "macos" => self.macos.as_ref(),
+            "linux" => self.linux.as_ref(),
+            _ => None,
+        };
+        let run = platform
+            .and_then(|o| o.run.clone())
+            .unwrap_or_else(|| self.run.clone());
+        let args = platform
+            .and_then(|o| o.args.clone())
+            .unwrap_or_else(|| self.args.clone());
+        // 覆盖了脚本但没有指定类型时重新推断，避免沿用通用配置中的解释器
+        let kind = match platform {
+            Some(o) if o.kind.is_some() => o.kind,
+            Some(o) if o.run.is_some() => None,
+            _ => self.kind,
+        }
+        .unwrap_or_else(|| infer_kind(&run));
+        ResolvedHook { kind, run, args }
+    }
+
+    /// 当前平台上执行钩子的命令
+    pub fn command(&self, project_dir: &Path) -> Command {
+        let cwd: PathBuf = match &self.cwd {
+            Some(cwd) => project_dir.join(cwd),
+            None => project_dir.to_path_buf(),
+        };
+        let mut command = self.resolve(std::env::consts::OS).command();
+        command.current_dir(cwd);
+        command
+    }
+
+    /// 执行由 [`Hook::command`] 构造（可能追加了环境变量）的命令，超时或被取消时终止
+    #[cfg(feature = "native")]
+    pub fn output(&self, command: &mut Command, timeout: Option<Duration>) -> Result<Output> {
+        cancel::output_with_timeout(command, timeout).map_err(|e| match e {
+            GeneratorError::Io(e) => {
+                GeneratorError::ExternalCommand(format!("{}: {}", self.run, e))
+            }
+            GeneratorError::Timeout(message) => {
+                GeneratorError::Timeout(format!("hook '{}': {}", self.run, message))
+            }
+            e => e,
+        })
+    }
+
+    /// 检查钩子的退出状态，非零退出码视为失败
+    pub fn check(&self, output: &Output) -> Result<()> {
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GeneratorError::ExternalCommand(format!(
+                "Hook '{}' failed: {}",
+                self.run,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+}
+
+impl ResolvedHook {
+    /// 按类型选择解释器
+    pub fn command(&self) -> Command {
+        let mut command = match self.kind {
+            HookKind::Shell if cfg!(windows) => {
+                let mut command = Command::new("cmd");
+                command.arg("/C").arg(&self.run);
+                return command;
+            }
+            HookKind::Shell => {
+                let mut command = Command::new("sh");
+                command.arg("-c").arg(&self.run);
+                return command;
+            }
+            HookKind::Node => {
+                let mut command = Command::new("node");
+                command.arg(&self.run);
+                command
+            }
+            HookKind::Powershell => {
+                let mut command = Command::new(powershell());
+                command.args(["-NoProfile", "-NonInteractive"]);
+                if cfg!(windows) {
+                    command.args(["-ExecutionPolicy", "Bypass"]);
+                }
+                command.arg("-File").arg(&self.run);
+                command
+            }
+            HookKind::Exec => Command::new(&self.run),
+        };
+        command.args(&self.args);
+        command
+    }
+}
+
+/// 按脚本扩展名推断钩子类型，包含空白的命令行视为 shell 命令
+fn infer_kind(run: &str) -> HookKind {
+    if run.contains(char::is_whitespace) {
+        return HookKind::Shell;
+    }
+    match Path::new(run).extension().and_then(|ext| ext.to_str()) {
+        Some("js" | "mjs" | "cjs") => HookKind::Node,
+        Some("ps1") => HookKind::Powershell,
+        _ => HookKind::Shell,
+    }
+}
+
+/// PowerShell 解释器：PATH 中有 `pwsh`（PowerShell 7+）时使用它，Windows 上退回自带的 `powershell`
+fn powershell() -> &'static str {
+    if on_path("pwsh") || !cfg!(windows) {
+        "pwsh"
+    } else {
+        "powershell"
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    let name = if cfg!(windows) {
+        format!("{}.exe", program)
+    } else {
+        program.to_string()
+    };
+    std::env::split_paths(&paths).any(|dir| dir.join(&name).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_hook_per_platform() {
+        let hook: Hook = serde_json::from_str(
+            r#"{
+                "run": "./scripts/setup.sh",
+                "windows": { "run": "scripts/setup.ps1" },
+                "macos": { "type": "exec", "args": ["--mac"] }
+            }"#,
+        )
+        .unwrap();
+
+        let linux = hook.resolve("linux");
+        assert_eq!(linux.kind, HookKind::Shell);
+        assert_eq!(linux.run, "./scripts/setup.sh");
+
+        let windows = hook.resolve("windows");
+        assert_eq!(windows.kind, HookKind::Powershell);
+        assert_eq!(windows.run, "scripts/setup.ps1");
+
+        let macos = hook.resolve("macos");
+        assert_eq!(macos.kind, HookKind::Exec);
+        assert_eq!(macos.args, ["--mac"]);
+
+        assert_eq!(infer_kind("scripts/setup.mjs"), HookKind::Node);
+        assert_eq!(infer_kind("node scripts/setup.js"), HookKind::Shell);
+
+        let node = ResolvedHook {
+            kind: HookKind::Node,
+            run: "setup.js".to_string(),
+            args: vec!["--ci".to_string()],
+        }
+        .command();
+        assert_eq!(node.get_program(), "node");
+        assert_eq!(node.get_args().collect::<Vec<_>>(), ["setup.js", "--ci"]);
+    }
+
+    #[cfg(all(unix, feature = "native"))]
+    #[test]
+    fn test_hook_output_in_cwd() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("app")).unwrap();
+        let hook = Hook {
+            kind: Some(HookKind::Exec),
+            run: "pwd".to_string(),
+            cwd: Some("app".to_string()),
+            ..Default::default()
+        };
+        let output = hook.output(&mut hook.command(dir.path()), None).unwrap();
+        hook.check(&output).unwrap();
+        assert!(
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .ends_with("app")
+        );
+
+        let failing = Hook {
+            run: "exit 3".to_string(),
+            ..Default::default()
+        };
+        let output = failing
+            .output(&mut failing.command(dir.path()), None)
+            .unwrap();
+        assert_eq!(output.status.code(), Some(3));
+        assert!(failing.check(&output).is_err());
+    }
+}
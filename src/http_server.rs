@@ -0,0 +1,388 @@
+//! REST 服务模块
+//! `http-server` 特性下用 axum 提供模板列表、变量 Schema 与生成任务接口，任务进度通过 SSE 推送，供内部脚手架门户使用
+//!
+//! 接口不做鉴权，只应部署在内网或带鉴权的反向代理之后。
+
+use crate::events::{self, Event, Listener};
+use crate::job_history::{JobRecord, JobStore};
+use crate::template_registry::{TemplateRegistryConfig, TemplateVariable};
+use crate::templates::{list_templates_by_type, resolve_template};
+use crate::variables::variables_schema;
+use crate::{ErrorDetails, GenerateOptions, GeneratorError, Result, cancel};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{self, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+
+/// 每个任务缓冲的实时事件数，订阅者落后更多时跳过中间的事件
+const EVENT_CAPACITY: usize = 256;
+
+/// 在 `addr` 上监听直到 Ctrl-C；`:8080` 表示监听所有网卡的 8080 端口
+pub fn serve(addr: &str) -> Result<()> {
+    let addr = match addr.strip_prefix(':') {
+        Some(port) => format!("0.0.0.0:{}", port),
+        None => addr.to_string(),
+    };
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        axum::serve(listener, router(Jobs::new(JobStore::default())))
+            .with_graceful_shutdown(cancel::global().cancelled())
+            .await?;
+        Ok(())
+    })
+}
+
+/// 路由：
+///
+/// - `GET /templates/{project_type}`：模板名称列表
+/// - `GET /templates/{project_type}/{template}/schema`：变量的 JSON Schema
+/// - `POST /jobs`：提交生成任务（请求体为 GenerateOptions），返回任务 id
+/// - `GET /jobs?limit=20`：生成历史，从新到旧
+/// - `GET /jobs/{id}`：任务记录（参数、状态、日志与结果）
+/// - `GET /jobs/{id}/events`：本进程中任务事件的 SSE 流（progress、log，最后是 result 或 error）
+/// - `POST /jobs/{id}/rerun`：按记录的参数重新生成，请求体可以是补充的变量，返回新任务 id
+pub fn router(jobs: Jobs) -> Router {
+    Router::new()
+        .route("/templates/{project_type}", get(list))
+        .route("/templates/{project_type}/{template}/schema", get(schema))
+        .route("/jobs", post(submit).get(history))
+        .route("/jobs/{id}", get(status))
+        .route("/jobs/{id}/events", get(job_events))
+        .route("/jobs/{id}/rerun", post(rerun))
+        .with_state(jobs)
+}
+
+/// 以 JSON 错误详情响应的生成器错误
+struct ApiError(StatusCode, ErrorDetails);
+
+impl From<GeneratorError> for ApiError {
+    fn from(error: GeneratorError) -> Self {
+        let status = match error {
+            GeneratorError::TemplateNotFound(_) => StatusCode::NOT_FOUND,
+            GeneratorError::InvalidProjectName(_)
+            | GeneratorError::InvalidVariables(_)
+            | GeneratorError::Configuration(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self(status, error.details(None, None))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(self.1)).into_response()
+    }
+}
+
+/// 在阻塞线程上执行文件系统操作
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> std::result::Result<T, ApiError> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| GeneratorError::Unknown(e.to_string()))?
+        .map_err(ApiError::from)
+}
+
+async fn list(
+    Path(project_type): Path<String>,
+) -> std::result::Result<Json<Vec<String>>, ApiError> {
+    blocking(move || list_templates_by_type(&project_type))
+        .await
+        .map(Json)
+}
+
+async fn schema(
+    Path((project_type, template)): Path<(String, String)>,
+) -> std::result::Result<Json<Value>, ApiError> {
+    blocking(move || {
+        let template =
+            resolve_template(&TemplateRegistryConfig::user()?, &project_type, &template)?;
+        Ok(variables_schema(&template.metadata))
+    })
+    .await
+    .map(Json)
+}
+
+async fn submit(
+    State(jobs): State<Jobs>,
+    Json(options): Json<GenerateOptions>,
+) -> std::result::Result<(StatusCode, Json<Value>), ApiError> {
+    let id = blocking(move || jobs.submit(options, None)).await?;
+    Ok((StatusCode::ACCEPTED, Json(json!({ "id": id }))))
+}
+
+async fn history(
+    State(jobs): State<Jobs>,
+    Query(query): Query<HistoryQuery>,
+) -> std::result::Result<Json<Vec<JobRecord>>, ApiError> {
+    blocking(move || jobs.store.list(query.limit))
+        .await
+        .map(Json)
+}
+
+async fn status(
+    State(jobs): State<Jobs>,
+    Path(id): Path<u64>,
+) -> std::result::Result<Json<JobRecord>, ApiError> {
+    blocking(move || jobs.store.get(id))
+        .await?
+        .map(Json)
+        .ok_or_else(|| job_not_found(id))
+}
+
+async fn rerun(
+    State(jobs): State<Jobs>,
+    Path(id): Path<u64>,
+    variables: Option<Json<HashMap<String, String>>>,
+) -> std::result::Result<(StatusCode, Json<Value>), ApiError> {
+    let store = jobs.store.clone();
+    let previous = blocking(move || store.get(id))
+        .await?
+        .ok_or_else(|| job_not_found(id))?;
+    let mut options = previous.options;
+    if let Some(Json(variables)) = variables {
+        options
+            .variables
+            .get_or_insert_with(HashMap::new)
+            .extend(variables);
+    }
+    let rerun = blocking(move || jobs.submit(options, Some(id))).await?;
+    Ok((StatusCode::ACCEPTED, Json(json!({ "id": rerun }))))
+}
+
+fn job_not_found(id: u64) -> ApiError {
+    ApiError(
+        StatusCode::NOT_FOUND,
+        GeneratorError::Configuration(format!("Generation job {} not found", id))
+            .details(None, None),
+    )
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+async fn job_events(
+    State(jobs): State<Jobs>,
+    Path(id): Path<u64>,
+) -> std::result::Result<
+    Sse<impl Stream<Item = std::result::Result<sse::Event, Infallible>>>,
+    StatusCode,
+> {
+    let (history, receiver) = jobs.subscribe(id).ok_or(StatusCode::NOT_FOUND)?;
+    // 先补发订阅前的事件；任务结束时发送端被丢弃，实时流随之结束
+    // 已结束的任务用一个立即关闭的通道代替
+    let receiver = receiver.unwrap_or_else(|| broadcast::channel(1).1);
+    let live = BroadcastStream::new(receiver).filter_map(|event| event.ok());
+    let stream = tokio_stream::iter(history)
+        .chain(live)
+        .map(|event| Ok(event.to_sse()));
+    Ok(Sse::new(stream).keep_alive(sse::KeepAlive::default()))
+}
+
+/// 任务事件
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobEvent {
+    /// SSE 事件名：progress、log、result 或 error
+    pub name: &'static str,
+    pub data: Value,
+}
+
+impl JobEvent {
+    fn to_sse(&self) -> sse::Event {
+        sse::Event::default()
+            .event(self.name)
+            .data(self.data.to_string())
+    }
+}
+
+/// 运行中与本进程执行过的任务的事件
+struct Job {
+    events: Vec<JobEvent>,
+    /// 任务结束后为 None
+    sender: Option<broadcast::Sender<JobEvent>>,
+}
+
+impl Job {
+    fn record(&mut self, event: JobEvent) {
+        if let Some(sender) = &self.sender {
+            // 没有订阅者时发送失败，事件仍记录在历史中
+            let _ = sender.send(event.clone());
+        }
+        self.events.push(event);
+    }
+}
+
+/// 生成任务：状态与结果保存在 [`JobStore`]，实时事件保存在进程内，每个任务在单独的线程上执行
+#[derive(Clone)]
+pub struct Jobs {
+    store: JobStore,
+    events: Arc<Mutex<HashMap<u64, Job>>>,
+}
+
+impl Jobs {
+    pub fn new(store: JobStore) -> Self {
+        Self {
+            store,
+            events: Arc::default(),
+        }
+    }
+
+    /// 记录并启动任务，立即返回 id
+    pub fn submit(&self, options: GenerateOptions, rerun_of: Option<u64>) -> Result<u64> {
+        let record = self.store.create(&options, rerun_of)?;
+        let id = record.id;
+        let (sender, _) = broadcast::channel(EVENT_CAPACITY);
+        self.lock().insert(
+            id,
+            Job {
+                events: Vec::new(),
+                sender: Some(sender),
+            },
+        );
+        let jobs = self.clone();
+        std::thread::spawn(move || {
+            let listener = Arc::new(JobListener {
+                jobs: jobs.clone(),
+                id,
+            });
+            let record = events::with_listener(listener, || jobs.store.run(record, options));
+            jobs.finish(id, record);
+        });
+        Ok(id)
+    }
+
+    /// 已发生的事件与后续事件的接收端；任务已结束时没有接收端
+    pub fn subscribe(
+        &self,
+        id: u64,
+    ) -> Option<(Vec<JobEvent>, Option<broadcast::Receiver<JobEvent>>)> {
+        let table = self.lock();
+        let job = table.get(&id)?;
+        Some((
+            job.events.clone(),
+            job.sender.as_ref().map(broadcast::Sender::subscribe),
+        ))
+    }
+
+    fn push(&self, id: u64, event: JobEvent) {
+        if let Some(job) = self.lock().get_mut(&id) {
+            job.record(event);
+        }
+    }
+
+    fn finish(&self, id: u64, record: Result<JobRecord>) {
+        let event = match record {
+            Ok(JobRecord {
+                result: Some(result),
+                ..
+            }) => JobEvent {
+                name: "result",
+                data: json!(result),
+            },
+            Ok(JobRecord { error, .. }) => JobEvent {
+                name: "error",
+                data: json!(error),
+            },
+            // 任务记录写入失败
+            Err(e) => JobEvent {
+                name: "error",
+                data: json!(e.details(None, None)),
+            },
+        };
+        if let Some(job) = self.lock().get_mut(&id) {
+            job.record(event);
+            job.sender = None;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, Job>> {
+        self.events.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 把生成事件记录到任务；门户不交互，缺少的变量按缺少报错
+struct JobListener {
+    jobs: Jobs,
+    id: u64,
+}
+
+impl Listener for JobListener {
+    fn event(&self, event: Event) {
+        let event = match event {
+            Event::Progress { phase } => JobEvent {
+                name: "progress",
+                data: json!({ "phase": phase }),
+            },
+            Event::Log { message } => JobEvent {
+                name: "log",
+                data: json!({ "message": message }),
+            },
+        };
+        self.jobs.push(self.id, event);
+    }
+
+    fn prompt(&self, _variable: &TemplateVariable) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job_history::JobState;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_job_records_events_and_outcome() {
+        let dir = tempdir().unwrap();
+        let jobs = Jobs::new(JobStore {
+            dir: dir.path().to_path_buf(),
+        });
+        let options = GenerateOptions {
+            name: "app".to_string(),
+            project_type: "cobol".to_string(),
+            ..Default::default()
+        };
+        let id = jobs.submit(options, None).unwrap();
+        assert!(jobs.subscribe(id + 1).is_none());
+
+        let started = Instant::now();
+        let record = loop {
+            let record = jobs.store.get(id).unwrap().unwrap();
+            if record.status != JobState::Running || started.elapsed() > Duration::from_secs(10) {
+                break record;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(record.status, JobState::Failed);
+        assert_eq!(record.error.unwrap().code, "TEMPLATE_NOT_FOUND");
+
+        // 记录在事件之前保存，稍等 finish 完成
+        while jobs.subscribe(id).unwrap().1.is_some() && started.elapsed() < Duration::from_secs(10)
+        {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        // 结束后订阅只得到历史事件，最后一个是 error
+        let (history, receiver) = jobs.subscribe(id).unwrap();
+        assert!(receiver.is_none());
+        assert_eq!(history.last().unwrap().name, "error");
+    }
+}
@@ -0,0 +1,185 @@
+//! HTTP 模板源模块
+//! 下载 tar.gz / zip 模板归档，校验 sha256 后经 extract 模块安全解压到内容寻址缓存
+
+use crate::content_cache::{ContentCache, archive_root};
+use crate::credentials::Credential;
+use crate::extract::Extractor;
+use crate::network::NetworkConfig;
+use crate::pipeline::TEMPLATE_METADATA_FILE;
+use crate::{GeneratorError, Result, cancel, command_audit};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// HTTP 模板归档获取器
+#[derive(Debug, Clone)]
+pub struct HttpFetcher {
+    /// 代理与 CA 证书
+    pub network: NetworkConfig,
+    /// 解压后的归档按内容哈希存放，指定校验和时可免下载复用
+    pub cache: ContentCache,
+    /// 单次请求的超时
+    pub timeout: Option<Duration>,
+    pub extractor: Extractor,
+}
+
+impl HttpFetcher {
+    /// 获取模板归档并返回模板根目录
+    ///
+    /// 指定 `checksum`（`sha256:<hex>` 或十六进制）时命中缓存即复用，否则每次重新下载（内容相同时不重复解压）。
+    pub fn fetch(
+        &self,
+        url: &str,
+        checksum: Option<&str>,
+        credential: Option<&Credential>,
+    ) -> Result<PathBuf> {
+        let expected = checksum.map(|checksum| {
+            checksum
+                .strip_prefix("sha256:")
+                .unwrap_or(checksum)
+                .to_ascii_lowercase()
+        });
+        if let Some(expected) = &expected
+            && let Some(dir) = self.cache.lookup(&format!("sha256:{}", expected))?
+        {
+            return template_root(&dir, url);
+        }
+
+        let archive = self.download(url, credential)?;
+        let actual = hex::encode(Sha256::digest(&archive));
+        if let Some(expected) = &expected
+            && actual != *expected
+        {
+            return Err(GeneratorError::FileOperation(format!(
+                "Checksum mismatch for {}: expected sha256:{}, got sha256:{}",
+                url, expected, actual
+            )));
+        }
+
+        let dir = self.cache.insert(&archive, &[], |archive, dest| {
+            self.extractor.extract(archive, dest).map(|_| ())
+        })?;
+        template_root(&dir, url)
+    }
+
+    fn download(&self, url: &str, credential: Option<&Credential>) -> Result<Vec<u8>> {
+        cancel::check()?;
+        command_audit::record_remote("http", &["GET", url])?;
+        let agent = self.network.http_agent(url, self.timeout)?;
+        // 带用户名时按 Basic 认证，否则按 Bearer 令牌
+        let authorization = credential.map(|credential| match &credential.username {
+            Some(username) => format!(
+                "Basic {}",
+                BASE64.encode(format!("{}:{}", username, credential.secret))
+            ),
+            None => format!("Bearer {}", credential.secret),
+        });
+
+        let request = || match &authorization {
+            Some(authorization) => agent.get(url).set("Authorization", authorization),
+            None => agent.get(url),
+        };
+        let response = self
+            .network
+            .retry_policy()
+            .call(url, request, |e| match e {
+                ureq::Error::Status(404, _) => GeneratorError::TemplateNotFound(url.to_string()),
+                ureq::Error::Status(code @ (401 | 403), _) => {
+                    GeneratorError::Configuration(format!(
+                        "Template server rejected credentials for {} (HTTP {})",
+                        url, code
+                    ))
+                }
+                e => GeneratorError::FileOperation(format!("Failed to download {}: {}", url, e)),
+            })?;
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body)?;
+        Ok(body)
+    }
+}
+
+fn template_root(dir: &Path, url: &str) -> Result<PathBuf> {
+    archive_root(dir, TEMPLATE_METADATA_FILE).ok_or_else(|| {
+        GeneratorError::TemplateNotFound(format!(
+            "{} does not contain {}",
+            url, TEMPLATE_METADATA_FILE
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use tempfile::tempdir;
+
+    fn archive() -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for (path, content) in [
+            ("templates-main/template.json", r#"{"name": "remote"}"#),
+            ("templates-main/src/main.ts", "main"),
+        ] {
+            writer.start_file(path, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// 对任意路径返回同一归档，记录请求次数
+    fn serve(body: Vec<u8>) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/templates.zip", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_fetch_verifies_checksum_and_reuses_cache() {
+        let archive = archive();
+        let checksum = format!("sha256:{}", hex::encode(Sha256::digest(&archive)));
+        let (url, requests) = serve(archive);
+        let cache = tempdir().unwrap();
+        let fetcher = HttpFetcher {
+            network: NetworkConfig::default(),
+            cache: ContentCache::new(cache.path(), None),
+            timeout: Some(Duration::from_secs(10)),
+            extractor: Extractor::default(),
+        };
+
+        let dir = fetcher.fetch(&url, Some(&checksum), None).unwrap();
+        assert!(dir.ends_with("templates-main"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/main.ts")).unwrap(),
+            "main"
+        );
+
+        fetcher.fetch(&url, Some(&checksum), None).unwrap();
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let wrong = format!("sha256:{}", "0".repeat(64));
+        assert!(fetcher.fetch(&url, Some(&wrong), None).is_err());
+    }
+}
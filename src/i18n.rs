@@ -0,0 +1,525 @@
+//! 本地化模块
+//! 命令行提示、错误与帮助信息的消息目录（zh-CN、en-US），语言按 `--lang`、`~/.generator/locale.json`、`LANG` 依次确定
+
+use crate::utils::generator_home;
+use crate::{GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 语言设置文件名，位于 `~/.generator` 下
+pub const LOCALE_FILE: &str = "locale.json";
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    ZhCn,
+    EnUs,
+}
+
+static LANG: AtomicU8 = AtomicU8::new(0);
+
+impl Lang {
+    /// 解析语言标签，接受 `zh-CN`、`zh_CN.UTF-8`、`en`、`en-US` 等写法
+    pub fn parse(value: &str) -> Result<Self> {
+        let tag = value
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .replace('_', "-")
+            .to_lowercase();
+        match tag.split('-').next().unwrap_or_default() {
+            "zh" => Ok(Self::ZhCn),
+            "en" => Ok(Self::EnUs),
+            _ => Err(GeneratorError::Configuration(format!(
+                "Unsupported language: {} (expected zh-CN or en-US)",
+                value
+            ))),
+        }
+    }
+
+    /// 语言标签
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::ZhCn => "zh-CN",
+            Self::EnUs => "en-US",
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::ZhCn => ZH_CN,
+            Self::EnUs => EN_US,
+        }
+    }
+}
+
+/// 语言设置（`~/.generator/locale.json`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocaleConfig {
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+impl LocaleConfig {
+    /// 读取 `dir` 下的语言设置，文件不存在时为空
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(LOCALE_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e))
+        })
+    }
+}
+
+/// 确定界面语言：显式指定 > 用户设置 > `LC_ALL` / `LC_MESSAGES` / `LANG` > zh-CN
+///
+/// 环境变量中无法识别的语言（如 `C`、`POSIX`）被忽略，显式指定与用户设置中的则报错。
+pub fn detect(explicit: Option<&str>) -> Result<Lang> {
+    if let Some(lang) = explicit {
+        return Lang::parse(lang);
+    }
+    if let Some(lang) = LocaleConfig::load(&generator_home())?.lang {
+        return Lang::parse(&lang);
+    }
+    Ok(["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| Lang::parse(&value).ok())
+        .unwrap_or(Lang::ZhCn))
+}
+
+/// 设置进程范围内的界面语言
+pub fn set_lang(lang: Lang) {
+    let value = match lang {
+        Lang::ZhCn => 0,
+        Lang::EnUs => 1,
+    };
+    LANG.store(value, Ordering::Relaxed);
+}
+
+/// 当前界面语言
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::EnUs,
+        _ => Lang::ZhCn,
+    }
+}
+
+/// 按当前语言查找消息，缺失时退回 zh-CN，仍缺失时返回键本身
+pub fn message(key: &'static str) -> &'static str {
+    lookup(lang(), key)
+        .or_else(|| lookup(Lang::ZhCn, key))
+        .unwrap_or(key)
+}
+
+/// 查找消息并替换其中的 `{name}` 占位符
+pub fn format(key: &'static str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(message(key).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    lang.catalog()
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, text)| *text)
+}
+
+/// 本地化消息：`t!("cli.generating")`、`t!("cli.template_used", template = name)`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::message($key).to_string()
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+const ZH_CN: &[(&str, &str)] = &[
+    // 交互模式
+    ("cli.select_mode", "请选择生成模式"),
+    ("cli.mode_nop", "NOP平台"),
+    ("cli.mode_custom", "自定义"),
+    ("cli.using_nop", "🎯 使用 NOP 平台模式"),
+    ("cli.using_nop_hint", "📋 NOP 平台将使用预定义的模板和配置"),
+    ("cli.using_custom", "🎯 使用自定义模式"),
+    ("cli.using_custom_hint", "📋 自定义模式允许您选择任意模板源"),
+    ("cli.unknown_mode", "未知的生成模式: {mode}"),
+    ("cli.select_project_type", "请选择项目类型"),
+    ("cli.type_vue", "Vue.js 项目"),
+    ("cli.type_react", "React 项目"),
+    ("cli.type_java", "Java 项目"),
+    ("cli.type_android", "Android 应用（Kotlin / Compose）"),
+    ("cli.type_python", "Python 项目（uv / Poetry）"),
+    ("cli.type_node_lib", "Node.js/TypeScript 库"),
+    ("cli.type_rust", "Rust crate"),
+    ("cli.type_monorepo", "单仓多包 workspace"),
+    ("cli.enter_name", "请输入项目名称"),
+    ("cli.empty_input", "输入不能为空"),
+    ("cli.invalid_value", "值无效，应为 {expected}"),
+    ("cli.select_template", "请选择模板（输入可搜索）"),
+    ("cli.select_features", "请选择要启用的特性（空格选择，回车确认）"),
+    ("cli.select_crud_source", "是否按数据库表结构生成 CRUD 代码?"),
+    ("cli.select_build_tool", "请选择构建工具"),
+    ("cli.crud_none", "跳过"),
+    ("cli.crud_jdbc", "连接数据库（JDBC）"),
+    ("cli.crud_ddl", "读取 DDL 文件"),
+    ("cli.enter_db_url", "JDBC 连接串"),
+    ("cli.enter_db_username", "数据库用户名（可留空）"),
+    ("cli.enter_db_password", "数据库密码（可留空）"),
+    ("cli.enter_ddl_file", "DDL 文件路径"),
+    ("cli.template_used", "🎨 使用模板: {template}"),
+    ("cli.summary", "📋 生成信息:"),
+    ("cli.summary_name", "   项目名称: {value}"),
+    ("cli.summary_type", "   项目类型: {value}"),
+    ("cli.summary_template", "   模板: {value}"),
+    ("cli.summary_variables", "   变量: {value}"),
+    ("cli.summary_features", "   特性: {value}"),
+    ("cli.confirm_generate", "确认生成项目?"),
+    ("cli.generating", "🔄 正在生成项目..."),
+    ("cli.generated", "✅ 项目生成成功!"),
+    ("cli.cancelled", "❌ 用户取消生成"),
+    ("cli.cancelled_message", "用户取消"),
+    ("cli.answers_saved", "💾 回答已保存到 {path}"),
+    ("network.retrying", "🔁 {operation} 第 {attempt} 次尝试失败（{error}），{delay} ms 后重试"),
+    ("cli.session_saved", "💾 已保存本次回答，解决问题后可使用 --resume 跳过提问继续生成"),
+    ("cli.resuming", "🔁 恢复上次中断的会话（失败原因: {error}）"),
+    // 命令入口
+    ("main.banner", "🚀 项目生成器 - Rust CLI 模式"),
+    ("main.cancelling", "⏹️  正在取消..."),
+    ("main.serving_http", "🌐 REST 服务已启动: http://{addr}（Ctrl-C 停止）"),
+    ("main.help_hint", "使用 'cargo run help' 查看帮助"),
+    ("main.help_shown", "帮助信息已显示"),
+    ("main.version", "项目生成器 v{version}"),
+    ("main.done", "🎉 操作完成!"),
+    ("main.not_done", "⚠️  操作未完成"),
+    ("main.audit_none", "没有需要执行的外部命令"),
+    ("main.audit_denied", "⛔ 被命令策略禁止: {pattern}"),
+    ("main.template_created", "✅ 模板骨架已创建:"),
+    ("main.backstage_imported", "✅ Backstage 模板已导入到 {path}:"),
+    ("main.backstage_skipped", "⚠️  未转换: {item}"),
+    ("main.lint_done", "✅ 模板校验完成"),
+    ("main.test_done", "✅ 模板试渲染完成"),
+    ("main.schema_written", "✅ JSON Schema 已写入 {path}"),
+    ("main.enter_secret", "🔑 请输入 {id} 的令牌或密码: "),
+    ("main.credential_saved", "✅ 凭据 {id} 已保存"),
+    ("main.credential_deleted", "✅ 凭据 {id} 已删除"),
+    ("main.credential_missing", "⚠️  凭据 {id} 不存在"),
+    ("main.usage_disabled", "⚠️  使用记录未开启，运行 generator-cli usage on 开启"),
+    ("main.usage_total", "📊 共生成 {total} 次"),
+    ("main.usage_line", "{template}  {count} 次  最近 {last_used}"),
+    ("main.usage_on", "✅ 使用记录已开启"),
+    ("main.usage_off", "✅ 使用记录已关闭"),
+    ("main.dev_watching", "👀 正在监听 {template}，预览输出到 {out}（Ctrl-C 退出）"),
+    ("main.dev_full", "🔄 重新渲染 {count} 个文件"),
+    ("main.dev_incremental", "🔄 增量渲染 {count} 个文件"),
+    ("main.dev_removed", "，移除 {count} 个"),
+    ("main.index_refreshed", "🗂️  模板索引已刷新: {templates} 个模板（重新解析 {updated}，复用 {reused}，移除 {removed}）"),
+    ("main.cache_dir", "📦 缓存目录: {path}"),
+    ("main.cache_size", "   {entries} 个归档，{aliases} 个别名，共 {size} MB"),
+    ("main.cache_limit", "   上限 {size} MB"),
+    ("main.doctor_ok", "✅ 环境检查通过"),
+    ("main.doctor_failed", "❌ 环境检查发现必须修复的问题"),
+    ("main.template_packed", "📦 模板已打包: {path}"),
+    ("main.template_installed", "✅ 模板已安装: {path}"),
+    ("main.template_published", "🚀 已发布 {name}@{version} 到 {registry}: {location}"),
+    // 错误上下文
+    ("error.error", "错误"),
+    ("error.help", "显示帮助失败"),
+    ("error.missing_args", "缺少参数"),
+    ("error.unknown_action", "未知操作"),
+    ("error.unknown_arg", "未知参数"),
+    ("error.invalid_lang", "无法设置语言"),
+    ("error.list", "列出模板失败"),
+    ("error.info", "获取模板信息失败"),
+    ("error.manifest_required", "请指定清单文件"),
+    ("error.batch", "批量生成失败"),
+    ("error.apply", "执行蓝图失败"),
+    ("error.audit", "审计蓝图失败"),
+    ("error.template_name_required", "请指定模板名称"),
+    ("error.new_template", "创建模板失败"),
+    ("error.backstage_source_required", "请指定 Backstage 的 template.yaml"),
+    ("error.import_backstage", "导入 Backstage 模板失败"),
+    ("error.lint", "模板校验失败"),
+    ("error.test", "模板试渲染失败"),
+    ("error.schema", "生成 JSON Schema 失败"),
+    ("error.credential", "凭据操作失败"),
+    ("error.usage_stats", "读取使用统计失败"),
+    ("error.usage_toggle", "设置使用记录失败"),
+    ("error.var_format", "变量格式应为 key=value"),
+    ("error.render", "❌ 渲染失败: {error}"),
+    ("error.dev", "监听模板失败"),
+    ("error.index", "刷新模板索引失败"),
+    ("error.serve", "守护进程异常退出"),
+    ("error.serve_http", "REST 服务启动失败"),
+    ("error.http_server_disabled", "构建时未启用 http-server 特性"),
+    ("error.cache_stats", "读取缓存统计失败"),
+    ("error.pack", "打包模板失败"),
+    ("error.bundle_required", "请指定模板包文件"),
+    ("error.install", "安装模板失败"),
+    ("error.registry_required", "请指定注册表名称"),
+    ("error.publish", "发布模板失败"),
+    // 帮助
+    (
+        "help.text",
+        "🚀 项目生成器 CLI
+================
+
+支持的项目类型:
+  - vue: Vue.js 项目
+  - react: React 项目
+  - java: Java 项目
+  - android: Android 应用（Kotlin / Jetpack Compose）
+  - python: Python 项目（uv / Poetry，src 布局）
+  - node-lib: Node.js/TypeScript 库
+  - rust: Rust crate / workspace
+  - monorepo: pnpm workspace（Turborepo / Nx）
+
+使用方法:
+  运行交互模式，按提示输入信息即可生成项目
+  generator-cli batch <projects.json>  按清单文件批量生成项目
+  generator-cli apply [generator.yaml]  执行蓝图，生成或更新其中的项目
+  generator-cli audit [generator.yaml]  列出执行蓝图时会运行的外部命令（不执行），~/.generator/policy.json 的 deny 列表禁止匹配的命令
+  generator-cli new-template <name> [--type vue]  创建模板骨架
+  generator-cli import-backstage <template.yaml> [--type vue]  将 Backstage 软件模板导入为模板目录
+  generator-cli lint-template [path]  校验模板
+  generator-cli test-template [path] [fixtures.json]  使用 fixture 试渲染模板
+  generator-cli pack [path] [--out dir]  将模板打包为单个 .gentpl 文件
+  generator-cli install <bundle.gentpl> [--registry ./templates] [--force]  校验并安装模板包到本地注册表
+  generator-cli publish <registry> [path] [--bump patch]  校验、打包并发布模板到 ~/.generator/registries.json 中的本地、git 或 npm 注册表
+  generator-cli schema [template.schema.json]  导出 template.json 的 JSON Schema
+  generator-cli list [type] / info <type> <template>  列出模板 / 查看模板信息
+  generator-cli credential set|delete <id> [--username u] [--file]  管理注册表凭据（默认存入系统钥匙串）
+  generator-cli usage [stats|on|off] [--endpoint url]  查看或开关本地模板使用记录（默认关闭）
+  generator-cli dev --template <dir> [--out ./preview] [--var k=v] [--feature f]  监听模板并实时渲染预览
+  generator-cli index [--force]  刷新本地模板索引（--force 重新解析全部模板）
+  generator-cli --serve-http :8080  启动 REST 服务（模板列表、变量 Schema、SSE 推送进度并可重新执行的生成任务，需 http-server 特性）
+  generator-cli serve  以守护进程运行，在 stdio 上提供 JSON-RPC 接口（list、search、generate、jobs、job、rerun、refresh、shutdown）
+  generator-cli cache stats  查看远程模板缓存的占用与上限
+  generator-cli doctor  检查 Java、Python、Node.js、git、缓存目录、注册表与 Java CLI jar 包
+  generator-cli --json | --quiet  输出 JSON（日志写入 stderr）/ 只输出结果
+  generator-cli --lang zh-CN|en-US  界面语言（默认按 ~/.generator/locale.json 或 LANG）
+  generator-cli --answers answers.json  使用答案文件，跳过所有交互问题
+  generator-cli --save-answers answers.json  保存本次交互的回答以便重放
+  generator-cli --resume  恢复上次生成失败的交互会话，不再重复提问
+  generator-cli --frozen  按锁文件记录的模板修订重新生成，模板已变化或未固定时失败
+  generator-cli --delegate  vue/react/java 项目委托给官方初始化工具（npm create vite@latest、Spring Initializr）生成
+  generator-cli --venv  python 项目生成后创建 .venv 并安装依赖（uv sync、poetry install 或 pip）
+  generator-cli --report report.html  生成后输出报告（.json 或 .html）",
+    ),
+];
+
+const EN_US: &[(&str, &str)] = &[
+    // 交互模式
+    ("cli.select_mode", "Select a generation mode"),
+    ("cli.mode_nop", "NOP platform"),
+    ("cli.mode_custom", "Custom"),
+    ("cli.using_nop", "🎯 Using NOP platform mode"),
+    ("cli.using_nop_hint", "📋 The NOP platform uses predefined templates and configuration"),
+    ("cli.using_custom", "🎯 Using custom mode"),
+    ("cli.using_custom_hint", "📋 Custom mode lets you pick any template source"),
+    ("cli.unknown_mode", "Unknown generation mode: {mode}"),
+    ("cli.select_project_type", "Select a project type"),
+    ("cli.type_vue", "Vue.js project"),
+    ("cli.type_react", "React project"),
+    ("cli.type_java", "Java project"),
+    ("cli.type_android", "Android app (Kotlin / Compose)"),
+    ("cli.type_python", "Python project (uv / Poetry)"),
+    ("cli.type_node_lib", "Node.js/TypeScript library"),
+    ("cli.type_rust", "Rust crate"),
+    ("cli.type_monorepo", "Multi-package workspace"),
+    ("cli.enter_name", "Project name"),
+    ("cli.empty_input", "Input cannot be empty"),
+    ("cli.invalid_value", "Invalid value, expected {expected}"),
+    ("cli.select_template", "Select a template (type to search)"),
+    ("cli.select_features", "Select features to enable (space to toggle, enter to confirm)"),
+    ("cli.select_crud_source", "Generate CRUD code from a database schema?"),
+    ("cli.select_build_tool", "Select a build tool"),
+    ("cli.crud_none", "Skip"),
+    ("cli.crud_jdbc", "Connect to a database (JDBC)"),
+    ("cli.crud_ddl", "Read a DDL file"),
+    ("cli.enter_db_url", "JDBC URL"),
+    ("cli.enter_db_username", "Database username (optional)"),
+    ("cli.enter_db_password", "Database password (optional)"),
+    ("cli.enter_ddl_file", "DDL file path"),
+    ("cli.template_used", "🎨 Using template: {template}"),
+    ("cli.summary", "📋 Summary:"),
+    ("cli.summary_name", "   Project name: {value}"),
+    ("cli.summary_type", "   Project type: {value}"),
+    ("cli.summary_template", "   Template: {value}"),
+    ("cli.summary_variables", "   Variables: {value}"),
+    ("cli.summary_features", "   Features: {value}"),
+    ("cli.confirm_generate", "Generate the project?"),
+    ("cli.generating", "🔄 Generating project..."),
+    ("cli.generated", "✅ Project generated!"),
+    ("cli.cancelled", "❌ Generation cancelled"),
+    ("cli.cancelled_message", "Cancelled by user"),
+    ("cli.answers_saved", "💾 Answers saved to {path}"),
+    ("network.retrying", "🔁 Attempt {attempt} of {operation} failed ({error}); retrying in {delay} ms"),
+    ("cli.session_saved", "💾 Answers saved; fix the problem and run with --resume to continue"),
+    ("cli.resuming", "🔁 Resuming the interrupted session (failed with: {error})"),
+    // 命令入口
+    ("main.banner", "🚀 Project Generator - Rust CLI"),
+    ("main.cancelling", "⏹️  Cancelling..."),
+    ("main.serving_http", "🌐 REST server listening on http://{addr} (Ctrl-C to stop)"),
+    ("main.help_hint", "Run 'cargo run help' for usage"),
+    ("main.help_shown", "Help shown"),
+    ("main.version", "Project Generator v{version}"),
+    ("main.done", "🎉 Done!"),
+    ("main.not_done", "⚠️  Not completed"),
+    ("main.audit_none", "No external commands would run"),
+    ("main.audit_denied", "⛔ Denied by command policy: {pattern}"),
+    ("main.template_created", "✅ Template skeleton created:"),
+    ("main.backstage_imported", "✅ Backstage template imported into {path}:"),
+    ("main.backstage_skipped", "⚠️  Not converted: {item}"),
+    ("main.lint_done", "✅ Template lint finished"),
+    ("main.test_done", "✅ Template test render finished"),
+    ("main.schema_written", "✅ JSON Schema written to {path}"),
+    ("main.enter_secret", "🔑 Token or password for {id}: "),
+    ("main.credential_saved", "✅ Credential {id} saved"),
+    ("main.credential_deleted", "✅ Credential {id} deleted"),
+    ("main.credential_missing", "⚠️  Credential {id} does not exist"),
+    ("main.usage_disabled", "⚠️  Usage logging is off, run generator-cli usage on to enable it"),
+    ("main.usage_total", "📊 {total} generation(s) in total"),
+    ("main.usage_line", "{template}  {count} time(s)  last {last_used}"),
+    ("main.usage_on", "✅ Usage logging enabled"),
+    ("main.usage_off", "✅ Usage logging disabled"),
+    ("main.dev_watching", "👀 Watching {template}, preview in {out} (Ctrl-C to exit)"),
+    ("main.dev_full", "🔄 Re-rendered {count} file(s)"),
+    ("main.dev_incremental", "🔄 Incrementally rendered {count} file(s)"),
+    ("main.dev_removed", ", removed {count}"),
+    ("main.index_refreshed", "🗂️  Template index refreshed: {templates} template(s) ({updated} re-parsed, {reused} reused, {removed} removed)"),
+    ("main.cache_dir", "📦 Cache directory: {path}"),
+    ("main.cache_size", "   {entries} archive(s), {aliases} alias(es), {size} MB in total"),
+    ("main.cache_limit", "   Limit {size} MB"),
+    ("main.doctor_ok", "✅ Environment looks good"),
+    ("main.doctor_failed", "❌ Environment has problems that must be fixed"),
+    ("main.template_packed", "📦 Template packed: {path}"),
+    ("main.template_installed", "✅ Template installed: {path}"),
+    ("main.template_published", "🚀 Published {name}@{version} to {registry}: {location}"),
+    // 错误上下文
+    ("error.error", "Error"),
+    ("error.help", "Failed to show help"),
+    ("error.missing_args", "Missing arguments"),
+    ("error.unknown_action", "Unknown action"),
+    ("error.unknown_arg", "Unknown argument"),
+    ("error.invalid_lang", "Cannot set language"),
+    ("error.list", "Failed to list templates"),
+    ("error.info", "Failed to get template info"),
+    ("error.manifest_required", "Specify a manifest file"),
+    ("error.batch", "Batch generation failed"),
+    ("error.apply", "Failed to apply blueprint"),
+    ("error.audit", "Failed to audit blueprint"),
+    ("error.template_name_required", "Specify a template name"),
+    ("error.new_template", "Failed to create template"),
+    ("error.backstage_source_required", "Specify a Backstage template.yaml"),
+    ("error.import_backstage", "Failed to import Backstage template"),
+    ("error.lint", "Template lint failed"),
+    ("error.test", "Template test render failed"),
+    ("error.schema", "Failed to generate JSON Schema"),
+    ("error.credential", "Credential operation failed"),
+    ("error.usage_stats", "Failed to read usage stats"),
+    ("error.usage_toggle", "Failed to change usage logging"),
+    ("error.var_format", "Variables must be key=value"),
+    ("error.render", "❌ Render failed: {error}"),
+    ("error.dev", "Failed to watch template"),
+    ("error.index", "Failed to refresh template index"),
+    ("error.serve", "Daemon exited with an error"),
+    ("error.serve_http", "Failed to run REST server"),
+    ("error.http_server_disabled", "built without the http-server feature"),
+    ("error.cache_stats", "Failed to read cache stats"),
+    ("error.pack", "Failed to pack template"),
+    ("error.bundle_required", "Specify a template bundle file"),
+    ("error.install", "Failed to install template"),
+    ("error.registry_required", "Specify a registry name"),
+    ("error.publish", "Failed to publish template"),
+    // 帮助
+    (
+        "help.text",
+        "🚀 Project Generator CLI
+========================
+
+Supported project types:
+  - vue: Vue.js project
+  - react: React project
+  - java: Java project
+  - android: Android app (Kotlin / Jetpack Compose)
+  - python: Python project (uv / Poetry, src layout)
+  - node-lib: Node.js/TypeScript library
+  - rust: Rust crate / workspace
+  - monorepo: pnpm workspace (Turborepo / Nx)
+
+Usage:
+  Run without arguments for interactive mode and answer the prompts
+  generator-cli batch <projects.json>  Generate projects from a manifest
+  generator-cli apply [generator.yaml]  Apply a blueprint, creating or updating its projects
+  generator-cli audit [generator.yaml]  List the external commands a blueprint would run without running them; the deny list in ~/.generator/policy.json blocks matching commands
+  generator-cli new-template <name> [--type vue]  Create a template skeleton
+  generator-cli import-backstage <template.yaml> [--type vue]  Import a Backstage software template as a template directory
+  generator-cli lint-template [path]  Lint a template
+  generator-cli test-template [path] [fixtures.json]  Test-render a template with fixtures
+  generator-cli pack [path] [--out dir]  Pack a template into a single .gentpl file
+  generator-cli install <bundle.gentpl> [--registry ./templates] [--force]  Verify and install a template bundle into the local registry
+  generator-cli publish <registry> [path] [--bump patch]  Validate, pack and publish a template to a local, git or npm registry from ~/.generator/registries.json
+  generator-cli schema [template.schema.json]  Export the JSON Schema of template.json
+  generator-cli list [type] / info <type> <template>  List templates / show template info
+  generator-cli credential set|delete <id> [--username u] [--file]  Manage registry credentials (system keychain by default)
+  generator-cli usage [stats|on|off] [--endpoint url]  Show or toggle local template usage logging (off by default)
+  generator-cli dev --template <dir> [--out ./preview] [--var k=v] [--feature f]  Watch a template and render a live preview
+  generator-cli index [--force]  Refresh the local template index (--force re-parses all templates)
+  generator-cli --serve-http :8080  Start the REST server (template list, variable schemas, generation jobs with SSE progress, history and rerun; needs the http-server feature)
+  generator-cli serve  Run as a daemon serving JSON-RPC on stdio (list, search, generate, jobs, job, rerun, refresh, shutdown)
+  generator-cli cache stats  Show remote template cache usage and limit
+  generator-cli doctor  Check Java, Python, Node.js, git, cache directory, registries and the Java CLI jar
+  generator-cli --json | --quiet  Print JSON (logs go to stderr) / print results only
+  generator-cli --lang zh-CN|en-US  Interface language (defaults to ~/.generator/locale.json or LANG)
+  generator-cli --answers answers.json  Use an answers file and skip all prompts
+  generator-cli --save-answers answers.json  Save this session's answers for replay
+  generator-cli --resume  Resume the last interactive session that failed, without repeating prompts
+  generator-cli --frozen  Regenerate from the template revision recorded in the lock file; fail if the template moved or is not pinned
+  generator-cli --delegate  Generate vue/react/java projects with the official initializer (npm create vite@latest, Spring Initializr)
+  generator-cli --venv  Create .venv and install dependencies after generating a python project (uv sync, poetry install or pip)
+  generator-cli --report report.html  Write a report after generation (.json or .html)",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lang_parse_and_catalogs() {
+        assert_eq!(Lang::parse("zh_CN.UTF-8").unwrap(), Lang::ZhCn);
+        assert_eq!(Lang::parse("en").unwrap(), Lang::EnUs);
+        assert_eq!(Lang::parse("EN-us").unwrap(), Lang::EnUs);
+        assert!(Lang::parse("fr-FR").is_err());
+        assert_eq!(detect(Some("en-US")).unwrap(), Lang::EnUs);
+
+        // 两个目录的键保持一致
+        let keys = |lang: Lang| {
+            lang.catalog()
+                .iter()
+                .map(|(key, _)| *key)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(Lang::ZhCn), keys(Lang::EnUs));
+
+        assert_eq!(
+            lookup(Lang::EnUs, "cli.generating"),
+            Some("🔄 Generating project...")
+        );
+        assert_eq!(
+            format("cli.template_used", &[("template", "basic".to_string())]),
+            "🎨 使用模板: basic"
+        );
+        assert_eq!(message("missing.key"), "missing.key");
+    }
+}
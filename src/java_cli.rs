@@ -1,17 +1,157 @@
 //! Java CLI 集成模块
 //! 负责Java环境检测和Java CLI jar包调用
 
-use std::process::Command;
+use std::process::{Command, ExitStatus, Stdio};
 use std::env;
+use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc::UnboundedSender;
 use anyhow::{Result, anyhow};
-use crate::utils::get_exe_dir;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::build_wrapper::BuildTool;
+use crate::cache_lock::CacheLock;
+use crate::network::NetworkConfig;
+use crate::utils::{generator_home, get_exe_dir};
+use crate::{GeneratorError, PhaseTimeouts, cancel, command_audit};
+
+/// 指定 jar 包路径的环境变量
+pub const JAR_PATH_ENV: &str = "GENERATOR_JAVA_CLI_JAR";
+/// 指定 jar 包下载地址的环境变量
+pub const JAR_URL_ENV: &str = "GENERATOR_JAVA_CLI_URL";
+/// 指定 jar 包 SHA-256 校验值的环境变量
+pub const JAR_SHA256_ENV: &str = "GENERATOR_JAVA_CLI_SHA256";
+/// Java CLI 配置文件（相对当前目录）
+pub const CONFIG_FILE: &str = "config/java_cli.json";
+
+const JAR_FILE_NAME: &str = "java-cli.jar";
 
 /// Java环境信息
 #[derive(Debug, Clone)]
 pub struct JavaEnvironment {
     pub java_path: String,
     pub version: String,
+    /// 发行商（`java.vendor`），如 `Eclipse Adoptium`
+    pub vendor: Option<String>,
+    /// java 可执行文件的来源
+    pub source: JavaSource,
+}
+
+/// java 可执行文件的发现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JavaSource {
+    /// 来自 JAVA_HOME 环境变量
+    JavaHome,
+    /// 来自 PATH
+    Path,
+}
+
+impl JavaSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JavaSource::JavaHome => "JAVA_HOME",
+            JavaSource::Path => "PATH",
+        }
+    }
+}
+
+impl JavaEnvironment {
+    /// 解析出的主版本号（`1.8.0_392` 视为 8）
+    pub fn major_version(&self) -> Option<u32> {
+        parse_java_major_version(&self.version)
+    }
+}
+
+/// Java 版本要求，以主版本号表示的闭区间
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JavaVersionRequirement {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl JavaVersionRequirement {
+    /// 解析 `17`、`17+`、`>=17`、`<=21`、`11-17` 形式的要求，版本号可写作 `1.8`
+    pub fn parse(requirement: &str) -> Result<Self> {
+        let requirement = requirement.trim();
+        let invalid = || anyhow!("Invalid Java version requirement: {}", requirement);
+        let major = |raw: &str| parse_java_major_version(raw).ok_or_else(invalid);
+
+        if let Some(min) = requirement
+            .strip_suffix('+')
+            .or_else(|| requirement.strip_prefix(">="))
+        {
+            return Ok(Self { min: Some(major(min)?), max: None });
+        }
+        if let Some(max) = requirement.strip_prefix("<=") {
+            return Ok(Self { min: None, max: Some(major(max)?) });
+        }
+        if let Some((min, max)) = requirement.split_once('-') {
+            return Ok(Self { min: Some(major(min)?), max: Some(major(max)?) });
+        }
+
+        let exact = major(requirement)?;
+        Ok(Self { min: Some(exact), max: Some(exact) })
+    }
+
+    /// 主版本号是否满足要求
+    pub fn matches(&self, major: u32) -> bool {
+        self.min.is_none_or(|min| major >= min) && self.max.is_none_or(|max| major <= max)
+    }
+}
+
+impl std::fmt::Display for JavaVersionRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => write!(f, "{}", min),
+            (Some(min), Some(max)) => write!(f, "{}-{}", min, max),
+            (Some(min), None) => write!(f, "{}+", min),
+            (None, Some(max)) => write!(f, "<={}", max),
+            (None, None) => write!(f, "any"),
+        }
+    }
+}
+
+/// 从版本号或 `java -version` 输出中解析主版本号
+///
+/// 兼容旧格式 `1.8.0_392`（主版本为 8）与新格式 `17.0.9`、`21`。
+pub fn parse_java_major_version(raw: &str) -> Option<u32> {
+    // `openjdk version "17.0.9" 2023-10-17` 中取引号内的版本号
+    let version = match raw.split('"').nth(1) {
+        Some(quoted) => quoted,
+        None => raw.trim(),
+    };
+
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    let first: u32 = parts.next()?.parse().ok()?;
+    if first == 1 {
+        parts.next().and_then(|minor| minor.parse().ok())
+    } else {
+        Some(first)
+    }
+}
+
+/// 检测 Java 环境并校验是否满足版本要求
+pub fn ensure_java_version(requirement: &str) -> Result<JavaEnvironment> {
+    let requirement = JavaVersionRequirement::parse(requirement)?;
+    let java_env = detect_java()
+        .map_err(|e| anyhow!("This template requires Java {}: {}", requirement, e))?;
+    let major = java_env.major_version().ok_or_else(|| {
+        anyhow!("Cannot determine Java version from '{}'", java_env.version)
+    })?;
+
+    if requirement.matches(major) {
+        Ok(java_env)
+    } else {
+        Err(anyhow!(
+            "This template requires Java {}, but found Java {} at {}",
+            requirement,
+            major,
+            java_env.java_path
+        ))
+    }
 }
 
 /// 检测Java环境
@@ -24,13 +164,15 @@ pub fn detect_java() -> Result<JavaEnvironment> {
             format!("{}/bin/java", java_home)
         };
         
-        if Path::new(&java_path).exists() {
-            if let Ok(version) = get_java_version(&java_path) {
-                return Ok(JavaEnvironment {
-                    java_path,
-                    version,
-                });
-            }
+        if Path::new(&java_path).exists()
+            && let Ok((version, vendor)) = get_java_version(&java_path)
+        {
+            return Ok(JavaEnvironment {
+                java_path,
+                version,
+                vendor,
+                source: JavaSource::JavaHome,
+            });
         }
     }
     
@@ -43,10 +185,12 @@ pub fn detect_java() -> Result<JavaEnvironment> {
     match Command::new(which_cmd).arg(java_cmd).output() {
         Ok(output) if output.status.success() => {
             let java_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if let Ok(version) = get_java_version(&java_path) {
+            if let Ok((version, vendor)) = get_java_version(&java_path) {
                 Ok(JavaEnvironment {
                     java_path,
                     version,
+                    vendor,
+                    source: JavaSource::Path,
                 })
             } else {
                 Err(anyhow!("Failed to get Java version"))
@@ -56,55 +200,414 @@ pub fn detect_java() -> Result<JavaEnvironment> {
     }
 }
 
-/// 获取Java版本信息
-fn get_java_version(java_path: &str) -> Result<String> {
+/// 获取Java版本信息与发行商
+fn get_java_version(java_path: &str) -> Result<(String, Option<String>)> {
     let output = Command::new(java_path)
+        .arg("-XshowSettings:properties")
         .arg("-version")
         .output()?;
     
     if output.status.success() {
         // Java版本信息通常输出到stderr
         let version_output = String::from_utf8_lossy(&output.stderr);
-        // 提取版本号（简化处理）
-        if let Some(line) = version_output.lines().next() {
-            Ok(line.to_string())
-        } else {
-            Err(anyhow!("Failed to parse Java version"))
-        }
+        parse_java_version_output(&version_output)
+            .ok_or_else(|| anyhow!("Failed to parse Java version"))
     } else {
         Err(anyhow!("Failed to execute java -version"))
     }
 }
 
+/// 解析 `java -XshowSettings:properties -version` 的输出
+///
+/// 属性行带缩进（`    java.vendor = Eclipse Adoptium`），版本行顶格（`openjdk version "17.0.9"`）。
+fn parse_java_version_output(output: &str) -> Option<(String, Option<String>)> {
+    let version = output
+        .lines()
+        .find(|line| !line.starts_with(char::is_whitespace) && line.contains(" version "))?
+        .trim()
+        .to_string();
+    let vendor = output.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("java.vendor = ")
+            .map(|vendor| vendor.trim().to_string())
+    });
+    Some((version, vendor))
+}
+
+/// Java CLI jar 包配置
+///
+/// 优先级：调用方传入 > 环境变量 > 配置文件 `config/java_cli.json`。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JavaCliConfig {
+    /// jar 包路径
+    #[serde(default)]
+    pub jar_path: Option<PathBuf>,
+    /// 本地找不到 jar 包时的下载地址
+    #[serde(default)]
+    pub download_url: Option<String>,
+    /// 下载文件的 SHA-256 校验值，可带 `sha256:` 前缀
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// 下载缓存目录，默认为 `~/.generator/cache`
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    /// 执行超时（秒），超时后终止 Java 进程
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// jar 包下载超时（秒）
+    #[serde(default)]
+    pub download_timeout_secs: Option<u64>,
+    /// 下载 jar 包时使用的代理与 CA 证书，未设置的项读取环境变量
+    #[serde(default)]
+    pub network: Option<NetworkConfig>,
+}
+
+/// 由分阶段超时设置得到 Java CLI 的执行与下载超时
+impl From<&PhaseTimeouts> for JavaCliConfig {
+    fn from(timeouts: &PhaseTimeouts) -> Self {
+        Self {
+            timeout_secs: timeouts.java_cli_secs.map(u64::from),
+            download_timeout_secs: timeouts.http_download_secs.map(u64::from),
+            ..Self::default()
+        }
+    }
+}
+
+impl JavaCliConfig {
+    /// 读取配置文件，文件不存在时返回空配置
+    pub fn load_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid Java CLI config {}: {}", path.display(), e))
+    }
+
+    /// 从环境变量读取配置
+    pub fn from_env() -> Self {
+        Self {
+            jar_path: env::var_os(JAR_PATH_ENV).map(PathBuf::from),
+            download_url: env::var(JAR_URL_ENV).ok(),
+            sha256: env::var(JAR_SHA256_ENV).ok(),
+            cache_dir: None,
+            timeout_secs: None,
+            download_timeout_secs: None,
+            network: None,
+        }
+    }
+
+    /// 合并环境变量与配置文件
+    pub fn resolve(self) -> Result<Self> {
+        Ok(self
+            .or(Self::from_env())
+            .or(Self::load_file(Path::new(CONFIG_FILE))?))
+    }
+
+    /// 以 `fallback` 补全未设置的字段
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            jar_path: self.jar_path.or(fallback.jar_path),
+            download_url: self.download_url.or(fallback.download_url),
+            sha256: self.sha256.or(fallback.sha256),
+            cache_dir: self.cache_dir.or(fallback.cache_dir),
+            timeout_secs: self.timeout_secs.or(fallback.timeout_secs),
+            download_timeout_secs: self.download_timeout_secs.or(fallback.download_timeout_secs),
+            network: self.network.or(fallback.network),
+        }
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.cache_dir
+            .clone()
+            .unwrap_or_else(|| generator_home().join("cache"))
+    }
+}
+
 /// 获取Java CLI jar包路径
 pub fn get_java_cli_jar_path() -> Result<PathBuf> {
-    let exe_dir = get_exe_dir()?;
-    let jar_path = exe_dir.join("assets").join("java-cli.jar");
-    
-    if jar_path.exists() {
-        Ok(jar_path)
+    resolve_java_cli_jar(&JavaCliConfig::default())
+}
+
+/// 按配置查找 Java CLI jar 包
+///
+/// 依次尝试显式配置的路径、可执行文件旁的 `assets/java-cli.jar`、下载缓存，
+/// 最后在配置了下载地址时下载并校验。
+pub fn resolve_java_cli_jar(config: &JavaCliConfig) -> Result<PathBuf> {
+    let config = config.clone().resolve()?;
+
+    if let Some(jar_path) = &config.jar_path {
+        return if jar_path.is_file() {
+            Ok(jar_path.clone())
+        } else {
+            Err(anyhow!("Configured Java CLI jar not found: {}", jar_path.display()))
+        };
+    }
+
+    let bundled = get_exe_dir()?.join("assets").join(JAR_FILE_NAME);
+    if bundled.is_file() {
+        return Ok(bundled);
+    }
+
+    let Some(url) = &config.download_url else {
+        return Err(anyhow!(
+            "Java CLI jar not found at: {}. Set {} or configure jar_path/download_url in {}",
+            bundled.display(),
+            JAR_PATH_ENV,
+            CONFIG_FILE
+        ));
+    };
+
+    // 持有缓存目录锁后再检查，并发的进程只有一个会下载，其余等待后直接使用
+    let _lock = CacheLock::exclusive(&config.cache_dir())?;
+    let cached = config.cache_dir().join(JAR_FILE_NAME);
+    if cached.is_file() && verify_checksum(&cached, config.sha256.as_deref()).is_ok() {
+        return Ok(cached);
+    }
+
+    let timeout = config.download_timeout_secs.map(Duration::from_secs);
+    let network = config.network.clone().unwrap_or_default().resolve();
+    download_jar(url, &cached, config.sha256.as_deref(), &network, timeout)?;
+    Ok(cached)
+}
+
+/// 不下载时可用的 Java CLI jar 包：显式配置的路径、可执行文件旁的 assets 或已下载的缓存
+pub fn local_java_cli_jar(config: &JavaCliConfig) -> Result<Option<PathBuf>> {
+    let config = config.clone().resolve()?;
+    if let Some(jar_path) = config.jar_path {
+        return Ok(jar_path.is_file().then_some(jar_path));
+    }
+
+    let bundled = get_exe_dir()?.join("assets").join(JAR_FILE_NAME);
+    if bundled.is_file() {
+        return Ok(Some(bundled));
+    }
+
+    let cached = config.cache_dir().join(JAR_FILE_NAME);
+    Ok((config.download_url.is_some() && cached.is_file()).then_some(cached))
+}
+
+/// 下载 jar 包到临时文件，校验通过后再移动到目标位置
+///
+/// 按 `network` 使用代理与 CA 证书；设置了 `timeout` 时连接与整个下载过程都受其限制，超时后删除临时文件。
+fn download_jar(
+    url: &str,
+    dest: &Path,
+    sha256: Option<&str>,
+    network: &NetworkConfig,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    command_audit::record_remote("http", &["GET", url])?;
+    let agent = network.http_agent(url, timeout)?;
+    let started = Instant::now();
+    let timed_out = |downloaded: u64| {
+        timeout.filter(|limit| started.elapsed() >= *limit).map(|limit| {
+            GeneratorError::Timeout(format!(
+                "downloading {} exceeded {}s after {} bytes",
+                url,
+                limit.as_secs(),
+                downloaded
+            ))
+        })
+    };
+
+    let response = network
+        .retry_policy()
+        .call(url, || agent.get(url), |e| match timed_out(0) {
+            Some(timeout) => timeout.into(),
+            None => anyhow!("Failed to download Java CLI jar from {}: {}", url, e),
+        })?;
+    let partial = dest.with_extension("jar.part");
+    {
+        let mut reader = response.into_reader();
+        let mut file = fs::File::create(&partial)?;
+        let mut buffer = [0u8; 64 * 1024];
+        let mut downloaded = 0u64;
+        loop {
+            let read = match cancel::check() {
+                Ok(()) => reader.read(&mut buffer).map_err(|e| match timed_out(downloaded) {
+                    Some(timeout) => timeout.into(),
+                    None => anyhow::Error::from(e),
+                }),
+                Err(e) => Err(e.into()),
+            };
+            let read = match read {
+                Ok(read) => read,
+                Err(e) => {
+                    drop(file);
+                    let _ = fs::remove_file(&partial);
+                    return Err(e);
+                }
+            };
+            if read == 0 {
+                break;
+            }
+            file.write_all(&buffer[..read])?;
+            downloaded += read as u64;
+        }
+    }
+
+    if let Err(e) = verify_checksum(&partial, sha256) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, dest)?;
+    Ok(())
+}
+
+/// 校验文件的 SHA-256，未配置校验值时直接通过
+fn verify_checksum(path: &Path, expected: Option<&str>) -> Result<()> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+
+    let mut hasher = Sha256::new();
+    let mut file = fs::File::open(path)?;
+    std::io::copy(&mut file, &mut hasher)?;
+    let actual = hex::encode(hasher.finalize());
+
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
     } else {
-        Err(anyhow!("Java CLI jar not found at: {}", jar_path.display()))
+        Err(anyhow!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected,
+            actual
+        ))
     }
 }
 
+/// Java CLI 输出的一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JavaOutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
 /// 执行Java CLI命令
-pub async fn execute_java_cli(args: Vec<String>) -> Result<String> {
+///
+/// 输出按行实时转发到 `progress`，配置了 `timeout_secs` 时超时会终止进程。
+pub async fn execute_java_cli(
+    args: Vec<String>,
+    cli: &JavaCliConfig,
+    progress: Option<UnboundedSender<JavaOutputLine>>,
+) -> Result<String> {
+    execute_java_cli_with_env(args, Vec::new(), cli, progress).await
+}
+
+/// 执行Java CLI命令，并为进程设置额外的环境变量（用于传递不应出现在命令行中的密码）
+pub async fn execute_java_cli_with_env(
+    args: Vec<String>,
+    envs: Vec<(String, String)>,
+    cli: &JavaCliConfig,
+    progress: Option<UnboundedSender<JavaOutputLine>>,
+) -> Result<String> {
     let java_env = detect_java()?;
-    let jar_path = get_java_cli_jar_path()?;
-    
-    let mut cmd = Command::new(&java_env.java_path);
-    cmd.arg("-jar")
-       .arg(jar_path)
-       .args(args);
-    
-    let output = cmd.output()?;
-    
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    let cli = cli.clone().resolve()?;
+    let jar_path = resolve_java_cli_jar(&cli)?;
+
+    let mut cmd = tokio::process::Command::new(&java_env.java_path);
+    cmd.arg("-jar").arg(jar_path).args(args).envs(envs);
+    if let Some(output) = command_audit::intercept(cmd.as_std())? {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+
+    let timeout = cli.timeout_secs.map(Duration::from_secs);
+    let (status, stdout, stderr) = run_streaming(cmd, timeout, progress).await?;
+
+    if status.success() {
+        Ok(stdout)
     } else {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        Err(anyhow!("Java CLI execution failed: {}", error_msg))
+        Err(anyhow!("Java CLI execution failed: {}", stderr))
+    }
+}
+
+/// 运行命令并逐行读取 stdout/stderr，返回退出状态与完整输出
+async fn run_streaming(
+    mut cmd: tokio::process::Command,
+    timeout: Option<Duration>,
+    progress: Option<UnboundedSender<JavaOutputLine>>,
+) -> Result<(ExitStatus, String, String)> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let stdout = child.stdout.take().ok_or_else(|| anyhow!("Failed to capture stdout"))?;
+    let stderr = child.stderr.take().ok_or_else(|| anyhow!("Failed to capture stderr"))?;
+
+    let forward = |line: JavaOutputLine| {
+        if let Some(progress) = &progress {
+            // 接收端已关闭时忽略，输出仍会被收集
+            let _ = progress.send(line);
+        }
+    };
+
+    let (mut stdout_text, mut stderr_text) = (String::new(), String::new());
+    let collect = async {
+        let mut stdout_lines = BufReader::new(stdout).lines();
+        let mut stderr_lines = BufReader::new(stderr).lines();
+        let (mut stdout_done, mut stderr_done) = (false, false);
+
+        while !(stdout_done && stderr_done) {
+            tokio::select! {
+                line = stdout_lines.next_line(), if !stdout_done => match line? {
+                    Some(line) => {
+                        stdout_text.push_str(&line);
+                        stdout_text.push('\n');
+                        forward(JavaOutputLine::Stdout(line));
+                    }
+                    None => stdout_done = true,
+                },
+                line = stderr_lines.next_line(), if !stderr_done => match line? {
+                    Some(line) => {
+                        stderr_text.push_str(&line);
+                        stderr_text.push('\n');
+                        forward(JavaOutputLine::Stderr(line));
+                    }
+                    None => stderr_done = true,
+                },
+            }
+        }
+
+        Ok::<_, anyhow::Error>(child.wait().await?)
+    };
+
+    let limited = async {
+        match timeout {
+            Some(limit) => tokio::time::timeout(limit, collect).await.map_err(|_| limit),
+            None => Ok(collect.await),
+        }
+    };
+
+    let token = cancel::current();
+    let outcome = tokio::select! {
+        outcome = limited => Some(outcome),
+        _ = token.cancelled() => None,
+    };
+    match outcome {
+        Some(Ok(status)) => Ok((status?, stdout_text, stderr_text)),
+        Some(Err(limit)) => {
+            let _ = child.kill().await;
+            // 超时前已收到的输出随错误一起返回
+            Err(GeneratorError::Timeout(format!(
+                "Java CLI exceeded {}s{}",
+                limit.as_secs(),
+                cancel::partial_output(stdout_text.as_bytes(), stderr_text.as_bytes())
+            ))
+            .into())
+        }
+        None => {
+            let _ = child.kill().await;
+            Err(GeneratorError::Cancelled.into())
+        }
     }
 }
 
@@ -117,6 +620,12 @@ pub struct JavaProjectOptions {
     pub artifact_id: Option<String>,
     pub version: Option<String>,
     pub output_path: Option<String>,
+    /// 构建工具，未设置时由 Java CLI 决定
+    pub build_tool: Option<BuildTool>,
+    /// Java CLI jar 包配置
+    pub cli: JavaCliConfig,
+    /// 接收 Java CLI 实时输出
+    pub progress: Option<UnboundedSender<JavaOutputLine>>,
 }
 
 /// 生成Java项目
@@ -153,8 +662,13 @@ pub async fn generate_java_project(options: JavaProjectOptions) -> Result<Vec<St
         args.push("--output".to_string());
         args.push(output_path.clone());
     }
+
+    if let Some(build_tool) = options.build_tool {
+        args.push("--build-tool".to_string());
+        args.push(build_tool.name().to_string());
+    }
     
-    let result = execute_java_cli(args).await?;
+    let result = execute_java_cli(args, &options.cli, options.progress.clone()).await?;
     
     // 解析结果，返回生成的文件列表
     // 这里需要根据Java CLI的实际输出格式来解析
@@ -167,6 +681,138 @@ pub async fn generate_java_project(options: JavaProjectOptions) -> Result<Vec<St
     Ok(files)
 }
 
+/// OpenAPI 桩代码生成选项
+#[derive(Debug, Clone)]
+pub struct JavaOpenApiOptions {
+    /// OpenAPI 规范文件
+    pub spec: PathBuf,
+    /// 生成模式 (client, server)
+    pub mode: String,
+    pub package_name: Option<String>,
+    pub output_path: PathBuf,
+    /// Java CLI jar 包配置
+    pub cli: JavaCliConfig,
+    /// 接收 Java CLI 实时输出
+    pub progress: Option<UnboundedSender<JavaOutputLine>>,
+}
+
+/// 按 OpenAPI 规范生成 Spring 客户端或服务端桩代码，返回生成的文件列表
+pub async fn generate_openapi_stubs(options: JavaOpenApiOptions) -> Result<Vec<String>> {
+    let mut args = vec![
+        "openapi".to_string(),
+        "--spec".to_string(),
+        options.spec.to_string_lossy().to_string(),
+        "--mode".to_string(),
+        options.mode.clone(),
+        "--output".to_string(),
+        options.output_path.to_string_lossy().to_string(),
+    ];
+    if let Some(package_name) = &options.package_name {
+        args.push("--package".to_string());
+        args.push(package_name.clone());
+    }
+
+    let result = execute_java_cli(args, &options.cli, options.progress.clone()).await?;
+    Ok(result
+        .lines()
+        .filter_map(|line| line.strip_prefix("Generated: "))
+        .map(str::to_string)
+        .collect())
+}
+
+/// 数据库表结构来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatabaseSource {
+    /// JDBC 连接串，连接信息中的用户名与密码单独传入
+    Jdbc {
+        url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+    /// DDL 文件
+    Ddl(PathBuf),
+}
+
+/// 按数据库表结构生成 CRUD 代码的选项
+#[derive(Debug, Clone)]
+pub struct JavaCrudOptions {
+    pub source: DatabaseSource,
+    /// 只生成这些表，为空时生成全部表
+    pub tables: Vec<String>,
+    pub package_name: Option<String>,
+    pub output_path: PathBuf,
+    /// Java CLI jar 包配置
+    pub cli: JavaCliConfig,
+    /// 接收 Java CLI 实时输出
+    pub progress: Option<UnboundedSender<JavaOutputLine>>,
+}
+
+/// 传递数据库密码的环境变量
+pub const DB_PASSWORD_ENV: &str = "GENERATOR_DB_PASSWORD";
+
+impl JavaCrudOptions {
+    /// Java CLI 参数与环境变量，密码只通过环境变量传递
+    pub fn command(&self) -> (Vec<String>, Vec<(String, String)>) {
+        let mut args = vec!["crud".to_string()];
+        let mut envs = Vec::new();
+        match &self.source {
+            DatabaseSource::Jdbc { url, username, password } => {
+                args.push("--jdbc-url".to_string());
+                args.push(url.clone());
+                if let Some(username) = username {
+                    args.push("--username".to_string());
+                    args.push(username.clone());
+                }
+                if let Some(password) = password {
+                    args.push("--password-env".to_string());
+                    args.push(DB_PASSWORD_ENV.to_string());
+                    envs.push((DB_PASSWORD_ENV.to_string(), password.clone()));
+                }
+            }
+            DatabaseSource::Ddl(path) => {
+                args.push("--ddl".to_string());
+                args.push(path.to_string_lossy().to_string());
+            }
+        }
+        if !self.tables.is_empty() {
+            args.push("--tables".to_string());
+            args.push(self.tables.join(","));
+        }
+        if let Some(package_name) = &self.package_name {
+            args.push("--package".to_string());
+            args.push(package_name.clone());
+        }
+        args.push("--output".to_string());
+        args.push(self.output_path.to_string_lossy().to_string());
+        (args, envs)
+    }
+}
+
+/// 按数据库表结构生成 entity、repository 与 controller，返回生成的文件列表
+pub async fn generate_crud(options: JavaCrudOptions) -> Result<Vec<String>> {
+    let (args, envs) = options.command();
+    let result =
+        execute_java_cli_with_env(args, envs, &options.cli, options.progress.clone()).await?;
+    Ok(result
+        .lines()
+        .filter_map(|line| line.strip_prefix("Generated: "))
+        .map(str::to_string)
+        .collect())
+}
+
+/// 在同步的生成流程中驱动 Java CLI，保留超时、取消等结构化错误
+pub fn block_on<T>(future: impl std::future::Future<Output = Result<T>>) -> crate::Result<T> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime
+        .block_on(future)
+        .map_err(|e| match e.downcast::<GeneratorError>() {
+            Ok(e) => e,
+            Err(e) => GeneratorError::ExternalCommand(e.to_string()),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,4 +832,91 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_java_version_output() {
+        let output = concat!(
+            "Property settings:\n",
+            "    java.home = /opt/jdk-17\n",
+            "    java.vendor = Eclipse Adoptium\n",
+            "    java.vendor.url = https://adoptium.net/\n",
+            "\n",
+            "openjdk version \"17.0.9\" 2023-10-17\n",
+            "OpenJDK Runtime Environment Temurin-17.0.9+9 (build 17.0.9+9)\n",
+        );
+        let (version, vendor) = parse_java_version_output(output).unwrap();
+        assert_eq!(version, "openjdk version \"17.0.9\" 2023-10-17");
+        assert_eq!(vendor.as_deref(), Some("Eclipse Adoptium"));
+        assert_eq!(parse_java_major_version(&version), Some(17));
+    }
+
+    #[test]
+    fn test_java_version_requirement() {
+        assert_eq!(parse_java_major_version("java version \"1.8.0_392\""), Some(8));
+        assert_eq!(
+            parse_java_major_version("openjdk version \"17.0.9\" 2023-10-17"),
+            Some(17)
+        );
+        assert_eq!(parse_java_major_version("openjdk version \"21\" 2023-09-19"), Some(21));
+
+        let spring_boot_3 = JavaVersionRequirement::parse("17+").unwrap();
+        assert!(!spring_boot_3.matches(8));
+        assert!(spring_boot_3.matches(21));
+        assert_eq!(spring_boot_3.to_string(), "17+");
+
+        let legacy = JavaVersionRequirement::parse("1.8-11").unwrap();
+        assert!(legacy.matches(8) && legacy.matches(11) && !legacy.matches(17));
+        assert!(JavaVersionRequirement::parse("latest").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_streaming_forwards_lines_and_times_out() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.args(["-c", "echo first; echo oops >&2; echo second"]);
+
+        let (status, stdout, stderr) = run_streaming(cmd, None, Some(sender)).await.unwrap();
+        assert!(status.success());
+        assert_eq!(stdout, "first\nsecond\n");
+        assert_eq!(stderr, "oops\n");
+
+        let mut lines = Vec::new();
+        while let Ok(line) = receiver.try_recv() {
+            lines.push(line);
+        }
+        assert_eq!(lines.len(), 3);
+        assert!(lines.contains(&JavaOutputLine::Stderr("oops".to_string())));
+
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.args(["-c", "sleep 5"]);
+        let result = run_streaming(cmd, Some(Duration::from_millis(100)), None).await;
+        assert!(result.unwrap_err().to_string().contains("timed out"));
+    }
+
+    #[test]
+    fn test_resolve_configured_jar_and_verify_checksum() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let jar_path = temp_dir.path().join("custom-cli.jar");
+        fs::write(&jar_path, b"jar").unwrap();
+
+        let config = JavaCliConfig {
+            jar_path: Some(jar_path.clone()),
+            ..Default::default()
+        };
+        assert_eq!(resolve_java_cli_jar(&config).unwrap(), jar_path);
+
+        let digest = hex::encode(Sha256::digest(b"jar"));
+        assert!(verify_checksum(&jar_path, Some(&format!("sha256:{}", digest))).is_ok());
+        assert!(verify_checksum(&jar_path, Some("deadbeef")).is_err());
+
+        let config_file = temp_dir.path().join("java_cli.json");
+        fs::write(&config_file, r#"{"download_url": "https://example.com/java-cli.jar"}"#).unwrap();
+        let loaded = JavaCliConfig::load_file(&config_file).unwrap();
+        assert_eq!(
+            loaded.download_url.as_deref(),
+            Some("https://example.com/java-cli.jar")
+        );
+        assert!(loaded.jar_path.is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,271 @@
+//! 生成任务记录模块
+//! 守护进程与 REST 服务中的每次生成保存为 `~/.generator/jobs/<id>.json`（参数、状态、日志与结果），可查询历史并按相同参数重新执行
+
+use crate::events::{self, Event, Listener};
+use crate::template_registry::{TemplateRegistryConfig, TemplateVariable};
+use crate::templates::{generate_project_from_template, resolve_template};
+use crate::utils::generator_home;
+use crate::variables::without_sensitive;
+use crate::{ErrorDetails, GenerateOptions, GenerateResult, GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// 一次生成任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub id: u64,
+    pub status: JobState,
+    /// 生成参数，敏感变量的值不保存，重新执行时需要再次提供
+    pub options: GenerateOptions,
+    #[serde(default)]
+    pub logs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<GenerateResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetails>,
+    /// RFC 3339 时间
+    pub started_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    /// 重新执行的来源任务
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerun_of: Option<u64>,
+}
+
+/// 任务记录的存储位置
+#[derive(Debug, Clone)]
+pub struct JobStore {
+    pub dir: PathBuf,
+}
+
+impl Default for JobStore {
+    fn default() -> Self {
+        Self {
+            dir: generator_home().join("jobs"),
+        }
+    }
+}
+
+impl JobStore {
+    fn path(&self, id: u64) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    /// 写入一条运行中的任务记录，id 取已有记录的最大值加一
+    pub fn create(&self, options: &GenerateOptions, rerun_of: Option<u64>) -> Result<JobRecord> {
+        fs::create_dir_all(&self.dir)?;
+        let mut id = self.ids()?.into_iter().max().unwrap_or(0) + 1;
+        // 其他进程可能同时创建任务，已存在的 id 顺延
+        let mut file = loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.path(id))
+            {
+                Ok(file) => break file,
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => id += 1,
+                Err(e) => return Err(e.into()),
+            }
+        };
+        let record = JobRecord {
+            id,
+            status: JobState::Running,
+            options: stored_options(options),
+            logs: Vec::new(),
+            result: None,
+            error: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            rerun_of,
+        };
+        file.write_all(serde_json::to_string_pretty(&record)?.as_bytes())?;
+        Ok(record)
+    }
+
+    /// 执行任务并保存结果与日志；日志同时转发给调用线程上已注册的监听者
+    pub fn run(&self, mut record: JobRecord, options: GenerateOptions) -> Result<JobRecord> {
+        let recorder = Arc::new(LogRecorder {
+            logs: Mutex::new(Vec::new()),
+            inner: events::current(),
+        });
+        let outcome =
+            events::with_listener(recorder.clone(), || generate_project_from_template(options));
+        record.logs = std::mem::take(&mut *recorder.logs.lock().unwrap_or_else(|e| e.into_inner()));
+        match outcome {
+            Ok(result) => {
+                record.status = JobState::Succeeded;
+                record.result = Some(result);
+            }
+            Err(e) => {
+                record.status = JobState::Failed;
+                record.error = Some(e.details(
+                    record.options.template.as_deref(),
+                    record.options.output_path.as_deref(),
+                ));
+            }
+        }
+        record.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        self.save(&record)?;
+        Ok(record)
+    }
+
+    /// 按记录中的参数重新执行，`variables` 补充或覆盖变量（如未保存的敏感变量）
+    pub fn rerun(&self, id: u64, variables: HashMap<String, String>) -> Result<JobRecord> {
+        let previous = self.get(id)?.ok_or_else(|| {
+            GeneratorError::Configuration(format!("Generation job {} not found", id))
+        })?;
+        let mut options = previous.options;
+        options
+            .variables
+            .get_or_insert_with(HashMap::new)
+            .extend(variables);
+        let record = self.create(&options, Some(id))?;
+        self.run(record, options)
+    }
+
+    /// 读取任务记录
+    pub fn get(&self, id: u64) -> Result<Option<JobRecord>> {
+        let path = self.path(id);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let record = serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e))
+        })?;
+        Ok(Some(record))
+    }
+
+    /// 最近的任务记录，按 id 从新到旧排列
+    pub fn list(&self, limit: Option<usize>) -> Result<Vec<JobRecord>> {
+        let mut ids = self.ids()?;
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids.truncate(limit.unwrap_or(usize::MAX));
+        let mut records = Vec::new();
+        for id in ids {
+            records.extend(self.get(id)?);
+        }
+        Ok(records)
+    }
+
+    fn save(&self, record: &JobRecord) -> Result<()> {
+        fs::write(self.path(record.id), serde_json::to_string_pretty(record)?)?;
+        Ok(())
+    }
+
+    fn ids(&self) -> Result<Vec<u64>> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && let Some(id) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.parse().ok())
+            {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+}
+
+/// 去掉模板声明为敏感的变量值；模板无法解析时生成也会失败，按原样保存
+fn stored_options(options: &GenerateOptions) -> GenerateOptions {
+    let mut options = options.clone();
+    let template = options.template.as_deref().unwrap_or("basic");
+    if let (Some(variables), Ok(template)) = (
+        &options.variables,
+        TemplateRegistryConfig::user()
+            .and_then(|config| resolve_template(&config, &options.project_type, template)),
+    ) {
+        options.variables = Some(
+            without_sensitive(&template.metadata, variables)
+                .into_iter()
+                .collect(),
+        );
+    }
+    options
+}
+
+/// 收集日志的监听者，事件与询问继续交给外层监听者
+struct LogRecorder {
+    logs: Mutex<Vec<String>>,
+    inner: Option<Arc<dyn Listener>>,
+}
+
+impl Listener for LogRecorder {
+    fn event(&self, event: Event) {
+        if let Event::Log { message } = &event {
+            self.logs
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(message.clone());
+        }
+        if let Some(inner) = &self.inner {
+            inner.event(event);
+        }
+    }
+
+    fn prompt(&self, variable: &TemplateVariable) -> Result<Option<String>> {
+        match &self.inner {
+            Some(inner) => inner.prompt(variable),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_jobs_are_recorded_and_rerun() {
+        let dir = tempdir().unwrap();
+        let store = JobStore {
+            dir: dir.path().to_path_buf(),
+        };
+        let options = GenerateOptions {
+            name: "app".to_string(),
+            project_type: "cobol".to_string(),
+            variables: Some(HashMap::from([("port".to_string(), "80".to_string())])),
+            ..Default::default()
+        };
+        let record = store.create(&options, None).unwrap();
+        assert_eq!(record.id, 1);
+        assert_eq!(store.get(1).unwrap().unwrap().status, JobState::Running);
+
+        let record = store.run(record, options).unwrap();
+        assert_eq!(record.status, JobState::Failed);
+        assert_eq!(record.error.unwrap().code, "TEMPLATE_NOT_FOUND");
+        assert!(record.finished_at.is_some());
+
+        let rerun = store
+            .rerun(1, HashMap::from([("host".to_string(), "x".to_string())]))
+            .unwrap();
+        assert_eq!((rerun.id, rerun.rerun_of), (2, Some(1)));
+        assert_eq!(rerun.options.variables.unwrap().len(), 2);
+        assert!(store.rerun(9, HashMap::new()).is_err());
+
+        let history = store.list(Some(1)).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, 2);
+        assert_eq!(store.list(None).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,386 @@
+//! Kubernetes 部署脚手架模块
+//! 内置的 `k8s`、`helm` 特性：为后端项目生成 Deployment、Service、Ingress 清单或等价的 Helm chart
+
+use crate::pipeline::RenderReport;
+use crate::{GeneratorError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 生成 `k8s/` 下的原生清单
+pub const K8S_FEATURE: &str = "k8s";
+/// 生成 `charts/<name>/` 下的 Helm chart
+pub const HELM_FEATURE: &str = "helm";
+
+/// 镜像，默认为 `<dockerRegistry>/<name>:latest`
+pub const IMAGE_VARIABLE: &str = "k8sImage";
+/// 容器端口，默认 8080
+pub const PORT_VARIABLE: &str = "k8sPort";
+/// 副本数，默认 1
+pub const REPLICAS_VARIABLE: &str = "k8sReplicas";
+/// CPU 请求，默认 `250m`
+pub const CPU_VARIABLE: &str = "k8sCpu";
+/// 内存请求与上限，默认 `512Mi`
+pub const MEMORY_VARIABLE: &str = "k8sMemory";
+/// Ingress 域名，默认 `<name>.local`
+pub const HOST_VARIABLE: &str = "k8sHost";
+
+/// 部署参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Deployment {
+    name: String,
+    image: String,
+    port: u16,
+    replicas: u32,
+    cpu: String,
+    memory: String,
+    host: String,
+    app_version: String,
+}
+
+impl Deployment {
+    fn from_variables(variables: &HashMap<String, String>) -> Result<Self> {
+        let variable = |name: &str| {
+            variables
+                .get(name)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let name = variable("name").unwrap_or("app").to_string();
+        let image = match (variable(IMAGE_VARIABLE), variable("dockerRegistry")) {
+            (Some(image), _) => image.to_string(),
+            (None, Some(registry)) => {
+                format!("{}/{}:latest", registry.trim_end_matches('/'), name)
+            }
+            (None, None) => format!("{}:latest", name),
+        };
+        let number = |variable_name: &str, value: Option<&str>| {
+            value.map(|value| {
+                value.parse::<u32>().map_err(|_| {
+                    GeneratorError::Configuration(format!(
+                        "{} must be a number, got '{}'",
+                        variable_name, value
+                    ))
+                })
+            })
+        };
+        let port = number(PORT_VARIABLE, variable(PORT_VARIABLE))
+            .transpose()?
+            .unwrap_or(8080);
+
+        Ok(Self {
+            host: variable(HOST_VARIABLE)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}.local", name)),
+            port: u16::try_from(port).map_err(|_| {
+                GeneratorError::Configuration(format!(
+                    "{} is out of range: {}",
+                    PORT_VARIABLE, port
+                ))
+            })?,
+            replicas: number(REPLICAS_VARIABLE, variable(REPLICAS_VARIABLE))
+                .transpose()?
+                .unwrap_or(1),
+            cpu: variable(CPU_VARIABLE).unwrap_or("250m").to_string(),
+            memory: variable(MEMORY_VARIABLE).unwrap_or("512Mi").to_string(),
+            app_version: variable("version").unwrap_or("1.0.0").to_string(),
+            image,
+            name,
+        })
+    }
+
+    /// 拆分镜像的仓库与标签，`registry:5000/app` 中的端口不视为标签
+    fn image_parts(&self) -> (&str, &str) {
+        match self.image.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, tag),
+            _ => (&self.image, "latest"),
+        }
+    }
+}
+
+/// 项目类型是否支持内置的 k8s / helm 特性（仅后端项目）
+pub fn supports(project_type: &str) -> bool {
+    matches!(project_type, "java" | "rust")
+}
+
+/// 在生成的项目中写入 `feature`（k8s 或 helm）对应的部署文件
+pub fn scaffold(
+    project_dir: &Path,
+    feature: &str,
+    project_type: &str,
+    variables: &HashMap<String, String>,
+) -> Result<RenderReport> {
+    if !supports(project_type) {
+        return Err(GeneratorError::Configuration(format!(
+            "Feature '{}' is only available for backend project types, not '{}'",
+            feature, project_type
+        )));
+    }
+    let deployment = Deployment::from_variables(variables)?;
+    let files = match feature {
+        HELM_FEATURE => helm_chart(&deployment),
+        _ => manifests(&deployment),
+    };
+
+    let mut report = RenderReport::default();
+    for (file, content) in files {
+        let path = project_dir.join(&file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &content)?;
+        report.bytes_written += content.len() as u64;
+        report.files.push(PathBuf::from(file));
+    }
+    Ok(report)
+}
+
+fn manifests(d: &Deployment) -> Vec<(String, String)> {
+    let Deployment {
+        name,
+        image,
+        port,
+        replicas,
+        cpu,
+        memory,
+        host,
+        ..
+    } = d;
+    vec![
+        (
+            "k8s/deployment.yaml".to_string(),
+            format!(
+                "apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {name}
+  labels:
+    app: {name}
+spec:
+  replicas: {replicas}
+  selector:
+    matchLabels:
+      app: {name}
+  template:
+    metadata:
+      labels:
+        app: {name}
+    spec:
+      containers:
+        - name: {name}
+          image: {image}
+          ports:
+            - containerPort: {port}
+          resources:
+            requests:
+              cpu: {cpu}
+              memory: {memory}
+            limits:
+              memory: {memory}
+"
+            ),
+        ),
+        (
+            "k8s/service.yaml".to_string(),
+            format!(
+                "apiVersion: v1
+kind: Service
+metadata:
+  name: {name}
+spec:
+  selector:
+    app: {name}
+  ports:
+    - port: 80
+      targetPort: {port}
+"
+            ),
+        ),
+        (
+            "k8s/ingress.yaml".to_string(),
+            format!(
+                "apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: {name}
+spec:
+  rules:
+    - host: {host}
+      http:
+        paths:
+          - path: /
+            pathType: Prefix
+            backend:
+              service:
+                name: {name}
+                port:
+                  number: 80
+"
+            ),
+        ),
+    ]
+}
+
+fn helm_chart(d: &Deployment) -> Vec<(String, String)> {
+    let (repository, tag) = d.image_parts();
+    let dir = format!("charts/{}", d.name);
+    vec![
+        (
+            format!("{}/Chart.yaml", dir),
+            format!(
+                "apiVersion: v2
+name: {name}
+description: Helm chart for {name}
+type: application
+version: 0.1.0
+appVersion: \"{app_version}\"
+",
+                name = d.name,
+                app_version = d.app_version
+            ),
+        ),
+        (
+            format!("{}/values.yaml", dir),
+            format!(
+                "replicaCount: {replicas}
+
+image:
+  repository: {repository}
+  tag: \"{tag}\"
+  pullPolicy: IfNotPresent
+
+containerPort: {port}
+
+service:
+  port: 80
+
+resources:
+  requests:
+    cpu: {cpu}
+    memory: {memory}
+  limits:
+    memory: {memory}
+
+ingress:
+  enabled: true
+  host: {host}
+",
+                replicas = d.replicas,
+                port = d.port,
+                cpu = d.cpu,
+                memory = d.memory,
+                host = d.host
+            ),
+        ),
+        (
+            format!("{}/templates/deployment.yaml", dir),
+            HELM_DEPLOYMENT.to_string(),
+        ),
+        (
+            format!("{}/templates/service.yaml", dir),
+            HELM_SERVICE.to_string(),
+        ),
+        (
+            format!("{}/templates/ingress.yaml", dir),
+            HELM_INGRESS.to_string(),
+        ),
+    ]
+}
+
+const HELM_DEPLOYMENT: &str = "apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {{ .Release.Name }}
+  labels:
+    app: {{ .Release.Name }}
+spec:
+  replicas: {{ .Values.replicaCount }}
+  selector:
+    matchLabels:
+      app: {{ .Release.Name }}
+  template:
+    metadata:
+      labels:
+        app: {{ .Release.Name }}
+    spec:
+      containers:
+        - name: {{ .Chart.Name }}
+          image: \"{{ .Values.image.repository }}:{{ .Values.image.tag }}\"
+          imagePullPolicy: {{ .Values.image.pullPolicy }}
+          ports:
+            - containerPort: {{ .Values.containerPort }}
+          resources:
+            {{- toYaml .Values.resources | nindent 12 }}
+";
+
+const HELM_SERVICE: &str = "apiVersion: v1
+kind: Service
+metadata:
+  name: {{ .Release.Name }}
+spec:
+  selector:
+    app: {{ .Release.Name }}
+  ports:
+    - port: {{ .Values.service.port }}
+      targetPort: {{ .Values.containerPort }}
+";
+
+const HELM_INGRESS: &str = "{{- if .Values.ingress.enabled }}
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: {{ .Release.Name }}
+spec:
+  rules:
+    - host: {{ .Values.ingress.host }}
+      http:
+        paths:
+          - path: /
+            pathType: Prefix
+            backend:
+              service:
+                name: {{ .Release.Name }}
+                port:
+                  number: {{ .Values.service.port }}
+{{- end }}
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scaffold_manifests_and_chart() {
+        let project = tempdir().unwrap();
+        let variables = HashMap::from([
+            ("name".to_string(), "orders".to_string()),
+            (
+                IMAGE_VARIABLE.to_string(),
+                "registry:5000/acme/orders:1.2".to_string(),
+            ),
+            (REPLICAS_VARIABLE.to_string(), "3".to_string()),
+            (MEMORY_VARIABLE.to_string(), "1Gi".to_string()),
+        ]);
+
+        let report = scaffold(project.path(), K8S_FEATURE, "java", &variables).unwrap();
+        assert_eq!(report.files.len(), 3);
+        let read = |path: &str| fs::read_to_string(project.path().join(path)).unwrap();
+        let deployment = read("k8s/deployment.yaml");
+        assert!(deployment.contains("  replicas: 3\n"));
+        assert!(deployment.contains("          image: registry:5000/acme/orders:1.2\n"));
+        assert!(deployment.contains("            - containerPort: 8080\n"));
+        assert!(read("k8s/ingress.yaml").contains("    - host: orders.local\n"));
+
+        scaffold(project.path(), HELM_FEATURE, "java", &variables).unwrap();
+        let values = read("charts/orders/values.yaml");
+        assert!(values.contains("  repository: registry:5000/acme/orders\n  tag: \"1.2\"\n"));
+        assert!(values.contains("    memory: 1Gi\n"));
+        assert!(read("charts/orders/Chart.yaml").contains("name: orders\n"));
+        assert!(read("charts/orders/templates/deployment.yaml").contains("{{ .Release.Name }}"));
+
+        assert!(scaffold(project.path(), K8S_FEATURE, "vue", &variables).is_err());
+        let invalid = HashMap::from([(PORT_VARIABLE.to_string(), "http".to_string())]);
+        assert!(scaffold(project.path(), K8S_FEATURE, "java", &invalid).is_err());
+    }
+}
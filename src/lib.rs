@@ -1,30 +1,447 @@
+#[cfg(feature = "native")]
+use napi::bindgen_prelude::{AsyncTask, ToNapiValue};
+#[cfg(feature = "native")]
+use napi::threadsafe_function::{
+    ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+#[cfg(feature = "native")]
+use napi::{Env, JsFunction, JsObject, JsUnknown, Ref, Status, Task, ValueType};
+#[cfg(feature = "native")]
+use std::sync::{Arc, Mutex, mpsc};
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 
+/// 只在 `native` 特性下编译的条目，关闭该特性时只保留渲染核心
+macro_rules! cfg_native {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "native")]
+            $item
+        )*
+    };
+}
+
 // 模块声明
-pub mod cli;
+// 渲染核心：变量校验与模板渲染，不依赖进程与网络，可编译到 wasm32
+pub mod computed;
 pub mod error;
-pub mod template_registry;
-pub mod templates;
-pub mod utils;
+pub mod events;
+pub mod hooks;
+pub mod render;
+pub mod template_helpers;
+pub mod template_metadata;
+pub mod text_format;
+pub mod variables;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// 命令行、napi 绑定、外部进程与网络模板源
+// 导出宏（`say!`、`t!`）的模块单独标注，宏展开出的 `#[macro_export]` 宏不能按路径引用
+#[cfg(feature = "native")]
+pub mod i18n;
+#[cfg(feature = "native")]
+pub mod output;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "http-server")]
+pub mod http_server;
+cfg_native! {
+    pub mod android_env;
+    pub mod answers;
+    pub mod backstage;
+    pub mod batch;
+    pub mod blueprint;
+    pub mod build_wrapper;
+    pub mod bundle;
+    pub mod cache_lock;
+    pub mod cancel;
+    pub mod changelog;
+    pub mod ci;
+    pub mod cli;
+    pub mod command_audit;
+    pub mod compat;
+    pub mod cookiecutter;
+    pub mod condition;
+    pub mod content_cache;
+    pub mod credentials;
+    pub mod crud;
+    pub mod daemon;
+    pub mod delegate;
+    pub mod dev;
+    pub mod docker;
+    pub mod doctor;
+    pub mod env_defaults;
+    pub mod extract;
+    pub mod features;
+    pub mod git_source;
+    pub mod http_source;
+    pub mod java_cli;
+    pub mod job_history;
+    pub mod kubernetes;
+    pub mod license;
+    pub mod lockfile;
+    pub mod monorepo;
+    pub mod network;
+    pub mod npm_source;
+    pub mod npmrc;
+    pub mod node_env;
+    pub mod openapi;
+    pub mod pipeline;
+    pub mod preview;
+    pub mod publish;
+    pub mod python_env;
+    pub mod readme;
+    pub mod report;
+    pub mod retry;
+    pub mod scaffold;
+    pub mod session;
+    pub mod template_harness;
+    pub mod template_index;
+    pub mod template_ignore;
+    pub mod template_lint;
+    pub mod template_registry;
+    pub mod template_version;
+    pub mod templates;
+    pub mod toolchain;
+    pub mod transaction;
+    pub mod update;
+    pub mod usage;
+    pub mod vcs;
+    pub mod workspace;
+    pub mod utils;
+}
 
 // 重新导出错误类型
-pub use error::{GeneratorError, Result};
+pub use error::{ErrorCode, GeneratorError, Result};
+pub use text_format::{BomPolicy, LineEnding};
+cfg_native! {
+    pub use build_wrapper::BuildTool;
+    pub use openapi::OpenApiMode;
+    pub use output::OutputMode;
+    pub use utils::SymlinkPolicy;
+}
+
+/// 内置支持的项目类型；各选项的 `project_type` 在 TypeScript 中以此提示，同时接受注册表模板的其他类型
+#[cfg(feature = "native")]
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProjectType {
+    #[napi(value = "java")]
+    Java,
+    #[napi(value = "vue")]
+    Vue,
+    #[napi(value = "react")]
+    React,
+    #[napi(value = "node-lib")]
+    NodeLib,
+    #[napi(value = "monorepo")]
+    Monorepo,
+    #[napi(value = "rust")]
+    Rust,
+    #[napi(value = "android")]
+    Android,
+    #[napi(value = "python")]
+    Python,
+}
 
 /// 项目生成选项
+#[cfg(feature = "native")]
 #[napi(object)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GenerateOptions {
     /// 项目名称
     pub name: String,
     /// 项目类型 (java, vue, react)
+    #[napi(ts_type = "`${ProjectType}` | (string & {})")]
     pub project_type: String,
+    /// 模板名称、模板目录或 Git 简写（如 `gh:org/repo#main/subdir`）
+    pub template: Option<String>,
+    /// 输出路径，支持 `~`；相对路径基于 workspace_root 解析
+    pub output_path: Option<String>,
+    /// 相对输出路径的基准目录，默认取 `GENERATOR_WORKSPACE` 环境变量，再退回当前目录
+    pub workspace_root: Option<String>,
+    /// 额外变量
+    pub variables: Option<std::collections::HashMap<String, String>>,
+    /// 启用的模板特性（如 eslint、docker）
+    pub features: Option<Vec<String>>,
+    /// 符号链接处理策略，默认 follow
+    pub symlink_policy: Option<SymlinkPolicy>,
+    /// 并行写入文件的工作线程数，默认为 CPU 核数
+    pub workers: Option<u32>,
+    /// 生成失败时保留已生成的部分文件（默认回滚）
+    pub keep_partial: Option<bool>,
+    /// 直接生成到已有目录（output_path）中并与现有文件合并
+    pub into_existing: Option<bool>,
+    /// 合并时覆盖内容不同的已有文件（仍会保护有未提交修改的 git 跟踪文件）
+    pub force: Option<bool>,
+    /// 为 Java 项目生成 Maven / Gradle wrapper（mvnw、gradlew）；Android 项目默认生成，未显式要求时失败只记为警告
+    pub build_wrapper: Option<bool>,
+    /// monorepo 中需要一并生成的初始子项目
+    pub packages: Option<Vec<MonorepoPackage>>,
+    /// 答案文件路径，预先提供变量值与模板选择
+    pub answers_file: Option<String>,
+    /// 各阶段外部操作的超时设置，未设置的阶段使用注册表配置
+    pub timeouts: Option<PhaseTimeouts>,
+    /// 生成报告路径，`.html` 结尾时输出 HTML，否则输出 JSON
+    pub report: Option<String>,
+    /// 严格模式：渲染后残留未解析的占位符时生成失败（默认仅给出警告）
+    pub strict: Option<bool>,
+    /// 文本文件的换行符，默认保持模板原样
+    pub line_endings: Option<LineEnding>,
+    /// 文本文件的 UTF-8 BOM 处理，默认保留模板原有的 BOM
+    pub bom: Option<BomPolicy>,
+    /// 生成 LICENSE 的许可证，并写入 package.json / pom.xml；不区分大小写，
+    /// 也接受 `apache`、`gpl`、`gpl-3.0-only`、`unlicensed` 等别名，其他值生成失败
+    #[napi(ts_type = "'MIT' | 'Apache-2.0' | 'GPL-3.0' | 'proprietary'")]
+    pub license: Option<String>,
+    /// 许可证中的版权持有人，默认取 `author` 变量，再退回项目名称
+    pub license_holder: Option<String>,
+    /// OpenAPI 规范的路径或 URL，按规范生成 API 客户端或服务端桩代码
+    pub openapi: Option<String>,
+    /// OpenAPI 生成模式，java 默认 server，vue / react 默认 client
+    pub openapi_mode: Option<OpenApiMode>,
+    /// java 项目按数据库表结构生成 CRUD 代码：JDBC URL（`jdbc:` 开头）或 DDL 文件路径
+    ///
+    /// 连接用户名与密码取 `dbUsername`、`dbPassword` 变量，均按敏感变量处理。
+    pub database: Option<String>,
+    /// 只为这些表生成 CRUD 代码，默认全部表
+    pub database_tables: Option<Vec<String>>,
+    /// 模板须位于的确切修订（git 提交，可用不短于 7 位的前缀），不一致时生成失败
+    pub template_revision: Option<String>,
+    /// 冻结模式：模板必须固定到确切修订（template_revision，否则取输出目录锁文件中记录的修订）
+    pub frozen: Option<bool>,
+    /// Java 项目的构建工具，须在模板的 `build_tools` 之内，未设置时取其中第一个；
+    /// 以 `buildTool` 变量传给模板
+    pub build_tool: Option<BuildTool>,
+    /// 委托给官方初始化工具（如 `npm create vite@latest`、Spring Initializr）生成而不使用本地模板，只支持已知的项目类型；
+    /// java 项目选择 `spring-initializr` 模板时总是委托
+    pub delegate: Option<bool>,
+    /// python 项目生成后创建 `.venv` 并安装依赖：uv 布局执行 `uv sync`，poetry 布局执行 `poetry install`，
+    /// 其余使用 `python -m venv` 与 pip
+    pub python_venv: Option<bool>,
+}
+
+/// 外部操作的分阶段超时（秒），未设置表示不限时
+#[napi(object)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTimeouts {
+    /// git 模板源的克隆与拉取
+    pub git_fetch_secs: Option<u32>,
+    /// HTTP 下载（模板压缩包、Java CLI jar 包）
+    pub http_download_secs: Option<u32>,
+    /// npm 模板包的获取（registry 查询与 tarball 下载）
+    pub npm_pack_secs: Option<u32>,
+    /// 钩子与蓝图后置步骤的执行
+    pub hook_secs: Option<u32>,
+    /// Java CLI 的执行
+    pub java_cli_secs: Option<u32>,
+}
+
+impl PhaseTimeouts {
+    /// 以 `fallback` 补全未设置的阶段
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            git_fetch_secs: self.git_fetch_secs.or(fallback.git_fetch_secs),
+            http_download_secs: self.http_download_secs.or(fallback.http_download_secs),
+            npm_pack_secs: self.npm_pack_secs.or(fallback.npm_pack_secs),
+            hook_secs: self.hook_secs.or(fallback.hook_secs),
+            java_cli_secs: self.java_cli_secs.or(fallback.java_cli_secs),
+        }
+    }
+
+    /// 秒数转换为 [`std::time::Duration`]
+    pub fn duration(secs: Option<u32>) -> Option<std::time::Duration> {
+        secs.map(|secs| std::time::Duration::from_secs(secs.into()))
+    }
+}
+
+/// 模板脚手架选项
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NewTemplateOptions {
     /// 模板名称
+    pub name: String,
+    /// 项目类型 (java, vue, react)
+    #[napi(ts_type = "`${ProjectType}` | (string & {})")]
+    pub project_type: String,
+    /// 模板目录，默认为 ./templates/<project_type>/<name>
+    pub path: Option<String>,
+    /// 模板描述
+    pub description: Option<String>,
+    /// 作者
+    pub author: Option<String>,
+}
+
+/// Backstage 模板导入选项
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackstageImportOptions {
+    /// Backstage 的 template.yaml，或包含它的目录
+    pub source: String,
+    /// 项目类型，默认为模板的 `spec.type`
+    #[napi(ts_type = "`${ProjectType}` | (string & {})")]
+    pub project_type: Option<String>,
+    /// 模板目录，默认为 ./templates/<project_type>/<metadata.name>
+    pub path: Option<String>,
+}
+
+/// Backstage 模板导入结果
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackstageImportResult {
+    /// 创建的模板目录
+    pub path: String,
+    /// 创建的文件（相对模板目录）
+    pub files: Vec<String>,
+    /// 未能转换的参数、values 与步骤，如 `steps.publish (publish:github)`
+    pub skipped: Vec<String>,
+}
+
+/// 结构化错误详情
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorDetails {
+    /// 稳定错误码，与 JS 错误对象的 `code` 相同，如 `TEMPLATE_NOT_FOUND`
+    pub code: String,
+    pub message: String,
+    /// 相关模板
     pub template: Option<String>,
-    /// 输出路径
+    /// 相关路径
+    pub path: Option<String>,
+    /// 底层原因
+    pub cause: Option<String>,
+    /// 变量校验失败时的全部问题（`INVALID_VARIABLES`）
+    #[serde(default)]
+    pub violations: Vec<VariableViolation>,
+}
+
+/// 单个变量的校验问题
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariableViolation {
+    /// 变量名
+    pub variable: String,
+    /// 问题类型 (missing, invalid-type, invalid-choice)
+    pub kind: String,
+    pub message: String,
+}
+
+/// 模板校验诊断
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// 严重程度 (error, warning)
+    pub severity: String,
+    /// 诊断代码，如 `undeclared-variable`
+    pub code: String,
+    pub message: String,
+    /// 相关文件（相对模板目录）
+    pub file: Option<String>,
+    /// 试渲染时对应的 fixture 名称
+    pub fixture: Option<String>,
+}
+
+/// 模板试渲染使用的变量 fixture
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateFixture {
+    /// fixture 名称，用于标注诊断
+    pub name: Option<String>,
+    /// 覆盖默认值的变量
+    pub variables: Option<std::collections::HashMap<String, String>>,
+    /// 启用的特性
+    pub features: Option<Vec<String>>,
+}
+
+/// 交互式 CLI 运行选项
+#[cfg(feature = "native")]
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CliRunOptions {
+    /// 答案文件路径，提供后跳过所有交互问题
+    pub answers_file: Option<String>,
+    /// 将本次回答保存到该路径，便于在 CI 中重放
+    pub save_answers: Option<String>,
+    /// 生成报告路径（`.json` / `.html`）
+    pub report: Option<String>,
+    /// 输出模式，默认 human
+    pub output: Option<OutputMode>,
+    /// 界面语言 (zh-CN, en-US)，默认按用户设置与 `LANG` 确定
+    pub lang: Option<String>,
+    /// 恢复上次失败的交互会话，跳过已回答的问题
+    pub resume: Option<bool>,
+    /// 冻结模式：按项目目录中锁文件记录的模板修订重新生成，模板已变化时失败
+    pub frozen: Option<bool>,
+    /// 委托给官方初始化工具生成
+    pub delegate: Option<bool>,
+    /// python 项目生成后创建虚拟环境并安装依赖
+    pub python_venv: Option<bool>,
+}
+
+/// 批量生成选项
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchOptions {
+    /// 所有项目共享的变量，项目自身的变量优先
+    pub variables: Option<std::collections::HashMap<String, String>>,
+    /// 项目未指定 output_path 时使用的输出路径
     pub output_path: Option<String>,
+    /// 某个项目失败后继续生成其余项目（默认立即停止）
+    pub continue_on_error: Option<bool>,
+    /// 冻结模式，项目自身未设置时使用
+    pub frozen: Option<bool>,
+}
+
+/// 蓝图中单个项目的执行结果
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlueprintProjectResult {
+    /// 项目名称
+    pub name: String,
+    /// 执行动作 (created, updated, unchanged, conflict, failed)
+    pub action: String,
+    /// 生成或变更的文件
+    pub files: Vec<String>,
+    /// 消息
+    pub message: Option<String>,
+}
+
+/// monorepo 子项目
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonorepoPackage {
+    /// 子项目名称
+    pub name: String,
+    /// 项目类型 (vue, react, java, node-lib, rust)
+    #[napi(ts_type = "`${ProjectType}` | (string & {})")]
+    pub project_type: String,
+    /// 模板名称
+    pub template: Option<String>,
+    /// 所在目录（相对 workspace 根目录），默认应用放在 apps，库放在 packages
+    pub directory: Option<String>,
+    /// 额外变量，未指定的变量继承 workspace
+    pub variables: Option<std::collections::HashMap<String, String>>,
+    /// 启用的模板特性
+    pub features: Option<Vec<String>>,
+}
+
+/// 组件子生成选项
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentOptions {
+    /// 项目类型 (java, vue, react)
+    #[napi(ts_type = "`${ProjectType}` | (string & {})")]
+    pub project_type: String,
+    /// 组件类型，如 component、hook、controller
+    pub kind: String,
+    /// 组件名称
+    pub name: String,
+    /// 目标项目路径，默认为当前目录
+    pub project_path: Option<String>,
     /// 额外变量
     pub variables: Option<std::collections::HashMap<String, String>>,
+    /// 覆盖内容不同的已有文件
+    pub force: Option<bool>,
 }
 
 /// 项目生成结果
@@ -37,44 +454,1228 @@ pub struct GenerateResult {
     pub files: Vec<String>,
     /// 消息
     pub message: Option<String>,
+    /// 生成统计信息
+    pub stats: Option<GenerationStats>,
+    /// 实际使用的模板版本
+    pub template_version: Option<String>,
+    /// 不影响生成的警告（如 engines 约束不满足）
+    pub warnings: Vec<String>,
+    /// 执行的操作 (created, merged, unchanged)，失败时为空
+    pub action: Option<String>,
+}
+
+/// 生成统计信息
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationStats {
+    /// 写入文件数
+    pub file_count: u32,
+    /// 写入总字节数
+    pub total_bytes: i64,
+    /// 耗时（毫秒）
+    pub duration_ms: i64,
+    /// 每秒写入文件数
+    pub files_per_second: f64,
+    /// 每秒写入 MB 数
+    pub megabytes_per_second: f64,
+}
+
+#[cfg(feature = "native")]
+impl GenerationStats {
+    /// 根据渲染结果与耗时计算吞吐量
+    pub fn from_report(report: &pipeline::RenderReport, elapsed: std::time::Duration) -> Self {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        Self {
+            file_count: report.files.len() as u32,
+            total_bytes: report.bytes_written as i64,
+            duration_ms: elapsed.as_millis() as i64,
+            files_per_second: report.files.len() as f64 / seconds,
+            megabytes_per_second: report.bytes_written as f64 / (1024.0 * 1024.0) / seconds,
+        }
+    }
+
+    /// 累加另一次生成的统计并重新计算吞吐量
+    pub fn merge(&mut self, other: &GenerationStats) {
+        self.file_count += other.file_count;
+        self.total_bytes += other.total_bytes;
+        self.duration_ms += other.duration_ms;
+
+        let seconds = (self.duration_ms as f64 / 1000.0).max(f64::EPSILON);
+        self.files_per_second = self.file_count as f64 / seconds;
+        self.megabytes_per_second = self.total_bytes as f64 / (1024.0 * 1024.0) / seconds;
+    }
+}
+
+/// 项目更新选项
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateOptions {
+    /// 项目路径（包含 .generator.lock）
+    pub project_path: String,
+    /// 覆盖锁文件中记录的变量
+    pub variables: Option<std::collections::HashMap<String, String>>,
+    /// 只计算变更，不写入文件
+    pub dry_run: Option<bool>,
+}
+
+/// 项目更新结果
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateResult {
+    /// 是否无冲突完成
+    pub success: bool,
+    /// 被更新的文件
+    pub updated: Vec<String>,
+    /// 新增的文件
+    pub created: Vec<String>,
+    /// 删除的文件
+    pub deleted: Vec<String>,
+    /// 存在冲突、保留项目版本的文件
+    pub conflicts: Vec<String>,
+    /// 更新后的模板版本
+    pub template_version: String,
+    /// 锁文件中的版本之后、更新后的版本及之前的模板变更，从新到旧
+    pub changelog: Vec<ChangelogEntry>,
+    /// 消息
+    pub message: Option<String>,
+}
+
+/// 模板变更日志中的一个版本
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version: String,
+    /// 发布日期，如 `2024-05-01`
+    pub date: Option<String>,
+    /// 该版本的变更说明（Markdown）
+    pub notes: String,
+}
+
+/// 单个文件的差异预览
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// 相对目标目录的路径
+    pub path: String,
+    /// 变更类型：added / modified
+    pub status: String,
+    /// 是否为二进制文件（不生成 diff 文本）
+    pub binary: bool,
+    /// 统一 diff 文本
+    pub diff: Option<String>,
+}
+
+/// 审计出的外部命令
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditedCommand {
+    /// 程序
+    pub program: String,
+    /// 参数
+    pub args: Vec<String>,
+    /// 工作目录
+    pub cwd: String,
+    /// 被命令策略禁止时为匹配的模式
+    pub denied_by: Option<String>,
+}
+
+/// 项目文件校验结果
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyResult {
+    /// 所有生成的文件均未被修改
+    pub clean: bool,
+    /// 生成后被修改的文件
+    pub modified: Vec<String>,
+    /// 生成后被删除的文件
+    pub missing: Vec<String>,
+    /// 校验的文件数
+    pub checked: u32,
+}
+
+/// 项目锁文件信息
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectLockInfo {
+    /// 项目名称
+    pub name: String,
+    /// 项目类型
+    pub project_type: String,
+    /// 模板名称
+    pub template: String,
+    /// 模板版本
+    pub template_version: String,
+    /// 来源类型 (local, git, http, npm)
+    pub source_kind: String,
+    /// 来源位置
+    pub source_location: String,
+    /// 来源修订（git commit、npm 版本等）
+    pub source_revision: Option<String>,
+    /// 生成时使用的变量
+    pub variables: std::collections::HashMap<String, String>,
+    /// 启用的特性
+    pub features: Vec<String>,
+    /// 生成器版本
+    pub generator_version: String,
+    /// 生成时间
+    pub generated_at: String,
+    /// 生成 API 桩代码所用的 OpenAPI 规范路径或 URL
+    pub openapi_source: Option<String>,
+}
+
+#[cfg(feature = "native")]
+impl From<lockfile::ProjectLock> for ProjectLockInfo {
+    fn from(lock: lockfile::ProjectLock) -> Self {
+        Self {
+            name: lock.name,
+            project_type: lock.project_type,
+            template: lock.template,
+            template_version: lock.template_version,
+            source_kind: lock.source.kind,
+            source_location: lock.source.location,
+            source_revision: lock.source.revision,
+            variables: lock.variables.into_iter().collect(),
+            features: lock.features,
+            generator_version: lock.generator_version,
+            generated_at: lock.generated_at,
+            openapi_source: lock.openapi.map(|openapi| openapi.source),
+        }
+    }
+}
+
+/// Java 环境信息
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JavaEnvironmentInfo {
+    /// 是否检测到可用的 Java
+    pub available: bool,
+    /// java 可执行文件路径
+    pub java_path: Option<String>,
+    /// 完整版本信息，如 `openjdk version "17.0.9" 2023-10-17`
+    pub version: Option<String>,
+    /// 主版本号，如 8、17、21
+    pub major_version: Option<u32>,
+    /// 发行商
+    pub vendor: Option<String>,
+    /// 发现方式 (JAVA_HOME, PATH)
+    pub source: Option<String>,
+    /// 未检测到 Java 时的原因
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "native")]
+impl From<java_cli::JavaEnvironment> for JavaEnvironmentInfo {
+    fn from(java_env: java_cli::JavaEnvironment) -> Self {
+        Self {
+            available: true,
+            major_version: java_env.major_version(),
+            source: Some(java_env.source.as_str().to_string()),
+            java_path: Some(java_env.java_path),
+            version: Some(java_env.version),
+            vendor: java_env.vendor,
+            error: None,
+        }
+    }
+}
+
+/// Python 环境信息
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PythonEnvironmentInfo {
+    /// 是否检测到可用的 Python
+    pub available: bool,
+    /// 解释器路径
+    pub python_path: Option<String>,
+    /// 版本号，如 `3.12.4`
+    pub version: Option<String>,
+    /// 发现方式 (VIRTUAL_ENV, PATH)
+    pub source: Option<String>,
+    /// 未检测到 Python 时的原因
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "native")]
+impl From<python_env::PythonEnvironment> for PythonEnvironmentInfo {
+    fn from(python: python_env::PythonEnvironment) -> Self {
+        Self {
+            available: true,
+            source: Some(python.source.as_str().to_string()),
+            python_path: Some(python.python_path),
+            version: Some(python.version),
+            error: None,
+        }
+    }
+}
+
+/// Node.js 环境信息，未安装的工具为空
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeEnvironmentInfo {
+    pub node: Option<String>,
+    pub npm: Option<String>,
+    pub pnpm: Option<String>,
+    pub yarn: Option<String>,
+    pub bun: Option<String>,
+}
+
+/// 环境诊断中的单项检查
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    /// 检查项，如 `java`、`cache-dir`、`registry:local`
+    pub name: String,
+    /// 结果 (pass, warn, fail)
+    pub status: String,
+    pub message: String,
+    /// 修复建议
+    pub hint: Option<String>,
+}
+
+/// 环境诊断报告
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DoctorReport {
+    /// 没有 fail 级别的检查项
+    pub ok: bool,
+    pub checks: Vec<DoctorCheck>,
+}
+
+/// 模板发布结果
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishResult {
+    /// 注册表名称
+    pub registry: String,
+    pub name: String,
+    /// 发布的版本
+    pub version: String,
+    /// 发布位置：本地安装目录、git 标签或 npm 的 `<包名>@<版本>`
+    pub location: String,
+}
+
+/// 模板特性信息
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateFeatureInfo {
+    /// 特性名称
+    pub name: String,
+    /// 特性描述
+    pub description: String,
+}
+
+/// 模板在当前环境中的兼容性
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCompatibility {
+    /// 模板名称
+    pub name: String,
+    pub compatible: bool,
+    /// 不兼容的原因（缺少或版本不符的 Java、Node.js 引擎，不支持的操作系统）
+    pub reasons: Vec<String>,
+}
+
+/// 模板使用统计
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageStats {
+    /// 是否开启了使用记录
+    pub enabled: bool,
+    /// 记录的生成次数
+    pub total: u32,
+    /// 按模板与版本汇总，次数多的在前
+    pub templates: Vec<TemplateUsage>,
+}
+
+/// 单个模板版本的使用情况
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateUsage {
+    pub project_type: String,
+    pub template: String,
+    pub version: String,
+    /// 生成次数
+    pub count: u32,
+    /// 最近一次使用时间（RFC 3339）
+    pub last_used: String,
+}
+
+/// 模板缓存统计
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// 缓存根目录
+    pub path: String,
+    /// 缓存的归档数（按内容去重）
+    pub entries: u32,
+    /// 指向归档的别名数（校验和、npm integrity、包版本）
+    pub aliases: u32,
+    /// 解压后的总字节数
+    pub total_bytes: i64,
+    /// 大小上限，未设置时不淘汰
+    pub max_bytes: Option<i64>,
+}
+
+/// 模板索引刷新结果
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexStats {
+    /// 索引文件路径
+    pub path: String,
+    /// 索引中的模板数
+    pub templates: u32,
+    /// 重新解析的模板数
+    pub updated: u32,
+    /// 未变化、直接复用的模板数
+    pub reused: u32,
+    /// 已从磁盘删除、移出索引的模板数
+    pub removed: u32,
+}
+
+/// GeneratorSession 的默认选项，generate 未设置的字段取这里的值
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct SessionOptions {
+    pub output_path: Option<String>,
+    pub workspace_root: Option<String>,
+    /// 与每次生成的变量合并，生成时提供的值优先
+    pub variables: Option<std::collections::HashMap<String, String>>,
+    pub timeouts: Option<PhaseTimeouts>,
+}
+
+/// `progress` 事件：进入生成阶段
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ProgressEvent {
+    /// 阶段名称（resolve、render、commit、hooks 等）
+    pub phase: String,
+}
+
+/// `prompt` 事件：模板需要而未提供的变量
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct VariablePrompt {
+    pub name: String,
+    pub description: String,
+    #[napi(ts_type = "'string' | 'boolean' | 'number' | 'choice'")]
+    pub var_type: String,
+    /// choice 类型的可选值
+    pub choices: Vec<String>,
+    /// 敏感变量，输入时应隐藏
+    pub sensitive: bool,
+}
+
+#[cfg(feature = "native")]
+impl From<&template_registry::TemplateVariable> for VariablePrompt {
+    fn from(variable: &template_registry::TemplateVariable) -> Self {
+        use template_registry::VariableType;
+        let (var_type, choices) = match &variable.var_type {
+            VariableType::String => ("string", Vec::new()),
+            VariableType::Boolean => ("boolean", Vec::new()),
+            VariableType::Number => ("number", Vec::new()),
+            VariableType::Choice { options } => ("choice", options.clone()),
+        };
+        Self {
+            name: variable.name.clone(),
+            description: variable.description.clone(),
+            var_type: var_type.to_string(),
+            choices,
+            sensitive: variable.sensitive,
+        }
+    }
 }
 
 /// 生成项目
+#[cfg(feature = "native")]
 #[napi]
-pub fn generate_project(options: GenerateOptions) -> napi::Result<GenerateResult> {
-    templates::generate_project_from_template(options).map_err(Into::into)
+pub fn generate_project(options: GenerateOptions) -> napi::Result<GenerateResult, ErrorCode> {
+    let template = options.template.clone();
+    let path = options.output_path.clone();
+    templates::generate_project_from_template(options)
+        .map_err(|e| e.into_napi(template.as_deref(), path.as_deref()))
+}
+
+/// 异步生成项目，可通过 AbortSignal 取消：终止进行中的下载与子进程并回滚已生成的部分
+///
+/// 设置了 `onPrompt` 时，模板需要而未提供的必填变量逐个交给它询问（如弹出原生对话框），
+/// 返回 undefined 表示不回答，按缺少变量报错。
+#[cfg(feature = "native")]
+#[napi(
+    ts_args_type = "options: GenerateOptions, signal?: AbortSignal, onPrompt?: (prompt: VariablePrompt) => string | undefined | Promise<string | undefined>"
+)]
+pub fn generate_project_async(
+    env: Env,
+    options: GenerateOptions,
+    signal: Option<JsObject>,
+    on_prompt: Option<JsFunction>,
+) -> napi::Result<AsyncTask<GenerateTask>> {
+    let token = cancel::CancellationToken::new();
+    if let Some(signal) = signal {
+        bind_abort_signal(env, &signal, &token)?;
+    }
+    let prompt = on_prompt
+        .map(|handler| PromptHandler::new(&env, handler).map(Arc::new))
+        .transpose()?;
+    Ok(AsyncTask::new(GenerateTask {
+        options: Some(options),
+        token,
+        listener: prompt.clone().map(|prompt| prompt as Arc<dyn events::Listener>),
+        prompt,
+        error: None,
+    }))
+}
+
+/// 回答变量询问的 JS 函数，返回值可以是字符串、undefined 或它们的 Promise
+#[cfg(feature = "native")]
+struct PromptHandler {
+    function: ThreadsafeFunction<(VariablePrompt, mpsc::Sender<PromptReply>), ErrorStrategy::Fatal>,
+    /// 函数的引用，生成结束后在 [`PromptHandler::release`] 中释放
+    handler: Arc<Mutex<Option<Ref<()>>>>,
+}
+
+/// 询问的结果，Promise 被拒绝时为拒绝原因
+#[cfg(feature = "native")]
+type PromptReply = std::result::Result<Option<String>, String>;
+
+#[cfg(feature = "native")]
+impl PromptHandler {
+    fn new(env: &Env, handler: JsFunction) -> napi::Result<Self> {
+        let handler = Arc::new(Mutex::new(Some(env.create_reference(handler)?)));
+        let reference = handler.clone();
+        // 处理函数在回调中直接调用以捕获异常与返回值，线程安全函数本身只负责切换到 JS 线程
+        let noop = env.create_function_from_closure("prompt", |_| Ok(()))?;
+        let mut function = noop.create_threadsafe_function(
+            0,
+            move |ctx: ThreadSafeCallContext<(VariablePrompt, mpsc::Sender<PromptReply>)>| {
+                let (prompt, reply) = ctx.value;
+                if let Some(handler) = lock(&reference).as_ref()
+                    && let Err(e) = call_prompt_handler(&ctx.env, handler, prompt, reply.clone())
+                {
+                    let _ = reply.send(Err(e.reason));
+                }
+                Ok(Vec::<()>::new())
+            },
+        )?;
+        function.unref(env)?;
+        Ok(Self { function, handler })
+    }
+
+    fn release(&self, env: Env) -> napi::Result<()> {
+        if let Some(mut handler) = lock(&self.handler).take() {
+            handler.unref(env)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "native")]
+impl events::Listener for PromptHandler {
+    fn event(&self, _event: events::Event) {}
+
+    fn prompt(&self, variable: &template_registry::TemplateVariable) -> Result<Option<String>> {
+        let (reply, answer) = mpsc::channel();
+        self.function.call(
+            (VariablePrompt::from(variable), reply),
+            ThreadsafeFunctionCallMode::NonBlocking,
+        );
+        wait_prompt_reply(&variable.name, &answer)
+    }
+}
+
+/// 等待处理函数的回答，期间响应取消；处理函数没有回答就被释放时视为不回答
+#[cfg(feature = "native")]
+fn wait_prompt_reply(name: &str, answer: &mpsc::Receiver<PromptReply>) -> Result<Option<String>> {
+    loop {
+        cancel::check()?;
+        match answer.recv_timeout(std::time::Duration::from_millis(50)) {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(reason)) => {
+                return Err(GeneratorError::Unknown(format!(
+                    "Prompt for '{}' failed: {}",
+                    name, reason
+                )));
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(None),
+        }
+    }
+}
+
+/// 在 JS 线程上调用处理函数，返回 Promise 时在其完成后回答
+#[cfg(feature = "native")]
+fn call_prompt_handler(
+    env: &Env,
+    handler: &Ref<()>,
+    prompt: VariablePrompt,
+    reply: mpsc::Sender<PromptReply>,
+) -> napi::Result<()> {
+    let handler: JsFunction = env.get_reference_value(handler)?;
+    let mut object = env.create_object()?;
+    object.set_named_property("name", prompt.name)?;
+    object.set_named_property("description", prompt.description)?;
+    object.set_named_property("varType", prompt.var_type)?;
+    object.set_named_property("choices", prompt.choices)?;
+    object.set_named_property("sensitive", prompt.sensitive)?;
+    let value = handler.call(None, &[object])?;
+    if !value.is_promise()? {
+        let _ = reply.send(Ok(prompt_answer(value)?));
+        return Ok(());
+    }
+
+    let promise = value.coerce_to_object()?;
+    let fulfilled = {
+        let reply = reply.clone();
+        env.create_function_from_closure("onFulfilled", move |ctx| {
+            let _ = reply.send(prompt_answer(ctx.get::<JsUnknown>(0)?).map_err(|e| e.reason));
+            Ok(())
+        })?
+    };
+    let rejected = env.create_function_from_closure("onRejected", move |ctx| {
+        let reason = ctx.get::<JsUnknown>(0)?.coerce_to_string()?.into_utf8()?.into_owned()?;
+        let _ = reply.send(Err(reason));
+        Ok(())
+    })?;
+    let then: JsFunction = promise.get_named_property("then")?;
+    then.call(Some(&promise), &[fulfilled, rejected])?;
+    Ok(())
+}
+
+/// 处理函数给出的值：字符串为回答，undefined / null 表示不回答
+#[cfg(feature = "native")]
+fn prompt_answer(value: JsUnknown) -> napi::Result<Option<String>> {
+    match value.get_type()? {
+        ValueType::Undefined | ValueType::Null => Ok(None),
+        ValueType::String => Ok(Some(value.coerce_to_string()?.into_utf8()?.into_owned()?)),
+        _ => Err(napi::Error::new(
+            Status::InvalidArg,
+            "Prompt handler must return a string or undefined".to_string(),
+        )),
+    }
+}
+
+/// 在 AbortSignal 触发 `abort` 时取消令牌
+#[cfg(feature = "native")]
+fn bind_abort_signal(
+    env: Env,
+    signal: &JsObject,
+    token: &cancel::CancellationToken,
+) -> napi::Result<()> {
+    if signal.get_named_property::<bool>("aborted")? {
+        token.cancel();
+        return Ok(());
+    }
+
+    let token = token.clone();
+    let on_abort = env.create_function_from_closure("onabort", move |_| {
+        token.cancel();
+        Ok(())
+    })?;
+    let add_event_listener: JsFunction = signal.get_named_property("addEventListener")?;
+    add_event_listener.call(
+        Some(signal),
+        &[
+            env.create_string("abort")?.into_unknown(),
+            on_abort.into_unknown(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// 在 libuv 线程池中执行的生成任务
+#[cfg(feature = "native")]
+pub struct GenerateTask {
+    options: Option<GenerateOptions>,
+    token: cancel::CancellationToken,
+    /// 接收生成事件的监听者
+    listener: Option<Arc<dyn events::Listener>>,
+    /// 调用方传入的变量询问函数，生成结束后释放
+    prompt: Option<Arc<PromptHandler>>,
+    /// 失败时的结构化详情，在 reject 中转换为带 code 的 JS 错误
+    error: Option<ErrorDetails>,
+}
+
+#[cfg(feature = "native")]
+impl Task for GenerateTask {
+    type Output = GenerateResult;
+    type JsValue = GenerateResult;
+
+    fn compute(&mut self) -> napi::Result<GenerateResult> {
+        let options = self.options.take().unwrap_or_default();
+        let template = options.template.clone();
+        let path = options.output_path.clone();
+        let generate = || {
+            cancel::with_token(&self.token, || {
+                templates::generate_project_from_template(options)
+            })
+        };
+        match self.listener.clone() {
+            Some(listener) => events::with_listener(listener, generate),
+            None => generate(),
+        }
+        .map_err(|e| {
+            self.error = Some(e.details(template.as_deref(), path.as_deref()));
+            napi::Error::from_reason(e.to_string())
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: GenerateResult) -> napi::Result<GenerateResult> {
+        Ok(output)
+    }
+
+    fn reject(&mut self, env: Env, err: napi::Error) -> napi::Result<GenerateResult> {
+        let Some(details) = self.error.take() else {
+            return Err(err);
+        };
+        let mut error = env.create_error(napi::Error::from_reason(details.message.clone()))?;
+        error.set_named_property("code", details.code)?;
+        error.set_named_property("template", details.template)?;
+        error.set_named_property("path", details.path)?;
+        error.set_named_property("cause", details.cause)?;
+        Err(napi::Error::from(error.into_unknown()))
+    }
+
+    fn finally(&mut self, env: Env) -> napi::Result<()> {
+        match self.prompt.take() {
+            Some(prompt) => prompt.release(env),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 事件式的生成会话：注册 `progress`、`log`、`prompt` 监听者后调用 generate，
+/// 缺少必填变量时发送 `prompt` 事件并等待 answer 给出的值
+#[cfg(feature = "native")]
+#[napi]
+pub struct GeneratorSession {
+    options: SessionOptions,
+    listeners: Arc<SessionListeners>,
+    token: Arc<Mutex<cancel::CancellationToken>>,
+}
+
+#[cfg(feature = "native")]
+#[napi]
+impl GeneratorSession {
+    #[napi(constructor)]
+    pub fn new(options: Option<SessionOptions>) -> Self {
+        Self {
+            options: options.unwrap_or_default(),
+            listeners: Arc::new(SessionListeners::new()),
+            token: Arc::default(),
+        }
+    }
+
+    /// 注册事件监听者
+    #[napi(
+        ts_args_type = "event: 'progress' | 'log' | 'prompt', listener: (payload: ProgressEvent | string | VariablePrompt) => void"
+    )]
+    pub fn on(&mut self, env: Env, event: String, listener: JsFunction) -> napi::Result<()> {
+        match event.as_str() {
+            "progress" => lock(&self.listeners.progress).push(threadsafe(&env, &listener)?),
+            "log" => lock(&self.listeners.log).push(threadsafe(&env, &listener)?),
+            "prompt" => lock(&self.listeners.prompt).push(threadsafe(&env, &listener)?),
+            _ => {
+                return Err(napi::Error::new(
+                    Status::InvalidArg,
+                    format!("Unknown event '{}' (expected progress, log or prompt)", event),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 回答 `prompt` 事件询问的变量，`value` 为空时按缺少变量报错
+    #[napi]
+    pub fn answer(&self, name: String, value: Option<String>) {
+        let _ = lock(&self.listeners.sender).send((name, value));
+    }
+
+    /// 生成项目，未设置的选项取会话的默认值
+    #[napi]
+    pub fn generate(&self, options: GenerateOptions) -> AsyncTask<GenerateTask> {
+        let token = cancel::CancellationToken::new();
+        *lock(&self.token) = token.clone();
+        AsyncTask::new(GenerateTask {
+            options: Some(self.with_defaults(options)),
+            token,
+            listener: Some(self.listeners.clone()),
+            prompt: None,
+            error: None,
+        })
+    }
+
+    /// 取消进行中的生成，等待回答的 prompt 随之结束
+    #[napi]
+    pub fn cancel(&self) {
+        lock(&self.token).cancel();
+    }
+}
+
+#[cfg(feature = "native")]
+impl GeneratorSession {
+    fn with_defaults(&self, options: GenerateOptions) -> GenerateOptions {
+        let defaults = &self.options;
+        let mut variables = defaults.variables.clone().unwrap_or_default();
+        variables.extend(options.variables.unwrap_or_default());
+        GenerateOptions {
+            output_path: options.output_path.or_else(|| defaults.output_path.clone()),
+            workspace_root: options
+                .workspace_root
+                .or_else(|| defaults.workspace_root.clone()),
+            variables: (!variables.is_empty()).then_some(variables),
+            timeouts: options.timeouts.or_else(|| defaults.timeouts.clone()),
+            ..options
+        }
+    }
+}
+
+/// 会话注册的 JS 监听者与 answer 发来的回答
+#[cfg(feature = "native")]
+struct SessionListeners {
+    progress: Mutex<Vec<ThreadsafeFunction<ProgressEvent, ErrorStrategy::Fatal>>>,
+    log: Mutex<Vec<ThreadsafeFunction<String, ErrorStrategy::Fatal>>>,
+    prompt: Mutex<Vec<ThreadsafeFunction<VariablePrompt, ErrorStrategy::Fatal>>>,
+    sender: Mutex<mpsc::Sender<(String, Option<String>)>>,
+    answers: Mutex<mpsc::Receiver<(String, Option<String>)>>,
+}
+
+#[cfg(feature = "native")]
+impl SessionListeners {
+    fn new() -> Self {
+        let (sender, answers) = mpsc::channel();
+        Self {
+            progress: Mutex::default(),
+            log: Mutex::default(),
+            prompt: Mutex::default(),
+            sender: Mutex::new(sender),
+            answers: Mutex::new(answers),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl events::Listener for SessionListeners {
+    fn event(&self, event: events::Event) {
+        match event {
+            events::Event::Progress { phase } => {
+                for listener in lock(&self.progress).iter() {
+                    listener.call(ProgressEvent { phase: phase.clone() }, ThreadsafeFunctionCallMode::NonBlocking);
+                }
+            }
+            events::Event::Log { message } => {
+                for listener in lock(&self.log).iter() {
+                    listener.call(message.clone(), ThreadsafeFunctionCallMode::NonBlocking);
+                }
+            }
+        }
+    }
+
+    fn prompt(&self, variable: &template_registry::TemplateVariable) -> Result<Option<String>> {
+        let answers = lock(&self.answers);
+        // 丢弃之前生成中未被取走的回答
+        while answers.try_recv().is_ok() {}
+        {
+            let listeners = lock(&self.prompt);
+            if listeners.is_empty() {
+                return Ok(None);
+            }
+            for listener in listeners.iter() {
+                listener.call(VariablePrompt::from(variable), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+        loop {
+            cancel::check()?;
+            match answers.recv_timeout(std::time::Duration::from_millis(50)) {
+                Ok((name, value)) if name == variable.name => return Ok(value),
+                Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(None),
+            }
+        }
+    }
+}
+
+/// 把 JS 函数包装为可从工作线程调用的监听者，不阻止 Node 进程退出
+#[cfg(feature = "native")]
+fn threadsafe<T: 'static + ToNapiValue>(
+    env: &Env,
+    listener: &JsFunction,
+) -> napi::Result<ThreadsafeFunction<T, ErrorStrategy::Fatal>> {
+    let mut function =
+        listener.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<T>| Ok(vec![ctx.value]))?;
+    function.unref(env)?;
+    Ok(function)
+}
+
+/// 获取锁，之前持有锁的线程 panic 时仍继续使用其中的数据
+#[cfg(feature = "native")]
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 批量生成多个项目
+#[cfg(feature = "native")]
+#[napi]
+pub fn generate_projects(
+    projects: Vec<GenerateOptions>,
+    options: Option<BatchOptions>,
+) -> napi::Result<Vec<GenerateResult>, ErrorCode> {
+    batch::generate_projects(projects, &options.unwrap_or_default()).map_err(Into::into)
+}
+
+/// 按 JSON 清单文件批量生成项目
+#[cfg(feature = "native")]
+#[napi]
+pub fn generate_projects_from_manifest(manifest_path: String) -> napi::Result<Vec<GenerateResult>, ErrorCode> {
+    batch::generate_from_manifest(std::path::Path::new(&manifest_path))
+        .map_err(|e| e.into_napi(None, Some(&manifest_path)))
+}
+
+/// 执行声明式蓝图文件（generator.yaml），已生成的项目按锁文件更新
+#[cfg(feature = "native")]
+#[napi]
+pub fn apply_blueprint(path: String) -> napi::Result<Vec<BlueprintProjectResult>, ErrorCode> {
+    blueprint::apply_blueprint(std::path::Path::new(&path))
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 在已有项目中生成组件
+#[cfg(feature = "native")]
+#[napi]
+pub fn generate_component(options: ComponentOptions) -> napi::Result<GenerateResult, ErrorCode> {
+    let path = options.project_path.clone();
+    templates::generate_component(options).map_err(|e| e.into_napi(None, path.as_deref()))
+}
+
+/// 列出项目类型可用的组件子生成器
+#[cfg(feature = "native")]
+#[napi]
+pub fn list_components(project_type: String) -> napi::Result<Vec<String>, ErrorCode> {
+    templates::list_components(&project_type).map_err(Into::into)
+}
+
+/// 按 .generator.lock 重新渲染模板并三方合并到已生成的项目
+#[cfg(feature = "native")]
+#[napi]
+pub fn update_project(options: UpdateOptions) -> napi::Result<UpdateResult, ErrorCode> {
+    let path = options.project_path.clone();
+    update::update_project(options).map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 列出按选项生成项目时会执行的外部命令（java、git、构建工具），不执行命令也不写入输出目录
+#[cfg(feature = "native")]
+#[napi]
+pub fn audit_generation(options: GenerateOptions) -> napi::Result<Vec<AuditedCommand>, ErrorCode> {
+    let template = options.template.clone();
+    command_audit::audit_generation(options).map_err(|e| e.into_napi(template.as_deref(), None))
+}
+
+/// 列出执行蓝图时会运行的外部命令与后置步骤，不执行命令也不写入项目目录
+#[cfg(feature = "native")]
+#[napi]
+pub fn audit_blueprint(path: String) -> napi::Result<Vec<AuditedCommand>, ErrorCode> {
+    blueprint::audit_blueprint(std::path::Path::new(&path))
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 预览模板渲染结果与已有目录的差异，不写入任何文件
+#[cfg(feature = "native")]
+#[napi]
+pub fn preview_diff(
+    options: GenerateOptions,
+    target_dir: String,
+) -> napi::Result<Vec<FileDiff>, ErrorCode> {
+    let template = options.template.clone();
+    preview::preview_diff(options, std::path::Path::new(&target_dir))
+        .map_err(|e| e.into_napi(template.as_deref(), Some(&target_dir)))
+}
+
+/// 读取项目的 .generator.lock，获取模板来源等信息
+#[cfg(feature = "native")]
+#[napi]
+pub fn read_project_lock(path: String) -> napi::Result<ProjectLockInfo, ErrorCode> {
+    lockfile::ProjectLock::read(std::path::Path::new(&path))
+        .map(Into::into)
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 按 .generator.lock 中的文件哈希校验项目，报告生成后被修改或删除的文件
+#[cfg(feature = "native")]
+#[napi]
+pub fn verify_project(path: String) -> napi::Result<VerifyResult, ErrorCode> {
+    lockfile::verify_project(std::path::Path::new(&path))
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 生成或刷新已有 Java 项目的 Maven / Gradle wrapper，返回写入的文件
+#[cfg(feature = "native")]
+#[napi]
+pub fn bootstrap_build_wrapper(project_path: String) -> napi::Result<Vec<String>, ErrorCode> {
+    build_wrapper::bootstrap(std::path::Path::new(&project_path))
+        .map(|files| {
+            files
+                .iter()
+                .map(|file| file.to_string_lossy().replace('\\', "/"))
+                .collect()
+        })
+        .map_err(|e| e.into_napi(None, Some(&project_path)))
+}
+
+/// 检测 Java 环境，供前端在选择 Java 模板前判断是否可用
+#[cfg(feature = "native")]
+#[napi]
+pub fn detect_java_environment() -> JavaEnvironmentInfo {
+    match java_cli::detect_java() {
+        Ok(java_env) => java_env.into(),
+        Err(e) => JavaEnvironmentInfo {
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// 检测 Python 解释器，供前端在选择 Python 模板前判断是否可用
+#[cfg(feature = "native")]
+#[napi]
+pub fn detect_python_environment() -> PythonEnvironmentInfo {
+    match python_env::detect_python() {
+        Ok(python) => python.into(),
+        Err(e) => PythonEnvironmentInfo {
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// 检测 Node.js、npm、pnpm、yarn、bun 的版本
+#[cfg(feature = "native")]
+#[napi]
+pub fn detect_node_environment() -> NodeEnvironmentInfo {
+    node_env::detect_node_environment()
+}
+
+/// 诊断生成器的运行环境（Java、Android SDK、Python、Node.js、git、缓存目录、注册表、Java CLI jar 包）
+#[cfg(feature = "native")]
+#[napi]
+pub fn run_diagnostics() -> DoctorReport {
+    doctor::run_diagnostics()
+}
+
+/// 创建模板骨架，返回创建的文件
+#[cfg(feature = "native")]
+#[napi]
+pub fn create_template(options: NewTemplateOptions) -> napi::Result<Vec<String>, ErrorCode> {
+    scaffold::create_template(&options)
+        .map_err(|e| e.into_napi(Some(&options.name), options.path.as_deref()))
+}
+
+/// 将 Backstage 软件模板导入为本工具的模板目录
+#[cfg(feature = "native")]
+#[napi]
+pub fn import_backstage_template(
+    options: BackstageImportOptions,
+) -> napi::Result<BackstageImportResult, ErrorCode> {
+    backstage::import_template(&options)
+        .map_err(|e| e.into_napi(None, Some(&options.source)))
+}
+
+/// 校验模板目录，返回诊断列表
+#[cfg(feature = "native")]
+#[napi]
+pub fn validate_template(path: String) -> napi::Result<Vec<Diagnostic>, ErrorCode> {
+    template_lint::validate_template(std::path::Path::new(&path))
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 使用 fixture 试渲染模板，返回诊断列表
+#[cfg(feature = "native")]
+#[napi]
+pub fn test_template(
+    path: String,
+    fixtures: Option<Vec<TemplateFixture>>,
+) -> napi::Result<Vec<Diagnostic>, ErrorCode> {
+    template_harness::test_template(std::path::Path::new(&path), &fixtures.unwrap_or_default())
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 将模板目录打包为 `.gentpl` 文件，返回包路径
+#[cfg(feature = "native")]
+#[napi]
+pub fn pack_template(path: String, output: Option<String>) -> napi::Result<String, ErrorCode> {
+    bundle::pack_template(
+        std::path::Path::new(&path),
+        output.as_deref().map(std::path::Path::new),
+    )
+    .map(|bundle| bundle.to_string_lossy().to_string())
+    .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 校验 `.gentpl` 模板包并安装到本地注册表（默认 ./templates），返回安装目录
+#[cfg(feature = "native")]
+#[napi]
+pub fn install_template(
+    bundle: String,
+    registry_dir: Option<String>,
+    force: Option<bool>,
+) -> napi::Result<String, ErrorCode> {
+    let registry_dir = registry_dir.unwrap_or_else(|| "templates".to_string());
+    bundle::install_template(
+        std::path::Path::new(&bundle),
+        std::path::Path::new(&registry_dir),
+        force.unwrap_or(false),
+    )
+    .map(|dir| dir.to_string_lossy().to_string())
+    .map_err(|e| e.into_napi(None, Some(&bundle)))
+}
+
+/// 发布模板到 `~/.generator/registries.json` 中的注册表，`bump` 为 major / minor / patch 时先提升版本
+#[cfg(feature = "native")]
+#[napi]
+pub fn publish_template(
+    path: String,
+    registry_name: String,
+    bump: Option<String>,
+) -> napi::Result<PublishResult, ErrorCode> {
+    publish::publish_template(std::path::Path::new(&path), &registry_name, bump.as_deref())
+        .map_err(|e| e.into_napi(None, Some(&path)))
+}
+
+/// 获取 template.json 的 JSON Schema
+#[cfg(feature = "native")]
+#[napi]
+pub fn get_template_schema() -> napi::Result<String, ErrorCode> {
+    template_registry::template_metadata_schema().map_err(Into::into)
 }
 
 /// 列出可用模板
+#[cfg(feature = "native")]
 #[napi]
-pub fn list_templates(project_type: String) -> napi::Result<Vec<String>> {
+pub fn list_templates(project_type: String) -> napi::Result<Vec<String>, ErrorCode> {
     templates::list_templates_by_type(&project_type).map_err(Into::into)
 }
 
+/// 列出当前环境（Java、Node.js、操作系统）可用的模板，`include_incompatible` 时一并返回不兼容的模板及原因
+#[cfg(feature = "native")]
+#[napi]
+pub fn list_compatible_templates(
+    project_type: String,
+    include_incompatible: Option<bool>,
+) -> napi::Result<Vec<TemplateCompatibility>, ErrorCode> {
+    compat::list_compatible_templates(&project_type, include_incompatible.unwrap_or(false))
+        .map_err(Into::into)
+}
+
+/// 读取模板的变更日志（CHANGELOG.md 或 git 标签之间的提交），`range` 为 semver 版本要求
+#[cfg(feature = "native")]
+#[napi]
+pub fn get_template_changelog(
+    project_type: String,
+    template: String,
+    range: Option<String>,
+) -> napi::Result<Vec<ChangelogEntry>, ErrorCode> {
+    changelog::template_changelog(&project_type, &template, range.as_deref())
+        .map_err(|e| e.into_napi(Some(&template), None))
+}
+
+/// 只读取本地模板索引列出模板，不扫描注册表也不访问网络，适合自动补全与界面启动时调用
+#[cfg(feature = "native")]
+#[napi]
+pub fn list_templates_sync(project_type: String) -> napi::Result<Vec<String>, ErrorCode> {
+    templates::list_templates_cached(&project_type).map_err(Into::into)
+}
+
+/// 从内置说明或本地模板索引读取模板信息，找不到时返回空
+#[cfg(feature = "native")]
+#[napi]
+pub fn get_template_info_sync(
+    project_type: String,
+    template: String,
+) -> napi::Result<Option<String>, ErrorCode> {
+    templates::get_template_info_cached(&project_type, &template)
+        .map_err(|e| e.into_napi(Some(&template), None))
+}
+
 /// 获取模板信息
+#[cfg(feature = "native")]
+#[napi]
+pub fn get_template_info(project_type: String, template: String) -> napi::Result<String, ErrorCode> {
+    templates::get_template_info(&project_type, &template)
+        .map_err(|e| e.into_napi(Some(&template), None))
+}
+
+/// 列出模板可用特性
+#[cfg(feature = "native")]
+#[napi]
+pub fn list_template_features(
+    project_type: String,
+    template: String,
+) -> napi::Result<Vec<TemplateFeatureInfo>, ErrorCode> {
+    templates::list_template_features(&project_type, &template)
+        .map_err(|e| e.into_napi(Some(&template), None))
+}
+
+/// 读取本地的模板使用统计
+#[cfg(feature = "native")]
+#[napi]
+pub fn get_usage_stats() -> napi::Result<UsageStats, ErrorCode> {
+    usage::UsageLog::default().stats().map_err(Into::into)
+}
+
+/// 开启或关闭本地使用记录；`endpoint` 为可选的上报地址
+#[cfg(feature = "native")]
+#[napi]
+pub fn set_usage_logging(enabled: bool, endpoint: Option<String>) -> napi::Result<(), ErrorCode> {
+    usage::UsageLog::default()
+        .set_config(&usage::UsageConfig { enabled, endpoint })
+        .map_err(Into::into)
+}
+
+/// 读取远程模板缓存的统计信息
+#[cfg(feature = "native")]
+#[napi]
+pub fn get_cache_stats() -> napi::Result<CacheStats, ErrorCode> {
+    template_registry::TemplateRegistryConfig::user()
+        .and_then(|config| config.content_cache().stats())
+        .map_err(Into::into)
+}
+
+/// 刷新本地模板索引；`force` 为真时重新解析所有模板
+#[cfg(feature = "native")]
+#[napi]
+pub fn refresh_index(force: Option<bool>) -> napi::Result<IndexStats, ErrorCode> {
+    template_registry::TemplateRegistryConfig::user()
+        .and_then(|config| {
+            template_index::TemplateIndex::from_config(&config).refresh(force.unwrap_or(false))
+        })
+        .map(|(_, stats)| stats)
+        .map_err(Into::into)
+}
+
+/// 取出最近一次抛出错误的结构化详情（错误码、模板、路径、底层原因）
+#[cfg(feature = "native")]
 #[napi]
-pub fn get_template_info(project_type: String, template: String) -> napi::Result<String> {
-    templates::get_template_info(&project_type, &template).map_err(Into::into)
+pub fn take_last_error() -> Option<ErrorDetails> {
+    error::take_last_error()
 }
 
 /// 交互式 CLI
+#[cfg(feature = "native")]
 #[napi]
-pub fn run_gen_cli() -> napi::Result<GenerateResult> {
-    let cli= cli::GenCli::new();
+pub fn run_gen_cli(options: Option<CliRunOptions>) -> napi::Result<GenerateResult, ErrorCode> {
+    let cli = cli::GenCli::with_options(options.unwrap_or_default())?;
     cli.run_interactive().map_err(Into::into)
 }
 
 /// 显示 CLI 帮助信息
+#[cfg(feature = "native")]
 #[napi]
-pub fn show_gen_cli_help() -> napi::Result<String> {
+pub fn show_gen_cli_help() -> napi::Result<String, ErrorCode> {
     let cli = cli::GenCli::new();
     cli.show_help();
-    Ok("帮助信息已显示".to_string())
+    Ok(t!("main.help_shown"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_get_template_info() {
@@ -90,4 +1691,104 @@ mod tests {
         let result_1 = list_templates("vue".to_string());
         println!("Templates: {:?}", result_1);
     }
+
+    #[test]
+    fn test_wait_prompt_reply() {
+        let (reply, answer) = mpsc::channel();
+        reply.send(Ok(Some("Ada".to_string()))).unwrap();
+        assert_eq!(
+            wait_prompt_reply("author", &answer).unwrap(),
+            Some("Ada".to_string())
+        );
+
+        reply.send(Err("dialog closed".to_string())).unwrap();
+        let error = wait_prompt_reply("author", &answer).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown error: Prompt for 'author' failed: dialog closed"
+        );
+
+        // 处理函数被释放而没有回答
+        drop(reply);
+        assert_eq!(wait_prompt_reply("author", &answer).unwrap(), None);
+
+        let (_reply, answer) = mpsc::channel();
+        let token = cancel::CancellationToken::new();
+        token.cancel();
+        assert!(matches!(
+            cancel::with_token(&token, || wait_prompt_reply("author", &answer)),
+            Err(GeneratorError::Cancelled)
+        ));
+    }
+
+    /// 按变量名回答询问并记录交给处理函数的询问，代替 JS 的 onPrompt 函数
+    struct PromptAnswers {
+        answers: HashMap<&'static str, &'static str>,
+        asked: Mutex<Vec<VariablePrompt>>,
+    }
+
+    impl events::Listener for PromptAnswers {
+        fn event(&self, _event: events::Event) {}
+
+        fn prompt(&self, variable: &template_registry::TemplateVariable) -> Result<Option<String>> {
+            lock(&self.asked).push(VariablePrompt::from(variable));
+            Ok(self
+                .answers
+                .get(variable.name.as_str())
+                .map(|value| value.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_prompt_answers_missing_variables_during_generation() {
+        let template_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(pipeline::TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "node-lib", "dependencies": [], "tags": [],
+                "variables": [
+                    {"name": "author", "description": "", "default": null, "required": true,
+                     "var_type": "string"},
+                    {"name": "license", "description": "", "default": null, "required": true,
+                     "var_type": {"choice": {"options": ["MIT", "Apache-2.0"]}}}]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.path().join("README.md"),
+            "{{author}} / {{license}}",
+        )
+        .unwrap();
+        let generate = |answers: &[(&'static str, &'static str)]| {
+            let listener = Arc::new(PromptAnswers {
+                answers: answers.iter().copied().collect(),
+                asked: Mutex::new(Vec::new()),
+            });
+            let result = events::with_listener(listener.clone(), || {
+                templates::generate_project_from_template(GenerateOptions {
+                    name: "demo-lib".to_string(),
+                    project_type: "node-lib".to_string(),
+                    template: Some(template_dir.path().to_string_lossy().to_string()),
+                    output_path: Some(output_dir.path().to_string_lossy().to_string()),
+                    ..Default::default()
+                })
+            });
+            let asked = lock(&listener.asked).clone();
+            (result, asked)
+        };
+
+        // 没有回答的必填变量按缺少变量报错
+        let (result, asked) = generate(&[("author", "Ada")]);
+        assert_eq!(result.unwrap_err().code(), "INVALID_VARIABLES");
+        assert_eq!(asked.len(), 2);
+        assert_eq!(asked[1].var_type, "choice");
+        assert_eq!(asked[1].choices, ["MIT", "Apache-2.0"]);
+
+        let (result, _) = generate(&[("author", "Ada"), ("license", "mit")]);
+        result.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output_dir.path().join("demo-lib/README.md")).unwrap(),
+            "Ada / MIT"
+        );
+    }
 }
@@ -0,0 +1,290 @@
+//! 许可证生成模块
+//! 为生成的项目写入 LICENSE 文件，并把 SPDX 标识写入 package.json 与 pom.xml
+
+use crate::{GeneratorError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 许可证文件名
+pub const LICENSE_FILE: &str = "LICENSE";
+
+/// 支持的许可证
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum License {
+    Mit,
+    Apache2,
+    Gpl3,
+    /// 专有软件，保留所有权利
+    Proprietary,
+}
+
+impl std::str::FromStr for License {
+    type Err = GeneratorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mit" => Ok(License::Mit),
+            "apache-2.0" | "apache2" | "apache" => Ok(License::Apache2),
+            "gpl-3.0" | "gpl-3.0-only" | "gpl3" | "gpl" => Ok(License::Gpl3),
+            "proprietary" | "unlicensed" => Ok(License::Proprietary),
+            other => Err(GeneratorError::Configuration(format!(
+                "Unknown license '{}', expected MIT, Apache-2.0, GPL-3.0 or proprietary",
+                other
+            ))),
+        }
+    }
+}
+
+impl License {
+    /// 写入 package.json 的 SPDX 标识，专有软件按 npm 约定为 `UNLICENSED`
+    pub fn spdx(self) -> &'static str {
+        match self {
+            License::Mit => "MIT",
+            License::Apache2 => "Apache-2.0",
+            License::Gpl3 => "GPL-3.0-only",
+            License::Proprietary => "UNLICENSED",
+        }
+    }
+
+    /// pom.xml 中 `<license>` 的名称与链接
+    fn maven(self) -> (&'static str, Option<&'static str>) {
+        match self {
+            License::Mit => ("MIT", Some("https://opensource.org/licenses/MIT")),
+            License::Apache2 => (
+                "Apache-2.0",
+                Some("https://www.apache.org/licenses/LICENSE-2.0"),
+            ),
+            License::Gpl3 => (
+                "GPL-3.0-only",
+                Some("https://www.gnu.org/licenses/gpl-3.0.html"),
+            ),
+            License::Proprietary => ("Proprietary", None),
+        }
+    }
+
+    /// LICENSE 文件内容
+    ///
+    /// Apache-2.0 与 GPL-3.0 使用许可证附录推荐的版权声明，全文通过链接引用
+    pub fn text(self, holder: &str, year: i32) -> String {
+        let template = match self {
+            License::Mit => MIT_TEXT,
+            License::Apache2 => APACHE2_TEXT,
+            License::Gpl3 => GPL3_TEXT,
+            License::Proprietary => PROPRIETARY_TEXT,
+        };
+        template
+            .replace("{year}", &year.to_string())
+            .replace("{holder}", holder)
+    }
+}
+
+/// 写入 LICENSE 并同步 package.json 与 pom.xml 的许可证声明，返回改动的文件（相对项目目录）
+pub fn apply_license(
+    project_dir: &Path,
+    license: License,
+    holder: &str,
+    year: i32,
+) -> Result<Vec<PathBuf>> {
+    fs::write(project_dir.join(LICENSE_FILE), license.text(holder, year))?;
+    let mut changed = vec![PathBuf::from(LICENSE_FILE)];
+
+    let package_json = project_dir.join("package.json");
+    if package_json.is_file() {
+        let mut document: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&package_json)?)?;
+        let Some(object) = document.as_object_mut() else {
+            return Err(GeneratorError::TemplateProcessing(
+                "package.json is not a JSON object".to_string(),
+            ));
+        };
+        object.insert(
+            "license".to_string(),
+            serde_json::Value::String(license.spdx().to_string()),
+        );
+        let mut content = serde_json::to_string_pretty(&document)?;
+        content.push('\n');
+        fs::write(&package_json, content)?;
+        changed.push(PathBuf::from("package.json"));
+    }
+
+    let pom = project_dir.join("pom.xml");
+    if pom.is_file() {
+        let content = fs::read_to_string(&pom)?;
+        fs::write(&pom, inject_maven_license(&content, license))?;
+        changed.push(PathBuf::from("pom.xml"));
+    }
+
+    Ok(changed)
+}
+
+/// 在 pom.xml 中写入 `<licenses>`：替换已有的声明，否则插入到依赖与构建配置之前
+fn inject_maven_license(pom: &str, license: License) -> String {
+    let (start, end, indent) = match (pom.find("<licenses>"), pom.find("</licenses>")) {
+        (Some(start), Some(end)) if start < end => {
+            let start = line_start(pom, start);
+            (
+                start,
+                end + "</licenses>".len(),
+                leading_whitespace(&pom[start..]),
+            )
+        }
+        _ => {
+            let anchor = [
+                "<properties>",
+                "<dependencyManagement>",
+                "<dependencies>",
+                "<build>",
+            ]
+            .iter()
+            .filter_map(|tag| pom.find(tag))
+            .min();
+            match anchor {
+                Some(position) => {
+                    let start = line_start(pom, position);
+                    (start, start, leading_whitespace(&pom[start..]))
+                }
+                None => {
+                    let Some(position) = pom.rfind("</project>") else {
+                        return pom.to_string();
+                    };
+                    let start = line_start(pom, position);
+                    let indent = format!("{}    ", leading_whitespace(&pom[start..]));
+                    (start, start, indent)
+                }
+            }
+        }
+    };
+
+    // `<project>` 的直接子元素缩进一级，沿用它作为缩进单位
+    let unit = if indent.is_empty() { "    " } else { &indent };
+    let (name, url) = license.maven();
+    let mut block = format!(
+        "{indent}<licenses>\n{indent}{unit}<license>\n{indent}{unit}{unit}<name>{name}</name>\n"
+    );
+    if let Some(url) = url {
+        block.push_str(&format!("{indent}{unit}{unit}<url>{url}</url>\n"));
+    }
+    block.push_str(&format!("{indent}{unit}</license>\n{indent}</licenses>"));
+    if start == end {
+        // 插入到锚点所在行之前
+        block.push('\n');
+    }
+
+    format!("{}{}{}", &pom[..start], block, &pom[end..])
+}
+
+fn line_start(text: &str, position: usize) -> usize {
+    text[..position].rfind('\n').map_or(0, |index| index + 1)
+}
+
+fn leading_whitespace(line: &str) -> String {
+    line.chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+const MIT_TEXT: &str = "MIT License
+
+Copyright (c) {year} {holder}
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the \"Software\"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+";
+
+const APACHE2_TEXT: &str = "Copyright {year} {holder}
+
+Licensed under the Apache License, Version 2.0 (the \"License\");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an \"AS IS\" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+";
+
+const GPL3_TEXT: &str = "Copyright (C) {year} {holder}
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, version 3 of the License.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+";
+
+const PROPRIETARY_TEXT: &str = "Copyright (c) {year} {holder}. All rights reserved.
+
+This software and its documentation are proprietary and confidential.
+Unauthorized copying, modification, distribution or use of this software,
+via any medium, is strictly prohibited without the prior written permission
+of {holder}.
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_apply_license() {
+        let project = tempdir().unwrap();
+        fs::write(
+            project.path().join("package.json"),
+            r#"{"name": "app", "license": "ISC"}"#,
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("pom.xml"),
+            "<project>\n  <artifactId>app</artifactId>\n  <dependencies>\n  </dependencies>\n</project>\n",
+        )
+        .unwrap();
+
+        let license: License = "apache-2.0".parse().unwrap();
+        let changed = apply_license(project.path(), license, "Acme Inc.", 2026).unwrap();
+        assert_eq!(changed.len(), 3);
+
+        let read = |path: &str| fs::read_to_string(project.path().join(path)).unwrap();
+        assert!(read(LICENSE_FILE).starts_with("Copyright 2026 Acme Inc.\n"));
+        let package: serde_json::Value = serde_json::from_str(&read("package.json")).unwrap();
+        assert_eq!(package["license"], "Apache-2.0");
+        assert_eq!(
+            read("pom.xml"),
+            "<project>\n  <artifactId>app</artifactId>\n  <licenses>\n    <license>\n      \
+             <name>Apache-2.0</name>\n      \
+             <url>https://www.apache.org/licenses/LICENSE-2.0</url>\n    </license>\n  \
+             </licenses>\n  <dependencies>\n  </dependencies>\n</project>\n"
+        );
+
+        // 再次生成时替换已有的声明
+        apply_license(project.path(), License::Proprietary, "Acme Inc.", 2026).unwrap();
+        let pom = read("pom.xml");
+        assert_eq!(pom.matches("<licenses>").count(), 1);
+        assert!(pom.contains("<name>Proprietary</name>"));
+        assert!(!pom.contains("<url>"));
+        assert!("BSD".parse::<License>().is_err());
+    }
+}
@@ -0,0 +1,403 @@
+//! 项目锁文件模块
+//! 在生成的项目中记录所用模板、版本与变量（`.generator.lock`），并保存模板原始渲染快照供后续更新做三方合并
+
+use crate::utils::copy_dir_recursive;
+use crate::{GeneratorError, Result, VerifyResult, vcs};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 锁文件名
+pub const LOCK_FILE_NAME: &str = ".generator.lock";
+
+/// 生成器元数据目录
+pub const GENERATOR_DIR: &str = ".generator";
+
+/// 模板原始渲染快照目录（相对项目根目录）
+pub const BASE_SNAPSHOT_DIR: &str = ".generator/base";
+
+/// 项目锁文件内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectLock {
+    /// 项目名称
+    pub name: String,
+    /// 项目类型
+    pub project_type: String,
+    /// 模板名称
+    pub template: String,
+    /// 模板版本
+    pub template_version: String,
+    /// 生成时使用的变量
+    pub variables: BTreeMap<String, String>,
+    /// 启用的特性
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// 模板来源
+    #[serde(default)]
+    pub source: LockSource,
+    /// 生成器版本
+    #[serde(default)]
+    pub generator_version: String,
+    /// 生成时间（RFC 3339）
+    #[serde(default)]
+    pub generated_at: String,
+    /// 生成 API 桩代码所用的 OpenAPI 规范
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub openapi: Option<OpenApiLock>,
+    /// 生成的文件及其 SHA-256（相对项目根目录）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+}
+
+/// OpenAPI 规范信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenApiLock {
+    /// 规范的原始路径或 URL
+    pub source: String,
+    /// 项目中保存的规范副本（相对项目根目录）
+    pub file: String,
+    /// 规范内容的 SHA-256
+    pub sha256: String,
+    /// 生成模式 (client, server)
+    pub mode: String,
+}
+
+/// 模板来源信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockSource {
+    /// 来源类型 (local, git, http, npm)
+    pub kind: String,
+    /// 来源位置（本地路径、git URL、npm 包名等）
+    pub location: String,
+    /// 确切修订（git commit、npm 版本、http 校验和）
+    #[serde(default)]
+    pub revision: Option<String>,
+}
+
+impl LockSource {
+    /// 本地模板来源；模板位于 git 仓库中时记录远程地址与当前提交
+    pub fn from_local(template_dir: &Path) -> Self {
+        match vcs::head_info(template_dir) {
+            Some(head) => Self {
+                kind: "git".to_string(),
+                location: head
+                    .remote_url
+                    .unwrap_or_else(|| template_dir.display().to_string()),
+                revision: Some(head.commit),
+            },
+            None => Self {
+                kind: "local".to_string(),
+                location: template_dir.display().to_string(),
+                revision: None,
+            },
+        }
+    }
+
+    /// 确切修订是否与固定值一致；git 提交可以用不短于 7 位的前缀固定
+    pub fn matches_pin(&self, pin: &str) -> bool {
+        let pin = pin.trim();
+        self.revision.as_deref().is_some_and(|revision| {
+            revision == pin
+                || (self.kind == "git"
+                    && pin.len() >= 7
+                    && pin.bytes().all(|b| b.is_ascii_hexdigit())
+                    && revision.starts_with(&pin.to_ascii_lowercase()))
+        })
+    }
+}
+
+impl ProjectLock {
+    /// 填充生成器版本与当前时间
+    pub fn stamp(mut self) -> Self {
+        self.generator_version = env!("CARGO_PKG_VERSION").to_string();
+        self.generated_at = chrono::Utc::now().to_rfc3339();
+        self
+    }
+
+    /// 是否与另一次生成使用相同的模板、变量、特性与生成器版本
+    ///
+    /// `volatile` 中的变量（如按当前年份提供的 `year`）不参与比较。
+    pub fn same_inputs(&self, other: &Self, volatile: &[String]) -> bool {
+        fn stable<'a>(
+            variables: &'a BTreeMap<String, String>,
+            volatile: &[String],
+        ) -> Vec<(&'a String, &'a String)> {
+            variables
+                .iter()
+                .filter(|(name, _)| !volatile.contains(name))
+                .collect()
+        }
+        self.name == other.name
+            && self.project_type == other.project_type
+            && self.template == other.template
+            && self.template_version == other.template_version
+            && stable(&self.variables, volatile) == stable(&other.variables, volatile)
+            && self.features == other.features
+            && self.source == other.source
+            && self.generator_version == other.generator_version
+            && self.openapi == other.openapi
+    }
+
+    /// 项目目录中已有由相同输入生成、且文件与清单一致的输出时返回其锁文件
+    pub fn find_unchanged(&self, project_dir: &Path, volatile: &[String]) -> Option<Self> {
+        let existing = Self::read(project_dir).ok()?;
+        let unchanged = !existing.files.is_empty()
+            && self.same_inputs(&existing, volatile)
+            && existing.changed_files(project_dir) == (Vec::new(), Vec::new());
+        unchanged.then_some(existing)
+    }
+
+    /// 与文件清单不一致的文件，返回（被修改的，被删除的）
+    pub fn changed_files(&self, project_dir: &Path) -> (Vec<String>, Vec<String>) {
+        let mut modified = Vec::new();
+        let mut missing = Vec::new();
+        for (file, hash) in &self.files {
+            let path = project_dir.join(file);
+            if !path.is_file() {
+                missing.push(file.clone());
+            } else if file_hash(&path).ok().as_ref() != Some(hash) {
+                modified.push(file.clone());
+            }
+        }
+        (modified, missing)
+    }
+
+    /// 读取项目目录下的锁文件
+    pub fn read(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(LOCK_FILE_NAME);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            GeneratorError::FileOperation(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 写入锁文件
+    pub fn write(&self, project_dir: &Path) -> Result<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs::write(project_dir.join(LOCK_FILE_NAME), content)?;
+        Ok(())
+    }
+}
+
+/// 项目中模板快照目录
+pub fn base_snapshot_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join(BASE_SNAPSHOT_DIR)
+}
+
+/// 将刚渲染的文件保存为快照
+pub fn write_base_snapshot(project_dir: &Path, files: &[PathBuf]) -> Result<()> {
+    let snapshot = base_snapshot_dir(project_dir);
+    if snapshot.exists() {
+        fs::remove_dir_all(&snapshot)?;
+    }
+
+    for relative in files {
+        let src = project_dir.join(relative);
+        let dest = snapshot.join(relative);
+        if src.is_dir() {
+            copy_dir_recursive(&src, &dest)
+                .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        } else if src.exists() {
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&src, &dest)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 内容的 SHA-256
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// 文件内容的 SHA-256
+pub fn file_hash(path: &Path) -> Result<String> {
+    Ok(content_hash(&fs::read(path)?))
+}
+
+/// 按锁文件中的哈希清单校验项目，报告生成后被修改或删除的文件
+pub fn verify_project(project_dir: &Path) -> Result<VerifyResult> {
+    let lock = ProjectLock::read(project_dir)?;
+    if lock.files.is_empty() {
+        return Err(GeneratorError::Configuration(format!(
+            "{} in {} has no file manifest; regenerate the project to record file hashes",
+            LOCK_FILE_NAME,
+            project_dir.display()
+        )));
+    }
+    let (modified, missing) = lock.changed_files(project_dir);
+    Ok(VerifyResult {
+        clean: modified.is_empty() && missing.is_empty(),
+        modified,
+        missing,
+        checked: lock.files.len() as u32,
+    })
+}
+
+/// 计算生成文件的哈希清单，目录递归展开，生成器自身的元数据不计入
+pub fn file_manifest(project_dir: &Path, files: &[PathBuf]) -> Result<BTreeMap<String, String>> {
+    let mut manifest = BTreeMap::new();
+    for relative in files.iter().filter(|file| project_dir.join(file).exists()) {
+        for entry in WalkDir::new(project_dir.join(relative)) {
+            let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(path) = entry.path().strip_prefix(project_dir) else {
+                continue;
+            };
+            if !is_generator_file(path) {
+                manifest.insert(
+                    path.to_string_lossy().replace('\\', "/"),
+                    file_hash(entry.path())?,
+                );
+            }
+        }
+    }
+    Ok(manifest)
+}
+
+/// 判断相对路径是否属于生成器自身的元数据
+pub fn is_generator_file(relative: &Path) -> bool {
+    relative == Path::new(LOCK_FILE_NAME) || relative.starts_with(GENERATOR_DIR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_records_provenance() {
+        let template_dir = tempdir().unwrap();
+        let project_dir = tempdir().unwrap();
+
+        let lock = ProjectLock {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: "basic".to_string(),
+            template_version: "1.0.0".to_string(),
+            variables: BTreeMap::from([("name".to_string(), "app".to_string())]),
+            features: vec![],
+            source: LockSource::from_local(template_dir.path()),
+            generator_version: String::new(),
+            generated_at: String::new(),
+            openapi: None,
+            files: BTreeMap::new(),
+        }
+        .stamp();
+        lock.write(project_dir.path()).unwrap();
+
+        let read = ProjectLock::read(project_dir.path()).unwrap();
+        assert_eq!(read.source.kind, "local");
+        assert_eq!(read.source.revision, None);
+        assert_eq!(read.generator_version, env!("CARGO_PKG_VERSION"));
+        assert!(chrono::DateTime::parse_from_rfc3339(&read.generated_at).is_ok());
+        assert_eq!(read.variables["name"], "app");
+    }
+
+    #[test]
+    fn test_find_unchanged_compares_inputs_and_manifest() {
+        let project_dir = tempdir().unwrap();
+        fs::create_dir_all(project_dir.path().join("src")).unwrap();
+        fs::write(project_dir.path().join("src/main.ts"), "console.log(1);\n").unwrap();
+        fs::write(project_dir.path().join("package.json"), "{}\n").unwrap();
+
+        let mut lock = ProjectLock {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: "basic".to_string(),
+            template_version: "1.0.0".to_string(),
+            variables: BTreeMap::new(),
+            features: vec![],
+            source: LockSource::default(),
+            generator_version: String::new(),
+            generated_at: String::new(),
+            openapi: None,
+            files: BTreeMap::new(),
+        }
+        .stamp();
+        let requested = lock.clone();
+        lock.files = file_manifest(
+            project_dir.path(),
+            &[PathBuf::from("src"), PathBuf::from("package.json")],
+        )
+        .unwrap();
+        assert_eq!(
+            lock.files.keys().collect::<Vec<_>>(),
+            vec!["package.json", "src/main.ts"]
+        );
+        lock.write(project_dir.path()).unwrap();
+        assert!(requested.find_unchanged(project_dir.path(), &[]).is_some());
+
+        let mut other = requested.clone();
+        other.features = vec!["docker".to_string()];
+        assert!(other.find_unchanged(project_dir.path(), &[]).is_none());
+
+        // 跨年后重复生成：year 随时间变化，不视为输入变化
+        let mut next_year = requested.clone();
+        next_year
+            .variables
+            .insert("year".to_string(), "2099".to_string());
+        let volatile = ["year".to_string()];
+        assert!(next_year.find_unchanged(project_dir.path(), &[]).is_none());
+        assert!(
+            next_year
+                .find_unchanged(project_dir.path(), &volatile)
+                .is_some()
+        );
+
+        fs::write(
+            project_dir.path().join("package.json"),
+            "{\"private\": true}\n",
+        )
+        .unwrap();
+        assert!(requested.find_unchanged(project_dir.path(), &[]).is_none());
+    }
+
+    #[test]
+    fn test_verify_project_reports_modified_and_missing_files() {
+        let project_dir = tempdir().unwrap();
+        for file in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(project_dir.path().join(file), file).unwrap();
+        }
+        let mut lock = ProjectLock {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: "basic".to_string(),
+            template_version: "1.0.0".to_string(),
+            variables: BTreeMap::new(),
+            features: vec![],
+            source: LockSource::default(),
+            generator_version: String::new(),
+            generated_at: String::new(),
+            openapi: None,
+            files: BTreeMap::new(),
+        };
+        lock.write(project_dir.path()).unwrap();
+        assert!(verify_project(project_dir.path()).is_err());
+
+        lock.files = file_manifest(
+            project_dir.path(),
+            &["a.txt", "b.txt", "c.txt"].map(PathBuf::from),
+        )
+        .unwrap();
+        lock.write(project_dir.path()).unwrap();
+        assert!(verify_project(project_dir.path()).unwrap().clean);
+
+        fs::write(project_dir.path().join("a.txt"), "edited").unwrap();
+        fs::remove_file(project_dir.path().join("c.txt")).unwrap();
+        let result = verify_project(project_dir.path()).unwrap();
+        assert!(!result.clean);
+        assert_eq!(result.modified, vec!["a.txt"]);
+        assert_eq!(result.missing, vec!["c.txt"]);
+        assert_eq!(result.checked, 3);
+    }
+}
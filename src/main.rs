@@ -2,14 +2,74 @@
 //! 可以通过 cargo run 直接执行
 
 // 使用库 crate
-use generator::{run_gen_cli, show_gen_cli_help};
+use generator::cancel;
+use generator::credentials::{Credential, FileCredentials, KeyringCredentials};
+use generator::daemon::{self, Daemon};
+use generator::dev::{self, DevSession};
+use generator::i18n;
+use generator::output::{self, OutputMode};
+use generator::{
+    BackstageImportOptions, CliRunOptions, Diagnostic, NewTemplateOptions, TemplateFixture,
+    apply_blueprint, audit_blueprint, create_template, generate_projects_from_manifest,
+    get_cache_stats, get_template_info, get_template_schema, get_usage_stats,
+    import_backstage_template, install_template, list_template_features,
+    list_templates, pack_template, publish_template, refresh_index, run_diagnostics, run_gen_cli,
+    say, set_usage_logging, show_gen_cli_help, t, test_template, validate_template,
+};
+use serde::Serialize;
+
+/// 支持的项目类型
+const PROJECT_TYPES: &[&str] = &["vue", "java", "node-lib", "rust", "monorepo"];
+
+/// `list` 命令的结果
+#[derive(Serialize)]
+struct TemplateList {
+    project_type: String,
+    templates: Vec<String>,
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 项目生成器 - Rust CLI 模式");
-    println!("==============================");
+    // 检查命令行参数，--json / --quiet 可以出现在任意位置
+    let mut args: Vec<String> = std::env::args().collect();
+    let json = take_flag(&mut args, "--json");
+    let quiet = take_flag(&mut args, "--quiet");
+    // serve 的 stdout 是 JSON-RPC 通道，日志与 JSON 模式一样写入 stderr
+    let mode = if json || args.get(1).is_some_and(|command| command == "serve") {
+        OutputMode::Json
+    } else if quiet {
+        OutputMode::Quiet
+    } else {
+        OutputMode::Human
+    };
+    output::set_mode(mode);
+    let lang = take_flag_value(&mut args, "--lang");
+    let resume = take_flag(&mut args, "--resume");
+    let frozen = take_flag(&mut args, "--frozen");
+    let delegate = take_flag(&mut args, "--delegate");
+    let python_venv = take_flag(&mut args, "--venv");
+    let serve_http_addr = take_flag_value(&mut args, "--serve-http");
+    match i18n::detect(lang.as_deref()) {
+        Ok(lang) => i18n::set_lang(lang),
+        Err(e) => fail(&t!("error.invalid_lang"), e),
+    }
 
-    // 检查命令行参数
-    let args: Vec<String> = std::env::args().collect();
+    // Ctrl-C 取消进行中的生成（终止子进程并回滚输出），再次按下立即退出
+    ctrlc::set_handler(|| {
+        let token = cancel::global();
+        if token.is_cancelled() {
+            std::process::exit(130);
+        }
+        eprintln!("\n{}", t!("main.cancelling"));
+        token.cancel();
+    })?;
+
+    say!("{}", t!("main.banner"));
+    say!("==============================");
+
+    if let Some(addr) = serve_http_addr {
+        serve_http(&addr);
+        return Ok(());
+    }
 
     if args.len() > 1 {
         match args[1].as_str() {
@@ -17,39 +77,684 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 match show_gen_cli_help() {
                     Ok(_) => return Ok(()),
                     Err(e) => {
-                        eprintln!("❌ 显示帮助失败: {}", e);
+                        eprintln!("❌ {}: {}", t!("error.help"), e);
+                        return Ok(());
+                    }
+                }
+            }
+            "list" => {
+                let types: Vec<&str> = match args.get(2) {
+                    Some(project_type) => vec![project_type.as_str()],
+                    None => PROJECT_TYPES.to_vec(),
+                };
+                let mut lists = Vec::new();
+                for project_type in types {
+                    match list_templates(project_type.to_string()) {
+                        Ok(templates) => lists.push(TemplateList {
+                            project_type: project_type.to_string(),
+                            templates,
+                        }),
+                        Err(e) => fail(&t!("error.list"), e),
+                    }
+                }
+                emit(&lists, |lists| {
+                    for list in lists {
+                        say!("📦 {}:", list.project_type);
+                        for template in &list.templates {
+                            println!("{}/{}", list.project_type, template);
+                        }
+                    }
+                })?;
+                return Ok(());
+            }
+            "info" => {
+                let (Some(project_type), Some(template)) = (args.get(2), args.get(3)) else {
+                    fail(
+                        &t!("error.missing_args"),
+                        "generator-cli info <project_type> <template>",
+                    );
+                };
+                match get_template_info(project_type.clone(), template.clone()) {
+                    Ok(info) => {
+                        let features =
+                            list_template_features(project_type.clone(), template.clone())
+                                .unwrap_or_default();
+                        let value = serde_json::json!({
+                            "project_type": project_type,
+                            "template": template,
+                            "info": info,
+                            "features": features,
+                        });
+                        emit(&value, |_| {
+                            println!("{}", info);
+                            for feature in &features {
+                                println!("  - {}: {}", feature.name, feature.description);
+                            }
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.info"), e),
+                }
+            }
+            "batch" => {
+                let Some(manifest) = args.get(2) else {
+                    fail(
+                        &t!("error.manifest_required"),
+                        "generator-cli batch <projects.json>",
+                    );
+                };
+                match generate_projects_from_manifest(manifest.clone()) {
+                    Ok(results) => {
+                        emit(&results, |results| {
+                            for result in results {
+                                let icon = if result.success { "✅" } else { "❌" };
+                                println!("{} {}", icon, result.message.as_deref().unwrap_or(""));
+                            }
+                        })?;
+                        if results.iter().any(|result| !result.success) {
+                            std::process::exit(1);
+                        }
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.batch"), e),
+                }
+            }
+            "apply" => {
+                let blueprint = args
+                    .get(2)
+                    .cloned()
+                    .unwrap_or_else(|| "generator.yaml".to_string());
+                match apply_blueprint(blueprint) {
+                    Ok(results) => {
+                        emit(&results, |results| {
+                            for result in results {
+                                println!("📦 {}: {}", result.name, result.action);
+                                if let Some(message) = &result.message {
+                                    println!("   {}", message);
+                                }
+                            }
+                        })?;
+                        if results
+                            .iter()
+                            .any(|result| result.action == "failed" || result.action == "conflict")
+                        {
+                            std::process::exit(1);
+                        }
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.apply"), e),
+                }
+            }
+            "audit" => {
+                let blueprint = args
+                    .get(2)
+                    .cloned()
+                    .unwrap_or_else(|| "generator.yaml".to_string());
+                match audit_blueprint(blueprint) {
+                    Ok(commands) => {
+                        emit(&commands, |commands| {
+                            if commands.is_empty() {
+                                say!("{}", t!("main.audit_none"));
+                            }
+                            for command in commands {
+                                println!(
+                                    "{}$ {} {}",
+                                    command.cwd,
+                                    command.program,
+                                    command.args.join(" ")
+                                );
+                                if let Some(pattern) = &command.denied_by {
+                                    println!("   {}", t!("main.audit_denied", pattern = pattern));
+                                }
+                            }
+                        })?;
+                        // 有被策略禁止的命令时以非零状态退出，便于在 CI 中拦截
+                        if commands.iter().any(|command| command.denied_by.is_some()) {
+                            std::process::exit(1);
+                        }
                         return Ok(());
                     }
+                    Err(e) => fail(&t!("error.audit"), e),
                 }
             }
             "version" | "--version" | "-v" => {
-                println!("项目生成器 v0.0.0");
+                emit(&serde_json::json!({ "version": "0.0.0" }), |_| {
+                    println!("{}", t!("main.version", version = "0.0.0"));
+                })?;
                 return Ok(());
             }
-            _ => {
-                println!("❌ 未知参数: {}", args[1]);
-                println!("使用 'cargo run help' 查看帮助");
+            "new-template" => {
+                let Some(name) = args.get(2) else {
+                    fail(
+                        &t!("error.template_name_required"),
+                        "generator-cli new-template <name> [--type vue] [--path dir]",
+                    );
+                };
+                let options = NewTemplateOptions {
+                    name: name.clone(),
+                    project_type: flag_value(&args, "--type").unwrap_or_else(|| "vue".to_string()),
+                    path: flag_value(&args, "--path"),
+                    ..Default::default()
+                };
+                match create_template(options) {
+                    Ok(files) => {
+                        say!("{}", t!("main.template_created"));
+                        emit(&files, |files| {
+                            for file in files {
+                                println!("   {}", file);
+                            }
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.new_template"), e),
+                }
+            }
+            "import-backstage" => {
+                let Some(source) = args.get(2) else {
+                    fail(
+                        &t!("error.backstage_source_required"),
+                        "generator-cli import-backstage <template.yaml> [--type vue] [--path dir]",
+                    );
+                };
+                let options = BackstageImportOptions {
+                    source: source.clone(),
+                    project_type: flag_value(&args, "--type"),
+                    path: flag_value(&args, "--path"),
+                };
+                match import_backstage_template(options) {
+                    Ok(result) => {
+                        say!("{}", t!("main.backstage_imported", path = result.path));
+                        emit(&result, |result| {
+                            for file in &result.files {
+                                println!("   {}", file);
+                            }
+                            for skipped in &result.skipped {
+                                println!("{}", t!("main.backstage_skipped", item = skipped));
+                            }
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.import_backstage"), e),
+                }
+            }
+            "lint-template" => {
+                let path = args.get(2).cloned().unwrap_or_else(|| ".".to_string());
+                match validate_template(path) {
+                    Ok(diagnostics) => {
+                        emit(&diagnostics, |diagnostics| print_diagnostics(diagnostics))?;
+                        if has_errors(&diagnostics) {
+                            std::process::exit(1);
+                        }
+                        say!("{}", t!("main.lint_done"));
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.lint"), e),
+                }
+            }
+            "test-template" => {
+                let path = args.get(2).cloned().unwrap_or_else(|| ".".to_string());
+                let fixtures = match args.get(3) {
+                    Some(file) => {
+                        let content = std::fs::read_to_string(file)?;
+                        Some(serde_json::from_str::<Vec<TemplateFixture>>(&content)?)
+                    }
+                    None => None,
+                };
+                match test_template(path, fixtures) {
+                    Ok(diagnostics) => {
+                        emit(&diagnostics, |diagnostics| print_diagnostics(diagnostics))?;
+                        if has_errors(&diagnostics) {
+                            std::process::exit(1);
+                        }
+                        say!("{}", t!("main.test_done"));
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.test"), e),
+                }
+            }
+            "pack" => {
+                let path = args
+                    .get(2)
+                    .filter(|arg| !arg.starts_with("--"))
+                    .cloned()
+                    .unwrap_or_else(|| ".".to_string());
+                match pack_template(path, flag_value(&args, "--out")) {
+                    Ok(bundle) => {
+                        emit(&serde_json::json!({ "bundle": bundle }), |_| {
+                            println!("{}", t!("main.template_packed", path = bundle));
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.pack"), e),
+                }
+            }
+            "install" => {
+                let Some(bundle) = args.get(2) else {
+                    fail(
+                        &t!("error.bundle_required"),
+                        "generator-cli install <bundle.gentpl> [--registry ./templates] [--force]",
+                    );
+                };
+                let force = args.iter().any(|arg| arg == "--force");
+                let registry = flag_value(&args, "--registry");
+                match install_template(bundle.clone(), registry, Some(force)) {
+                    Ok(dir) => {
+                        emit(&serde_json::json!({ "path": dir }), |_| {
+                            println!("{}", t!("main.template_installed", path = dir));
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.install"), e),
+                }
+            }
+            "publish" => {
+                let Some(registry) = args.get(2) else {
+                    fail(
+                        &t!("error.registry_required"),
+                        "generator-cli publish <registry> [path] [--bump patch]",
+                    );
+                };
+                let path = args
+                    .get(3)
+                    .filter(|arg| !arg.starts_with("--"))
+                    .cloned()
+                    .unwrap_or_else(|| ".".to_string());
+                match publish_template(path, registry.clone(), flag_value(&args, "--bump")) {
+                    Ok(result) => {
+                        emit(&result, |result| {
+                            println!(
+                                "{}",
+                                t!(
+                                    "main.template_published",
+                                    name = result.name,
+                                    version = result.version,
+                                    registry = result.registry,
+                                    location = result.location
+                                )
+                            );
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.publish"), e),
+                }
+            }
+            "schema" => match get_template_schema() {
+                Ok(schema) => {
+                    match args.get(2) {
+                        Some(file) => {
+                            std::fs::write(file, schema)?;
+                            say!("{}", t!("main.schema_written", path = file));
+                        }
+                        None => println!("{}", schema),
+                    }
+                    return Ok(());
+                }
+                Err(e) => fail(&t!("error.schema"), e),
+            },
+            "credential" => {
+                let (Some(action), Some(id)) = (args.get(2), args.get(3)) else {
+                    fail(
+                        &t!("error.missing_args"),
+                        "generator-cli credential set|delete <id> [--username u] [--file]",
+                    );
+                };
+                let use_file = args.iter().any(|arg| arg == "--file");
+                let file = FileCredentials::default();
+                let result = match action.as_str() {
+                    "set" => {
+                        output::prompt(format_args!("{}", t!("main.enter_secret", id = id)))?;
+                        let mut secret = String::new();
+                        std::io::stdin().read_line(&mut secret)?;
+                        let credential = Credential {
+                            username: flag_value(&args, "--username"),
+                            secret: secret.trim().to_string(),
+                        };
+                        if use_file {
+                            file.store(id, &credential)
+                        } else {
+                            KeyringCredentials.store(id, &credential)
+                        }
+                        .map(|_| true)
+                    }
+                    "delete" => {
+                        if use_file {
+                            file.delete(id)
+                        } else {
+                            KeyringCredentials.delete(id)
+                        }
+                    }
+                    _ => fail(&t!("error.unknown_action"), action),
+                };
+                match result {
+                    Ok(changed) => {
+                        emit(&serde_json::json!({ "id": id, "changed": changed }), |_| {
+                            match (action.as_str(), changed) {
+                                ("set", _) => say!("{}", t!("main.credential_saved", id = id)),
+                                (_, true) => say!("{}", t!("main.credential_deleted", id = id)),
+                                (_, false) => say!("{}", t!("main.credential_missing", id = id)),
+                            }
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.credential"), e),
+                }
+            }
+            "usage" => match args.get(2).map(String::as_str).unwrap_or("stats") {
+                "stats" => match get_usage_stats() {
+                    Ok(stats) => {
+                        emit(&stats, |stats| {
+                            if !stats.enabled {
+                                say!("{}", t!("main.usage_disabled"));
+                            }
+                            say!("{}", t!("main.usage_total", total = stats.total));
+                            for usage in &stats.templates {
+                                println!(
+                                    "{}",
+                                    t!(
+                                        "main.usage_line",
+                                        template = format!(
+                                            "{}/{}@{}",
+                                            usage.project_type, usage.template, usage.version
+                                        ),
+                                        count = usage.count,
+                                        last_used = usage.last_used
+                                    )
+                                );
+                            }
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.usage_stats"), e),
+                },
+                action @ ("on" | "off") => {
+                    let enabled = action == "on";
+                    match set_usage_logging(enabled, flag_value(&args, "--endpoint")) {
+                        Ok(()) => {
+                            emit(&serde_json::json!({ "enabled": enabled }), |_| {
+                                if enabled {
+                                    say!("{}", t!("main.usage_on"));
+                                } else {
+                                    say!("{}", t!("main.usage_off"));
+                                }
+                            })?;
+                            return Ok(());
+                        }
+                        Err(e) => fail(&t!("error.usage_toggle"), e),
+                    }
+                }
+                action => fail(&t!("error.unknown_action"), action),
+            },
+            "dev" => {
+                let Some(template) = flag_value(&args, "--template") else {
+                    fail(&t!("error.missing_args"), "--template <dir>");
+                };
+                let out = flag_value(&args, "--out").unwrap_or_else(|| "./preview".to_string());
+                let mut variables = std::collections::HashMap::new();
+                for pair in flag_values(&args, "--var") {
+                    let Some((key, value)) = pair.split_once('=') else {
+                        fail(&t!("error.var_format"), pair);
+                    };
+                    variables.insert(key.to_string(), value.to_string());
+                }
+                let mut session = DevSession::new(
+                    template.clone().into(),
+                    out.clone().into(),
+                    variables,
+                    flag_values(&args, "--feature"),
+                );
+
+                say!(
+                    "{}",
+                    t!("main.dev_watching", template = template, out = out)
+                );
+                let result = dev::watch(&mut session, |render| match render {
+                    Ok(render) if render.written.is_empty() && render.removed.is_empty() => {}
+                    Ok(render) => say!(
+                        "{}{}",
+                        if render.full {
+                            t!("main.dev_full", count = render.written.len())
+                        } else {
+                            t!("main.dev_incremental", count = render.written.len())
+                        },
+                        if render.removed.is_empty() {
+                            String::new()
+                        } else {
+                            t!("main.dev_removed", count = render.removed.len())
+                        }
+                    ),
+                    Err(e) => eprintln!("{}", t!("error.render", error = e)),
+                });
+                match result {
+                    Ok(()) => return Ok(()),
+                    Err(e) if e.is_cancelled() => return Ok(()),
+                    Err(e) => fail(&t!("error.dev"), e),
+                }
+            }
+            "doctor" => {
+                let report = run_diagnostics();
+                emit(&report, |report| {
+                    for check in &report.checks {
+                        let icon = match check.status.as_str() {
+                            "pass" => "✅",
+                            "warn" => "⚠️ ",
+                            _ => "❌",
+                        };
+                        println!("{} {}: {}", icon, check.name, check.message);
+                        if let Some(hint) = &check.hint {
+                            println!("   {}", hint);
+                        }
+                    }
+                    if report.ok {
+                        say!("\n{}", t!("main.doctor_ok"));
+                    } else {
+                        say!("\n{}", t!("main.doctor_failed"));
+                    }
+                })?;
+                if !report.ok {
+                    std::process::exit(1);
+                }
                 return Ok(());
             }
+            "index" => match refresh_index(Some(args.iter().any(|arg| arg == "--force"))) {
+                Ok(stats) => {
+                    emit(&stats, |stats| {
+                        say!(
+                            "{}",
+                            t!(
+                                "main.index_refreshed",
+                                templates = stats.templates,
+                                updated = stats.updated,
+                                reused = stats.reused,
+                                removed = stats.removed
+                            )
+                        );
+                        say!("   {}", stats.path);
+                    })?;
+                    return Ok(());
+                }
+                Err(e) => fail(&t!("error.index"), e),
+            },
+            "serve" => {
+                let result = Daemon::from_config().and_then(|mut daemon| {
+                    let output: daemon::Output =
+                        std::sync::Arc::new(std::sync::Mutex::new(std::io::stdout()));
+                    daemon::serve(&mut daemon, std::io::stdin().lock(), output)
+                });
+                match result {
+                    Ok(()) => return Ok(()),
+                    Err(e) => fail(&t!("error.serve"), e),
+                }
+            }
+            "cache" => match args.get(2).map(String::as_str).unwrap_or("stats") {
+                "stats" => match get_cache_stats() {
+                    Ok(stats) => {
+                        emit(&stats, |stats| {
+                            say!("{}", t!("main.cache_dir", path = stats.path));
+                            say!(
+                                "{}",
+                                t!(
+                                    "main.cache_size",
+                                    entries = stats.entries,
+                                    aliases = stats.aliases,
+                                    size = format!(
+                                        "{:.1}",
+                                        stats.total_bytes as f64 / 1024.0 / 1024.0
+                                    )
+                                )
+                            );
+                            if let Some(max_bytes) = stats.max_bytes {
+                                say!(
+                                    "{}",
+                                    t!(
+                                        "main.cache_limit",
+                                        size = format!("{:.0}", max_bytes as f64 / 1024.0 / 1024.0)
+                                    )
+                                );
+                            }
+                        })?;
+                        return Ok(());
+                    }
+                    Err(e) => fail(&t!("error.cache_stats"), e),
+                },
+                action => fail(&t!("error.unknown_action"), action),
+            },
+            // 交互模式参数，在下方解析
+            "generate" | "--answers" | "--save-answers" | "--report" => {}
+            _ => {
+                say!("{}", t!("main.help_hint"));
+                fail(&t!("error.unknown_arg"), &args[1]);
+            }
         }
     }
 
-    match run_gen_cli() {
+    let options = CliRunOptions {
+        answers_file: flag_value(&args, "--answers"),
+        save_answers: flag_value(&args, "--save-answers"),
+        report: flag_value(&args, "--report"),
+        output: Some(mode),
+        lang: Some(i18n::lang().tag().to_string()),
+        resume: Some(resume),
+        frozen: Some(frozen),
+        delegate: Some(delegate),
+        python_venv: Some(python_venv),
+    };
+
+    match run_gen_cli(Some(options)) {
         Ok(result) => {
-            if result.success {
-                println!("\n🎉 操作完成!");
-                if let Some(message) = result.message {
-                    println!("📝 {}", message);
+            emit(&result, |result| {
+                if result.success {
+                    say!("\n{}", t!("main.done"));
+                    if let Some(message) = &result.message {
+                        println!("📝 {}", message);
+                    }
+                } else {
+                    println!("\n{}", t!("main.not_done"));
                 }
-            } else {
-                println!("\n⚠️  操作未完成");
-            }
-        }
-        Err(e) => {
-            eprintln!("\n❌ 错误: {}", e);
-            std::process::exit(1);
+            })?;
         }
+        Err(e) => fail(&t!("error.error"), e),
     }
 
     Ok(())
 }
+
+/// 移除参数列表中的开关，返回是否存在
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != before
+}
+
+/// 移除参数列表中的 `--flag value`，返回其取值
+fn take_flag_value(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    let value = args.get(index + 1).cloned();
+    args.drain(index..(index + 2).min(args.len()));
+    value
+}
+
+/// 读取 `--flag value` 形式的参数值
+fn flag_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .cloned()
+}
+
+/// 可重复出现的参数的所有取值
+fn flag_values(args: &[String], flag: &str) -> Vec<String> {
+    args.windows(2)
+        .filter(|pair| pair[0] == flag)
+        .map(|pair| pair[1].clone())
+        .collect()
+}
+
+/// JSON 模式下在 stdout 输出结构化结果，否则按可读格式输出
+fn emit<T: Serialize>(
+    value: &T,
+    human: impl FnOnce(&T),
+) -> Result<(), Box<dyn std::error::Error>> {
+    if output::mode() == OutputMode::Json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        human(value);
+    }
+    Ok(())
+}
+
+/// 输出错误并以非零状态退出；JSON 模式下在 stdout 输出 `{"success": false, "error": ...}`
+/// `--serve-http`：启动供脚手架门户使用的 REST 服务，Ctrl-C 停止
+#[cfg(feature = "http-server")]
+fn serve_http(addr: &str) {
+    say!("{}", t!("main.serving_http", addr = addr));
+    if let Err(e) = generator::http_server::serve(addr) {
+        fail(&t!("error.serve_http"), e);
+    }
+}
+
+#[cfg(not(feature = "http-server"))]
+fn serve_http(_addr: &str) {
+    fail(&t!("error.serve_http"), t!("error.http_server_disabled"));
+}
+
+fn fail(context: &str, error: impl std::fmt::Display) -> ! {
+    if output::mode() == OutputMode::Json {
+        println!(
+            "{}",
+            serde_json::json!({ "success": false, "error": format!("{}: {}", context, error) })
+        );
+    } else {
+        eprintln!("\n❌ {}: {}", context, error);
+    }
+    std::process::exit(1);
+}
+
+fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == "error")
+}
+
+/// 打印诊断列表
+fn print_diagnostics(diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let icon = if diagnostic.severity == "error" {
+            "❌"
+        } else {
+            "⚠️"
+        };
+        let location = [diagnostic.fixture.as_deref(), diagnostic.file.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if location.is_empty() {
+            println!("{} [{}] {}", icon, diagnostic.code, diagnostic.message);
+        } else {
+            println!(
+                "{} [{}] {}: {}",
+                icon, diagnostic.code, location, diagnostic.message
+            );
+        }
+    }
+}
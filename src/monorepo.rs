@@ -0,0 +1,198 @@
+//! 单仓多包（monorepo）生成模块
+//! 先生成 workspace 根目录，再依次调用各项目类型的生成器在 `apps/`、`packages/` 下生成初始子项目
+
+use crate::template_registry::TemplateRegistryConfig;
+use crate::templates::generate_single_project;
+use crate::{GenerateOptions, GenerateResult, MonorepoPackage, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// monorepo 项目类型
+pub const MONOREPO_TYPE: &str = "monorepo";
+
+/// 生成 workspace 根目录及其子项目，返回合并后的结果
+///
+/// 任一子项目失败时删除整个 workspace（`keep_partial` 为真时保留）。
+pub fn generate_monorepo(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> Result<GenerateResult> {
+    let packages = options.packages.clone().unwrap_or_default();
+    let root_options = GenerateOptions {
+        packages: None,
+        ..options.clone()
+    };
+
+    let mut combined = generate_single_project(root_options, config)?;
+    let root_dir = PathBuf::from(options.output_path.as_deref().unwrap_or("."));
+    let root_dir = if options.into_existing.unwrap_or(false) {
+        root_dir
+    } else {
+        root_dir.join(&options.name)
+    };
+
+    for package in &packages {
+        match generate_package(&root_dir, &options, package, config) {
+            Ok(result) => merge_result(&mut combined, &options.name, package, result),
+            Err(e) => {
+                if !options.keep_partial.unwrap_or(false) && !options.into_existing.unwrap_or(false)
+                {
+                    let _ = fs::remove_dir_all(&root_dir);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    combined.message = Some(format!(
+        "monorepo {} 生成成功，包含 {} 个子项目{}",
+        options.name,
+        packages.len(),
+        packages
+            .iter()
+            .map(|package| format!("\n  - {} ({})", package_path(package), package.project_type))
+            .collect::<String>()
+    ));
+    Ok(combined)
+}
+
+/// 子项目所在目录：库类型默认放在 `packages/`，其余放在 `apps/`
+fn package_dir(package: &MonorepoPackage) -> &str {
+    match package.directory.as_deref() {
+        Some(directory) => directory,
+        None if package.project_type == "node-lib" => "packages",
+        None if package.project_type == "rust" && package.template.as_deref() == Some("lib") => {
+            "packages"
+        }
+        None => "apps",
+    }
+}
+
+fn package_path(package: &MonorepoPackage) -> String {
+    format!("{}/{}", package_dir(package), package.name)
+}
+
+fn generate_package(
+    root_dir: &Path,
+    root: &GenerateOptions,
+    package: &MonorepoPackage,
+    config: &TemplateRegistryConfig,
+) -> Result<GenerateResult> {
+    // 子项目继承 workspace 的变量，自身变量优先
+    let mut variables = root.variables.clone().unwrap_or_default();
+    variables.extend(package.variables.clone().unwrap_or_default());
+    variables.insert("workspace".to_string(), root.name.clone());
+
+    generate_single_project(
+        GenerateOptions {
+            name: package.name.clone(),
+            project_type: package.project_type.clone(),
+            template: package.template.clone(),
+            output_path: Some(
+                root_dir
+                    .join(package_dir(package))
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            variables: Some(variables),
+            features: package.features.clone(),
+            symlink_policy: root.symlink_policy,
+            workers: root.workers,
+            keep_partial: root.keep_partial,
+            ..Default::default()
+        },
+        config,
+    )
+}
+
+/// 将子项目结果并入 workspace 结果，文件路径加上 workspace 前缀
+fn merge_result(
+    combined: &mut GenerateResult,
+    root_name: &str,
+    package: &MonorepoPackage,
+    result: GenerateResult,
+) {
+    let prefix = format!("{}/{}", root_name, package_dir(package));
+    combined.files.extend(
+        result
+            .files
+            .into_iter()
+            .map(|file| format!("{}/{}", prefix, file)),
+    );
+    combined.warnings.extend(
+        result
+            .warnings
+            .into_iter()
+            .map(|warning| format!("{}: {}", package_path(package), warning)),
+    );
+    if let (Some(total), Some(stats)) = (combined.stats.as_mut(), result.stats) {
+        total.merge(&stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generate_monorepo_with_packages() {
+        let output_dir = tempdir().unwrap();
+
+        let result = crate::templates::generate_project_from_template(GenerateOptions {
+            name: "platform".to_string(),
+            project_type: MONOREPO_TYPE.to_string(),
+            template: Some("pnpm".to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            features: Some(vec!["turborepo".to_string()]),
+            packages: Some(vec![
+                MonorepoPackage {
+                    name: "shared".to_string(),
+                    project_type: "node-lib".to_string(),
+                    ..Default::default()
+                },
+                MonorepoPackage {
+                    name: "server".to_string(),
+                    project_type: "rust".to_string(),
+                    template: Some("bin".to_string()),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let root = output_dir.path().join("platform");
+        assert!(root.join("pnpm-workspace.yaml").exists());
+        assert!(root.join("turbo.json").exists());
+        assert!(root.join("packages/shared/package.json").exists());
+        assert!(root.join("apps/server/Cargo.toml").exists());
+        assert!(
+            result
+                .files
+                .contains(&"platform/packages/shared/src/index.ts".to_string())
+        );
+        assert!(result.stats.unwrap().file_count as usize >= result.files.len());
+    }
+
+    #[test]
+    fn test_failed_package_rolls_back_workspace() {
+        let output_dir = tempdir().unwrap();
+
+        let result = crate::templates::generate_project_from_template(GenerateOptions {
+            name: "platform".to_string(),
+            project_type: MONOREPO_TYPE.to_string(),
+            template: Some("pnpm".to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            packages: Some(vec![MonorepoPackage {
+                name: "web".to_string(),
+                project_type: "unknown".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+
+        assert!(result.is_err());
+        assert!(!output_dir.path().join("platform").exists());
+    }
+}
@@ -0,0 +1,275 @@
+//! 网络配置模块
+//! 代理与自定义 CA 证书设置，供 HTTP 下载、git 与 npm 模板源共用；未配置的项读取 HTTP(S)_PROXY 等环境变量
+
+use crate::retry::RetryPolicy;
+use crate::{GeneratorError, Result};
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::pem::PemObject;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// 指定 CA 证书包的环境变量
+pub const CA_BUNDLE_ENV: &str = "GENERATOR_CA_BUNDLE";
+
+/// 代理与 CA 证书配置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// http:// 地址使用的代理，未设置时读取 `HTTP_PROXY`
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// https:// 地址使用的代理，未设置时读取 `HTTPS_PROXY`
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+    /// 不经过代理的主机（域名后缀，`*` 表示全部），未设置时读取 `NO_PROXY`
+    #[serde(default)]
+    pub no_proxy: Option<Vec<String>>,
+    /// 额外信任的 CA 证书包（PEM），未设置时读取 `GENERATOR_CA_BUNDLE`
+    #[serde(default)]
+    pub ca_bundle: Option<PathBuf>,
+    /// 远程模板源（HTTP、npm、git）暂时性故障的重试策略，未设置时使用默认策略
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+}
+
+impl NetworkConfig {
+    /// 从环境变量读取配置（大小写形式均可）
+    pub fn from_env() -> Self {
+        Self {
+            http_proxy: env_var("HTTP_PROXY"),
+            https_proxy: env_var("HTTPS_PROXY"),
+            no_proxy: env_var("NO_PROXY").map(|value| {
+                value
+                    .split(',')
+                    .map(|host| host.trim().to_string())
+                    .filter(|host| !host.is_empty())
+                    .collect()
+            }),
+            ca_bundle: env::var_os(CA_BUNDLE_ENV).map(PathBuf::from),
+            retry: None,
+        }
+    }
+
+    /// 以 `fallback` 补全未设置的字段
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            http_proxy: self.http_proxy.or(fallback.http_proxy),
+            https_proxy: self.https_proxy.or(fallback.https_proxy),
+            no_proxy: self.no_proxy.or(fallback.no_proxy),
+            ca_bundle: self.ca_bundle.or(fallback.ca_bundle),
+            retry: self.retry.or(fallback.retry),
+        }
+    }
+
+    /// 远程请求使用的重试策略
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry.clone().unwrap_or_default()
+    }
+
+    /// 合并环境变量
+    pub fn resolve(self) -> Self {
+        self.or(Self::from_env())
+    }
+
+    /// 访问 `url` 时使用的代理，命中 `no_proxy` 时返回 None
+    pub fn proxy_for(&self, url: &str) -> Option<&str> {
+        let host = host_of(url)?;
+        let bypass = self.no_proxy.iter().flatten().any(|pattern| {
+            let pattern = pattern.trim_start_matches("*.").trim_start_matches('.');
+            pattern == "*" || host == pattern || host.ends_with(&format!(".{}", pattern))
+        });
+        if bypass {
+            return None;
+        }
+
+        let proxy = if url.starts_with("http://") {
+            &self.http_proxy
+        } else {
+            &self.https_proxy
+        };
+        proxy.as_deref().filter(|proxy| !proxy.is_empty())
+    }
+
+    /// 构造访问 `url` 的 HTTP 客户端，应用代理、CA 证书与超时
+    pub fn http_agent(&self, url: &str, timeout: Option<Duration>) -> Result<ureq::Agent> {
+        let mut builder = ureq::AgentBuilder::new();
+        if let Some(proxy) = self.proxy_for(url) {
+            let proxy = ureq::Proxy::new(proxy).map_err(|e| {
+                GeneratorError::Configuration(format!("Invalid proxy {}: {}", proxy, e))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(ca_bundle) = &self.ca_bundle {
+            builder = builder.tls_config(Arc::new(tls_config(ca_bundle)?));
+        }
+        if let Some(limit) = timeout {
+            builder = builder.timeout(limit);
+        }
+        Ok(builder.build())
+    }
+
+    /// 访问 `url` 的 git 拉取选项，应用代理与 CA 证书
+    pub fn git_fetch_options(&self, url: &str) -> Result<git2::FetchOptions<'static>> {
+        if let Some(ca_bundle) = &self.ca_bundle {
+            set_git_ca_bundle(ca_bundle)?;
+        }
+
+        let mut proxy_options = git2::ProxyOptions::new();
+        if let Some(proxy) = self.proxy_for(url) {
+            proxy_options.url(proxy);
+        }
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.proxy_options(proxy_options);
+        Ok(fetch_options)
+    }
+
+    /// 为 npm 子进程设置代理与 CA 证书（`npm_config_*` 环境变量）
+    pub fn apply_to_npm(&self, command: &mut Command, registry: &str) {
+        if let Some(proxy) = self.proxy_for(registry) {
+            command
+                .env("npm_config_proxy", proxy)
+                .env("npm_config_https_proxy", proxy);
+        }
+        if let Some(no_proxy) = &self.no_proxy {
+            command.env("npm_config_noproxy", no_proxy.join(","));
+        }
+        if let Some(ca_bundle) = &self.ca_bundle {
+            command.env("npm_config_cafile", ca_bundle);
+        }
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name)
+        .or_else(|_| env::var(name.to_lowercase()))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+/// 取出 URL 中的主机名（去掉协议、用户信息与端口）
+fn host_of(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// 内置根证书加上 `ca_bundle` 中的证书
+fn tls_config(ca_bundle: &Path) -> Result<rustls::ClientConfig> {
+    let pem = fs::read(ca_bundle).map_err(|e| {
+        GeneratorError::Configuration(format!(
+            "Cannot read CA bundle {}: {}",
+            ca_bundle.display(),
+            e
+        ))
+    })?;
+    let certificates = CertificateDer::pem_slice_iter(&pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            GeneratorError::Configuration(format!(
+                "Invalid CA bundle {}: {}",
+                ca_bundle.display(),
+                e
+            ))
+        })?;
+    if certificates.is_empty() {
+        return Err(GeneratorError::Configuration(format!(
+            "No certificates found in CA bundle {}",
+            ca_bundle.display()
+        )));
+    }
+
+    let mut roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let (_, ignored) = roots.add_parsable_certificates(certificates);
+    if ignored > 0 {
+        return Err(GeneratorError::Configuration(format!(
+            "{} certificate(s) in CA bundle {} could not be parsed",
+            ignored,
+            ca_bundle.display()
+        )));
+    }
+
+    rustls::ClientConfig::builder_with_provider(rustls::crypto::ring::default_provider().into())
+        .with_protocol_versions(rustls::ALL_VERSIONS)
+        .map_err(|e| GeneratorError::Configuration(format!("TLS configuration failed: {}", e)))
+        .map(|builder| builder.with_root_certificates(roots).with_no_client_auth())
+}
+
+/// libgit2 的 CA 设置是进程级的，只在首次拉取前设置一次
+fn set_git_ca_bundle(ca_bundle: &Path) -> Result<()> {
+    static CONFIGURED: OnceLock<std::result::Result<PathBuf, String>> = OnceLock::new();
+    let configured = CONFIGURED.get_or_init(|| {
+        // SAFETY: 由 OnceLock 保证只执行一次，且发生在本进程的首次 git 网络操作之前
+        unsafe { git2::opts::set_ssl_cert_file(ca_bundle) }
+            .map(|_| ca_bundle.to_path_buf())
+            .map_err(|e| e.to_string())
+    });
+
+    match configured {
+        Ok(path) if path == ca_bundle => Ok(()),
+        Ok(path) => Err(GeneratorError::Configuration(format!(
+            "git CA bundle already set to {}",
+            path.display()
+        ))),
+        Err(e) => Err(GeneratorError::Configuration(format!(
+            "Cannot use CA bundle {} for git: {}",
+            ca_bundle.display(),
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_for_honors_no_proxy() {
+        let config = NetworkConfig {
+            http_proxy: Some("http://proxy:3128".to_string()),
+            https_proxy: Some("http://secure-proxy:3128".to_string()),
+            no_proxy: Some(vec!["internal.corp".to_string(), "localhost".to_string()]),
+            ca_bundle: None,
+            retry: None,
+        };
+
+        assert_eq!(
+            config.proxy_for("https://github.com/org/repo.git"),
+            Some("http://secure-proxy:3128")
+        );
+        assert_eq!(
+            config.proxy_for("http://user:pw@example.com:8080/a"),
+            Some("http://proxy:3128")
+        );
+        assert_eq!(config.proxy_for("https://git.internal.corp/repo"), None);
+        assert_eq!(config.proxy_for("http://localhost:4873"), None);
+    }
+
+    #[test]
+    fn test_invalid_ca_bundle_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("ca.pem");
+        fs::write(&bundle, "not a certificate").unwrap();
+
+        let config = NetworkConfig {
+            ca_bundle: Some(bundle),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.http_agent("https://example.com", None),
+            Err(GeneratorError::Configuration(_))
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+//! Node.js 环境检测模块
+//! 检测 node、npm、pnpm、yarn、bun 的版本，并校验模板声明的 `engines` 约束
+
+use crate::NodeEnvironmentInfo;
+use crate::template_version::parse_version;
+use semver::VersionReq;
+use std::collections::HashMap;
+use std::process::Command;
+
+/// 检测 Node.js 及各包管理器的版本，未安装的工具为 None
+pub fn detect_node_environment() -> NodeEnvironmentInfo {
+    NodeEnvironmentInfo {
+        node: tool_version("node"),
+        npm: tool_version("npm"),
+        pnpm: tool_version("pnpm"),
+        yarn: tool_version("yarn"),
+        bun: tool_version("bun"),
+    }
+}
+
+/// 执行 `<tool> --version` 并返回去掉 `v` 前缀的版本号
+fn tool_version(tool: &str) -> Option<String> {
+    // Windows 上 npm、pnpm、yarn 是 .cmd 脚本
+    let program = match tool {
+        "npm" | "pnpm" | "yarn" if cfg!(windows) => format!("{}.cmd", tool),
+        _ => tool.to_string(),
+    };
+
+    let output = Command::new(program).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout);
+    let version = version.lines().next()?.trim();
+    Some(version.strip_prefix('v').unwrap_or(version).to_string())
+}
+
+impl NodeEnvironmentInfo {
+    /// 按 engines 中的名称获取已检测到的版本
+    pub fn version_of(&self, engine: &str) -> Option<&str> {
+        match engine {
+            "node" => self.node.as_deref(),
+            "npm" => self.npm.as_deref(),
+            "pnpm" => self.pnpm.as_deref(),
+            "yarn" => self.yarn.as_deref(),
+            "bun" => self.bun.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// 检查模板的 engines 约束，返回不满足时的警告
+pub fn check_engines(
+    engines: &HashMap<String, String>,
+    environment: &NodeEnvironmentInfo,
+) -> Vec<String> {
+    let mut warnings: Vec<String> = engines
+        .iter()
+        .filter_map(|(engine, range)| {
+            let Some(installed) = environment.version_of(engine) else {
+                return Some(format!(
+                    "{} {} is required but not installed",
+                    engine, range
+                ));
+            };
+            let satisfied = parse_version(installed).zip(parse_engine_range(range)).map(
+                |(version, alternatives)| alternatives.iter().any(|req| req.matches(&version)),
+            );
+            match satisfied {
+                Some(true) => None,
+                Some(false) => Some(format!(
+                    "{} {} is required, found {}",
+                    engine, range, installed
+                )),
+                None => Some(format!(
+                    "Cannot check {} version {} against {}",
+                    engine, installed, range
+                )),
+            }
+        })
+        .collect();
+    warnings.sort();
+    warnings
+}
+
+/// 解析 npm 风格的版本范围（`>=18 <21`、`^18 || ^20`）为可选的多个 semver 要求
+fn parse_engine_range(range: &str) -> Option<Vec<VersionReq>> {
+    range
+        .split("||")
+        .map(|alternative| {
+            let comparators: Vec<&str> = alternative.split_whitespace().collect();
+            if comparators.is_empty() {
+                return VersionReq::parse("*").ok();
+            }
+            VersionReq::parse(&comparators.join(", ")).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_engines() {
+        let environment = NodeEnvironmentInfo {
+            node: Some("18.19.0".to_string()),
+            pnpm: Some("8.15.1".to_string()),
+            ..Default::default()
+        };
+        let engines = HashMap::from([
+            ("node".to_string(), ">=18 <21".to_string()),
+            ("pnpm".to_string(), "^7 || ^9".to_string()),
+            ("bun".to_string(), ">=1".to_string()),
+        ]);
+
+        assert_eq!(
+            check_engines(&engines, &environment),
+            vec![
+                "bun >=1 is required but not installed".to_string(),
+                "pnpm ^7 || ^9 is required, found 8.15.1".to_string(),
+            ]
+        );
+    }
+}
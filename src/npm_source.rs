@@ -0,0 +1,405 @@
+//! npm 模板源模块
+//! 按 .npmrc 选择 registry 与认证，解析版本后下载 tarball，校验 integrity 后经 extract 模块解压到内容寻址缓存
+
+use crate::content_cache::{ContentCache, archive_root};
+use crate::extract::Extractor;
+use crate::network::NetworkConfig;
+use crate::npmrc::Npmrc;
+use crate::pipeline::TEMPLATE_METADATA_FILE;
+use crate::{GeneratorError, Result, cancel, command_audit};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Deserialize;
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// registry 返回的包信息（只取需要的字段）
+#[derive(Debug, Deserialize)]
+struct Packument {
+    #[serde(rename = "dist-tags", default)]
+    dist_tags: HashMap<String, String>,
+    #[serde(default)]
+    versions: HashMap<String, PackageVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageVersion {
+    dist: Dist,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dist {
+    tarball: String,
+    #[serde(default)]
+    integrity: Option<String>,
+}
+
+/// npm 模板包获取器
+#[derive(Debug, Clone)]
+pub struct NpmFetcher {
+    /// registry 与认证配置
+    pub npmrc: Npmrc,
+    /// 代理与 CA 证书
+    pub network: NetworkConfig,
+    /// 解压后的包按内容哈希存放，并以 `npm:<package>@<version>` 与 integrity 为别名
+    pub cache: ContentCache,
+    /// 单次请求的超时
+    pub timeout: Option<Duration>,
+}
+
+impl NpmFetcher {
+    /// 获取模板包并返回解压后的目录
+    ///
+    /// `version` 可以是 dist-tag（如 `latest`）、精确版本或 semver 范围；`registry` 覆盖 .npmrc 中的设置。
+    /// 固定了 `integrity` 时缓存中已有该归档则无需联网，否则解析出的版本必须具有相同的 integrity。
+    pub fn fetch(
+        &self,
+        package: &str,
+        version: &str,
+        integrity: Option<&str>,
+        registry: Option<&str>,
+    ) -> Result<PathBuf> {
+        if let Some(integrity) = integrity
+            && let Some(dir) = self.cache.lookup(integrity.trim())?
+        {
+            return package_root(&dir, package);
+        }
+
+        let registry = match registry {
+            Some(registry) => format!("{}/", registry.trim_end_matches('/')),
+            None => self.npmrc.registry_for(package),
+        };
+        let packument_url = format!("{}{}", registry, package.replacen('/', "%2f", 1));
+        let packument: Packument = serde_json::from_slice(&self.get(
+            &packument_url,
+            "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8",
+        )?)?;
+
+        let resolved = resolve_version(&packument, version)
+            .ok_or_else(|| GeneratorError::TemplateNotFound(format!("{}@{}", package, version)))?;
+        // 已发布的版本不可变，按包版本或 integrity（其他 registry 上的同一归档）命中即可复用
+        let dist = &packument.versions[resolved].dist;
+        if let Some(pinned) = integrity
+            && !same_integrity(dist.integrity.as_deref().unwrap_or_default(), pinned)
+        {
+            return Err(GeneratorError::Configuration(format!(
+                "{}@{} has integrity '{}', not the pinned '{}'",
+                package,
+                resolved,
+                dist.integrity.as_deref().unwrap_or_default(),
+                pinned.trim()
+            )));
+        }
+        let mut aliases = vec![format!("npm:{}@{}", package, resolved)];
+        aliases.extend(dist.integrity.clone());
+        for alias in &aliases {
+            if let Some(dir) = self.cache.lookup(alias)? {
+                return package_root(&dir, package);
+            }
+        }
+
+        let tarball = self.get(&dist.tarball, "application/octet-stream")?;
+        if let Some(integrity) = &dist.integrity {
+            verify_integrity(&tarball, integrity, &dist.tarball)?;
+        }
+
+        let dir = self.cache.insert(&tarball, &aliases, |tarball, dest| {
+            Extractor::default().extract(tarball, dest).map(|_| ())
+        })?;
+        package_root(&dir, package)
+    }
+
+    /// 带 .npmrc 认证的 GET 请求
+    fn get(&self, url: &str, accept: &str) -> Result<Vec<u8>> {
+        cancel::check()?;
+        command_audit::record_remote("http", &["GET", url])?;
+        let agent = self.network.http_agent(url, self.timeout)?;
+        let authorization = self.npmrc.authorization(url)?;
+
+        let request = || {
+            let request = agent.get(url).set("Accept", accept);
+            match &authorization {
+                Some(authorization) => request.set("Authorization", authorization),
+                None => request,
+            }
+        };
+        let response = self
+            .network
+            .retry_policy()
+            .call(url, request, |e| match e {
+                ureq::Error::Status(404, _) => GeneratorError::TemplateNotFound(url.to_string()),
+                ureq::Error::Status(code @ (401 | 403), _) => {
+                    GeneratorError::Configuration(format!(
+                        "npm registry rejected credentials for {} (HTTP {}); check .npmrc",
+                        url, code
+                    ))
+                }
+                e => GeneratorError::FileOperation(format!("Failed to fetch {}: {}", url, e)),
+            })?;
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body)?;
+        Ok(body)
+    }
+}
+
+/// 包内容位于顶层的 `package/` 目录下
+fn package_root(dir: &Path, package: &str) -> Result<PathBuf> {
+    archive_root(dir, TEMPLATE_METADATA_FILE).ok_or_else(|| {
+        GeneratorError::TemplateNotFound(format!(
+            "{} does not contain {}",
+            package, TEMPLATE_METADATA_FILE
+        ))
+    })
+}
+
+/// 依次按 dist-tag、精确版本、semver 范围（取最高版本）解析
+fn resolve_version<'a>(packument: &'a Packument, spec: &str) -> Option<&'a str> {
+    if let Some(version) = packument.dist_tags.get(spec) {
+        return packument
+            .versions
+            .get_key_value(version)
+            .map(|(v, _)| v.as_str());
+    }
+    if let Some((version, _)) = packument.versions.get_key_value(spec) {
+        return Some(version);
+    }
+
+    let requirement = semver::VersionReq::parse(spec).ok()?;
+    packument
+        .versions
+        .keys()
+        .filter_map(|version| Some((semver::Version::parse(version).ok()?, version)))
+        .filter(|(version, _)| requirement.matches(version))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, version)| version.as_str())
+}
+
+/// 两个 integrity 是否有相同的哈希（可各自包含多个以空白分隔的哈希）
+fn same_integrity(actual: &str, pinned: &str) -> bool {
+    pinned
+        .split_whitespace()
+        .any(|hash| actual.split_whitespace().any(|other| other == hash))
+}
+
+/// 校验 `sha512-<base64>` 形式的 integrity，其他算法跳过
+fn verify_integrity(content: &[u8], integrity: &str, url: &str) -> Result<()> {
+    let Some(expected) = integrity
+        .split_whitespace()
+        .find_map(|hash| hash.strip_prefix("sha512-"))
+    else {
+        return Ok(());
+    };
+
+    let actual = BASE64.encode(Sha512::digest(content));
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(GeneratorError::FileOperation(format!(
+            "Integrity check failed for {}: expected sha512-{}, got sha512-{}",
+            url, expected, actual
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateOptions;
+    use crate::template_registry::{TemplateRegistry, TemplateRegistryConfig, TemplateSource};
+    use crate::templates::generate_project_with_config;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use tempfile::tempdir;
+
+    fn tarball() -> Vec<u8> {
+        package(&[
+            ("package/template.json", r#"{"name": "private"}"#),
+            ("package/src/index.ts", "export {};"),
+        ])
+    }
+
+    fn package(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    /// 只接受带令牌请求的最小 registry
+    fn serve(tarball: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let integrity = format!("sha512-{}", BASE64.encode(Sha512::digest(&tarball)));
+        let packument = serde_json::json!({
+            "dist-tags": { "latest": "1.2.0" },
+            "versions": {
+                "1.1.0": { "dist": { "tarball": format!("{}/old.tgz", base) } },
+                "1.2.0": { "dist": { "tarball": format!("{}/pkg.tgz", base), "integrity": integrity } },
+            },
+        })
+        .to_string();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut authorized = false;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    authorized |= line.eq_ignore_ascii_case("authorization: Bearer t0ken\r\n");
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or("");
+                let (status, body) = match (authorized, path) {
+                    (false, _) => ("401 Unauthorized", Vec::new()),
+                    (_, "/@acme%2fvue-template") => ("200 OK", packument.clone().into_bytes()),
+                    (_, "/pkg.tgz") => ("200 OK", tarball.clone()),
+                    _ => ("404 Not Found", Vec::new()),
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn test_fetch_private_scoped_package() {
+        let base = serve(tarball());
+        let host = base.trim_start_matches("http:");
+        let npmrc = Npmrc::parse(&format!(
+            "@acme:registry={}/\n{}/:_authToken=t0ken\n",
+            base, host
+        ))
+        .unwrap();
+        let cache = tempdir().unwrap();
+        let fetcher = NpmFetcher {
+            npmrc,
+            network: NetworkConfig::default(),
+            cache: ContentCache::new(cache.path(), None),
+            timeout: Some(Duration::from_secs(10)),
+        };
+
+        let dir = fetcher
+            .fetch("@acme/vue-template", "^1.0.0", None, None)
+            .unwrap();
+        assert!(dir.ends_with("package"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/index.ts")).unwrap(),
+            "export {};"
+        );
+
+        assert!(matches!(
+            fetcher.fetch("@acme/vue-template", "^2.0.0", None, None),
+            Err(GeneratorError::TemplateNotFound(_))
+        ));
+
+        // 固定的 integrity 命中缓存时不访问 registry，不一致时拒绝
+        let integrity = format!("sha512-{}", BASE64.encode(Sha512::digest(tarball())));
+        let offline = NpmFetcher {
+            npmrc: Npmrc::default(),
+            ..fetcher.clone()
+        };
+        assert_eq!(
+            offline
+                .fetch("@acme/vue-template", "1.2.0", Some(&integrity), None)
+                .unwrap(),
+            dir
+        );
+        assert!(matches!(
+            fetcher.fetch("@acme/vue-template", "1.2.0", Some("sha512-AAAA"), None),
+            Err(GeneratorError::Configuration(_))
+        ));
+
+        let anonymous = NpmFetcher {
+            npmrc: Npmrc::default(),
+            ..fetcher
+        };
+        assert!(matches!(
+            anonymous.fetch("@acme/vue-template", "latest", None, Some(&base)),
+            Err(GeneratorError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_generate_from_npm_registry_through_npmrc() {
+        let base = serve(package(&[
+            (
+                "package/template.json",
+                r#"{"name": "@acme/vue-template", "version": "1.2.0", "description": "",
+                    "author": "", "project_type": "vue", "variables": [], "dependencies": [],
+                    "tags": []}"#,
+            ),
+            ("package/README.md", "# {{name}}"),
+        ]));
+        let home = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let npmrc = home.path().join(".npmrc");
+        std::fs::write(
+            &npmrc,
+            format!(
+                "@acme:registry={}/\n{}/:_authToken=t0ken\n",
+                base,
+                base.trim_start_matches("http:")
+            ),
+        )
+        .unwrap();
+        let config = TemplateRegistryConfig {
+            registries: vec![TemplateRegistry {
+                name: "acme".to_string(),
+                source: TemplateSource::Npm {
+                    package: "@acme/vue-template".to_string(),
+                    version: "^1.0.0".to_string(),
+                    registry: None,
+                    integrity: None,
+                },
+                enabled: true,
+                priority: 0,
+            }],
+            cache_dir: home.path().join("cache"),
+            npmrc: Some(npmrc),
+            ..Default::default()
+        };
+
+        let result = generate_project_with_config(
+            GenerateOptions {
+                name: "app".to_string(),
+                project_type: "vue".to_string(),
+                template: Some("@acme/vue-template".to_string()),
+                output_path: Some(output_dir.path().to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            &config,
+        )
+        .unwrap();
+        assert_eq!(result.template_version.as_deref(), Some("1.2.0"));
+        assert_eq!(
+            std::fs::read_to_string(output_dir.path().join("app/README.md")).unwrap(),
+            "# app"
+        );
+    }
+}
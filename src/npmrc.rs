@@ -0,0 +1,250 @@
+//! .npmrc 解析模块
+//! 读取项目与用户级 `.npmrc` 中的 registry、scope registry 与认证信息，私有 npm 模板包无需重复配置认证
+
+use crate::{GeneratorError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 默认的 npm registry
+pub const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org/";
+
+/// 某个 registry 的认证信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NpmAuth {
+    /// `_authToken`
+    pub token: Option<String>,
+    /// `_auth`，即 base64 编码的 `user:password`
+    pub basic: Option<String>,
+    /// `username`
+    pub username: Option<String>,
+    /// `_password`（base64 编码）
+    pub password: Option<String>,
+}
+
+/// 合并后的 .npmrc 配置
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Npmrc {
+    /// 默认 registry
+    pub registry: Option<String>,
+    /// `@scope:registry`，键不含 `@`
+    pub scopes: HashMap<String, String>,
+    /// 按 registry 路径（`//host/path/`）索引的认证信息
+    pub auth: HashMap<String, NpmAuth>,
+}
+
+impl Npmrc {
+    /// 读取用户级（`NPM_CONFIG_USERCONFIG` 或 `~/.npmrc`）与当前目录的 `.npmrc`，项目配置优先
+    pub fn load() -> Result<Self> {
+        let user = env::var_os("NPM_CONFIG_USERCONFIG")
+            .or_else(|| env::var_os("npm_config_userconfig"))
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .or_else(|| env::var_os("USERPROFILE"))
+                    .map(|home| PathBuf::from(home).join(".npmrc"))
+            });
+
+        let mut npmrc = Self::default();
+        for path in user
+            .iter()
+            .map(PathBuf::as_path)
+            .chain([Path::new(".npmrc")])
+        {
+            npmrc.merge(Self::load_file(path)?);
+        }
+        Ok(npmrc)
+    }
+
+    /// 读取单个 .npmrc 文件，不存在时返回空配置
+    pub fn load_file(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| match e {
+            GeneratorError::Configuration(message) => {
+                GeneratorError::Configuration(format!("{}: {}", path.display(), message))
+            }
+            e => e,
+        })
+    }
+
+    /// 解析 .npmrc 内容，`${VAR}` 替换为环境变量
+    pub fn parse(content: &str) -> Result<Self> {
+        Self::parse_with(content, |name| env::var(name).ok())
+    }
+
+    /// 同 [`Npmrc::parse`]，`${VAR}` 的值由 `var` 提供
+    pub fn parse_with(content: &str, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut npmrc = Self::default();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = expand_env(value.trim().trim_matches('"'), &var)?;
+
+            if key == "registry" {
+                npmrc.registry = Some(value);
+            } else if let Some(scope) = key
+                .strip_prefix('@')
+                .and_then(|key| key.strip_suffix(":registry"))
+            {
+                npmrc.scopes.insert(scope.to_string(), value);
+            } else if let Some((prefix, field)) = key.rsplit_once(':')
+                && prefix.starts_with("//")
+            {
+                let auth = npmrc.auth.entry(with_trailing_slash(prefix)).or_default();
+                match field {
+                    "_authToken" => auth.token = Some(value),
+                    "_auth" => auth.basic = Some(value),
+                    "username" => auth.username = Some(value),
+                    "_password" => auth.password = Some(value),
+                    _ => {}
+                }
+            }
+        }
+        Ok(npmrc)
+    }
+
+    /// 以 `other` 覆盖当前配置
+    pub fn merge(&mut self, other: Self) {
+        if other.registry.is_some() {
+            self.registry = other.registry;
+        }
+        self.scopes.extend(other.scopes);
+        self.auth.extend(other.auth);
+    }
+
+    /// 包对应的 registry：scope registry、默认 registry，最后为 npmjs
+    pub fn registry_for(&self, package: &str) -> String {
+        let scoped = package
+            .strip_prefix('@')
+            .and_then(|name| name.split_once('/'))
+            .and_then(|(scope, _)| self.scopes.get(scope));
+        let registry = scoped
+            .or(self.registry.as_ref())
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_REGISTRY);
+        with_trailing_slash(registry)
+    }
+
+    /// 请求 `url` 时的 `Authorization` 头，按最长的 registry 路径前缀匹配
+    pub fn authorization(&self, url: &str) -> Result<Option<String>> {
+        let target = nerf_dart(url);
+        let Some(auth) = self
+            .auth
+            .iter()
+            .filter(|(prefix, _)| target.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, auth)| auth)
+        else {
+            return Ok(None);
+        };
+
+        if let Some(token) = &auth.token {
+            return Ok(Some(format!("Bearer {}", token)));
+        }
+        if let Some(basic) = &auth.basic {
+            return Ok(Some(format!("Basic {}", basic)));
+        }
+        if let (Some(username), Some(password)) = (&auth.username, &auth.password) {
+            let password = BASE64.decode(password).map_err(|e| {
+                GeneratorError::Configuration(format!("Invalid _password for {}: {}", target, e))
+            })?;
+            let credentials = [username.as_bytes(), b":", &password].concat();
+            return Ok(Some(format!("Basic {}", BASE64.encode(credentials))));
+        }
+        Ok(None)
+    }
+}
+
+/// 去掉协议的 registry 路径（npm 的 “nerf dart”），如 `//npm.example.com/repo/`
+fn nerf_dart(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    format!("//{}", rest)
+}
+
+fn with_trailing_slash(value: &str) -> String {
+    if value.ends_with('/') {
+        value.to_string()
+    } else {
+        format!("{}/", value)
+    }
+}
+
+/// 替换 `${VAR}`，变量未设置时报错（与 npm 行为一致）
+fn expand_env(value: &str, var: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + end];
+        let replacement = var(name).ok_or_else(|| {
+            GeneratorError::Configuration(format!("Environment variable {} is not set", name))
+        })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&replacement);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_npmrc_scopes_and_auth() {
+        let var = |name: &str| (name == "NPM_TOKEN").then(|| "secret-token".to_string());
+        let npmrc = Npmrc::parse_with(
+            r#"
+# 公司私有 registry
+registry=https://registry.npmjs.org/
+@acme:registry=https://npm.acme.dev/repo
+//npm.acme.dev/repo/:_authToken=${NPM_TOKEN}
+//legacy.acme.dev/:username=ci
+//legacy.acme.dev/:_password=cGFzcw==
+"#,
+            var,
+        )
+        .unwrap();
+
+        assert_eq!(
+            npmrc.registry_for("@acme/vue-template"),
+            "https://npm.acme.dev/repo/"
+        );
+        assert_eq!(npmrc.registry_for("vue-template"), DEFAULT_REGISTRY);
+        assert_eq!(
+            npmrc
+                .authorization("https://npm.acme.dev/repo/@acme%2fvue-template")
+                .unwrap(),
+            Some("Bearer secret-token".to_string())
+        );
+        assert_eq!(
+            npmrc
+                .authorization("https://legacy.acme.dev/pkg/-/pkg-1.0.0.tgz")
+                .unwrap(),
+            Some(format!("Basic {}", BASE64.encode("ci:pass")))
+        );
+        assert_eq!(
+            npmrc
+                .authorization("https://registry.npmjs.org/vue")
+                .unwrap(),
+            None
+        );
+        assert!(Npmrc::parse_with("//x/:_authToken=${NPM_UNSET}", var).is_err());
+    }
+}
@@ -0,0 +1,778 @@
+//! OpenAPI 脚手架模块
+//! 按 OpenAPI 规范在生成的项目中搭建 API 客户端或服务端桩代码：java 委托 Java CLI 生成 Spring 代码，vue / react 生成 TypeScript 客户端
+
+use crate::java_cli::{self, JavaCliConfig, JavaOpenApiOptions};
+use crate::lockfile::OpenApiLock;
+use crate::network::NetworkConfig;
+use crate::{GeneratorError, Result, cancel, command_audit};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 项目中保存规范副本的目录
+pub const SPEC_DIR: &str = "openapi";
+/// TypeScript 客户端的输出目录
+pub const TS_CLIENT_DIR: &str = "src/api";
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "patch", "head", "options"];
+
+/// 生成模式
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenApiMode {
+    /// 调用 API 的客户端
+    #[napi(value = "client")]
+    Client,
+    /// 实现 API 的服务端桩代码
+    #[napi(value = "server")]
+    Server,
+}
+
+impl OpenApiMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OpenApiMode::Client => "client",
+            OpenApiMode::Server => "server",
+        }
+    }
+
+    /// 项目类型支持的模式，未指定时 java 生成服务端、vue / react 生成客户端
+    pub fn resolve(project_type: &str, mode: Option<Self>) -> Result<Self> {
+        let mode = match mode {
+            Some(mode) => mode,
+            None if project_type == "java" => OpenApiMode::Server,
+            None => OpenApiMode::Client,
+        };
+        match (project_type, mode) {
+            ("java", _) | ("vue" | "react", OpenApiMode::Client) => Ok(mode),
+            _ => Err(GeneratorError::Configuration(format!(
+                "OpenAPI {} generation is not available for project type '{}'",
+                mode, project_type
+            ))),
+        }
+    }
+}
+
+impl FromStr for OpenApiMode {
+    type Err = GeneratorError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "client" => Ok(OpenApiMode::Client),
+            "server" => Ok(OpenApiMode::Server),
+            other => Err(GeneratorError::Configuration(format!(
+                "Unknown OpenAPI mode '{}', expected client or server",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for OpenApiMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 已加载的 OpenAPI 规范
+#[derive(Debug, Clone)]
+pub struct OpenApiSpec {
+    /// 原始路径或 URL
+    pub source: String,
+    pub content: String,
+    /// 内容的 SHA-256（十六进制）
+    pub sha256: String,
+    document: Value,
+}
+
+impl OpenApiSpec {
+    /// 从本地路径或 http(s) URL 读取规范
+    pub fn load(source: &str, network: &NetworkConfig, timeout: Option<Duration>) -> Result<Self> {
+        let content = if source.starts_with("http://") || source.starts_with("https://") {
+            cancel::check()?;
+            command_audit::record_remote("http", &["GET", source])?;
+            let agent = network.http_agent(source, timeout)?;
+            let response = network.retry_policy().call(
+                source,
+                || agent.get(source),
+                |e| GeneratorError::FileOperation(format!("Failed to download {}: {}", source, e)),
+            )?;
+            let mut content = String::new();
+            response.into_reader().read_to_string(&mut content)?;
+            content
+        } else {
+            fs::read_to_string(source).map_err(|e| {
+                GeneratorError::FileOperation(format!("Cannot read {}: {}", source, e))
+            })?
+        };
+        Self::parse(source, content)
+    }
+
+    /// 解析 JSON 或 YAML 格式的规范
+    pub fn parse(source: &str, content: String) -> Result<Self> {
+        let document: Value = if content.trim_start().starts_with('{') {
+            serde_json::from_str(&content)?
+        } else {
+            serde_yaml::from_str(&content).map_err(|e| {
+                GeneratorError::Configuration(format!("Invalid OpenAPI spec {}: {}", source, e))
+            })?
+        };
+        if document.get("openapi").is_none() && document.get("swagger").is_none() {
+            return Err(GeneratorError::Configuration(format!(
+                "{} is not an OpenAPI document: missing 'openapi' version",
+                source
+            )));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            sha256: hex::encode(Sha256::digest(content.as_bytes())),
+            content,
+            document,
+        })
+    }
+
+    /// 项目中保存的规范副本（相对项目根目录）
+    pub fn file(&self) -> PathBuf {
+        let json = self.content.trim_start().starts_with('{');
+        Path::new(SPEC_DIR).join(if json { "openapi.json" } else { "openapi.yaml" })
+    }
+
+    /// 写入锁文件的规范信息
+    pub fn lock(&self, mode: OpenApiMode) -> OpenApiLock {
+        OpenApiLock {
+            source: self.source.clone(),
+            file: self.file().to_string_lossy().replace('\\', "/"),
+            sha256: self.sha256.clone(),
+            mode: mode.to_string(),
+        }
+    }
+}
+
+/// 一次 OpenAPI 脚手架生成
+#[derive(Debug, Clone)]
+pub struct OpenApiScaffold {
+    pub spec: OpenApiSpec,
+    pub mode: OpenApiMode,
+    pub project_type: String,
+    /// java 项目委托 Java CLI 时使用的配置
+    pub java_cli: JavaCliConfig,
+}
+
+impl OpenApiScaffold {
+    /// 保存规范副本并生成桩代码，返回写入的文件（相对项目目录）
+    pub fn scaffold(
+        &self,
+        project_dir: &Path,
+        variables: &HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let spec_file = self.spec.file();
+        let spec_path = project_dir.join(&spec_file);
+        if let Some(parent) = spec_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&spec_path, &self.spec.content)?;
+        let mut files = vec![spec_file];
+
+        if self.project_type == "java" {
+            files.extend(self.java_stubs(project_dir, &spec_path, variables)?);
+        } else {
+            for (file, content) in typescript_client(&self.spec.document) {
+                let path = project_dir.join(&file);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+                files.push(file);
+            }
+        }
+        Ok(files)
+    }
+
+    fn java_stubs(
+        &self,
+        project_dir: &Path,
+        spec_path: &Path,
+        variables: &HashMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let options = JavaOpenApiOptions {
+            spec: spec_path.to_path_buf(),
+            mode: self.mode.to_string(),
+            package_name: variables.get("package").cloned(),
+            output_path: project_dir.to_path_buf(),
+            cli: self.java_cli.clone(),
+            progress: None,
+        };
+        let generated = java_cli::block_on(java_cli::generate_openapi_stubs(options))?;
+
+        Ok(generated
+            .into_iter()
+            .map(PathBuf::from)
+            .map(|path| match path.strip_prefix(project_dir) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => path,
+            })
+            .collect())
+    }
+}
+
+/// TypeScript 客户端：`types.ts` 中的模型与 `client.ts` 中每个操作对应的请求函数
+fn typescript_client(document: &Value) -> Vec<(PathBuf, String)> {
+    let empty = Map::new();
+    let schemas = document
+        .pointer("/components/schemas")
+        .or_else(|| document.get("definitions"))
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    let mut types = String::from("// 由 OpenAPI 规范生成，请勿手动修改\n");
+    for (name, schema) in schemas {
+        types.push('\n');
+        if let Some(description) = schema.get("description").and_then(Value::as_str) {
+            types.push_str(&format!("/** {} */\n", doc_line(description)));
+        }
+        let name = type_name(name);
+        if schema.get("properties").is_some() && schema.get("allOf").is_none() {
+            types.push_str(&format!(
+                "export interface {} {}\n",
+                name,
+                object_type(schema, &mut BTreeSet::new(), 0)
+            ));
+        } else {
+            types.push_str(&format!(
+                "export type {} = {};\n",
+                name,
+                ts_type(schema, &mut BTreeSet::new())
+            ));
+        }
+    }
+
+    let base_url = document
+        .pointer("/servers/0/url")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let mut referenced = BTreeSet::new();
+    let mut operations = String::new();
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    for (path, item) in paths {
+        for method in HTTP_METHODS {
+            if let Some(operation) = item.get(*method) {
+                operations.push_str(&operation_function(
+                    document,
+                    path,
+                    method,
+                    item,
+                    operation,
+                    &mut referenced,
+                ));
+            }
+        }
+    }
+
+    let mut client = String::from("// 由 OpenAPI 规范生成，请勿手动修改\n");
+    if !referenced.is_empty() {
+        let names: Vec<&str> = referenced.iter().map(String::as_str).collect();
+        client.push_str(&format!(
+            "import type {{ {} }} from './types';\n",
+            names.join(", ")
+        ));
+    }
+    client.push_str(&format!(
+        "
+export let baseUrl = '{}';
+
+export function setBaseUrl(url: string) {{
+  baseUrl = url;
+}}
+
+async function request<T>(
+  method: string,
+  path: string,
+  options: {{ query?: Record<string, unknown>; body?: unknown }} = {{}},
+): Promise<T> {{
+  const url = new URL(baseUrl + path, window.location.origin);
+  for (const [key, value] of Object.entries(options.query ?? {{}})) {{
+    if (value !== undefined && value !== null) {{
+      url.searchParams.set(key, String(value));
+    }}
+  }}
+  const response = await fetch(url, {{
+    method,
+    headers: options.body === undefined ? undefined : {{ 'Content-Type': 'application/json' }},
+    body: options.body === undefined ? undefined : JSON.stringify(options.body),
+  }});
+  if (!response.ok) {{
+    throw new Error(`${{method}} ${{path}} failed: ${{response.status}}`);
+  }}
+  return (response.status === 204 ? undefined : await response.json()) as T;
+}}
+",
+        base_url.replace('\'', "\\'")
+    ));
+    client.push_str(&operations);
+
+    vec![
+        (Path::new(TS_CLIENT_DIR).join("types.ts"), types),
+        (Path::new(TS_CLIENT_DIR).join("client.ts"), client),
+    ]
+}
+
+fn operation_function(
+    document: &Value,
+    path: &str,
+    method: &str,
+    item: &Value,
+    operation: &Value,
+    referenced: &mut BTreeSet<String>,
+) -> String {
+    let name = match operation.get("operationId").and_then(Value::as_str) {
+        Some(id) => identifier(id),
+        None => identifier(&format!(
+            "{} {}",
+            method,
+            path.replace('{', " by ").replace('}', " ")
+        )),
+    };
+
+    // 路径级参数在前，操作级同名参数覆盖
+    let mut parameters: Vec<&Value> = Vec::new();
+    for parameter in [item, operation]
+        .into_iter()
+        .filter_map(|value| value.get("parameters").and_then(Value::as_array))
+        .flatten()
+    {
+        let parameter = resolve_ref(document, parameter);
+        let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+        parameters.retain(|existing| key(existing) != key(parameter));
+        parameters.push(parameter);
+    }
+
+    let mut arguments = Vec::new();
+    let mut url = path.to_string();
+    let mut query = Vec::new();
+    let mut query_required = false;
+    for parameter in &parameters {
+        let Some(parameter_name) = parameter.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let schema = parameter.get("schema").unwrap_or(parameter);
+        let ty = ts_type(schema, referenced);
+        let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+        match parameter.get("in").and_then(Value::as_str) {
+            Some("path") => {
+                let argument = identifier(parameter_name);
+                url = url.replace(
+                    &format!("{{{}}}", parameter_name),
+                    &format!("${{encodeURIComponent(String({}))}}", argument),
+                );
+                arguments.push(format!("{}: {}", argument, ty));
+            }
+            Some("query") => {
+                query_required |= required;
+                query.push(format!(
+                    "{}{}: {}",
+                    property_name(parameter_name),
+                    if required { "" } else { "?" },
+                    ty
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let body = operation
+        .get("requestBody")
+        .map(|body| resolve_ref(document, body))
+        .and_then(|body| {
+            let schema = body.pointer("/content/application~1json/schema")?;
+            let required = body.get("required").and_then(Value::as_bool) == Some(true);
+            Some((ts_type(schema, referenced), required))
+        });
+    // 可选参数必须位于必填参数之后
+    if let Some((ty, true)) = &body {
+        arguments.push(format!("body: {}", ty));
+    }
+    if !query.is_empty() {
+        arguments.push(format!(
+            "query{}: {{ {} }}",
+            if query_required { "" } else { "?" },
+            query.join("; ")
+        ));
+    }
+    if let Some((ty, false)) = &body {
+        arguments.push(format!("body?: {}", ty));
+    }
+
+    let response = operation
+        .get("responses")
+        .and_then(Value::as_object)
+        .and_then(|responses| {
+            ["200", "201", "202", "2XX", "default"]
+                .iter()
+                .find_map(|status| responses.get(*status))
+        })
+        .map(|response| resolve_ref(document, response))
+        .and_then(|response| response.pointer("/content/application~1json/schema"))
+        .map(|schema| ts_type(schema, referenced))
+        .unwrap_or_else(|| "void".to_string());
+
+    let mut options = Vec::new();
+    if !query.is_empty() {
+        options.push("query");
+    }
+    if body.is_some() {
+        options.push("body");
+    }
+    let options = if options.is_empty() {
+        String::new()
+    } else {
+        format!(", {{ {} }}", options.join(", "))
+    };
+
+    let mut function = String::from("\n");
+    if let Some(summary) = operation
+        .get("summary")
+        .or_else(|| operation.get("description"))
+        .and_then(Value::as_str)
+    {
+        function.push_str(&format!("/** {} */\n", doc_line(summary)));
+    }
+    function.push_str(&format!(
+        "export function {}({}): Promise<{}> {{\n  return request<{}>('{}', `{}`{});\n}}\n",
+        name,
+        arguments.join(", "),
+        response,
+        response,
+        method.to_ascii_uppercase(),
+        url,
+        options
+    ));
+    function
+}
+
+/// 解析指向文档内部的 `$ref`
+fn resolve_ref<'a>(document: &'a Value, value: &'a Value) -> &'a Value {
+    value
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'))
+        .and_then(|pointer| document.pointer(pointer))
+        .unwrap_or(value)
+}
+
+/// JSON Schema 对应的 TypeScript 类型，引用的模型名记入 `referenced`
+fn ts_type(schema: &Value, referenced: &mut BTreeSet<String>) -> String {
+    let ty = if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = type_name(reference.rsplit('/').next().unwrap_or(reference));
+        referenced.insert(name.clone());
+        name
+    } else if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        values
+            .iter()
+            .map(|value| match value {
+                Value::String(value) => format!("'{}'", value.replace('\'', "\\'")),
+                value => value.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    } else if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        composite(parts, " & ", referenced)
+    } else if let Some(parts) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        composite(parts, " | ", referenced)
+    } else {
+        match schema.get("type").and_then(Value::as_str) {
+            Some("string") if schema.get("format").and_then(Value::as_str) == Some("binary") => {
+                "Blob".to_string()
+            }
+            Some("string") => "string".to_string(),
+            Some("integer" | "number") => "number".to_string(),
+            Some("boolean") => "boolean".to_string(),
+            Some("array") => {
+                let item = schema
+                    .get("items")
+                    .map(|items| ts_type(items, referenced))
+                    .unwrap_or_else(|| "unknown".to_string());
+                if item.contains(' ') {
+                    format!("({})[]", item)
+                } else {
+                    format!("{}[]", item)
+                }
+            }
+            _ if schema.get("properties").is_some() => object_type(schema, referenced, 0),
+            _ => match schema.get("additionalProperties") {
+                Some(Value::Object(values)) => format!(
+                    "Record<string, {}>",
+                    ts_type(&Value::Object(values.clone()), referenced)
+                ),
+                _ if schema.get("type").and_then(Value::as_str) == Some("object") => {
+                    "Record<string, unknown>".to_string()
+                }
+                _ => "unknown".to_string(),
+            },
+        }
+    };
+
+    if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        format!("{} | null", ty)
+    } else {
+        ty
+    }
+}
+
+fn composite(parts: &[Value], separator: &str, referenced: &mut BTreeSet<String>) -> String {
+    parts
+        .iter()
+        .map(|part| {
+            let ty = ts_type(part, referenced);
+            if ty.contains(' ') && !ty.starts_with('{') {
+                format!("({})", ty)
+            } else {
+                ty
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 带 `properties` 的对象类型，`depth` 为嵌套层级
+fn object_type(schema: &Value, referenced: &mut BTreeSet<String>, depth: usize) -> String {
+    let required: BTreeSet<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return "Record<string, unknown>".to_string();
+    };
+
+    let indent = "  ".repeat(depth + 1);
+    let mut object = String::from("{\n");
+    for (name, property) in properties {
+        if let Some(description) = property.get("description").and_then(Value::as_str) {
+            object.push_str(&format!("{}/** {} */\n", indent, doc_line(description)));
+        }
+        let ty = if property.get("properties").is_some() && property.get("$ref").is_none() {
+            object_type(property, referenced, depth + 1)
+        } else {
+            ts_type(property, referenced)
+        };
+        object.push_str(&format!(
+            "{}{}{}: {};\n",
+            indent,
+            property_name(name),
+            if required.contains(name.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            ty
+        ));
+    }
+    object.push_str(&"  ".repeat(depth));
+    object.push('}');
+    object
+}
+
+/// 模型名：去掉非法字符并转为 PascalCase
+fn type_name(name: &str) -> String {
+    let name: String = words(name).iter().map(|word| capitalize(word)).collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        format!("Model{}", name)
+    } else {
+        name
+    }
+}
+
+/// 函数与参数名：camelCase
+fn identifier(name: &str) -> String {
+    let words = words(name);
+    let mut identifier = String::new();
+    for (index, word) in words.iter().enumerate() {
+        if index == 0 {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                identifier.extend(first.to_lowercase());
+                identifier.push_str(chars.as_str());
+            }
+        } else {
+            identifier.push_str(&capitalize(word));
+        }
+    }
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) || identifier.is_empty() {
+        format!("_{}", identifier)
+    } else {
+        identifier
+    }
+}
+
+/// 属性名不是合法标识符时加引号
+fn property_name(name: &str) -> String {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if valid {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "\\'"))
+    }
+}
+
+fn words(name: &str) -> Vec<&str> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// 单行文档注释，避免描述中的 `*/` 提前结束注释
+fn doc_line(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("*/", "*\\/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const SPEC: &str = r#"openapi: 3.0.3
+servers:
+  - url: https://api.example.com/v1
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: List all pets
+      parameters:
+        - name: limit
+          in: query
+          schema: { type: integer }
+      responses:
+        '200':
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: '#/components/schemas/Pet' } }
+    post:
+      operationId: create-pet
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: { $ref: '#/components/schemas/NewPet' }
+      responses:
+        '201':
+          content:
+            application/json:
+              schema: { $ref: '#/components/schemas/Pet' }
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema: { type: string }
+    delete:
+      responses:
+        '204': { description: deleted }
+components:
+  schemas:
+    NewPet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string }
+        tag: { type: string, nullable: true }
+    Pet:
+      allOf:
+        - $ref: '#/components/schemas/NewPet'
+        - type: object
+          properties:
+            id: { type: integer }
+    Status:
+      type: string
+      enum: [available, sold]
+"#;
+
+    #[test]
+    fn test_scaffold_typescript_client() {
+        let project = tempdir().unwrap();
+        let spec = OpenApiSpec::parse("pets.yaml", SPEC.to_string()).unwrap();
+        let scaffold = OpenApiScaffold {
+            spec: spec.clone(),
+            mode: OpenApiMode::resolve("vue", None).unwrap(),
+            project_type: "vue".to_string(),
+            java_cli: JavaCliConfig::default(),
+        };
+        let files = scaffold.scaffold(project.path(), &HashMap::new()).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("openapi/openapi.yaml"),
+                PathBuf::from("src/api/types.ts"),
+                PathBuf::from("src/api/client.ts"),
+            ]
+        );
+
+        let read = |path: &str| fs::read_to_string(project.path().join(path)).unwrap();
+        assert_eq!(read("openapi/openapi.yaml"), SPEC);
+        let types = read("src/api/types.ts");
+        assert!(
+            types.contains(
+                "export interface NewPet {\n  name: string;\n  tag?: string | null;\n}\n"
+            )
+        );
+        assert!(types.contains("export type Pet = NewPet & {\n  id?: number;\n};\n"));
+        assert!(types.contains("export type Status = 'available' | 'sold';\n"));
+
+        let client = read("src/api/client.ts");
+        assert!(client.contains("import type { NewPet, Pet } from './types';\n"));
+        assert!(client.contains("export let baseUrl = 'https://api.example.com/v1';\n"));
+        assert!(client.contains(
+            "/** List all pets */\nexport function listPets(query?: { limit?: number }): Promise<Pet[]> {\n  \
+             return request<Pet[]>('GET', `/pets`, { query });\n}\n"
+        ));
+        assert!(client.contains("export function createPet(body: NewPet): Promise<Pet> {\n"));
+        assert!(client.contains(
+            "export function deletePetsByPetId(petId: string): Promise<void> {\n  \
+             return request<void>('DELETE', `/pets/${encodeURIComponent(String(petId))}`);\n}\n"
+        ));
+
+        let lock = spec.lock(scaffold.mode);
+        assert_eq!(
+            (lock.file.as_str(), lock.mode.as_str()),
+            ("openapi/openapi.yaml", "client")
+        );
+        assert_eq!(lock.sha256.len(), 64);
+        assert!(OpenApiMode::resolve("react", Some(OpenApiMode::Server)).is_err());
+        assert_eq!(
+            OpenApiMode::resolve("java", None).unwrap(),
+            OpenApiMode::Server
+        );
+        assert!(OpenApiSpec::parse("x.json", "{\"paths\": {}}".to_string()).is_err());
+    }
+}
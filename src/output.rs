@@ -0,0 +1,108 @@
+//! 命令行输出模块
+//! 控制提示与日志的去向：默认写入 stdout；JSON 模式写入 stderr，保证 stdout 只有结构化结果；安静模式不输出
+
+use crate::events::{self, Event};
+use crate::{GeneratorError, Result};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 输出模式
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    /// 带表情符号的可读输出
+    #[napi(value = "human")]
+    Human,
+    /// stdout 输出 JSON，日志写入 stderr
+    #[napi(value = "json")]
+    Json,
+    /// 只输出结果与错误
+    #[napi(value = "quiet")]
+    Quiet,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+impl OutputMode {
+    /// 解析 `human`、`json`、`quiet`
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "quiet" => Ok(Self::Quiet),
+            _ => Err(GeneratorError::Configuration(format!(
+                "Unknown output mode: {} (expected human, json or quiet)",
+                value
+            ))),
+        }
+    }
+}
+
+/// 设置进程范围内的输出模式
+pub fn set_mode(mode: OutputMode) {
+    let value = match mode {
+        OutputMode::Human => 0,
+        OutputMode::Json => 1,
+        OutputMode::Quiet => 2,
+    };
+    MODE.store(value, Ordering::Relaxed);
+}
+
+/// 当前输出模式
+pub fn mode() -> OutputMode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => OutputMode::Json,
+        2 => OutputMode::Quiet,
+        _ => OutputMode::Human,
+    }
+}
+
+/// 输出一行日志，见 [`say!`](crate::say)；注册了事件监听者时转发给监听者
+pub fn log(args: fmt::Arguments) {
+    if events::emit(Event::Log {
+        message: args.to_string(),
+    }) {
+        return;
+    }
+    match mode() {
+        OutputMode::Human => println!("{}", args),
+        OutputMode::Json => eprintln!("{}", args),
+        OutputMode::Quiet => {}
+    }
+}
+
+/// 输出交互提示（不换行）；非可读模式下写入 stderr，避免混入 JSON 结果
+pub fn prompt(args: fmt::Arguments) -> Result<()> {
+    if mode() == OutputMode::Human {
+        print!("{}", args);
+        io::stdout().flush()?;
+    } else {
+        eprint!("{}", args);
+        io::stderr().flush()?;
+    }
+    Ok(())
+}
+
+/// 按当前输出模式打印日志行
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        $crate::output::log(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output_mode() {
+        assert_eq!(OutputMode::parse("json").unwrap(), OutputMode::Json);
+        assert_eq!(OutputMode::parse("quiet").unwrap(), OutputMode::Quiet);
+        assert!(OutputMode::parse("yaml").is_err());
+    }
+}
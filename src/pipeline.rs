@@ -0,0 +1,1064 @@
+//! 模板生成管线
+//! 遍历模板目录，按忽略规则过滤，渲染文件路径与内容后写入输出目录
+
+use crate::cancel;
+use crate::condition::Condition;
+use crate::cookiecutter;
+use crate::features::FEATURES_DIR;
+use crate::render::TemplateRenderer;
+use crate::template_ignore::{IGNORE_FILE_NAME, TemplateIgnore};
+use crate::template_lint::{Placeholder, placeholders};
+use crate::template_registry::{TemplateFileRule, TemplateMetadata};
+use crate::template_version;
+use crate::text_format::{self, BomPolicy, LineEnding, TextFormatter};
+use crate::utils::{SymlinkPolicy, copy_permissions, copy_symlink};
+use crate::{GeneratorError, Result, workspace};
+use globset::{GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 模板元数据文件名
+pub const TEMPLATE_METADATA_FILE: &str = "template.json";
+
+/// 模板钩子脚本目录
+pub const HOOKS_DIR: &str = "hooks";
+
+/// 生成后展示给用户的“下一步”说明，用最终的变量渲染；在忽略规则中声明后不写入输出目录
+pub const NEXT_STEPS_FILE: &str = "next_steps.md.hbs";
+
+/// 文件名后缀：去掉后缀输出，内容不渲染（如 `App.vue.raw` → `App.vue`）
+pub const RAW_SUFFIX: &str = ".raw";
+
+/// 模板目录中的元数据文件：template.json，或兼容的 cookiecutter.json
+pub fn metadata_file(template_dir: &Path) -> Option<PathBuf> {
+    [TEMPLATE_METADATA_FILE, cookiecutter::COOKIECUTTER_FILE]
+        .iter()
+        .map(|file| template_dir.join(file))
+        .find(|path| path.is_file())
+}
+
+/// 读取模板目录下的 template.json；只有 cookiecutter.json 时按 cookiecutter 模板构造
+pub fn load_template_metadata(template_dir: &Path) -> Result<TemplateMetadata> {
+    if cookiecutter::is_cookiecutter_template(template_dir) {
+        return cookiecutter::load_metadata(template_dir);
+    }
+    let content = fs::read_to_string(template_dir.join(TEMPLATE_METADATA_FILE))?;
+    parse_template_metadata(&content)
+}
+
+/// 解析 template.json；先检查 `min_generator_version`，避免旧版本生成器误解析新增的字段
+pub fn parse_template_metadata(content: &str) -> Result<TemplateMetadata> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+    if let Some(required) = value.get("min_generator_version").and_then(|v| v.as_str()) {
+        let name = value
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        template_version::check_generator_version(name, required)?;
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// 将模板目录渲染到输出目录
+pub fn render_template_dir(
+    template_dir: &Path,
+    output_dir: &Path,
+    metadata: &TemplateMetadata,
+    variables: &HashMap<String, String>,
+    options: &RenderOptions,
+) -> Result<RenderReport> {
+    let ignore = TemplateIgnore::load(template_dir, &metadata.ignore)?;
+    let file_filter = FileFilter::new(&metadata.files, variables)?;
+    let renderer = TemplateRenderer::load(template_dir, metadata)?;
+    let context = RenderContext::new(metadata, variables, options, &renderer)?;
+
+    render_tree(template_dir, output_dir, &context, |relative, is_dir| {
+        !is_reserved_file(metadata, relative)
+            && !ignore.is_ignored(relative, is_dir)
+            && file_filter.is_included(relative)
+    })
+}
+
+/// 只渲染模板中的指定文件或目录（相对模板目录），仍遵循忽略与条件规则
+pub fn render_template_files(
+    template_dir: &Path,
+    output_dir: &Path,
+    metadata: &TemplateMetadata,
+    variables: &HashMap<String, String>,
+    options: &RenderOptions,
+    files: &[PathBuf],
+) -> Result<RenderReport> {
+    let ignore = TemplateIgnore::load(template_dir, &metadata.ignore)?;
+    let file_filter = FileFilter::new(&metadata.files, variables)?;
+    let renderer = TemplateRenderer::load(template_dir, metadata)?;
+    let context = RenderContext::new(metadata, variables, options, &renderer)?;
+
+    render_tree(template_dir, output_dir, &context, |relative, is_dir| {
+        // 选中的目录渲染其下全部文件
+        let selected = files
+            .iter()
+            .any(|file| relative.starts_with(file) || (is_dir && file.starts_with(relative)));
+        selected
+            && !is_reserved_file(metadata, relative)
+            && !ignore.is_ignored(relative, is_dir)
+            && file_filter.is_included(relative)
+    })
+}
+
+/// 将特性片段等覆盖目录渲染到输出目录，同名文件会被覆盖
+///
+/// `renderer` 来自基础模板，片段与基础模板使用同一引擎及其 helper、partial。
+pub fn render_overlay_dir(
+    overlay_dir: &Path,
+    output_dir: &Path,
+    metadata: &TemplateMetadata,
+    variables: &HashMap<String, String>,
+    options: &RenderOptions,
+    renderer: &TemplateRenderer,
+) -> Result<RenderReport> {
+    let context = RenderContext::new(metadata, variables, options, renderer)?;
+    render_tree(overlay_dir, output_dir, &context, |_, _| true)
+}
+
+/// 渲染选项
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// 符号链接处理策略
+    pub symlinks: SymlinkPolicy,
+    /// 并行写入的工作线程数，默认为 CPU 核数
+    pub workers: Option<usize>,
+    /// 渲染文本的换行符
+    pub line_endings: LineEnding,
+    /// 渲染文本的 UTF-8 BOM 处理
+    pub bom: BomPolicy,
+}
+
+/// 单次渲染共享的上下文
+struct RenderContext<'a> {
+    variables: &'a HashMap<String, String>,
+    options: &'a RenderOptions,
+    renderer: &'a TemplateRenderer,
+    /// 按扩展名视为二进制的文件（小写，不含点）
+    binary_extensions: Vec<String>,
+    raw: RawMatcher,
+    /// 文本输出的换行符与 BOM 规范化
+    text: TextFormatter,
+}
+
+impl<'a> RenderContext<'a> {
+    fn new(
+        metadata: &TemplateMetadata,
+        variables: &'a HashMap<String, String>,
+        options: &'a RenderOptions,
+        renderer: &'a TemplateRenderer,
+    ) -> Result<Self> {
+        let binary_extensions = DEFAULT_BINARY_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .chain(
+                metadata
+                    .binary_extensions
+                    .iter()
+                    .map(|ext| ext.trim_start_matches('.').to_lowercase()),
+            )
+            .collect();
+
+        Ok(Self {
+            variables,
+            options,
+            renderer,
+            binary_extensions,
+            raw: RawMatcher::new(&metadata.raw)?,
+            text: TextFormatter::new(&metadata.attributes, options.line_endings, options.bom)?,
+        })
+    }
+
+    /// 根据扩展名或内容判断是否为二进制文件
+    fn is_binary(&self, path: &Path, content: &[u8]) -> bool {
+        let by_extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| self.binary_extensions.contains(&ext));
+
+        by_extension || is_binary_content(content)
+    }
+}
+
+/// 原样复制、不渲染内容的文件：`raw` glob 或 `.raw` 后缀
+pub(crate) struct RawMatcher {
+    globs: GlobSet,
+}
+
+impl RawMatcher {
+    pub(crate) fn new(patterns: &[String]) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(
+                GlobBuilder::new(pattern)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| {
+                        GeneratorError::Configuration(format!(
+                            "Invalid raw pattern '{}': {}",
+                            pattern, e
+                        ))
+                    })?,
+            );
+        }
+        let globs = builder
+            .build()
+            .map_err(|e| GeneratorError::Configuration(e.to_string()))?;
+        Ok(Self { globs })
+    }
+
+    pub(crate) fn is_raw(&self, relative: &Path) -> bool {
+        relative.to_string_lossy().ends_with(RAW_SUFFIX) || self.globs.is_match(relative)
+    }
+}
+
+/// 以 `render: false` front-matter 开头的文件返回去掉该头部后的内容
+///
+/// ```text
+/// ---
+/// render: false
+/// ---
+/// ```
+pub(crate) fn raw_front_matter(content: &str) -> Option<&str> {
+    let body = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let end = body.find("\n---")?;
+    if !body[..end]
+        .lines()
+        .any(|line| line.trim() == "render: false")
+    {
+        return None;
+    }
+    let rest = &body[end + 4..];
+    Some(
+        rest.strip_prefix("\r\n")
+            .or_else(|| rest.strip_prefix('\n'))
+            .unwrap_or(rest),
+    )
+}
+
+/// 默认按二进制处理的扩展名
+const DEFAULT_BINARY_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "ico", "webp", "icns", "woff", "woff2", "ttf", "otf",
+    "eot", "jar", "war", "class", "zip", "gz", "tgz", "7z", "pdf", "exe", "dll", "so", "dylib",
+    "mp3", "mp4", "wasm",
+];
+
+/// 二进制内容嗅探的字节数
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// 通过空字节嗅探判断内容是否为二进制
+pub fn is_binary_content(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
+/// 渲染结果
+#[derive(Debug, Clone, Default)]
+pub struct RenderReport {
+    /// 写入文件的相对路径（按遍历顺序）
+    pub files: Vec<PathBuf>,
+    /// 写入的总字节数
+    pub bytes_written: u64,
+    /// 渲染后仍残留在输出中的占位符
+    pub unresolved: Vec<UnresolvedPlaceholder>,
+}
+
+/// 渲染后残留在输出文件中的占位符，通常是变量名拼写错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedPlaceholder {
+    /// 输出文件的相对路径
+    pub file: PathBuf,
+    /// 所在行号（从 1 开始）
+    pub line: usize,
+    /// 变量名
+    pub name: String,
+}
+
+impl std::fmt::Display for UnresolvedPlaceholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{{{}}}}} at {}:{}",
+            self.name,
+            self.file.to_string_lossy().replace('\\', "/"),
+            self.line
+        )
+    }
+}
+
+impl RenderReport {
+    /// 合并另一次渲染的结果，重复路径只保留一次
+    pub fn merge(&mut self, other: RenderReport) {
+        self.bytes_written += other.bytes_written;
+        self.unresolved.extend(other.unresolved);
+        for path in other.files {
+            if !self.files.contains(&path) {
+                self.files.push(path);
+            }
+        }
+    }
+}
+
+/// 待执行的文件写入任务
+struct FileJob {
+    src: PathBuf,
+    dest: PathBuf,
+    /// 输出目录内的相对路径
+    relative: PathBuf,
+    preserve_symlink: bool,
+    /// 内容原样复制
+    raw: bool,
+}
+
+/// 遍历目录树，渲染 `include` 接受的文件
+fn render_tree(
+    root: &Path,
+    output_dir: &Path,
+    context: &RenderContext,
+    include: impl Fn(&Path, bool) -> bool,
+) -> Result<RenderReport> {
+    let mut files = Vec::new();
+    let mut jobs = Vec::new();
+
+    let walker = WalkDir::new(root)
+        .follow_links(context.options.symlinks == SymlinkPolicy::Follow)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| match entry.path().strip_prefix(root) {
+            Ok(relative) => include(relative, entry.file_type().is_dir()),
+            Err(_) => false,
+        });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| match e.loop_ancestor() {
+            Some(ancestor) => GeneratorError::FileOperation(format!(
+                "Symlink cycle detected: {} points to {}",
+                e.path()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default(),
+                ancestor.display()
+            )),
+            None => GeneratorError::FileOperation(e.to_string()),
+        })?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let is_symlink = entry.path_is_symlink();
+        if is_symlink && context.options.symlinks == SymlinkPolicy::Skip {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(root)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        let target_relative = render_relative_path(relative, context.variables, context.renderer)?;
+
+        jobs.push(FileJob {
+            src: entry.path().to_path_buf(),
+            dest: workspace::safe_join(output_dir, &target_relative)?,
+            relative: target_relative.clone(),
+            preserve_symlink: is_symlink && context.options.symlinks == SymlinkPolicy::Preserve,
+            raw: context.raw.is_raw(relative),
+        });
+        files.push(target_relative);
+    }
+    check_case_collisions(output_dir, &files)?;
+
+    let mut report = run_jobs(&jobs, context)?;
+    report.files = files;
+    Ok(report)
+}
+
+/// 检查只有大小写不同的输出路径
+///
+/// macOS、Windows 的文件系统默认不区分大小写，`Readme.md` 与 `README.md` 会写入同一个文件；
+/// 渲染结果之间、以及渲染结果与输出目录中已有条目之间的这类冲突都在写入前报错。
+fn check_case_collisions(output_dir: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut seen: HashMap<String, PathBuf> = HashMap::new();
+    let mut existing: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    let mut collisions = Vec::new();
+
+    for file in files {
+        let mut prefix = PathBuf::new();
+        for component in file.components() {
+            let parent = prefix.clone();
+            prefix.push(component);
+            let name = component.as_os_str().to_string_lossy().to_string();
+
+            let key = prefix.to_string_lossy().to_lowercase();
+            match seen.get(&key) {
+                Some(other) if *other != prefix || prefix == *file => {
+                    collisions.push(format!("{} <-> {}", other.display(), prefix.display()));
+                    break;
+                }
+                Some(_) => continue,
+                None => {
+                    seen.insert(key, prefix.clone());
+                }
+            }
+
+            let entries = existing.entry(parent.clone()).or_insert_with(|| {
+                fs::read_dir(output_dir.join(&parent))
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .map(|entry| entry.file_name().to_string_lossy().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            });
+            if let Some(entry) = entries
+                .iter()
+                .find(|entry| **entry != name && entry.to_lowercase() == name.to_lowercase())
+            {
+                collisions.push(format!(
+                    "{} <-> {} (existing)",
+                    prefix.display(),
+                    parent.join(entry).display()
+                ));
+                break;
+            }
+        }
+    }
+
+    if collisions.is_empty() {
+        return Ok(());
+    }
+    Err(GeneratorError::Conflict(format!(
+        "Paths differ only in letter case and would overwrite each other on case-insensitive \
+         file systems: {}",
+        collisions.join(", ")
+    )))
+}
+
+/// 在有界线程池中执行写入任务，汇总写入的字节数与残留占位符
+fn run_jobs(jobs: &[FileJob], context: &RenderContext) -> Result<RenderReport> {
+    // 工作线程中没有线程级令牌，先在调用线程取出
+    let token = cancel::current();
+    let run = |job: &FileJob| -> Result<RenderReport> {
+        token.check()?;
+        if job.preserve_symlink {
+            copy_symlink(&job.src, &job.dest)
+                .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+            return Ok(RenderReport::default());
+        }
+        let (bytes_written, leftovers) = write_rendered_file(job, context)?;
+        Ok(RenderReport {
+            files: Vec::new(),
+            bytes_written,
+            unresolved: leftovers
+                .into_iter()
+                .map(|(line, name)| UnresolvedPlaceholder {
+                    file: job.relative.clone(),
+                    line,
+                    name,
+                })
+                .collect(),
+        })
+    };
+    let collect = |reports: Vec<RenderReport>| {
+        reports
+            .into_iter()
+            .fold(RenderReport::default(), |mut total, report| {
+                total.merge(report);
+                total
+            })
+    };
+
+    if context.options.workers == Some(1) || jobs.len() <= 1 {
+        return jobs.iter().map(run).collect::<Result<_>>().map(collect);
+    }
+
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(workers) = context.options.workers {
+        builder = builder.num_threads(workers);
+    }
+    let pool = builder
+        .build()
+        .map_err(|e| GeneratorError::Unknown(format!("Failed to start worker pool: {}", e)))?;
+
+    pool.install(|| jobs.par_iter().map(run).collect::<Result<_>>())
+        .map(collect)
+}
+
+/// 按条件包含的文件过滤器
+struct FileFilter {
+    /// 规则匹配器及其条件求值结果
+    rules: Vec<(GlobMatcher, bool)>,
+}
+
+impl FileFilter {
+    fn new(rules: &[TemplateFileRule], variables: &HashMap<String, String>) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let matcher = GlobBuilder::new(&rule.path)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| {
+                        GeneratorError::Configuration(format!(
+                            "Invalid file rule '{}': {}",
+                            rule.path, e
+                        ))
+                    })?
+                    .compile_matcher();
+                let included = match &rule.when {
+                    Some(expr) => Condition::parse(expr)?.evaluate(variables),
+                    None => true,
+                };
+                Ok((matcher, included))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// 所有匹配该路径的规则条件都成立时才包含
+    fn is_included(&self, relative: &Path) -> bool {
+        self.rules
+            .iter()
+            .filter(|(matcher, _)| matcher.is_match(relative))
+            .all(|(_, included)| *included)
+    }
+}
+
+/// 模板清单与忽略文件，以及清单中声明的特性片段目录和钩子目录不会被复制到输出目录
+///
+/// 变更日志、下一步说明与 partial 目录可能同时是生成项目的一部分，
+/// 只有在忽略规则中声明时才不复制。
+fn is_reserved_file(metadata: &TemplateMetadata, relative: &Path) -> bool {
+    relative == Path::new(TEMPLATE_METADATA_FILE)
+        || relative == Path::new(IGNORE_FILE_NAME)
+        || (!metadata.hooks.is_empty() && relative == Path::new(HOOKS_DIR))
+        || metadata.features.iter().any(|feature| match &feature.path {
+            Some(path) => relative == Path::new(path),
+            None => relative == Path::new(FEATURES_DIR).join(&feature.name),
+        })
+}
+
+/// 渲染路径中的变量，如 `src/{{name}}/index.ts`，并去掉 `.raw` 后缀
+pub(crate) fn render_relative_path(
+    relative: &Path,
+    variables: &HashMap<String, String>,
+    renderer: &TemplateRenderer,
+) -> Result<PathBuf> {
+    let rendered = renderer.render(&relative.to_string_lossy(), variables)?;
+    Ok(PathBuf::from(
+        rendered.strip_suffix(RAW_SUFFIX).unwrap_or(&rendered),
+    ))
+}
+
+/// 渲染单个文件，raw、二进制或非 UTF-8 内容原样复制
+///
+/// 返回写入的字节数与渲染后残留的占位符（行号、变量名）
+fn write_rendered_file(
+    job: &FileJob,
+    context: &RenderContext,
+) -> Result<(u64, Vec<(usize, String)>)> {
+    let (src, dest) = (job.src.as_path(), job.dest.as_path());
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // 只读取文件头判断是否为二进制，二进制文件直接流式复制
+    let mut head = Vec::with_capacity(BINARY_SNIFF_LEN);
+    let mut file = fs::File::open(src)?;
+    (&mut file)
+        .take(BINARY_SNIFF_LEN as u64)
+        .read_to_end(&mut head)?;
+
+    let mut leftovers = Vec::new();
+    let is_binary = !text_format::is_utf16_text(&head) && context.is_binary(src, &head);
+    let bytes_written = if job.raw || is_binary {
+        fs::copy(src, dest)?
+    } else {
+        let mut bytes = head;
+        file.read_to_end(&mut bytes)?;
+        // 文本统一以 UTF-8 写出，并按选项与模板规则规范化换行符与 BOM
+        let content = match text_format::decode_text(bytes) {
+            Ok((content, had_bom)) => {
+                let output = match raw_front_matter(&content) {
+                    Some(body) => body.to_string(),
+                    None => {
+                        let rendered = context.renderer.render(&content, context.variables)?;
+                        leftovers = unresolved_placeholders(&content, &rendered, context.variables);
+                        rendered
+                    }
+                };
+                context.text.format(&job.relative, &output, had_bom)
+            }
+            Err(bytes) => bytes,
+        };
+        fs::write(dest, &content)?;
+        content.len() as u64
+    };
+
+    // 保留 gradlew、mvnw 等脚本的可执行位
+    copy_permissions(src, dest)?;
+    Ok((bytes_written, leftovers))
+}
+
+/// 查找渲染结果中残留的占位符，返回行号与变量名
+///
+/// 只统计模板源中作为占位符出现且没有取值的变量，raw 块与 `\{{` 转义有意输出的字面量不算残留
+pub(crate) fn unresolved_placeholders(
+    source: &str,
+    rendered: &str,
+    variables: &HashMap<String, String>,
+) -> Vec<(usize, String)> {
+    if !rendered.contains("{{") {
+        return Vec::new();
+    }
+    let referenced: HashSet<String> = placeholders(source)
+        .into_iter()
+        .filter_map(|placeholder| match placeholder {
+            Placeholder::Variable(name) if !variables.contains_key(&name) => Some(name),
+            _ => None,
+        })
+        .collect();
+
+    let mut found = Vec::new();
+    for (index, line) in rendered.lines().enumerate() {
+        for placeholder in placeholders(line) {
+            if let Placeholder::Variable(name) = placeholder
+                && referenced.contains(&name)
+            {
+                found.push((index + 1, name));
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_metadata(dir: &Path, ignore: &str) {
+        write_metadata_with_files(dir, ignore, "[]");
+    }
+
+    fn write_metadata_with_files(dir: &Path, ignore: &str, files: &str) {
+        let metadata = format!(
+            r#"{{
+                "name": "demo",
+                "version": "1.0.0",
+                "description": "demo template",
+                "author": "test",
+                "project_type": "vue",
+                "variables": [],
+                "dependencies": [],
+                "tags": [],
+                "ignore": {},
+                "files": {}
+            }}"#,
+            ignore, files
+        );
+        fs::write(dir.join(TEMPLATE_METADATA_FILE), metadata).unwrap();
+    }
+
+    #[test]
+    fn test_min_generator_version_rejects_newer_templates() {
+        let metadata = |min: &str| {
+            format!(
+                r#"{{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                    "project_type": "vue", "variables": [], "dependencies": [], "tags": [],
+                    "min_generator_version": "{}", "hooks": "field from a newer release"}}"#,
+                min
+            )
+        };
+
+        let error = parse_template_metadata(&metadata("99.0.0")).unwrap_err();
+        assert!(matches!(error, GeneratorError::Configuration(_)));
+        let message = error.to_string();
+        assert!(message.contains("requires generator 99.0.0 or newer"));
+        assert!(message.contains(env!("CARGO_PKG_VERSION")));
+
+        // 版本满足时照常解析，字段格式错误仍报告为解析错误
+        assert!(matches!(
+            parse_template_metadata(&metadata(env!("CARGO_PKG_VERSION"))),
+            Err(GeneratorError::Serialization(_))
+        ));
+        assert!(parse_template_metadata(&metadata("not-a-version")).is_err());
+    }
+
+    #[test]
+    fn test_render_template_dir_honors_ignore_rules() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, r#"["screenshots/"]"#);
+        fs::write(root.join(IGNORE_FILE_NAME), "docs/\n").unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("screenshots")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("docs/template-guide.md"), "internal").unwrap();
+        fs::write(root.join("screenshots/preview.png"), [0u8, 1, 2]).unwrap();
+        fs::write(
+            root.join("src/{{name}}.ts"),
+            "export const name = '{{name}}';",
+        )
+        .unwrap();
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        let written = render_template_dir(
+            root,
+            output_dir.path(),
+            &metadata,
+            &variables,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(written.files, vec![PathBuf::from("src/app.ts")]);
+        assert_eq!(
+            fs::read_to_string(output_dir.path().join("src/app.ts")).unwrap(),
+            "export const name = 'app';"
+        );
+        assert!(!output_dir.path().join(TEMPLATE_METADATA_FILE).exists());
+        assert!(!output_dir.path().join("docs").exists());
+    }
+
+    #[test]
+    fn test_render_skips_only_declared_metadata_dirs() {
+        let template_dir = tempdir().unwrap();
+        let root = template_dir.path();
+        for file in [
+            "hooks/setup.sh",
+            "features/eslint/.eslintrc",
+            "features/docs/index.md",
+            "partials/header.hbs",
+            "CHANGELOG.md",
+            NEXT_STEPS_FILE,
+        ] {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::write(root.join(file), "").unwrap();
+        }
+        let render = |declared: &str| {
+            let output_dir = tempdir().unwrap();
+            let metadata = parse_template_metadata(&format!(
+                r#"{{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                    "project_type": "vue", "variables": [], "dependencies": [], "tags": [],
+                    {}}}"#,
+                declared
+            ))
+            .unwrap();
+            render_template_dir(
+                root,
+                output_dir.path(),
+                &metadata,
+                &HashMap::new(),
+                &RenderOptions::default(),
+            )
+            .unwrap()
+            .files
+        };
+
+        // 未声明时与普通文件一样复制
+        assert_eq!(render(r#""ignore": []"#).len(), 6);
+        assert_eq!(
+            render(
+                r#""hooks": [{"run": "hooks/setup.sh"}],
+                   "features": [{"name": "eslint"}],
+                   "ignore": ["partials/", "CHANGELOG.md", "next_steps.md.hbs"]"#
+            ),
+            vec![PathBuf::from("features/docs/index.md")]
+        );
+    }
+
+    #[test]
+    fn test_render_template_dir_conditional_files() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata_with_files(
+            root,
+            "[]",
+            r#"[
+                {"path": "src/router/**", "when": "useRouter == true"},
+                {"path": "src/stores/**", "when": "usePinia"}
+            ]"#,
+        );
+        fs::create_dir_all(root.join("src/router")).unwrap();
+        fs::create_dir_all(root.join("src/stores")).unwrap();
+        fs::write(root.join("src/main.ts"), "main").unwrap();
+        fs::write(root.join("src/router/index.ts"), "router").unwrap();
+        fs::write(root.join("src/stores/index.ts"), "store").unwrap();
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([
+            ("useRouter".to_string(), "true".to_string()),
+            ("usePinia".to_string(), "false".to_string()),
+        ]);
+        let written = render_template_dir(
+            root,
+            output_dir.path(),
+            &metadata,
+            &variables,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            written.files,
+            vec![
+                PathBuf::from("src/main.ts"),
+                PathBuf::from("src/router/index.ts")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binary_files_copied_verbatim() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        let sniffed = b"{{name}}\0binary".to_vec();
+        fs::write(root.join("data.bin"), &sniffed).unwrap();
+        fs::write(root.join("logo.png"), "{{name}}").unwrap();
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        render_template_dir(
+            root,
+            output_dir.path(),
+            &metadata,
+            &variables,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read(output_dir.path().join("data.bin")).unwrap(),
+            sniffed
+        );
+        assert_eq!(
+            fs::read_to_string(output_dir.path().join("logo.png")).unwrap(),
+            "{{name}}"
+        );
+    }
+
+    #[test]
+    fn test_raw_files_skip_rendering() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        let mut metadata = load_template_metadata(root).unwrap();
+        metadata.raw = vec!["locales/*.json".to_string()];
+        fs::create_dir_all(root.join("locales")).unwrap();
+        fs::write(root.join("locales/en.json"), r#"{"hi": "{{name}}"}"#).unwrap();
+        fs::write(root.join("{{name}}.vue.raw"), "<p>{{ name }}</p>").unwrap();
+        fs::write(
+            root.join("Card.vue"),
+            "---\nrender: false\n---\n<p>{{name}}</p>",
+        )
+        .unwrap();
+
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        let written = render_template_dir(
+            root,
+            output_dir.path(),
+            &metadata,
+            &variables,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        let read = |path: &str| fs::read_to_string(output_dir.path().join(path)).unwrap();
+        assert!(written.files.contains(&PathBuf::from("app.vue")));
+        assert_eq!(read("app.vue"), "<p>{{ name }}</p>");
+        assert_eq!(read("locales/en.json"), r#"{"hi": "{{name}}"}"#);
+        assert_eq!(read("Card.vue"), "<p>{{name}}</p>");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rendered_files_keep_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        fs::write(root.join("mvnw"), "#!/bin/sh\necho {{name}}\n").unwrap();
+        fs::set_permissions(root.join("mvnw"), fs::Permissions::from_mode(0o755)).unwrap();
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        render_template_dir(
+            root,
+            output_dir.path(),
+            &metadata,
+            &variables,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        let mode = fs::metadata(output_dir.path().join("mvnw"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn test_render_rejects_case_insensitive_collisions() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        fs::write(root.join("README.md"), "upper").unwrap();
+        fs::write(root.join("Readme.md"), "mixed").unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("docs/{{name}}.md"), "").unwrap();
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([("name".to_string(), "Guide".to_string())]);
+        let render = |output: &Path| {
+            render_template_dir(
+                root,
+                output,
+                &metadata,
+                &variables,
+                &RenderOptions::default(),
+            )
+        };
+        let error = render(output_dir.path()).unwrap_err();
+        assert!(matches!(error, GeneratorError::Conflict(_)));
+        assert!(error.to_string().contains("README.md <-> Readme.md"));
+        assert!(!output_dir.path().join("README.md").exists());
+
+        // 与输出目录中已有的条目冲突同样报错
+        fs::remove_file(root.join("Readme.md")).unwrap();
+        fs::create_dir_all(output_dir.path().join("Docs")).unwrap();
+        let error = render(output_dir.path()).unwrap_err();
+        assert!(error.to_string().contains("docs <-> Docs (existing)"));
+
+        let clean = tempdir().unwrap();
+        assert_eq!(render(clean.path()).unwrap().files.len(), 2);
+    }
+
+    #[test]
+    fn test_parallel_render_reports_bytes() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        for i in 0..32 {
+            fs::write(root.join(format!("file{:02}.txt", i)), "{{name}}").unwrap();
+        }
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        let options = RenderOptions {
+            workers: Some(4),
+            ..Default::default()
+        };
+        let report =
+            render_template_dir(root, output_dir.path(), &metadata, &variables, &options).unwrap();
+
+        assert_eq!(report.files.len(), 32);
+        assert_eq!(report.files[0], PathBuf::from("file00.txt"));
+        assert_eq!(report.bytes_written, 32 * 3);
+    }
+
+    #[test]
+    fn test_render_reports_unresolved_placeholders() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/{{name}}.ts"),
+            "// {{name}}\nexport const title = '{{titel}}';\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("App.vue"),
+            "<p>{{{{raw}}}}{{ message }}{{{{/raw}}}}</p>\n<p>\\{{count}}</p>",
+        )
+        .unwrap();
+
+        let metadata = load_template_metadata(root).unwrap();
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        let report = render_template_dir(
+            root,
+            output_dir.path(),
+            &metadata,
+            &variables,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            report.unresolved,
+            vec![UnresolvedPlaceholder {
+                file: PathBuf::from("src/app.ts"),
+                line: 2,
+                name: "titel".to_string(),
+            }]
+        );
+        assert_eq!(
+            report.unresolved[0].to_string(),
+            "{{titel}} at src/app.ts:2"
+        );
+    }
+
+    #[test]
+    fn test_render_normalizes_line_endings() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let root = template_dir.path();
+
+        write_metadata(root, "[]");
+        let mut metadata = load_template_metadata(root).unwrap();
+        metadata.attributes =
+            serde_json::from_str(r#"[{"path": "*.bat", "eol": "crlf"}]"#).unwrap();
+        fs::create_dir_all(root.join("bin")).unwrap();
+        fs::write(root.join("README.md"), "\u{feff}# {{name}}\r\n\ntext\r\n").unwrap();
+        fs::write(root.join("bin/run.bat"), "echo {{name}}\n").unwrap();
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("{{name}}\r\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        fs::write(root.join("notes.txt"), utf16).unwrap();
+
+        let variables = HashMap::from([("name".to_string(), "app".to_string())]);
+        let options = RenderOptions {
+            line_endings: LineEnding::Lf,
+            bom: BomPolicy::Strip,
+            ..Default::default()
+        };
+        render_template_dir(root, output_dir.path(), &metadata, &variables, &options).unwrap();
+
+        let read = |path: &str| fs::read(output_dir.path().join(path)).unwrap();
+        assert_eq!(read("README.md"), b"# app\n\ntext\n");
+        assert_eq!(read("bin/run.bat"), b"echo app\r\n");
+        assert_eq!(read("notes.txt"), b"app\n");
+    }
+}
@@ -0,0 +1,143 @@
+//! 差异预览模块
+//! 将模板渲染到临时目录后与已有目录逐文件比较，生成统一 diff，不修改目标目录
+
+use crate::pipeline::is_binary_content;
+use crate::template_registry::TemplateRegistryConfig;
+use crate::variables::resolve_variables;
+use crate::{FileDiff, GenerateOptions, Result, answers, templates};
+use std::fs;
+use std::path::Path;
+
+/// diff 中每个变更块保留的上下文行数
+const CONTEXT_LINES: usize = 3;
+
+/// 预览按 `options` 生成的内容写入 `target_dir` 时会产生的变化
+///
+/// 只返回新增与修改的文件；目标目录中模板不会生成的文件不受影响，不会列出。
+pub fn preview_diff(options: GenerateOptions, target_dir: &Path) -> Result<Vec<FileDiff>> {
+    let options = answers::apply_answers_file(options)?;
+    let template_name = options.template.clone().unwrap_or("basic".to_string());
+    let config = TemplateRegistryConfig::user()?;
+    let template = templates::resolve_template(&config, &options.project_type, &template_name)?;
+    let metadata = &template.metadata;
+
+    let rendered = tempfile::tempdir()?;
+    let report = templates::render_to_dir(
+        &template.dir,
+        metadata,
+        rendered.path(),
+        &resolve_variables(metadata, &templates::build_variables(&options)?)?,
+        &templates::build_render_options(&options)?,
+        &options.features.clone().unwrap_or_default(),
+    )?;
+
+    let mut diffs = Vec::new();
+    for relative in &report.files {
+        let source = rendered.path().join(relative);
+        // 保留的符号链接不参与比较
+        if !source.is_file() {
+            continue;
+        }
+        let new = fs::read(&source)?;
+        let target = target_dir.join(relative);
+        let old = if target.is_file() {
+            Some(fs::read(&target)?)
+        } else {
+            None
+        };
+        if old.as_deref() == Some(new.as_slice()) {
+            continue;
+        }
+
+        let path = relative.to_string_lossy().replace('\\', "/");
+        diffs.push(file_diff(path, old.as_deref(), &new));
+    }
+    Ok(diffs)
+}
+
+/// 单个文件的差异，二进制或非 UTF-8 内容不生成 diff 文本
+fn file_diff(path: String, old: Option<&[u8]>, new: &[u8]) -> FileDiff {
+    let status = if old.is_some() { "modified" } else { "added" };
+    let text = |content: &[u8]| {
+        (!is_binary_content(content))
+            .then(|| std::str::from_utf8(content).ok())
+            .flatten()
+            .map(str::to_string)
+    };
+    let (old_text, new_text) = (text(old.unwrap_or_default()), text(new));
+
+    let diff = match (old_text, new_text) {
+        (Some(old_text), Some(new_text)) => {
+            let original = if old.is_some() {
+                format!("a/{}", path)
+            } else {
+                "/dev/null".to_string()
+            };
+            let patch = diffy::DiffOptions::new()
+                .set_context_len(CONTEXT_LINES)
+                .set_original_filename(original)
+                .set_modified_filename(format!("b/{}", path))
+                .create_patch(&old_text, &new_text)
+                .to_string();
+            Some(patch)
+        }
+        _ => None,
+    };
+
+    FileDiff {
+        binary: diff.is_none(),
+        path,
+        status: status.to_string(),
+        diff,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_preview_diff_reports_changes_without_writing() {
+        let template = tempdir().unwrap();
+        let target = tempdir().unwrap();
+        fs::write(
+            template.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        fs::write(template.path().join("README.md"), "# {{name}}\n\nnew\n").unwrap();
+        fs::write(template.path().join("same.txt"), "same\n").unwrap();
+        fs::write(template.path().join("logo.png"), [0u8, 1, 2]).unwrap();
+        fs::write(target.path().join("README.md"), "# app\n\nold\n").unwrap();
+        fs::write(target.path().join("same.txt"), "same\n").unwrap();
+
+        let diffs = preview_diff(
+            GenerateOptions {
+                name: "app".to_string(),
+                project_type: "vue".to_string(),
+                template: Some(template.path().to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            target.path(),
+        )
+        .unwrap();
+
+        assert_eq!(diffs.len(), 2);
+        let readme = diffs.iter().find(|diff| diff.path == "README.md").unwrap();
+        assert_eq!(readme.status, "modified");
+        let patch = readme.diff.as_deref().unwrap();
+        assert!(patch.contains("--- a/README.md"));
+        assert!(patch.contains("-old\n+new\n"));
+        let logo = diffs.iter().find(|diff| diff.path == "logo.png").unwrap();
+        assert_eq!((logo.status.as_str(), logo.binary), ("added", true));
+
+        assert_eq!(
+            fs::read_to_string(target.path().join("README.md")).unwrap(),
+            "# app\n\nold\n"
+        );
+        assert!(!target.path().join("logo.png").exists());
+    }
+}
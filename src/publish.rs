@@ -0,0 +1,505 @@
+//! 模板发布模块
+//! 校验、按需提升版本并打包模板，再发布到注册表：本地目录直接安装，git 仓库提交并打标签后推送，npm 按 registry 发布协议上传
+
+use crate::PublishResult;
+use crate::bundle::{self, BundleManifest};
+use crate::credentials::{Credential, CredentialStore};
+use crate::network::NetworkConfig;
+use crate::npmrc::Npmrc;
+use crate::pipeline::{self, TEMPLATE_METADATA_FILE};
+use crate::template_registry::{TemplateRegistryConfig, TemplateSource};
+use crate::template_version;
+use crate::utils::generator_home;
+use crate::{GeneratorError, PhaseTimeouts, Result, cancel};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+use std::fs;
+use std::path::Path;
+
+/// 按 `~/.generator/registries.json` 中名为 `registry_name` 的注册表发布模板
+pub fn publish_template(
+    template_dir: &Path,
+    registry_name: &str,
+    bump: Option<&str>,
+) -> Result<PublishResult> {
+    let config = TemplateRegistryConfig::load(&generator_home())?;
+    publish_to(&config, template_dir, registry_name, bump)
+}
+
+/// 发布模板到 `config` 中的注册表
+///
+/// `bump`（`major`、`minor`、`patch`）会先改写 template.json 中的版本；同一版本不能重复发布。
+pub fn publish_to(
+    config: &TemplateRegistryConfig,
+    template_dir: &Path,
+    registry_name: &str,
+    bump: Option<&str>,
+) -> Result<PublishResult> {
+    let registry = config
+        .registries
+        .iter()
+        .find(|registry| registry.name == registry_name)
+        .ok_or_else(|| {
+            GeneratorError::Configuration(format!("Unknown registry '{}'", registry_name))
+        })?;
+
+    bundle::ensure_valid(template_dir)?;
+    if let Some(bump) = bump {
+        bump_version(template_dir, bump)?;
+    }
+
+    let staging = tempfile::tempdir()?;
+    let bundle = bundle::pack_template(template_dir, Some(staging.path()))?;
+    let manifest = bundle::read_bundle_manifest(&bundle)?;
+    if template_version::parse_version(&manifest.version).is_none() {
+        return Err(GeneratorError::Configuration(format!(
+            "Template version '{}' is not a valid semver version",
+            manifest.version
+        )));
+    }
+
+    let location = match &registry.source {
+        TemplateSource::Local { path } => publish_local(&bundle, &manifest, path)?,
+        TemplateSource::Git {
+            url,
+            branch,
+            subfolder,
+            auth,
+            ..
+        } => {
+            let credential = match auth {
+                Some(auth) => auth.resolve(&CredentialStore::default())?,
+                None => None,
+            };
+            let target = GitTarget {
+                url,
+                branch: branch.as_deref(),
+                subfolder: subfolder.as_deref(),
+                credential: credential.as_ref(),
+            };
+            publish_git(&bundle, &manifest, &target)?
+        }
+        TemplateSource::Npm {
+            package, registry, ..
+        } => {
+            let npm = NpmPublisher {
+                npmrc: Npmrc::load()?,
+                network: config.network.clone().resolve(),
+                timeout: PhaseTimeouts::duration(config.timeouts.npm_pack_secs),
+            };
+            npm.publish(&bundle, &manifest, package, registry.as_deref())?
+        }
+        TemplateSource::Http { .. } => {
+            return Err(GeneratorError::Configuration(format!(
+                "Registry '{}' is an HTTP source and does not support publishing",
+                registry_name
+            )));
+        }
+    };
+
+    Ok(PublishResult {
+        registry: registry_name.to_string(),
+        name: manifest.name,
+        version: manifest.version,
+        location,
+    })
+}
+
+/// 提升 template.json 中的版本，只替换版本值以保留原有格式
+fn bump_version(template_dir: &Path, bump: &str) -> Result<()> {
+    let metadata = pipeline::load_template_metadata(template_dir)?;
+    let current = template_version::parse_version(&metadata.version).ok_or_else(|| {
+        GeneratorError::Configuration(format!(
+            "Template version '{}' is not a valid semver version",
+            metadata.version
+        ))
+    })?;
+    let version = match bump {
+        "major" => semver::Version::new(current.major + 1, 0, 0),
+        "minor" => semver::Version::new(current.major, current.minor + 1, 0),
+        "patch" => semver::Version::new(current.major, current.minor, current.patch + 1),
+        _ => {
+            return Err(GeneratorError::Configuration(format!(
+                "Unknown version bump '{}', expected major, minor or patch",
+                bump
+            )));
+        }
+    };
+
+    let path = template_dir.join(TEMPLATE_METADATA_FILE);
+    let content = fs::read_to_string(&path)?;
+    let pattern = regex::Regex::new(&format!(
+        r#""version"(\s*):(\s*)"{}""#,
+        regex::escape(&metadata.version)
+    ))
+    .map_err(|e| GeneratorError::Unknown(e.to_string()))?;
+    let updated = pattern.replace(&content, format!(r#""version"${{1}}:${{2}}"{}""#, version));
+    fs::write(&path, updated.as_bytes())?;
+    Ok(())
+}
+
+/// 安装到本地注册表，已有相同或更高版本时拒绝
+fn publish_local(bundle: &Path, manifest: &BundleManifest, root: &Path) -> Result<String> {
+    let existing = root.join(&manifest.project_type).join(&manifest.name);
+    if let Ok(installed) = pipeline::load_template_metadata(&existing) {
+        let newer = match (
+            template_version::parse_version(&installed.version),
+            template_version::parse_version(&manifest.version),
+        ) {
+            (Some(installed), Some(publishing)) => publishing > installed,
+            _ => false,
+        };
+        if !newer {
+            return Err(already_published(manifest, &installed.version));
+        }
+    }
+    let dir = bundle::install_template(bundle, root, true)?;
+    Ok(dir.display().to_string())
+}
+
+/// git 发布目标
+struct GitTarget<'a> {
+    url: &'a str,
+    branch: Option<&'a str>,
+    subfolder: Option<&'a str>,
+    credential: Option<&'a Credential>,
+}
+
+/// 克隆仓库，将模板写入 `<subfolder>/<project_type>/<name>` 后提交、打标签 `<name>@<version>` 并推送
+fn publish_git(bundle: &Path, manifest: &BundleManifest, target: &GitTarget) -> Result<String> {
+    let checkout = tempfile::tempdir()?;
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(fetch_options(target.credential));
+    if let Some(branch) = target.branch {
+        builder.branch(branch);
+    }
+    let repo = builder
+        .clone(target.url, checkout.path())
+        .map_err(|e| git_error("clone", target.url, e))?;
+
+    let tag = format!("{}@{}", manifest.name, manifest.version);
+    if repo.refname_to_id(&format!("refs/tags/{}", tag)).is_ok() {
+        return Err(already_published(manifest, &manifest.version));
+    }
+
+    let root = match target.subfolder {
+        Some(subfolder) => checkout.path().join(subfolder),
+        None => checkout.path().to_path_buf(),
+    };
+    bundle::install_template(bundle, &root, true)?;
+
+    let git = |e: git2::Error| git_error("commit", target.url, e);
+    let mut index = repo.index().map_err(git)?;
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .map_err(git)?;
+    index.update_all(["*"], None).map_err(git)?;
+    index.write().map_err(git)?;
+    let tree = repo
+        .find_tree(index.write_tree().map_err(git)?)
+        .map_err(git)?;
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("generator", "generator@localhost"))
+        .map_err(git)?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let message = format!("Publish {}", tag);
+    let commit = repo
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            parent.as_ref().into_iter().collect::<Vec<_>>().as_slice(),
+        )
+        .map_err(git)?;
+    repo.tag(
+        &tag,
+        &repo.find_object(commit, None).map_err(git)?,
+        &signature,
+        &message,
+        false,
+    )
+    .map_err(git)?;
+
+    let branch = repo
+        .head()
+        .map_err(git)?
+        .shorthand()
+        .unwrap_or("main")
+        .to_string();
+    push(
+        &repo,
+        target,
+        &[
+            format!("refs/heads/{0}:refs/heads/{0}", branch),
+            format!("refs/tags/{0}:refs/tags/{0}", tag),
+        ],
+    )?;
+    Ok(tag)
+}
+
+fn push(repo: &git2::Repository, target: &GitTarget, refspecs: &[String]) -> Result<()> {
+    let mut rejected = Vec::new();
+    {
+        let mut callbacks = remote_callbacks(target.credential);
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(status) = status {
+                rejected.push(format!("{} ({})", refname, status));
+            }
+            Ok(())
+        });
+        let mut options = git2::PushOptions::new();
+        options.remote_callbacks(callbacks);
+        repo.find_remote("origin")
+            .and_then(|mut remote| remote.push(refspecs, Some(&mut options)))
+            .map_err(|e| git_error("push", target.url, e))?;
+    }
+    if !rejected.is_empty() {
+        return Err(GeneratorError::ExternalCommand(format!(
+            "git push to {} was rejected: {}",
+            target.url,
+            rejected.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn fetch_options(credential: Option<&Credential>) -> git2::FetchOptions<'_> {
+    let mut options = git2::FetchOptions::new();
+    options.remote_callbacks(remote_callbacks(credential));
+    options
+}
+
+/// 有凭据时按用户名/令牌认证，否则交给 ssh-agent 或 git 默认凭据
+fn remote_callbacks(credential: Option<&Credential>) -> git2::RemoteCallbacks<'_> {
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |_url, username, allowed| match credential {
+        Some(credential) if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) => {
+            git2::Cred::userpass_plaintext(
+                credential.username.as_deref().unwrap_or("x-access-token"),
+                &credential.secret,
+            )
+        }
+        _ if allowed.contains(git2::CredentialType::SSH_KEY) => {
+            git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        }
+        _ => git2::Cred::default(),
+    });
+    callbacks
+}
+
+fn git_error(action: &str, url: &str, e: git2::Error) -> GeneratorError {
+    GeneratorError::ExternalCommand(format!("git {} {} failed: {}", action, url, e.message()))
+}
+
+fn already_published(manifest: &BundleManifest, existing: &str) -> GeneratorError {
+    GeneratorError::Conflict(format!(
+        "{}@{} is already published (latest: {}); bump the template version first",
+        manifest.name, manifest.version, existing
+    ))
+}
+
+/// npm 发布器
+struct NpmPublisher {
+    npmrc: Npmrc,
+    network: NetworkConfig,
+    timeout: Option<std::time::Duration>,
+}
+
+impl NpmPublisher {
+    /// 以 `package` 为包名上传模板，返回 `<package>@<version>`
+    fn publish(
+        &self,
+        bundle: &Path,
+        manifest: &BundleManifest,
+        package: &str,
+        registry: Option<&str>,
+    ) -> Result<String> {
+        cancel::check()?;
+        let registry = match registry {
+            Some(registry) => format!("{}/", registry.trim_end_matches('/')),
+            None => self.npmrc.registry_for(package),
+        };
+        let url = format!("{}{}", registry, package.replacen('/', "%2f", 1));
+        let tarball = npm_tarball(bundle, manifest, package)?;
+        let document = publish_document(manifest, package, &registry, &tarball);
+
+        let mut request = self
+            .network
+            .http_agent(&url, self.timeout)?
+            .put(&url)
+            .set("Content-Type", "application/json");
+        if let Some(authorization) = self.npmrc.authorization(&url)? {
+            request = request.set("Authorization", &authorization);
+        }
+        request
+            .send_string(&document.to_string())
+            .map_err(|e| match e {
+                ureq::Error::Status(409, _) => already_published(manifest, &manifest.version),
+                ureq::Error::Status(code @ (401 | 403), _) => {
+                    GeneratorError::Configuration(format!(
+                        "npm registry rejected publishing {} (HTTP {}); check .npmrc credentials and that the version is new",
+                        url, code
+                    ))
+                }
+                e => GeneratorError::FileOperation(format!("Failed to publish {}: {}", url, e)),
+            })?;
+        Ok(format!("{}@{}", package, manifest.version))
+    }
+}
+
+/// 将模板文件放入 `package/` 打成 npm 使用的 tar.gz
+///
+/// registry 以发布文档中的元数据为准；模板本身不含 package.json 时才补一个最小的 package.json。
+fn npm_tarball(bundle: &Path, manifest: &BundleManifest, package: &str) -> Result<Vec<u8>> {
+    let unpacked = tempfile::tempdir()?;
+    let template_dir = bundle::install_template(bundle, unpacked.path(), false)?;
+
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    builder.append_dir_all("package", &template_dir)?;
+    if !template_dir.join("package.json").exists() {
+        let package_json = serde_json::to_vec_pretty(&serde_json::json!({
+            "name": package,
+            "version": manifest.version,
+            "description": manifest.description,
+            "keywords": ["generator-template", manifest.project_type],
+        }))?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(package_json.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, "package/package.json", package_json.as_slice())?;
+    }
+    Ok(builder.into_inner()?.finish()?)
+}
+
+/// npm registry 的发布文档（`PUT /<package>`），tarball 以 base64 附件内联
+fn publish_document(
+    manifest: &BundleManifest,
+    package: &str,
+    registry: &str,
+    tarball: &[u8],
+) -> serde_json::Value {
+    let file_name = format!(
+        "{}-{}.tgz",
+        package.rsplit('/').next().unwrap_or(package),
+        manifest.version
+    );
+    let version = &manifest.version;
+    serde_json::json!({
+        "_id": package,
+        "name": package,
+        "description": manifest.description,
+        "dist-tags": { "latest": version },
+        "versions": {
+            version: {
+                "_id": format!("{}@{}", package, version),
+                "name": package,
+                "version": version,
+                "description": manifest.description,
+                "keywords": ["generator-template", manifest.project_type],
+                "dist": {
+                    "shasum": hex::encode(Sha1::digest(tarball)),
+                    "integrity": format!("sha512-{}", BASE64.encode(Sha512::digest(tarball))),
+                    "tarball": format!("{}{}/-/{}", registry, package, file_name),
+                },
+            },
+        },
+        "_attachments": {
+            file_name: {
+                "content_type": "application/octet-stream",
+                "data": BASE64.encode(tarball),
+                "length": tarball.len(),
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_registry::TemplateRegistry;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_publish_template_to_local_and_git_registries() {
+        let dir = tempdir().unwrap();
+        let template = dir.path().join("source");
+        fs::create_dir_all(&template).unwrap();
+        fs::write(
+            template.join(TEMPLATE_METADATA_FILE),
+            "{\n  \"name\": \"demo\",\n  \"version\": \"1.0.0\",\n  \"description\": \"\",\n  \"author\": \"\",\n  \"project_type\": \"vue\",\n  \"variables\": [],\n  \"dependencies\": [],\n  \"tags\": []\n}\n",
+        )
+        .unwrap();
+        fs::write(template.join("README.md"), "{{name}}").unwrap();
+
+        let remote = dir.path().join("remote.git");
+        git2::Repository::init_bare(&remote).unwrap();
+        let registry = |name: &str, source: TemplateSource| TemplateRegistry {
+            name: name.to_string(),
+            source,
+            enabled: true,
+            priority: 0,
+        };
+        let config = TemplateRegistryConfig {
+            registries: vec![
+                registry(
+                    "local",
+                    TemplateSource::Local {
+                        path: dir.path().join("templates"),
+                    },
+                ),
+                registry(
+                    "git",
+                    TemplateSource::Git {
+                        url: remote.to_string_lossy().to_string(),
+                        branch: None,
+                        subfolder: Some("templates".to_string()),
+                        auth: None,
+                        commit: None,
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let result = publish_to(&config, &template, "local", None).unwrap();
+        assert_eq!(result.version, "1.0.0");
+        let local = dir.path().join("templates/vue/demo");
+        assert!(local.join("README.md").is_file());
+        // 同一版本不能重复发布，提升版本后覆盖并保留原有格式
+        assert!(publish_to(&config, &template, "local", None).is_err());
+        let result = publish_to(&config, &template, "local", Some("minor")).unwrap();
+        assert_eq!(result.version, "1.1.0");
+        assert!(
+            fs::read_to_string(template.join(TEMPLATE_METADATA_FILE))
+                .unwrap()
+                .contains("  \"version\": \"1.1.0\",\n")
+        );
+
+        let result = publish_to(&config, &template, "git", None).unwrap();
+        assert_eq!(result.location, "demo@1.1.0");
+        let repo = git2::Repository::open_bare(&remote).unwrap();
+        let tagged = repo
+            .revparse_single("refs/tags/demo@1.1.0")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        assert!(
+            tagged
+                .tree()
+                .unwrap()
+                .get_path(Path::new("templates/vue/demo/README.md"))
+                .is_ok()
+        );
+        assert!(publish_to(&config, &template, "git", None).is_err());
+        assert!(publish_to(&config, &template, "missing", None).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Python 环境模块
+//! 检测 Python 解释器（已激活的虚拟环境优先，其次 PATH），并在生成后按项目布局（uv、poetry、pip）创建 `.venv` 并安装依赖
+
+use crate::command_audit::{command_line, describe};
+use crate::report::{HookRun, millis};
+use crate::{GeneratorError, Result, cancel};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// 项目内虚拟环境的目录名
+pub const VENV_DIR: &str = ".venv";
+
+/// 模板声明最低 Python 版本的变量，如 `3.12`
+pub const PYTHON_VERSION_VARIABLE: &str = "pythonVersion";
+
+/// Python 解释器信息
+#[derive(Debug, Clone)]
+pub struct PythonEnvironment {
+    pub python_path: String,
+    /// 版本号，如 `3.12.4`
+    pub version: String,
+    /// 解释器的来源
+    pub source: PythonSource,
+}
+
+/// 解释器的发现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonSource {
+    /// 来自 VIRTUAL_ENV 环境变量（已激活的虚拟环境）
+    VirtualEnv,
+    /// 来自 PATH
+    Path,
+}
+
+impl PythonSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PythonSource::VirtualEnv => "VIRTUAL_ENV",
+            PythonSource::Path => "PATH",
+        }
+    }
+}
+
+impl PythonEnvironment {
+    /// 主次版本号，如 (3, 12)
+    pub fn major_minor(&self) -> Option<(u32, u32)> {
+        parse_python_version(&self.version)
+    }
+}
+
+/// 从版本号或 `python --version` 输出中解析主次版本号，如 `Python 3.12.4` 为 (3, 12)
+pub fn parse_python_version(raw: &str) -> Option<(u32, u32)> {
+    let version = raw.trim();
+    let version = version.strip_prefix("Python ").unwrap_or(version);
+    let mut parts = version.split('.');
+    let major = parts.next()?.trim().parse().ok()?;
+    // 预发布版本如 `3.13.0rc1` 只取前导数字
+    let minor = parts
+        .next()
+        .map(|minor| {
+            minor
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>()
+        })
+        .and_then(|minor| minor.parse().ok())
+        .unwrap_or(0);
+    Some((major, minor))
+}
+
+/// 检测 Python 解释器：VIRTUAL_ENV > PATH 中的 python3 > python
+pub fn detect_python() -> Result<PythonEnvironment> {
+    if let Some(venv) = env::var_os("VIRTUAL_ENV").filter(|venv| !venv.is_empty()) {
+        let python_path = venv_python(Path::new(&venv));
+        if python_path.is_file()
+            && let Some(version) = python_version(&python_path.to_string_lossy())
+        {
+            return Ok(PythonEnvironment {
+                python_path: python_path.to_string_lossy().to_string(),
+                version,
+                source: PythonSource::VirtualEnv,
+            });
+        }
+    }
+
+    // Windows 上的 python3.exe 多半是应用商店的占位程序，先找 python
+    let candidates: &[&str] = if cfg!(windows) {
+        &["python.exe", "python3.exe"]
+    } else {
+        &["python3", "python"]
+    };
+    let which_cmd = if cfg!(windows) { "where" } else { "which" };
+    for candidate in candidates {
+        let Ok(output) = Command::new(which_cmd).arg(candidate).output() else {
+            continue;
+        };
+        if !output.status.success() {
+            continue;
+        }
+        // where 可能返回多个匹配，取第一个
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(python_path) = stdout.lines().next().map(str::trim) else {
+            continue;
+        };
+        if let Some(version) = python_version(python_path) {
+            return Ok(PythonEnvironment {
+                python_path: python_path.to_string(),
+                version,
+                source: PythonSource::Path,
+            });
+        }
+    }
+    Err(GeneratorError::Configuration(
+        "Python not found in PATH. Install Python 3 or activate a virtual environment.".to_string(),
+    ))
+}
+
+/// 执行 `python --version`，Python 2 把版本写到 stderr
+fn python_version(python_path: &str) -> Option<String> {
+    let output = Command::new(python_path).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let line = if stdout.trim().is_empty() {
+        stderr.trim()
+    } else {
+        stdout.trim()
+    };
+    let version = line.strip_prefix("Python ")?.trim();
+    Some(version.to_string())
+}
+
+/// 检测解释器是否满足模板要求的最低版本，不满足时返回警告；没有解释器也能生成项目
+pub fn interpreter_warning(required: Option<&str>) -> Option<String> {
+    let python = match detect_python() {
+        Ok(python) => python,
+        Err(e) => return Some(e.to_string()),
+    };
+    let required = required.and_then(parse_python_version)?;
+    match python.major_minor() {
+        Some(found) if found >= required => None,
+        _ => Some(format!(
+            "This template requires Python {}.{}+, but found Python {} at {}",
+            required.0, required.1, python.version, python.python_path
+        )),
+    }
+}
+
+/// 虚拟环境中的解释器路径
+fn venv_python(venv: &Path) -> PathBuf {
+    if cfg!(windows) {
+        venv.join("Scripts").join("python.exe")
+    } else {
+        venv.join("bin").join("python")
+    }
+}
+
+/// 项目的依赖管理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonLayout {
+    /// `uv.lock` 或 pyproject.toml 中的 `[tool.uv]`
+    Uv,
+    /// pyproject.toml 中的 `[tool.poetry]`
+    Poetry,
+    /// 其余带 pyproject.toml 或 requirements.txt 的项目
+    Pip,
+}
+
+impl PythonLayout {
+    /// 按项目文件识别布局，既没有 pyproject.toml 也没有 requirements.txt 时返回 None
+    pub fn detect(project_dir: &Path) -> Option<Self> {
+        let pyproject = fs::read_to_string(project_dir.join("pyproject.toml")).ok();
+        let has_table = |table: &str| {
+            pyproject.as_deref().is_some_and(|content| {
+                content
+                    .lines()
+                    .any(|line| line.trim().starts_with(&format!("[{}", table)))
+            })
+        };
+        if project_dir.join("uv.lock").is_file() || has_table("tool.uv") {
+            Some(PythonLayout::Uv)
+        } else if has_table("tool.poetry") {
+            Some(PythonLayout::Poetry)
+        } else if pyproject.is_some() || project_dir.join("requirements.txt").is_file() {
+            Some(PythonLayout::Pip)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PythonLayout::Uv => "uv",
+            PythonLayout::Poetry => "poetry",
+            PythonLayout::Pip => "pip",
+        }
+    }
+
+    /// 在 `project_dir` 中创建 `.venv` 并安装依赖的命令；pip 布局需要先检测解释器
+    fn commands(&self, project_dir: &Path) -> Result<Vec<Command>> {
+        let command = |program: &str, args: &[&str]| {
+            let mut command = Command::new(program);
+            command.args(args).current_dir(project_dir);
+            command
+        };
+        Ok(match self {
+            PythonLayout::Uv => vec![command("uv", &["sync"])],
+            PythonLayout::Poetry => {
+                // 虚拟环境放在项目内，与其它布局一致
+                let mut install = command("poetry", &["install"]);
+                install.env("POETRY_VIRTUALENVS_IN_PROJECT", "true");
+                vec![install]
+            }
+            PythonLayout::Pip => {
+                let python = detect_python()?;
+                let venv_python = venv_python(&project_dir.join(VENV_DIR));
+                let install: &[&str] = if project_dir.join("pyproject.toml").is_file() {
+                    &["-m", "pip", "install", "-e", "."]
+                } else {
+                    &["-m", "pip", "install", "-r", "requirements.txt"]
+                };
+                vec![
+                    command(&python.python_path, &["-m", "venv", VENV_DIR]),
+                    command(&venv_python.to_string_lossy(), install),
+                ]
+            }
+        })
+    }
+}
+
+/// 为生成的项目创建虚拟环境并安装依赖，某条命令失败时停止
+///
+/// 返回执行的命令，与模板钩子一起记入报告。
+pub fn bootstrap(project_dir: &Path, timeout: Option<Duration>) -> Result<Vec<HookRun>> {
+    let layout = PythonLayout::detect(project_dir).ok_or_else(|| {
+        GeneratorError::Configuration(format!(
+            "No pyproject.toml or requirements.txt found in {}",
+            project_dir.display()
+        ))
+    })?;
+
+    let mut runs = Vec::new();
+    for mut command in layout.commands(project_dir)? {
+        let line = command_line(&describe(&command));
+        let started = Instant::now();
+        let output = cancel::output_with_timeout(&mut command, timeout).map_err(|e| match e {
+            GeneratorError::Io(e) => GeneratorError::ExternalCommand(format!(
+                "{}: {} (is {} installed?)",
+                line,
+                e,
+                layout.as_str()
+            )),
+            e => e,
+        });
+        let result = output
+            .as_ref()
+            .map_err(ToString::to_string)
+            .and_then(|output| {
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} failed: {}",
+                        line,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ))
+                }
+            });
+        runs.push(HookRun {
+            command: line,
+            exit_code: output.as_ref().ok().and_then(|output| output.status.code()),
+            duration_ms: millis(started.elapsed()),
+            error: result.as_ref().err().cloned(),
+        });
+        if let Err(error) = result {
+            return Err(match output {
+                Err(e) => e,
+                Ok(_) => GeneratorError::ExternalCommand(error),
+            });
+        }
+    }
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateOptions;
+    use crate::command_audit::{CommandPolicy, with_audit};
+    use crate::templates::{generate_project_from_template, get_template_info};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generate_python_projects_with_venv_bootstrap() {
+        assert_eq!(parse_python_version("Python 3.12.4\n"), Some((3, 12)));
+        assert_eq!(parse_python_version("3.13.0rc1"), Some((3, 13)));
+        assert_eq!(parse_python_version("3"), Some((3, 0)));
+        assert_eq!(parse_python_version("unknown"), None);
+
+        let output = tempdir().unwrap();
+        let generate = |template: &str| {
+            with_audit(CommandPolicy::default(), || {
+                generate_project_from_template(GenerateOptions {
+                    name: format!("{}-app", template),
+                    project_type: "python".to_string(),
+                    template: Some(template.to_string()),
+                    output_path: Some(output.path().to_string_lossy().to_string()),
+                    python_venv: Some(true),
+                    ..Default::default()
+                })
+            })
+        };
+
+        // 审计模式下只记录安装命令，包目录按项目名转换为合法的模块名
+        for template in ["uv", "poetry"] {
+            assert!(get_template_info("python", template).is_ok());
+        }
+        let (result, commands) = generate("uv");
+        assert!(result.unwrap().success);
+        let project = output.path().join("uv-app");
+        assert_eq!(PythonLayout::detect(&project), Some(PythonLayout::Uv));
+        assert!(project.join("src/uv_app/__init__.py").is_file());
+        let pyproject = fs::read_to_string(project.join("pyproject.toml")).unwrap();
+        assert!(pyproject.contains("name = \"uv-app\""));
+        assert!(pyproject.contains("requires-python = \">=3.12\""));
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].program, "uv");
+        assert_eq!(commands[0].args, ["sync"]);
+        assert_eq!(commands[0].cwd, project.to_string_lossy());
+
+        let (result, commands) = generate("poetry");
+        assert!(result.unwrap().success);
+        let project = output.path().join("poetry-app");
+        assert_eq!(PythonLayout::detect(&project), Some(PythonLayout::Poetry));
+        assert!(project.join("src/poetry_app/__init__.py").is_file());
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].program, "poetry");
+        assert_eq!(commands[0].args, ["install"]);
+
+        // 只有 requirements.txt 的项目使用 venv 与 pip
+        let plain = tempdir().unwrap();
+        fs::write(plain.path().join("requirements.txt"), "requests\n").unwrap();
+        assert_eq!(PythonLayout::detect(plain.path()), Some(PythonLayout::Pip));
+        fs::remove_file(plain.path().join("requirements.txt")).unwrap();
+        assert_eq!(PythonLayout::detect(plain.path()), None);
+    }
+}
@@ -0,0 +1,237 @@
+//! README 生成模块
+//! 按项目元数据与生成结果中的构建文件生成结构化的 README：标题、徽章、简介与开发命令
+
+use crate::Result;
+use crate::license::{LICENSE_FILE, License};
+use crate::toolchain::{self, Toolchain};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// README 文件名
+pub const README_FILE: &str = "README.md";
+
+/// package.json 中按此顺序列出的常用脚本
+const NODE_SCRIPTS: &[&str] = &["dev", "start", "build", "test", "lint"];
+
+/// README 使用的项目信息
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectSummary<'a> {
+    pub name: &'a str,
+    pub project_type: &'a str,
+    /// 项目简介（`description` 变量）
+    pub description: Option<&'a str>,
+    pub license: Option<License>,
+}
+
+impl Toolchain {
+    /// 构建工具徽章
+    fn badge(&self) -> String {
+        let (tool, color) = match self {
+            Toolchain::Node { manager } => (*manager, "CB3837"),
+            Toolchain::Maven { .. } => ("maven", "C71A36"),
+            Toolchain::Gradle { .. } => ("gradle", "02303A"),
+            Toolchain::Cargo => ("cargo", "DEA584"),
+        };
+        format!("![build: {tool}](https://img.shields.io/badge/build-{tool}-{color}.svg)")
+    }
+
+    /// 安装、运行、构建与测试命令
+    fn commands(&self, project_dir: &Path, project_type: &str) -> Vec<String> {
+        let read = |name: &str| fs::read_to_string(project_dir.join(name)).unwrap_or_default();
+
+        match self {
+            Toolchain::Node { manager } => {
+                // 没有 package.json 或未声明脚本时给出前端项目的常用命令
+                let scripts = toolchain::node_scripts(project_dir, NODE_SCRIPTS)
+                    .unwrap_or_else(|| vec!["dev", "build"]);
+                std::iter::once(format!("{} install", manager))
+                    .chain(
+                        scripts
+                            .into_iter()
+                            .map(|script| format!("{} run {}", manager, script)),
+                    )
+                    .collect()
+            }
+            Toolchain::Maven { command } => {
+                let mut commands = vec![format!("{} package", command)];
+                if read("pom.xml").contains("spring-boot") {
+                    commands.push(format!("{} spring-boot:run", command));
+                }
+                commands.push(format!("{} test", command));
+                commands
+            }
+            Toolchain::Gradle { command } => {
+                let script = read("build.gradle") + &read("build.gradle.kts");
+                let mut commands = vec![format!("{} build", command)];
+                if script.contains("org.springframework.boot") {
+                    commands.push(format!("{} bootRun", command));
+                } else if script.contains("application") {
+                    commands.push(format!("{} run", command));
+                }
+                commands.push(format!("{} test", command));
+                commands
+            }
+            Toolchain::Cargo => {
+                let mut commands = vec!["cargo build".to_string()];
+                if project_type != "rust" || project_dir.join("src/main.rs").exists() {
+                    commands.push("cargo run".to_string());
+                }
+                commands.push("cargo test".to_string());
+                commands
+            }
+        }
+    }
+
+    /// 发布到包仓库后的版本徽章（npm 非私有包、cargo 库 crate）
+    fn registry_badge(&self, project_dir: &Path) -> Option<String> {
+        match self {
+            Toolchain::Node { .. } => {
+                let content = fs::read_to_string(project_dir.join("package.json")).ok()?;
+                let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+                if package.get("private").and_then(|p| p.as_bool()) == Some(true) {
+                    return None;
+                }
+                let name = package.get("name")?.as_str()?;
+                Some(format!(
+                    "[![npm](https://img.shields.io/npm/v/{name}.svg)](https://www.npmjs.com/package/{name})"
+                ))
+            }
+            Toolchain::Cargo if project_dir.join("src/lib.rs").exists() => {
+                let manifest = fs::read_to_string(project_dir.join("Cargo.toml")).ok()?;
+                if manifest.contains("publish = false") {
+                    return None;
+                }
+                let name = manifest
+                    .lines()
+                    .find_map(|line| line.trim().strip_prefix("name"))?
+                    .trim_start_matches([' ', '='])
+                    .trim()
+                    .trim_matches('"');
+                Some(format!(
+                    "[![crates.io](https://img.shields.io/crates/v/{name}.svg)](https://crates.io/crates/{name})"
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 生成 README 内容
+pub fn render_readme(project_dir: &Path, summary: &ProjectSummary) -> String {
+    let toolchain = Toolchain::detect(project_dir, summary.project_type);
+
+    let mut badges = Vec::new();
+    if let Some(license) = summary.license {
+        let label = match license {
+            License::Proprietary => "proprietary".to_string(),
+            // shields.io 路径中的 `-` 需要写成 `--`
+            license => license.spdx().replace('-', "--"),
+        };
+        badges.push(format!(
+            "[![license](https://img.shields.io/badge/license-{label}-blue.svg)]({LICENSE_FILE})"
+        ));
+    }
+    if let Some(toolchain) = &toolchain {
+        badges.push(toolchain.badge());
+        badges.extend(toolchain.registry_badge(project_dir));
+    }
+
+    let mut readme = format!("# {}\n\n", summary.name);
+    if !badges.is_empty() {
+        readme.push_str(&badges.join(" "));
+        readme.push_str("\n\n");
+    }
+    if let Some(description) = summary.description.filter(|d| !d.trim().is_empty()) {
+        readme.push_str(description.trim());
+        readme.push_str("\n\n");
+    }
+
+    if let Some(toolchain) = &toolchain {
+        readme.push_str("## 开发\n\n```bash\n");
+        for command in toolchain.commands(project_dir, summary.project_type) {
+            readme.push_str(&command);
+            readme.push('\n');
+        }
+        readme.push_str("```\n");
+    }
+
+    if let Some(license) = summary.license {
+        let name = match license {
+            License::Proprietary => "专有许可，保留所有权利",
+            license => license.spdx(),
+        };
+        readme.push_str(&format!(
+            "\n## 许可证\n\n{}，详见 [{}]({})。\n",
+            name, LICENSE_FILE, LICENSE_FILE
+        ));
+    }
+
+    readme
+}
+
+/// 项目目录中没有 README 时生成，返回写入的文件（相对项目目录）
+pub fn ensure_readme(project_dir: &Path, summary: &ProjectSummary) -> Result<Option<PathBuf>> {
+    let path = project_dir.join(README_FILE);
+    if path.exists() {
+        return Ok(None);
+    }
+    fs::write(&path, render_readme(project_dir, summary))?;
+    Ok(Some(PathBuf::from(README_FILE)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_render_readme() {
+        let project = tempdir().unwrap();
+        fs::write(
+            project.path().join("package.json"),
+            r#"{"name": "my-lib", "scripts": {"build": "tsup", "test": "vitest", "prepare": "x"}}"#,
+        )
+        .unwrap();
+        fs::write(project.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let summary = ProjectSummary {
+            name: "my-lib",
+            project_type: "node-lib",
+            description: Some("A tiny library"),
+            license: Some(License::Apache2),
+        };
+        let written = ensure_readme(project.path(), &summary).unwrap();
+        assert_eq!(written, Some(PathBuf::from(README_FILE)));
+        assert_eq!(
+            fs::read_to_string(project.path().join(README_FILE)).unwrap(),
+            "# my-lib\n\n\
+             [![license](https://img.shields.io/badge/license-Apache--2.0-blue.svg)](LICENSE) \
+             ![build: pnpm](https://img.shields.io/badge/build-pnpm-CB3837.svg) \
+             [![npm](https://img.shields.io/npm/v/my-lib.svg)](https://www.npmjs.com/package/my-lib)\n\n\
+             A tiny library\n\n\
+             ## 开发\n\n```bash\npnpm install\npnpm run build\npnpm run test\n```\n\n\
+             ## 许可证\n\nApache-2.0，详见 [LICENSE](LICENSE)。\n"
+        );
+        // 已有 README 时不覆盖
+        assert_eq!(ensure_readme(project.path(), &summary).unwrap(), None);
+
+        let java = tempdir().unwrap();
+        fs::write(
+            java.path().join("pom.xml"),
+            "<project>spring-boot</project>",
+        )
+        .unwrap();
+        fs::write(java.path().join("mvnw"), "").unwrap();
+        let readme = render_readme(
+            java.path(),
+            &ProjectSummary {
+                name: "demo",
+                project_type: "java",
+                description: None,
+                license: None,
+            },
+        );
+        assert!(readme.contains("./mvnw package\n./mvnw spring-boot:run\n./mvnw test\n"));
+        assert!(!readme.contains("## 许可证"));
+    }
+}
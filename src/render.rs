@@ -0,0 +1,438 @@
+//! 模板渲染模块
+//! 按模板引擎渲染文件内容与路径：内置的 `{{变量}}`、helper 与 partial，以及 Tera、Liquid；不涉及文件读写以外的外部操作
+
+use crate::template_helpers::{PARTIALS_DIR, TemplateHelpers, partial_name};
+use crate::template_metadata::{TemplateEngine, TemplateMetadata, VariableType};
+use crate::variables::{TypedValue, coerce, resolve_variables};
+use crate::{GeneratorError, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Tera 引擎中变量的另一个入口，兼容 cookiecutter 模板的 `{{ cookiecutter.project_name }}`
+pub const COOKIECUTTER_NAMESPACE: &str = "cookiecutter";
+
+/// 渲染内存中的模板文件（相对路径 → 内容），返回渲染后的路径与内容
+///
+/// 变量先按 template.json 的声明校验与补全；`partials/` 下的文件作为 partial 使用，不出现在结果中。
+/// 不读写磁盘，也不处理条件文件、特性与钩子，供浏览器中的模板预览使用。
+pub fn render_files(
+    metadata: &TemplateMetadata,
+    files: &BTreeMap<String, String>,
+    provided: &HashMap<String, String>,
+) -> Result<BTreeMap<String, String>> {
+    let variables = resolve_variables(metadata, provided)?;
+    let partials_prefix = format!("{}/", PARTIALS_DIR);
+    let partials = files
+        .iter()
+        .filter_map(|(path, content)| {
+            let relative = path.strip_prefix(&partials_prefix)?;
+            Some((partial_name(relative), content.clone()))
+        })
+        .collect();
+    let renderer = TemplateRenderer::new(metadata, TemplateHelpers::new(metadata, partials)?);
+
+    let mut rendered = BTreeMap::new();
+    for (path, content) in files {
+        if path.starts_with(&partials_prefix) {
+            continue;
+        }
+        rendered.insert(
+            renderer.render(path, &variables)?,
+            renderer.render(content, &variables)?,
+        );
+    }
+    Ok(rendered)
+}
+
+/// 模板渲染器：按 template.json 的 `engine` 选择引擎，每次生成单独创建
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRenderer {
+    pub engine: TemplateEngine,
+    /// 模板自带的 helper 与 partial（仅 handlebars 引擎使用）
+    pub helpers: TemplateHelpers,
+    /// 声明的变量类型，其他引擎按类型传入布尔值与数字
+    pub variable_types: HashMap<String, VariableType>,
+}
+
+impl TemplateRenderer {
+    pub fn load(template_dir: &Path, metadata: &TemplateMetadata) -> Result<Self> {
+        Ok(Self::new(
+            metadata,
+            TemplateHelpers::load(template_dir, metadata)?,
+        ))
+    }
+
+    pub fn new(metadata: &TemplateMetadata, helpers: TemplateHelpers) -> Self {
+        Self {
+            engine: metadata.engine,
+            helpers,
+            variable_types: metadata
+                .variables
+                .iter()
+                .map(|variable| (variable.name.clone(), variable.var_type.clone()))
+                .collect(),
+        }
+    }
+
+    /// 渲染文件内容或路径
+    pub fn render(&self, content: &str, variables: &HashMap<String, String>) -> Result<String> {
+        match self.engine {
+            TemplateEngine::Handlebars => render_template_with(content, variables, &self.helpers),
+            TemplateEngine::Tera => {
+                let mut context = tera::Context::new();
+                let mut namespace = serde_json::Map::new();
+                for (key, value) in variables {
+                    let value = self.typed_value(key, value).to_json();
+                    context.insert(key.as_str(), &value);
+                    namespace.insert(key.clone(), value);
+                }
+                if !variables.contains_key(COOKIECUTTER_NAMESPACE) {
+                    context.insert(COOKIECUTTER_NAMESPACE, &namespace);
+                }
+                tera::Tera::one_off(content, &context, false).map_err(|e| engine_error("Tera", &e))
+            }
+            TemplateEngine::Liquid => {
+                let globals: liquid::Object = variables
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match self.typed_value(key, value) {
+                            TypedValue::Boolean(flag) => liquid::model::Value::scalar(flag),
+                            TypedValue::Number(number) => liquid::model::Value::scalar(number),
+                            TypedValue::String(text) => liquid::model::Value::scalar(text),
+                        };
+                        (key.clone().into(), value)
+                    })
+                    .collect();
+                liquid_parser()?
+                    .parse(content)
+                    .and_then(|template| template.render(&globals))
+                    .map_err(|e| engine_error("Liquid", &e))
+            }
+            TemplateEngine::None => Ok(content.to_string()),
+        }
+    }
+
+    /// 按声明类型转换；未声明的 `true` / `false` 也作为布尔值，使 `{% if useRouter %}` 按字面含义判断
+    fn typed_value(&self, key: &str, value: &str) -> TypedValue {
+        if let Some(typed) = self
+            .variable_types
+            .get(key)
+            .and_then(|var_type| coerce(var_type, value).ok())
+        {
+            return typed;
+        }
+        match value {
+            "true" => TypedValue::Boolean(true),
+            "false" => TypedValue::Boolean(false),
+            _ => TypedValue::String(value.to_string()),
+        }
+    }
+
+    /// 只检查语法，不渲染；handlebars 风格占位符由 lint 单独检查
+    pub fn check(&self, content: &str) -> Result<()> {
+        match self.engine {
+            TemplateEngine::Tera => tera::Tera::default()
+                .add_raw_template("template", content)
+                .map_err(|e| engine_error("Tera", &e)),
+            TemplateEngine::Liquid => liquid_parser()?
+                .parse(content)
+                .map(|_| ())
+                .map_err(|e| engine_error("Liquid", &e)),
+            TemplateEngine::Handlebars | TemplateEngine::None => Ok(()),
+        }
+    }
+}
+
+fn liquid_parser() -> Result<liquid::Parser> {
+    liquid::ParserBuilder::with_stdlib()
+        .build()
+        .map_err(|e| engine_error("Liquid", &e))
+}
+
+/// 引擎错误的具体原因在 source 链中，拼接后返回
+fn engine_error(engine: &str, error: &dyn std::error::Error) -> GeneratorError {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    GeneratorError::TemplateProcessing(format!("{} template error: {}", engine, message))
+}
+
+/// 渲染模板文件：替换 `{{变量}}` 并调用内置 helper（如 `{{pascalCase name}}`）
+///
+/// 无法解析的 `{{ ... }}`（如 Vue 模板中的插值）原样保留；与变量同名的插值可写作 `\{{name}}`
+/// 或放在 `{{{{raw}}}} ... {{{{/raw}}}}` 中。
+pub fn render_template(
+    template_content: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    render_template_with(template_content, variables, &TemplateHelpers::default())
+}
+
+/// 原样输出块的起止标记
+pub(crate) const RAW_BLOCK_OPEN: &str = "{{{{raw}}}}";
+pub(crate) const RAW_BLOCK_CLOSE: &str = "{{{{/raw}}}}";
+
+/// partial 最大嵌套层数，超出时视为递归引用
+const MAX_PARTIAL_DEPTH: usize = 16;
+
+/// 使用模板自带的 helper 与 partial（`{{> header}}`）渲染
+pub fn render_template_with(
+    template_content: &str,
+    variables: &HashMap<String, String>,
+    helpers: &TemplateHelpers,
+) -> Result<String> {
+    render_nested(template_content, variables, helpers, 0)
+}
+
+fn render_nested(
+    template_content: &str,
+    variables: &HashMap<String, String>,
+    helpers: &TemplateHelpers,
+    depth: usize,
+) -> Result<String> {
+    let mut result = String::with_capacity(template_content.len());
+    let mut rest = template_content;
+
+    while let Some(start) = rest.find("{{") {
+        // `\{{` 输出字面的 `{{`
+        if rest[..start].ends_with('\\') {
+            result.push_str(&rest[..start - 1]);
+            result.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        result.push_str(&rest[..start]);
+        // `{{{{raw}}}} ... {{{{/raw}}}}` 之间的内容原样输出
+        if let Some(block) = rest[start..].strip_prefix(RAW_BLOCK_OPEN)
+            && let Some(end) = block.find(RAW_BLOCK_CLOSE)
+        {
+            result.push_str(&block[..end]);
+            rest = &block[end + RAW_BLOCK_CLOSE.len()..];
+            continue;
+        }
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let expression = &after[..end];
+        let value = match expression.trim().strip_prefix('>') {
+            Some(partial) => render_partial(partial, variables, helpers, depth)?,
+            None => render_expression(expression, variables, helpers),
+        };
+        match value {
+            Some(value) => {
+                result.push_str(&value);
+                rest = &after[end + 2..];
+            }
+            // 保留开头的 `{{` 继续扫描，内部可能还有可替换的占位符
+            None => {
+                result.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+/// 以当前变量渲染 partial，未找到时返回 None（由 lint-template 报告）
+fn render_partial(
+    reference: &str,
+    variables: &HashMap<String, String>,
+    helpers: &TemplateHelpers,
+    depth: usize,
+) -> Result<Option<String>> {
+    let Some(partial) = reference
+        .split_whitespace()
+        .next()
+        .and_then(|name| helpers.partial(name))
+    else {
+        return Ok(None);
+    };
+    if depth >= MAX_PARTIAL_DEPTH {
+        return Err(GeneratorError::TemplateProcessing(format!(
+            "Partial '{}' is nested more than {} levels deep, check for recursive partials",
+            reference.trim(),
+            MAX_PARTIAL_DEPTH
+        )));
+    }
+    render_nested(partial, variables, helpers, depth + 1).map(Some)
+}
+
+/// 求值 `{{ ... }}` 内的表达式：变量名，或 helper 加参数（变量名或带引号的字符串）
+fn render_expression(
+    expression: &str,
+    variables: &HashMap<String, String>,
+    helpers: &TemplateHelpers,
+) -> Option<String> {
+    if let Some(value) = variables.get(expression) {
+        return Some(value.clone());
+    }
+
+    let tokens = expression_tokens(expression.trim())?;
+    let (helper, args) = tokens.split_first()?;
+    if !helpers.is_helper(helper) {
+        return None;
+    }
+    let args = args
+        .iter()
+        .map(
+            |arg| match arg.strip_prefix('"').and_then(|arg| arg.strip_suffix('"')) {
+                Some(literal) => Some(literal.to_string()),
+                None => variables.get(arg).cloned(),
+            },
+        )
+        .collect::<Option<Vec<_>>>()?;
+    helpers.call(helper, &args)
+}
+
+/// 按空白拆分表达式，双引号内的空白不拆分（引号保留）；引号不成对时返回 None
+fn expression_tokens(expression: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in expression.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                current.push(ch);
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template_metadata::TemplateVariable;
+
+    #[test]
+    fn test_render_template_helpers() {
+        let variables = HashMap::from([
+            ("name".to_string(), "user-profile".to_string()),
+            ("entity".to_string(), "category".to_string()),
+        ]);
+        let rendered = render_template(
+            "{{pascalCase name}} {{ camelCase name }} {{snakeCase name}} {{pluralize entity}} \
+             {{upper \"ok\"}} {{name}} {{ message }} {{pascalCase missing}}",
+            &variables,
+        )
+        .unwrap();
+        assert_eq!(
+            rendered,
+            "UserProfile userProfile user_profile categories OK user-profile \
+             {{ message }} {{pascalCase missing}}"
+        );
+        assert_eq!(
+            render_template("{{a {{name}}", &variables).unwrap(),
+            "{{a user-profile"
+        );
+        assert_eq!(
+            render_template(
+                "\\{{name}} {{{{raw}}}}{{name}} {{upper name}}{{{{/raw}}}} {{name}}",
+                &variables
+            )
+            .unwrap(),
+            "{{name}} {{name}} {{upper name}} user-profile"
+        );
+    }
+
+    #[test]
+    fn test_render_with_selected_engine() {
+        let variables = HashMap::from([
+            ("name".to_string(), "app".to_string()),
+            ("useRouter".to_string(), "false".to_string()),
+        ]);
+        let renderer = |engine| TemplateRenderer {
+            engine,
+            ..Default::default()
+        };
+
+        let tera = renderer(TemplateEngine::Tera);
+        assert_eq!(
+            tera.render(
+                "{{ name | upper }}{% if useRouter %} router{% endif %}",
+                &variables
+            )
+            .unwrap(),
+            "APP"
+        );
+        assert!(tera.check("{% if %}").is_err());
+
+        let liquid = renderer(TemplateEngine::Liquid);
+        assert_eq!(
+            liquid
+                .render(
+                    "{{ name | capitalize }}{% if useRouter %} router{% endif %}",
+                    &variables
+                )
+                .unwrap(),
+            "App"
+        );
+        assert!(matches!(
+            liquid.render("{% if %}", &variables),
+            Err(GeneratorError::TemplateProcessing(_))
+        ));
+
+        let raw = "{{ name }} {% raw %}";
+        assert_eq!(
+            renderer(TemplateEngine::None)
+                .render(raw, &variables)
+                .unwrap(),
+            raw
+        );
+    }
+
+    #[test]
+    fn test_render_files_in_memory() {
+        let metadata = TemplateMetadata {
+            variables: vec![TemplateVariable {
+                name: "author".to_string(),
+                description: String::new(),
+                default: Some("Ada".to_string()),
+                required: false,
+                var_type: VariableType::String,
+                sensitive: false,
+            }],
+            ..Default::default()
+        };
+        let files = BTreeMap::from([
+            (
+                "partials/header.hbs".to_string(),
+                "// {{author}}".to_string(),
+            ),
+            (
+                "src/{{name}}.ts".to_string(),
+                "{{> header}}\nexport const {{camelCase name}} = 1;".to_string(),
+            ),
+        ]);
+        let provided = HashMap::from([("name".to_string(), "my-app".to_string())]);
+        let rendered = render_files(&metadata, &files, &provided).unwrap();
+        assert_eq!(
+            rendered,
+            BTreeMap::from([(
+                "src/my-app.ts".to_string(),
+                "// Ada\nexport const myApp = 1;".to_string()
+            )])
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! 生成报告模块
+//! 汇总模板来源、使用的变量、写入的文件、钩子退出码与各阶段耗时，写成 JSON 或 HTML 供审计留档
+
+use crate::events::{self, Event};
+use crate::lockfile::LockSource;
+use crate::retry::RetryEvent;
+use crate::{GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 一次生成的报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// 项目名称
+    pub project: String,
+    /// 输出目录
+    pub output_dir: String,
+    /// 模板来源
+    pub template: ReportTemplate,
+    /// 生成时使用的变量
+    pub variables: BTreeMap<String, String>,
+    /// 启用的特性
+    pub features: Vec<String>,
+    /// 写入的文件（相对输出目录）
+    pub files: Vec<String>,
+    /// 来自基础模板或覆盖层的模板文件（相对模板目录）及其来源层，模板自身的文件不列出
+    pub provenance: BTreeMap<String, String>,
+    /// 执行的钩子与后置步骤
+    pub hooks: Vec<HookRun>,
+    /// 各阶段耗时
+    pub phases: Vec<PhaseTiming>,
+    /// 警告
+    pub warnings: Vec<String>,
+    /// 远程请求的重试
+    #[serde(default)]
+    pub retries: Vec<RetryEvent>,
+    /// 生成器版本
+    pub generator_version: String,
+    /// 生成时间（RFC 3339）
+    pub generated_at: String,
+}
+
+/// 报告中的模板信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportTemplate {
+    pub project_type: String,
+    pub name: String,
+    pub version: String,
+    pub source: LockSource,
+}
+
+/// 一次钩子执行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookRun {
+    /// 执行的命令
+    pub command: String,
+    /// 退出码，被信号终止、超时或无法启动时为空
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// 失败原因
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// 阶段耗时
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub name: String,
+    pub duration_ms: u64,
+}
+
+/// 依次记录各阶段耗时
+#[derive(Debug, Default)]
+pub struct PhaseTimer {
+    pub phases: Vec<PhaseTiming>,
+}
+
+impl PhaseTimer {
+    /// 执行 `f` 并记录为名为 `name` 的阶段，开始时发送进度事件
+    pub fn time<T>(&mut self, name: &str, f: impl FnOnce() -> T) -> T {
+        events::emit(Event::Progress {
+            phase: name.to_string(),
+        });
+        let started = Instant::now();
+        let result = f();
+        self.phases.push(PhaseTiming {
+            name: name.to_string(),
+            duration_ms: millis(started.elapsed()),
+        });
+        result
+    }
+}
+
+pub(crate) fn millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+impl GenerationReport {
+    /// 写入报告，扩展名为 `.html` / `.htm` 时输出 HTML，否则输出 JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("html" | "htm") => self.to_html(),
+            _ => serde_json::to_string_pretty(self)? + "\n",
+        };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content).map_err(|e| {
+            GeneratorError::FileOperation(format!("Cannot write report {}: {}", path.display(), e))
+        })
+    }
+
+    /// 渲染为独立的 HTML 页面
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>生成报告 - {project}</title>\n\
+             <style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin-bottom:1.5em}}\
+             td,th{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>\n\
+             </head>\n<body>\n<h1>生成报告 - {project}</h1>\n",
+            project = escape(&self.project)
+        );
+
+        table(
+            &mut html,
+            "概要",
+            &["项", "值"],
+            [
+                vec!["输出目录".to_string(), self.output_dir.clone()],
+                vec![
+                    "模板".to_string(),
+                    format!(
+                        "{}/{}@{}",
+                        self.template.project_type, self.template.name, self.template.version
+                    ),
+                ],
+                vec![
+                    "来源".to_string(),
+                    format!(
+                        "{} {} {}",
+                        self.template.source.kind,
+                        self.template.source.location,
+                        self.template.source.revision.as_deref().unwrap_or("")
+                    ),
+                ],
+                vec!["特性".to_string(), self.features.join(", ")],
+                vec!["生成器版本".to_string(), self.generator_version.clone()],
+                vec!["生成时间".to_string(), self.generated_at.clone()],
+            ],
+        );
+        table(
+            &mut html,
+            "变量",
+            &["名称", "值"],
+            self.variables
+                .iter()
+                .map(|(name, value)| vec![name.clone(), value.clone()]),
+        );
+        table(
+            &mut html,
+            "阶段耗时",
+            &["阶段", "耗时 (ms)"],
+            self.phases
+                .iter()
+                .map(|phase| vec![phase.name.clone(), phase.duration_ms.to_string()]),
+        );
+        table(
+            &mut html,
+            "钩子",
+            &["命令", "退出码", "耗时 (ms)", "错误"],
+            self.hooks.iter().map(|hook| {
+                vec![
+                    hook.command.clone(),
+                    hook.exit_code
+                        .map(|code| code.to_string())
+                        .unwrap_or_default(),
+                    hook.duration_ms.to_string(),
+                    hook.error.clone().unwrap_or_default(),
+                ]
+            }),
+        );
+        table(
+            &mut html,
+            "重试",
+            &["操作", "尝试", "错误", "等待 (ms)"],
+            self.retries.iter().map(|retry| {
+                vec![
+                    retry.operation.clone(),
+                    retry.attempt.to_string(),
+                    retry.error.clone(),
+                    retry.delay_ms.to_string(),
+                ]
+            }),
+        );
+        table(
+            &mut html,
+            "文件",
+            &["路径"],
+            self.files.iter().map(|file| vec![file.clone()]),
+        );
+        table(
+            &mut html,
+            "文件来源",
+            &["模板文件", "来源层"],
+            self.provenance
+                .iter()
+                .map(|(file, layer)| vec![file.clone(), layer.clone()]),
+        );
+        table(
+            &mut html,
+            "警告",
+            &["内容"],
+            self.warnings.iter().map(|warning| vec![warning.clone()]),
+        );
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn table(
+    html: &mut String,
+    title: &str,
+    headers: &[&str],
+    rows: impl IntoIterator<Item = Vec<String>>,
+) {
+    let _ = write!(html, "<h2>{}</h2>\n<table>\n<tr>", escape(title));
+    for header in headers {
+        let _ = write!(html, "<th>{}</th>", escape(header));
+    }
+    html.push_str("</tr>\n");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            let _ = write!(html, "<td>{}</td>", escape(&cell));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_json_and_html_reports() {
+        let dir = tempdir().unwrap();
+        let report = GenerationReport {
+            project: "demo".to_string(),
+            variables: BTreeMap::from([("title".to_string(), "<b>x</b>".to_string())]),
+            hooks: vec![HookRun {
+                command: "npm install".to_string(),
+                exit_code: Some(0),
+                duration_ms: 12,
+                error: None,
+            }],
+            phases: vec![PhaseTiming {
+                name: "render".to_string(),
+                duration_ms: 3,
+            }],
+            provenance: BTreeMap::from([(
+                ".github/workflows/ci.yml".to_string(),
+                "override:org".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        let json_path = dir.path().join("report.json");
+        report.write(&json_path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json["hooks"][0]["exit_code"], 0);
+
+        let html_path = dir.path().join("out/report.html");
+        report.write(&html_path).unwrap();
+        let html = fs::read_to_string(&html_path).unwrap();
+        assert!(html.contains("&lt;b&gt;x&lt;/b&gt;"));
+        assert!(html.contains("<td>npm install</td><td>0</td>"));
+        assert!(html.contains("<td>.github/workflows/ci.yml</td><td>override:org</td>"));
+    }
+}
@@ -0,0 +1,184 @@
+//! 网络重试模块
+//! 按指数退避重试远程模板源的 HTTP 下载与 git 拉取；每次重试都写入日志，并记录下来供生成报告汇总
+
+use crate::{GeneratorError, Result, cancel, say, t};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 重试策略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// 最多尝试次数（含首次），1 表示不重试
+    pub attempts: u32,
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍
+    pub backoff_ms: u64,
+    /// 单次等待时间上限（毫秒）
+    pub max_backoff_ms: u64,
+    /// 视为暂时性故障的 HTTP 状态码，连接失败等传输错误总是重试
+    pub retryable_status: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff_ms: 500,
+            max_backoff_ms: 10_000,
+            retryable_status: vec![408, 429, 500, 502, 503, 504],
+        }
+    }
+}
+
+/// 一次重试记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryEvent {
+    /// 重试的操作（如下载的 URL）
+    pub operation: String,
+    /// 失败的是第几次尝试（从 1 开始）
+    pub attempt: u32,
+    /// 失败原因
+    pub error: String,
+    /// 下次尝试前的等待时间（毫秒）
+    pub delay_ms: u64,
+}
+
+static EVENTS: Mutex<Vec<RetryEvent>> = Mutex::new(Vec::new());
+
+/// 取出并清空已记录的重试
+pub fn take_events() -> Vec<RetryEvent> {
+    std::mem::take(&mut *EVENTS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+impl RetryPolicy {
+    /// 第 `retry` 次重试（从 1 开始）前的等待时间
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+        Duration::from_millis(
+            self.backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+
+    /// HTTP 请求错误是否值得重试
+    pub fn is_retryable(&self, error: &ureq::Error) -> bool {
+        match error {
+            ureq::Error::Status(code, _) => self.retryable_status.contains(code),
+            ureq::Error::Transport(_) => true,
+        }
+    }
+
+    /// 执行 `f`，`retryable` 判定为暂时性故障时按退避间隔重试，取消后不再重试
+    pub fn run<T, E: Display>(
+        &self,
+        operation: &str,
+        retryable: impl Fn(&E) -> bool,
+        mut f: impl FnMut() -> std::result::Result<T, E>,
+    ) -> Result<std::result::Result<T, E>> {
+        let mut attempt = 1;
+        loop {
+            cancel::check()?;
+            let error = match f() {
+                Ok(value) => return Ok(Ok(value)),
+                Err(error) if attempt < self.attempts.max(1) && retryable(&error) => error,
+                Err(error) => return Ok(Err(error)),
+            };
+            let delay = self.delay(attempt);
+            say!(
+                "{}",
+                t!(
+                    "network.retrying",
+                    operation = operation,
+                    attempt = attempt,
+                    error = error,
+                    delay = delay.as_millis()
+                )
+            );
+            EVENTS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(RetryEvent {
+                    operation: operation.to_string(),
+                    attempt,
+                    error: error.to_string(),
+                    delay_ms: delay.as_millis() as u64,
+                });
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// 发送 `request` 构造的 HTTP 请求，暂时性故障时重试，最终失败时经 `error` 转换
+    pub fn call<E: From<GeneratorError>>(
+        &self,
+        url: &str,
+        request: impl Fn() -> ureq::Request,
+        error: impl FnOnce(ureq::Error) -> E,
+    ) -> std::result::Result<ureq::Response, E> {
+        let response = self.run::<_, Box<ureq::Error>>(
+            url,
+            |e| self.is_retryable(e),
+            || request().call().map_err(Box::new),
+        )?;
+        response.map_err(|e| error(*e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff_and_events() {
+        let policy = RetryPolicy {
+            attempts: 3,
+            backoff_ms: 1,
+            max_backoff_ms: 2,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(1));
+        assert_eq!(policy.delay(2), Duration::from_millis(2));
+        assert_eq!(policy.delay(10), Duration::from_millis(2));
+
+        let mut calls = 0;
+        let result = policy
+            .run(
+                "retry-test://flaky",
+                |_: &String| true,
+                || {
+                    calls += 1;
+                    if calls < 3 {
+                        Err("503".to_string())
+                    } else {
+                        Ok(calls)
+                    }
+                },
+            )
+            .unwrap();
+        assert_eq!(result, Ok(3));
+        let events: Vec<_> = take_events()
+            .into_iter()
+            .filter(|event| event.operation == "retry-test://flaky")
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].attempt, 2);
+
+        // 不可重试的错误与用尽次数时直接返回最后一次错误
+        let mut calls = 0;
+        let result: std::result::Result<(), String> = policy
+            .run(
+                "retry-test://fatal",
+                |error: &String| error != "404",
+                || {
+                    calls += 1;
+                    Err("404".to_string())
+                },
+            )
+            .unwrap();
+        assert_eq!(result, Err("404".to_string()));
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,155 @@
+//! 模板脚手架模块
+//! 生成一个可直接使用的模板骨架（带类型化变量的 template.json、示例文件、.genignore 与 hooks 目录）
+
+use crate::pipeline::{HOOKS_DIR, TEMPLATE_METADATA_FILE};
+use crate::template_ignore::IGNORE_FILE_NAME;
+use crate::template_registry::{TemplateMetadata, TemplateVariable, VariableType};
+use crate::{GeneratorError, NewTemplateOptions, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 创建模板骨架，返回创建的文件（相对模板目录）
+pub fn create_template(options: &NewTemplateOptions) -> Result<Vec<String>> {
+    let template_dir = match &options.path {
+        Some(path) => PathBuf::from(path),
+        None => Path::new("templates")
+            .join(&options.project_type)
+            .join(&options.name),
+    };
+    if template_dir.exists() && fs::read_dir(&template_dir)?.next().is_some() {
+        return Err(GeneratorError::FileOperation(format!(
+            "Template directory already exists and is not empty: {}",
+            template_dir.display()
+        )));
+    }
+
+    let metadata = TemplateMetadata {
+        name: options.name.clone(),
+        version: "0.1.0".to_string(),
+        description: options
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("{} 模板", options.name)),
+        author: options.author.clone().unwrap_or_default(),
+        project_type: options.project_type.clone(),
+        variables: example_variables(),
+        tags: vec![options.project_type.clone()],
+        ignore: vec!["*.log".to_string(), format!("{}/", HOOKS_DIR)],
+        ..Default::default()
+    };
+    let mut metadata_json = serde_json::to_string_pretty(&metadata)?;
+    metadata_json.push('\n');
+
+    let files: Vec<(String, String)> = vec![
+        (TEMPLATE_METADATA_FILE.to_string(), metadata_json),
+        ("README.md".to_string(), README.to_string()),
+        ("src/{{name}}.txt".to_string(), EXAMPLE_FILE.to_string()),
+        (IGNORE_FILE_NAME.to_string(), GENIGNORE.to_string()),
+        (format!("{}/README.md", HOOKS_DIR), HOOKS_README.to_string()),
+    ];
+
+    for (relative, content) in &files {
+        let path = template_dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    Ok(files.into_iter().map(|(relative, _)| relative).collect())
+}
+
+/// 覆盖各种变量类型的示例
+fn example_variables() -> Vec<TemplateVariable> {
+    vec![
+        TemplateVariable {
+            name: "description".to_string(),
+            description: "项目描述".to_string(),
+            default: Some("A new project".to_string()),
+            required: false,
+            var_type: VariableType::String,
+            sensitive: false,
+        },
+        TemplateVariable {
+            name: "author".to_string(),
+            description: "作者".to_string(),
+            default: None,
+            required: true,
+            var_type: VariableType::String,
+            sensitive: false,
+        },
+        TemplateVariable {
+            name: "useTypescript".to_string(),
+            description: "是否使用 TypeScript".to_string(),
+            default: Some("true".to_string()),
+            required: false,
+            var_type: VariableType::Boolean,
+            sensitive: false,
+        },
+        TemplateVariable {
+            name: "license".to_string(),
+            description: "开源协议".to_string(),
+            default: Some("MIT".to_string()),
+            required: false,
+            var_type: VariableType::Choice {
+                options: vec![
+                    "MIT".to_string(),
+                    "Apache-2.0".to_string(),
+                    "UNLICENSED".to_string(),
+                ],
+            },
+            sensitive: false,
+        },
+    ]
+}
+
+const README: &str = "# {{name}}
+
+{{description}}
+
+作者: {{author}}，协议: {{license}}
+";
+
+const EXAMPLE_FILE: &str = "文件名与内容中的 {{name}} 都会被替换为项目名称。
+";
+
+const GENIGNORE: &str = "# 不复制到生成项目中的文件（gitignore 语法）
+*.log
+";
+
+const HOOKS_README: &str = "# hooks
+
+放置生成后执行的钩子脚本，该目录不会被复制到生成的项目中。
+
+在 template.json 的 `hooks` 中声明要执行的脚本（`shell`、`node`、`powershell`、`exec`，可用 `windows`、`macos`、`linux` 按平台覆盖），
+脚本可通过环境变量 `GENERATOR_TEMPLATE_DIR` 找到模板目录。
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::load_template_metadata;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_template_skeleton() {
+        let dir = tempdir().unwrap();
+        let template_dir = dir.path().join("my-template");
+
+        let options = NewTemplateOptions {
+            name: "my-template".to_string(),
+            project_type: "vue".to_string(),
+            path: Some(template_dir.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let files = create_template(&options).unwrap();
+        assert!(files.contains(&"template.json".to_string()));
+
+        let metadata = load_template_metadata(&template_dir).unwrap();
+        assert_eq!(metadata.project_type, "vue");
+        assert_eq!(metadata.variables.len(), 4);
+        assert!(template_dir.join("hooks").is_dir());
+
+        assert!(create_template(&options).is_err());
+    }
+}
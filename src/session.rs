@@ -0,0 +1,103 @@
+//! 交互会话恢复模块
+//! 交互式生成失败时把已完成的回答与所选模板保存到临时文件，`--resume` 时跳过已回答的问题直接重新生成
+
+use crate::answers::Answers;
+use crate::{GeneratorError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 会话文件名（位于系统临时目录）
+pub const SESSION_FILE: &str = "generator-cli-session.json";
+
+/// 中断的交互会话
+///
+/// 生成在暂存目录中进行，失败时已回滚，恢复时从头执行生成阶段但不再重复提问；
+/// 敏感变量不会写入会话文件，恢复时重新询问。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Session {
+    /// 已完成的回答（含 `template` 等问题的答案）
+    pub answers: Answers,
+    /// 失败原因
+    pub error: String,
+    /// 失败时间（RFC 3339）
+    pub failed_at: String,
+}
+
+impl Session {
+    pub fn new(answers: Answers, error: &GeneratorError) -> Self {
+        Self {
+            answers,
+            error: error.to_string(),
+            failed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// 默认的会话文件路径
+    pub fn default_path() -> PathBuf {
+        std::env::temp_dir().join(SESSION_FILE)
+    }
+
+    /// 读取会话，文件不存在时返回 None
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid session file {}: {}", path.display(), e))
+        })
+    }
+
+    /// 写入会话，仅当前用户可读
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs::write(path, content)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    /// 生成成功后删除会话文件
+    pub fn clear(path: &Path) -> Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_session_roundtrip_and_clear() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(SESSION_FILE);
+        assert!(Session::load(&path).unwrap().is_none());
+
+        let mut answers = Answers::default();
+        answers
+            .prompts
+            .insert("template".to_string(), "spring-boot".to_string());
+        let error = GeneratorError::ExternalCommand("npm install exited with 1".to_string());
+        Session::new(answers, &error).save(&path).unwrap();
+
+        let session = Session::load(&path).unwrap().unwrap();
+        assert_eq!(session.answers.prompts["template"], "spring-boot");
+        assert_eq!(
+            session.error,
+            "External command failed: npm install exited with 1"
+        );
+
+        Session::clear(&path).unwrap();
+        Session::clear(&path).unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,260 @@
+//! 模板试渲染模块
+//! 用一组或多组变量 fixture 将模板渲染到临时目录，检查渲染失败、残留占位符与输出中的 JSON/XML 语法错误
+
+use crate::computed::apply_computed;
+use crate::pipeline::{RenderOptions, is_binary_content};
+use crate::template_lint::{Placeholder, diagnostic, placeholders};
+use crate::template_registry::{TemplateMetadata, TemplateRegistryConfig};
+use crate::{Diagnostic, GeneratorError, Result, TemplateFixture, templates};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// 以 fixture 试渲染模板；未提供 fixture 时使用变量默认值渲染一次
+pub fn test_template(template_dir: &Path, fixtures: &[TemplateFixture]) -> Result<Vec<Diagnostic>> {
+    let template = TemplateRegistryConfig::default().resolve_layers(template_dir)?;
+    let (template_dir, metadata) = (template.dir.as_path(), &template.metadata);
+    let default_fixture = [TemplateFixture::default()];
+    let fixtures = if fixtures.is_empty() {
+        &default_fixture[..]
+    } else {
+        fixtures
+    };
+
+    let mut diagnostics = Vec::new();
+    for (index, fixture) in fixtures.iter().enumerate() {
+        let label = fixture
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("fixture-{}", index + 1));
+        let mut found = render_fixture(template_dir, metadata, fixture)?;
+        for diagnostic in &mut found {
+            diagnostic.fixture = Some(label.clone());
+        }
+        diagnostics.extend(found);
+    }
+
+    Ok(diagnostics)
+}
+
+fn render_fixture(
+    template_dir: &Path,
+    metadata: &TemplateMetadata,
+    fixture: &TemplateFixture,
+) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    let mut variables = templates::default_variables(metadata, "fixture-app");
+    variables.extend(fixture.variables.clone().unwrap_or_default());
+
+    for variable in &metadata.variables {
+        if variable.required && !variables.contains_key(&variable.name) {
+            diagnostics.push(diagnostic(
+                "error",
+                "missing-variable",
+                format!("Required variable '{}' has no value", variable.name),
+                None,
+            ));
+        }
+    }
+    if let Err(e) = apply_computed(metadata, &mut variables) {
+        diagnostics.push(diagnostic("error", "invalid-computed", e.to_string(), None));
+        return Ok(diagnostics);
+    }
+
+    let output = tempfile::tempdir()?;
+    let features = fixture.features.clone().unwrap_or_default();
+    if let Err(e) = templates::render_to_dir(
+        template_dir,
+        metadata,
+        output.path(),
+        &variables,
+        &RenderOptions::default(),
+        &features,
+    ) {
+        diagnostics.push(diagnostic("error", "render-failed", e.to_string(), None));
+        return Ok(diagnostics);
+    }
+
+    let declared: Vec<&str> = metadata
+        .variables
+        .iter()
+        .map(|variable| variable.name.as_str())
+        .chain(metadata.computed.iter().map(|computed| computed.name.as_str()))
+        .chain(["name", "project_type"])
+        .collect();
+
+    for entry in WalkDir::new(output.path()).min_depth(1).sort_by_file_name() {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(output.path())
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        let display = relative.to_string_lossy().replace('\\', "/");
+
+        let content = fs::read(entry.path())?;
+        if is_binary_content(&content) {
+            continue;
+        }
+        let content = String::from_utf8_lossy(&content);
+
+        for placeholder in placeholders(&display)
+            .into_iter()
+            .chain(placeholders(&content))
+        {
+            if let Placeholder::Variable(name) = placeholder {
+                // 已声明变量残留说明渲染遗漏；未声明的可能是目标框架自身的插值语法（如 Vue）
+                let severity = if declared.contains(&name.as_str()) {
+                    "error"
+                } else {
+                    "warning"
+                };
+                diagnostics.push(diagnostic(
+                    severity,
+                    "unresolved-placeholder",
+                    format!("Placeholder '{{{{{}}}}}' was not rendered", name),
+                    Some(&display),
+                ));
+            }
+        }
+
+        let syntax_error = match relative.extension().and_then(|ext| ext.to_str()) {
+            Some("json") if !is_jsonc(relative) => {
+                serde_json::from_str::<serde_json::Value>(&content)
+                    .err()
+                    .map(|e| ("invalid-json", e.to_string()))
+            }
+            Some("xml") => check_xml(&content).err().map(|e| ("invalid-xml", e)),
+            _ => None,
+        };
+        if let Some((code, message)) = syntax_error {
+            diagnostics.push(diagnostic("error", code, message, Some(&display)));
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+/// 允许注释的 JSON 配置文件（tsconfig.json 等），不做严格校验
+fn is_jsonc(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("tsconfig") || name.starts_with("jsconfig"))
+        || path.starts_with(".vscode")
+}
+
+/// 检查 XML 是否格式良好（词法合法且标签正确配对）
+fn check_xml(content: &str) -> std::result::Result<(), String> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut pending: Option<String> = None;
+
+    for token in xmlparser::Tokenizer::from(content) {
+        match token.map_err(|e| e.to_string())? {
+            xmlparser::Token::ElementStart { prefix, local, .. } => {
+                pending = Some(qualified_name(prefix.as_str(), local.as_str()));
+            }
+            xmlparser::Token::ElementEnd { end, .. } => match end {
+                xmlparser::ElementEnd::Open => stack.extend(pending.take()),
+                xmlparser::ElementEnd::Empty => pending = None,
+                xmlparser::ElementEnd::Close(prefix, local) => {
+                    let name = qualified_name(prefix.as_str(), local.as_str());
+                    match stack.pop() {
+                        Some(open) if open == name => {}
+                        Some(open) => {
+                            return Err(format!("Expected </{}>, found </{}>", open, name));
+                        }
+                        None => return Err(format!("Unexpected </{}>", name)),
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    match stack.pop() {
+        Some(open) => Err(format!("Unclosed element <{}>", open)),
+        None => Ok(()),
+    }
+}
+
+fn qualified_name(prefix: &str, local: &str) -> String {
+    if prefix.is_empty() {
+        local.to_string()
+    } else {
+        format!("{}:{}", prefix, local)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_check_xml() {
+        assert!(check_xml("<?xml version=\"1.0\"?><project><a/><b>x</b></project>").is_ok());
+        assert!(check_xml("<project><a></b></project>").is_err());
+        assert!(check_xml("<project>").is_err());
+    }
+
+    #[test]
+    fn test_template_with_fixtures() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "vue", "dependencies": [], "tags": [],
+                "variables": [
+                    {"name": "version", "description": "", "default": "1.0.0", "required": false, "var_type": "string"}
+                ]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "{{name}}", "version": "{{version}}", "author": "{{author}}"}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pom.xml"),
+            "<project><name>{{name}}</name></project>",
+        )
+        .unwrap();
+
+        let fixtures = vec![
+            TemplateFixture::default(),
+            TemplateFixture {
+                name: Some("broken".to_string()),
+                variables: Some(HashMap::from([("version".to_string(), "1\"".to_string())])),
+                features: None,
+            },
+        ];
+        let diagnostics = test_template(dir.path(), &fixtures).unwrap();
+        let summary: Vec<(&str, &str, &str)> = diagnostics
+            .iter()
+            .map(|d| {
+                (
+                    d.fixture.as_deref().unwrap(),
+                    d.severity.as_str(),
+                    d.code.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("fixture-1", "warning", "unresolved-placeholder"),
+                ("broken", "warning", "unresolved-placeholder"),
+                ("broken", "error", "invalid-json"),
+            ]
+        );
+
+        // 自带模板在默认变量下应当可以干净渲染
+        let shipped = test_template(Path::new("templates/node-lib/basic"), &[]).unwrap();
+        assert!(shipped.is_empty(), "{:?}", shipped);
+    }
+}
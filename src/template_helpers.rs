@@ -0,0 +1,359 @@
+//! 模板 helper 模块
+//! 提供 `{{pascalCase name}}` 等内置 helper，并加载模板包自带的 partials 与 template.json 中声明的 helper
+
+use crate::template_metadata::{HelperRule, TemplateMetadata};
+use crate::{GeneratorError, Result};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 模板 partial 目录
+pub const PARTIALS_DIR: &str = "partials";
+
+/// 内置 helper 名称
+pub const BUILTIN_HELPERS: &[&str] = &[
+    "pascalCase",
+    "camelCase",
+    "kebabCase",
+    "snakeCase",
+    "upper",
+    "lower",
+    "pluralize",
+    "uuid",
+    "now",
+    "year",
+];
+
+/// 是否为内置 helper
+pub fn is_helper(name: &str) -> bool {
+    BUILTIN_HELPERS.contains(&name)
+}
+
+/// 调用 helper，名称未知或参数个数不符时返回 None
+///
+/// `now` 可带一个 strftime 格式参数，默认输出 `%Y-%m-%d %H:%M:%S`。
+pub fn call_helper(name: &str, args: &[String]) -> Option<String> {
+    match (name, args) {
+        ("pascalCase", [value]) => Some(pascal_case(value)),
+        ("camelCase", [value]) => Some(camel_case(value)),
+        ("kebabCase", [value]) => Some(join_words(value, "-")),
+        ("snakeCase", [value]) => Some(join_words(value, "_")),
+        ("upper", [value]) => Some(value.to_uppercase()),
+        ("lower", [value]) => Some(value.to_lowercase()),
+        ("pluralize", [value]) => Some(pluralize(value)),
+        ("uuid", []) => uuid_v4(),
+        ("now", []) => Some(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+        ("now", [format]) => format_now(format),
+        ("year", []) => Some(chrono::Local::now().format("%Y").to_string()),
+        _ => None,
+    }
+}
+
+/// 模板包自带的 helper 与 partial
+///
+/// 每次生成从模板目录单独加载，不注册到全局，不同模板之间互不可见。
+#[derive(Debug, Clone, Default)]
+pub struct TemplateHelpers {
+    helpers: HashMap<String, CustomHelper>,
+    partials: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+enum CustomHelper {
+    Replace {
+        regex: Regex,
+        replacement: String,
+    },
+    Map {
+        values: HashMap<String, String>,
+        default: Option<String>,
+    },
+}
+
+impl TemplateHelpers {
+    /// 加载 `partials/` 下的 partial 与 template.json 中声明的 helper
+    pub fn load(template_dir: &Path, metadata: &TemplateMetadata) -> Result<Self> {
+        Self::new(metadata, load_partials(&template_dir.join(PARTIALS_DIR))?)
+    }
+
+    /// 以已读取的 partial（名称 → 内容）编译 template.json 中声明的 helper
+    pub fn new(metadata: &TemplateMetadata, partials: HashMap<String, String>) -> Result<Self> {
+        let mut helpers = HashMap::new();
+        for helper in &metadata.helpers {
+            if is_helper(&helper.name) {
+                return Err(GeneratorError::Configuration(format!(
+                    "Helper '{}' conflicts with a built-in helper",
+                    helper.name
+                )));
+            }
+            let compiled = match &helper.rule {
+                HelperRule::Replace {
+                    pattern,
+                    replacement,
+                } => CustomHelper::Replace {
+                    regex: Regex::new(pattern).map_err(|e| {
+                        GeneratorError::Configuration(format!(
+                            "Invalid pattern for helper '{}': {}",
+                            helper.name, e
+                        ))
+                    })?,
+                    replacement: replacement.clone(),
+                },
+                HelperRule::Map { values, default } => CustomHelper::Map {
+                    values: values.clone(),
+                    default: default.clone(),
+                },
+            };
+            helpers.insert(helper.name.clone(), compiled);
+        }
+
+        Ok(Self { helpers, partials })
+    }
+
+    /// 是否为内置或模板声明的 helper
+    pub fn is_helper(&self, name: &str) -> bool {
+        is_helper(name) || self.helpers.contains_key(name)
+    }
+
+    /// 调用内置或模板声明的 helper；模板 helper 只接受一个参数
+    pub fn call(&self, name: &str, args: &[String]) -> Option<String> {
+        let Some(helper) = self.helpers.get(name) else {
+            return call_helper(name, args);
+        };
+        let [value] = args else {
+            return None;
+        };
+        Some(match helper {
+            CustomHelper::Replace { regex, replacement } => {
+                regex.replace_all(value, replacement.as_str()).into_owned()
+            }
+            CustomHelper::Map { values, default } => values
+                .get(value)
+                .or(default.as_ref())
+                .unwrap_or(value)
+                .clone(),
+        })
+    }
+
+    /// 按名称查找 partial（`{{> header}}` 对应 `partials/header.hbs` 或 `partials/header`）
+    pub fn partial(&self, name: &str) -> Option<&str> {
+        self.partials.get(name).map(String::as_str)
+    }
+}
+
+/// 读取 partial 目录，`.hbs` 文件以去掉扩展名的相对路径命名
+fn load_partials(dir: &Path) -> Result<HashMap<String, String>> {
+    let mut partials = HashMap::new();
+    if !dir.is_dir() {
+        return Ok(partials);
+    }
+    for entry in walkdir::WalkDir::new(dir).min_depth(1) {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?
+            .to_string_lossy()
+            .replace('\\', "/");
+        partials.insert(partial_name(&relative), fs::read_to_string(entry.path())?);
+    }
+    Ok(partials)
+}
+
+/// partial 目录中的文件（相对路径）对应的 partial 名称，`.hbs` 扩展名不计入名称
+pub fn partial_name(relative: &str) -> String {
+    relative.strip_suffix(".hbs").unwrap_or(relative).to_string()
+}
+
+/// 按分隔符、小写到大写及缩写边界拆分单词，如 `userHTTPServer` → `user`、`HTTP`、`Server`
+fn words(input: &str) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (index, &ch) in chars.iter().enumerate() {
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if ch.is_uppercase() && !current.is_empty() {
+            let prev = chars[index - 1];
+            let next_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn pascal_case(input: &str) -> String {
+    words(input).iter().map(|word| capitalize(word)).collect()
+}
+
+fn camel_case(input: &str) -> String {
+    words(input)
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            if index == 0 {
+                word.to_lowercase()
+            } else {
+                capitalize(word)
+            }
+        })
+        .collect()
+}
+
+fn join_words(input: &str, separator: &str) -> String {
+    words(input)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 英文复数的常见规则：`-y` → `-ies`，`-s/-x/-z/-ch/-sh` 加 `es`，其余加 `s`
+fn pluralize(word: &str) -> String {
+    if word.is_empty() {
+        return String::new();
+    }
+    let lower = word.to_lowercase();
+    let upper = word.chars().any(char::is_alphabetic) && word == word.to_uppercase();
+    let suffix = |suffix: &str| {
+        if upper {
+            suffix.to_uppercase()
+        } else {
+            suffix.to_string()
+        }
+    };
+
+    let before_y = lower.strip_suffix('y').and_then(|stem| stem.chars().last());
+    if before_y.is_some_and(|ch| !"aeiou".contains(ch)) {
+        return format!("{}{}", &word[..word.len() - 1], suffix("ies"));
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|ending| lower.ends_with(ending))
+    {
+        return format!("{}{}", word, suffix("es"));
+    }
+    format!("{}{}", word, suffix("s"))
+}
+
+/// 随机 UUID（版本 4）
+fn uuid_v4() -> Option<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).ok()?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+/// 无效的格式串返回 None，避免 chrono 在格式化时 panic
+fn format_now(format: &str) -> Option<String> {
+    let items: Vec<chrono::format::Item> = chrono::format::StrftimeItems::new(format).collect();
+    if items.contains(&chrono::format::Item::Error) {
+        return None;
+    }
+    Some(
+        chrono::Local::now()
+            .format_with_items(items.into_iter())
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, value: &str) -> String {
+        call_helper(name, &[value.to_string()]).unwrap()
+    }
+
+    #[test]
+    fn test_case_helpers() {
+        assert_eq!(call("pascalCase", "user-profile card"), "UserProfileCard");
+        assert_eq!(call("camelCase", "UserHTTPServer"), "userHttpServer");
+        assert_eq!(call("kebabCase", "userProfile2Card"), "user-profile2-card");
+        assert_eq!(call("snakeCase", "my-app.v2"), "my_app_v2");
+        assert_eq!(call("upper", "app"), "APP");
+        assert_eq!(call("pluralize", "category"), "categories");
+        assert_eq!(call("pluralize", "box"), "boxes");
+        assert_eq!(call("pluralize", "day"), "days");
+        assert_eq!(call("pluralize", "USER"), "USERS");
+
+        let uuid = call_helper("uuid", &[]).unwrap();
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(&uuid[14..15], "4");
+        assert_eq!(call_helper("year", &[]).unwrap().len(), 4);
+        assert_eq!(call("now", "%Y").len(), 4);
+        assert!(call_helper("now", &["%Q".to_string()]).is_none());
+        assert!(call_helper("upper", &[]).is_none());
+    }
+
+    #[test]
+    fn test_template_helpers_and_partials() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(PARTIALS_DIR).join("java")).unwrap();
+        fs::write(
+            dir.path().join(PARTIALS_DIR).join("header.hbs"),
+            "// {{name}}",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(PARTIALS_DIR).join("java/license.hbs"),
+            "MIT",
+        )
+        .unwrap();
+        let metadata: TemplateMetadata = serde_json::from_value(serde_json::json!({
+            "name": "demo", "version": "1.0.0", "description": "", "author": "",
+            "project_type": "java", "variables": [], "dependencies": [], "tags": [],
+            "helpers": [
+                {"name": "packagePath", "type": "replace", "pattern": "\\.", "replacement": "/"},
+                {"name": "javaType", "type": "map", "values": {"string": "String"}, "default": "Object"}
+            ]
+        }))
+        .unwrap();
+
+        let helpers = TemplateHelpers::load(dir.path(), &metadata).unwrap();
+        let call = |name: &str, value: &str| helpers.call(name, &[value.to_string()]).unwrap();
+        assert_eq!(call("packagePath", "com.example.app"), "com/example/app");
+        assert_eq!(call("javaType", "string"), "String");
+        assert_eq!(call("javaType", "date"), "Object");
+        assert_eq!(call("upper", "ok"), "OK");
+        assert_eq!(helpers.partial("header"), Some("// {{name}}"));
+        assert_eq!(helpers.partial("java/license"), Some("MIT"));
+
+        // 其他模板加载时看不到这些 helper
+        assert!(!TemplateHelpers::default().is_helper("packagePath"));
+    }
+}
@@ -0,0 +1,78 @@
+//! 模板忽略规则模块
+//! 解析 `.genignore` 文件与 template.json 中的 `ignore` 列表（gitignore 风格）
+
+use crate::{GeneratorError, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+/// 模板目录中的忽略文件名
+pub const IGNORE_FILE_NAME: &str = ".genignore";
+
+/// 模板忽略规则
+pub struct TemplateIgnore {
+    matcher: Gitignore,
+}
+
+impl TemplateIgnore {
+    /// 根据模板目录下的 `.genignore` 与元数据中的规则构建
+    pub fn load(template_dir: &Path, patterns: &[String]) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(template_dir);
+
+        let ignore_file = template_dir.join(IGNORE_FILE_NAME);
+        if ignore_file.is_file()
+            && let Some(err) = builder.add(&ignore_file)
+        {
+            return Err(GeneratorError::Configuration(format!(
+                "Invalid {}: {}",
+                IGNORE_FILE_NAME, err
+            )));
+        }
+
+        for pattern in patterns {
+            builder.add_line(None, pattern).map_err(|e| {
+                GeneratorError::Configuration(format!(
+                    "Invalid ignore pattern '{}': {}",
+                    pattern, e
+                ))
+            })?;
+        }
+
+        let matcher = builder
+            .build()
+            .map_err(|e| GeneratorError::Configuration(format!("Invalid ignore rules: {}", e)))?;
+
+        Ok(Self { matcher })
+    }
+
+    /// 判断模板内的相对路径是否被忽略
+    pub fn is_ignored(&self, relative_path: &Path, is_dir: bool) -> bool {
+        self.matcher
+            .matched_path_or_any_parents(relative_path, is_dir)
+            .is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_ignore_file_and_metadata_patterns() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join(IGNORE_FILE_NAME),
+            "docs/\n*.png\n!logo.png\n",
+        )
+        .unwrap();
+
+        let ignore = TemplateIgnore::load(temp_dir.path(), &[".github/".to_string()]).unwrap();
+
+        assert!(ignore.is_ignored(Path::new("docs/guide.md"), false));
+        assert!(ignore.is_ignored(Path::new("screenshot.png"), false));
+        assert!(!ignore.is_ignored(Path::new("logo.png"), false));
+        assert!(ignore.is_ignored(Path::new(".github"), true));
+        assert!(!ignore.is_ignored(Path::new("src/main.ts"), false));
+    }
+}
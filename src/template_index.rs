@@ -0,0 +1,308 @@
+//! 本地模板索引模块
+//! 将本地注册表中各模板的元数据缓存到索引文件，按目录与 template.json 的修改时间增量刷新，变化的模板并行解析
+
+use crate::pipeline;
+use crate::template_registry::{COMPONENTS_DIR, TemplateMetadata, TemplateRegistryConfig};
+use crate::{GeneratorError, IndexStats, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 索引文件名（位于缓存目录）
+pub const INDEX_FILE: &str = "template-index.json";
+
+/// 索引中的一个模板
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// 模板目录
+    pub dir: PathBuf,
+    /// 所在的项目类型目录
+    pub project_type: String,
+    /// 目录名，可能带版本后缀（如 `basic@2.0.0`）
+    pub dir_name: String,
+    /// 目录与 template.json 的修改时间、大小，变化时重新解析
+    pub fingerprint: String,
+    pub metadata: TemplateMetadata,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexFile {
+    #[serde(default)]
+    entries: BTreeMap<String, IndexEntry>,
+}
+
+/// 本地模板索引
+#[derive(Debug, Clone)]
+pub struct TemplateIndex {
+    /// 索引文件路径
+    pub path: PathBuf,
+    /// 按优先级排列的本地注册表根目录
+    pub roots: Vec<PathBuf>,
+}
+
+impl TemplateIndex {
+    /// 使用注册表配置中的本地注册表与缓存目录
+    pub fn from_config(config: &TemplateRegistryConfig) -> Self {
+        Self {
+            path: config.cache_dir.join(INDEX_FILE),
+            roots: config.local_roots().into_iter().cloned().collect(),
+        }
+    }
+
+    /// 刷新索引并返回全部模板；`force` 为真时忽略指纹重新解析所有模板
+    pub fn refresh(&self, force: bool) -> Result<(Vec<IndexEntry>, IndexStats)> {
+        let mut index = self.load();
+        let candidates = self.scan();
+        let mut stats = IndexStats {
+            path: self.path.display().to_string(),
+            ..Default::default()
+        };
+        stats.removed = index
+            .entries
+            .keys()
+            .filter(|key| !candidates.iter().any(|(candidate, ..)| candidate == *key))
+            .count() as u32;
+
+        let (fresh, stale): (Vec<_>, Vec<_>) =
+            candidates
+                .into_iter()
+                .partition(|(key, _, _, fingerprint)| {
+                    !force
+                        && index
+                            .entries
+                            .get(key)
+                            .is_some_and(|entry| entry.fingerprint == *fingerprint)
+                });
+        stats.reused = fresh.len() as u32;
+
+        // 解析失败的模板不进入索引，由 lint-template 报告具体问题
+        let parsed: Vec<IndexEntry> = stale
+            .into_par_iter()
+            .filter_map(|(_, dir, project_type, fingerprint)| {
+                let metadata = pipeline::load_template_metadata(&dir).ok()?;
+                Some(IndexEntry {
+                    dir_name: file_name(&dir),
+                    dir,
+                    project_type,
+                    fingerprint,
+                    metadata,
+                })
+            })
+            .collect();
+        stats.updated = parsed.len() as u32;
+
+        let keep: Vec<String> = fresh.into_iter().map(|(key, ..)| key).collect();
+        index.entries.retain(|key, _| keep.contains(key));
+        for entry in parsed {
+            index.entries.insert(entry.dir.display().to_string(), entry);
+        }
+        stats.templates = index.entries.len() as u32;
+
+        self.save(&index)?;
+        let entries = self.ordered(index);
+        Ok((entries, stats))
+    }
+
+    /// 某个项目类型的模板，按注册表优先级排列
+    pub fn templates(&self, project_type: Option<&str>) -> Result<Vec<IndexEntry>> {
+        let (entries, _) = self.refresh(false)?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| {
+                project_type.is_none_or(|project_type| entry.project_type == project_type)
+            })
+            .collect())
+    }
+
+    /// 只读取已保存的索引，不扫描注册表目录，供自动补全等需要快速返回的场景使用；索引不存在时先构建
+    pub fn cached(&self, project_type: Option<&str>) -> Result<Vec<IndexEntry>> {
+        if !self.path.is_file() {
+            return self.templates(project_type);
+        }
+        Ok(self
+            .ordered(self.load())
+            .into_iter()
+            .filter(|entry| {
+                project_type.is_none_or(|project_type| entry.project_type == project_type)
+            })
+            .collect())
+    }
+
+    /// `<root>/<project_type>/<template>/template.json`，跳过组件目录
+    fn scan(&self) -> Vec<(String, PathBuf, String, String)> {
+        let mut candidates = Vec::new();
+        for root in &self.roots {
+            let Ok(types) = fs::read_dir(root) else {
+                continue;
+            };
+            for type_dir in types
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+            {
+                let project_type = file_name(&type_dir);
+                if project_type == COMPONENTS_DIR || !type_dir.is_dir() {
+                    continue;
+                }
+                let Ok(templates) = fs::read_dir(&type_dir) else {
+                    continue;
+                };
+                for dir in templates
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                {
+                    if let Some(fingerprint) = fingerprint(&dir) {
+                        candidates.push((
+                            dir.display().to_string(),
+                            dir,
+                            project_type.clone(),
+                            fingerprint,
+                        ));
+                    }
+                }
+            }
+        }
+        candidates
+    }
+
+    /// 按根目录优先级、项目类型与目录名排序
+    fn ordered(&self, index: IndexFile) -> Vec<IndexEntry> {
+        let mut entries: Vec<IndexEntry> = index.entries.into_values().collect();
+        entries.sort_by_key(|entry| {
+            let priority = self
+                .roots
+                .iter()
+                .position(|root| entry.dir.starts_with(root))
+                .unwrap_or(usize::MAX);
+            (priority, entry.project_type.clone(), entry.dir_name.clone())
+        });
+        entries
+    }
+
+    fn load(&self) -> IndexFile {
+        // 索引只是缓存，损坏时整体重建
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, index: &IndexFile) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // 先写临时文件再替换，并发的进程不会读到写了一半的索引
+        let write = || -> std::io::Result<()> {
+            let staging = tempfile::NamedTempFile::new_in(
+                self.path.parent().unwrap_or_else(|| Path::new(".")),
+            )?;
+            fs::write(staging.path(), serde_json::to_string(index)?)?;
+            staging.persist(&self.path)?;
+            Ok(())
+        };
+        write().map_err(|e| {
+            GeneratorError::FileOperation(format!(
+                "Cannot write template index {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// 目录与 template.json 的修改时间及大小，没有 template.json 时返回 None
+fn fingerprint(dir: &Path) -> Option<String> {
+    let metadata = fs::metadata(pipeline::metadata_file(dir)?).ok()?;
+    let modified = |metadata: &fs::Metadata| {
+        metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or(0)
+    };
+    let dir_modified = fs::metadata(dir).map(|dir| modified(&dir)).unwrap_or(0);
+    Some(format!(
+        "{}:{}:{}",
+        dir_modified,
+        modified(&metadata),
+        metadata.len()
+    ))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use tempfile::tempdir;
+
+    fn write_template(root: &Path, project_type: &str, name: &str, description: &str) {
+        let dir = root.join(project_type).join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(TEMPLATE_METADATA_FILE),
+            serde_json::json!({
+                "name": name,
+                "version": "1.0.0",
+                "description": description,
+                "author": "test",
+                "project_type": project_type,
+                "variables": [],
+                "dependencies": [],
+                "tags": [],
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_refresh_is_incremental() {
+        let root = tempdir().unwrap();
+        let cache = tempdir().unwrap();
+        write_template(root.path(), "vue", "basic", "first");
+        write_template(root.path(), "vue", "admin", "admin");
+        write_template(root.path(), "rust", "bin", "bin");
+        fs::create_dir_all(root.path().join(COMPONENTS_DIR).join("vue")).unwrap();
+
+        let index = TemplateIndex {
+            path: cache.path().join(INDEX_FILE),
+            roots: vec![root.path().to_path_buf()],
+        };
+        let (entries, stats) = index.refresh(false).unwrap();
+        assert_eq!((stats.templates, stats.updated, stats.reused), (3, 3, 0));
+        assert_eq!(entries[0].project_type, "rust");
+
+        let (_, stats) = index.refresh(false).unwrap();
+        assert_eq!((stats.updated, stats.reused), (0, 3));
+
+        write_template(root.path(), "vue", "basic", "second changed");
+        fs::remove_dir_all(root.path().join("rust")).unwrap();
+        let (_, stats) = index.refresh(false).unwrap();
+        assert_eq!((stats.updated, stats.reused, stats.removed), (1, 1, 1));
+
+        let vue = index.templates(Some("vue")).unwrap();
+        assert_eq!(vue.len(), 2);
+        assert!(
+            vue.iter()
+                .any(|entry| entry.metadata.description == "second changed")
+        );
+
+        let (_, stats) = index.refresh(true).unwrap();
+        assert_eq!((stats.updated, stats.reused), (2, 0));
+
+        // 缓存查询不会发现索引之后新增的模板
+        write_template(root.path(), "vue", "mobile", "mobile");
+        assert_eq!(index.cached(Some("vue")).unwrap().len(), 2);
+        assert_eq!(index.templates(Some("vue")).unwrap().len(), 3);
+        assert_eq!(index.cached(Some("vue")).unwrap().len(), 3);
+    }
+}
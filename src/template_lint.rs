@@ -0,0 +1,513 @@
+//! 模板校验模块
+//! 检查 template.json、文件中的占位符、partials、hooks 与忽略规则，返回诊断列表而不是在首个问题处失败
+
+use crate::computed::Expr;
+use crate::condition::Condition;
+use crate::env_defaults::BUILTIN_SOURCES;
+use crate::pipeline::{
+    self, HOOKS_DIR, RawMatcher, TEMPLATE_METADATA_FILE, is_binary_content, raw_front_matter,
+};
+use crate::render::{RAW_BLOCK_CLOSE, RAW_BLOCK_OPEN, TemplateRenderer};
+use crate::template_helpers::{self, PARTIALS_DIR};
+use crate::template_ignore::TemplateIgnore;
+use crate::template_registry::{
+    PatchOperation, TemplateEngine, TemplateMetadata, TemplateRegistryConfig, VariableType,
+};
+use crate::{Diagnostic, GeneratorError, Result};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// 渲染时始终提供的内置变量
+const BUILTIN_VARIABLES: &[&str] = &["name", "project_type"];
+
+/// 作为块/内联 helper 使用的关键字，不视为变量
+const HELPER_KEYWORDS: &[&str] = &["if", "unless", "each", "with", "else", "this", "lookup"];
+
+/// 模板中的占位符
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Placeholder {
+    /// 变量引用（`{{name}}`、`{{#if useRouter}}` 中的 `useRouter`）
+    Variable(String),
+    /// partial 引用（`{{> header}}`）
+    Partial(String),
+}
+
+/// 校验模板目录
+pub fn validate_template(template_dir: &Path) -> Result<Vec<Diagnostic>> {
+    if !template_dir.is_dir() {
+        return Err(GeneratorError::TemplateNotFound(format!(
+            "Template directory not found: {}",
+            template_dir.display()
+        )));
+    }
+
+    let mut diagnostics = Vec::new();
+    let metadata_path = template_dir.join(TEMPLATE_METADATA_FILE);
+    let metadata: TemplateMetadata = match fs::read_to_string(&metadata_path) {
+        Ok(content) => match pipeline::parse_template_metadata(&content) {
+            Ok(metadata) => metadata,
+            Err(GeneratorError::Configuration(message)) => {
+                diagnostics.push(error(
+                    "unsupported-generator-version",
+                    message,
+                    Some(TEMPLATE_METADATA_FILE),
+                ));
+                return Ok(diagnostics);
+            }
+            Err(GeneratorError::Serialization(e)) => {
+                diagnostics.push(error(
+                    "invalid-metadata",
+                    format!("template.json does not match the schema: {}", e),
+                    Some(TEMPLATE_METADATA_FILE),
+                ));
+                return Ok(diagnostics);
+            }
+            Err(e) => return Err(e),
+        },
+        Err(_) => {
+            diagnostics.push(error(
+                "missing-metadata",
+                "template.json not found".to_string(),
+                None,
+            ));
+            return Ok(diagnostics);
+        }
+    };
+
+    // 继承其他模板时按叠加基础模板后的整体校验
+    let resolved = match &metadata.extends {
+        Some(extends) => match TemplateRegistryConfig::default().resolve_layers(template_dir) {
+            Ok(resolved) => Some(resolved),
+            Err(e) => {
+                diagnostics.push(error(
+                    "unresolved-extends",
+                    format!("Cannot resolve base template '{}': {}", extends, e),
+                    Some(TEMPLATE_METADATA_FILE),
+                ));
+                None
+            }
+        },
+        None => None,
+    };
+    let (template_dir, metadata) = match &resolved {
+        Some(resolved) => (resolved.dir.as_path(), resolved.metadata.clone()),
+        None => (template_dir, metadata),
+    };
+
+    check_metadata(&metadata, &mut diagnostics);
+
+    // 收集所有引用的变量：文件内容、文件名、条件与补丁
+    let mut used: BTreeMap<String, String> = BTreeMap::new();
+    for rule in &metadata.files {
+        if let Some(when) = &rule.when {
+            match Condition::parse(when) {
+                Ok(condition) => {
+                    for name in condition.variables() {
+                        used.entry(name.to_string())
+                            .or_insert_with(|| TEMPLATE_METADATA_FILE.to_string());
+                    }
+                }
+                Err(e) => diagnostics.push(error(
+                    "invalid-condition",
+                    e.to_string(),
+                    Some(TEMPLATE_METADATA_FILE),
+                )),
+            }
+        }
+    }
+    for computed in &metadata.computed {
+        match Expr::parse(&computed.expr) {
+            Ok(expr) => {
+                for name in expr.variables() {
+                    used.entry(name.to_string())
+                        .or_insert_with(|| TEMPLATE_METADATA_FILE.to_string());
+                }
+            }
+            Err(e) => diagnostics.push(error(
+                "invalid-computed",
+                format!("Computed variable '{}': {}", computed.name, e),
+                Some(TEMPLATE_METADATA_FILE),
+            )),
+        }
+    }
+    for feature in &metadata.features {
+        for patch in &feature.patches {
+            let mut texts = vec![patch.file.as_str()];
+            match &patch.operation {
+                PatchOperation::Append { content } => texts.push(content),
+                PatchOperation::Replace { replace, .. } => texts.push(replace),
+                PatchOperation::JsonMerge { .. } => {}
+            }
+            for text in texts {
+                collect_variables(text, TEMPLATE_METADATA_FILE, &mut used);
+            }
+        }
+    }
+
+    let renderer = TemplateRenderer {
+        engine: metadata.engine,
+        ..Default::default()
+    };
+    let raw = match RawMatcher::new(&metadata.raw) {
+        Ok(raw) => Some(raw),
+        Err(e) => {
+            diagnostics.push(error(
+                "invalid-raw",
+                e.to_string(),
+                Some(TEMPLATE_METADATA_FILE),
+            ));
+            None
+        }
+    };
+    for entry in WalkDir::new(template_dir).min_depth(1).sort_by_file_name() {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        let relative = entry
+            .path()
+            .strip_prefix(template_dir)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if relative == Path::new(TEMPLATE_METADATA_FILE) || relative.starts_with(HOOKS_DIR) {
+            continue;
+        }
+        let display = relative.to_string_lossy().replace('\\', "/");
+
+        collect_variables(&display, &display, &mut used);
+        if !entry.file_type().is_file() || raw.as_ref().is_some_and(|raw| raw.is_raw(relative)) {
+            continue;
+        }
+
+        let content = fs::read(entry.path())?;
+        if is_binary_content(&content) {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&content);
+        if raw_front_matter(&text).is_some() {
+            continue;
+        }
+        // 其他引擎只检查语法，无法从中可靠地提取变量
+        if metadata.engine != TemplateEngine::Handlebars {
+            if let Err(e) = renderer.check(&text) {
+                diagnostics.push(error("invalid-template", e.to_string(), Some(&display)));
+            }
+            continue;
+        }
+        for placeholder in placeholders(&text) {
+            match placeholder {
+                Placeholder::Variable(name) => {
+                    used.entry(name).or_insert_with(|| display.clone());
+                }
+                Placeholder::Partial(name) => {
+                    if !partial_exists(template_dir, &name) {
+                        diagnostics.push(error(
+                            "missing-partial",
+                            format!("Partial '{}' not found in {}/", name, PARTIALS_DIR),
+                            Some(&display),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let declared: HashSet<&str> = metadata
+        .variables
+        .iter()
+        .map(|variable| variable.name.as_str())
+        .chain(metadata.computed.iter().map(|computed| computed.name.as_str()))
+        .collect();
+    for (name, file) in &used {
+        if !declared.contains(name.as_str())
+            && !BUILTIN_VARIABLES.contains(&name.as_str())
+            && !BUILTIN_SOURCES.iter().any(|(builtin, _)| builtin == name)
+        {
+            diagnostics.push(warning(
+                "undeclared-variable",
+                format!(
+                    "Variable '{}' is used but not declared in template.json",
+                    name
+                ),
+                Some(file),
+            ));
+        }
+    }
+    for variable in &metadata.variables {
+        if metadata.engine == TemplateEngine::Handlebars && !used.contains_key(&variable.name) {
+            diagnostics.push(warning(
+                "unused-variable",
+                format!("Variable '{}' is declared but never used", variable.name),
+                Some(TEMPLATE_METADATA_FILE),
+            ));
+        }
+    }
+
+    check_hooks(template_dir, &mut diagnostics)?;
+
+    if let Err(e) = TemplateIgnore::load(template_dir, &metadata.ignore) {
+        diagnostics.push(error("invalid-ignore", e.to_string(), None));
+    }
+
+    Ok(diagnostics)
+}
+
+/// 检查元数据字段本身
+fn check_metadata(metadata: &TemplateMetadata, diagnostics: &mut Vec<Diagnostic>) {
+    let file = Some(TEMPLATE_METADATA_FILE);
+    if metadata.name.trim().is_empty() {
+        diagnostics.push(error(
+            "empty-name",
+            "Template name is empty".to_string(),
+            file,
+        ));
+    }
+    if semver::Version::parse(&metadata.version).is_err() {
+        diagnostics.push(warning(
+            "invalid-version",
+            format!("Version '{}' is not a valid semver", metadata.version),
+            file,
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for variable in &metadata.variables {
+        if !seen.insert(variable.name.as_str()) {
+            diagnostics.push(error(
+                "duplicate-variable",
+                format!("Variable '{}' is declared more than once", variable.name),
+                file,
+            ));
+        }
+
+        let Some(default) = &variable.default else {
+            continue;
+        };
+        let valid = match &variable.var_type {
+            VariableType::String => true,
+            VariableType::Boolean => matches!(default.as_str(), "true" | "false"),
+            VariableType::Number => default.parse::<f64>().is_ok(),
+            VariableType::Choice { options } => options.contains(default),
+        };
+        if !valid {
+            diagnostics.push(error(
+                "invalid-default",
+                format!(
+                    "Default value '{}' of variable '{}' does not match its type",
+                    default, variable.name
+                ),
+                file,
+            ));
+        }
+    }
+}
+
+/// 检查 hooks 目录中的脚本
+fn check_hooks(template_dir: &Path, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
+    let hooks_dir = template_dir.join(HOOKS_DIR);
+    if !hooks_dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(&hooks_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let display = format!("{}/{}", HOOKS_DIR, entry.file_name().to_string_lossy());
+        if !path.is_file() {
+            diagnostics.push(error(
+                "invalid-hook",
+                "Hook must be a file".to_string(),
+                Some(&display),
+            ));
+            continue;
+        }
+        if path.extension().is_some_and(|ext| ext == "md") {
+            continue;
+        }
+
+        let content = fs::read(&path)?;
+        if content.iter().all(u8::is_ascii_whitespace) {
+            diagnostics.push(error(
+                "invalid-hook",
+                "Hook script is empty".to_string(),
+                Some(&display),
+            ));
+        } else if !content.starts_with(b"#!") && !has_interpreter_extension(&path) {
+            diagnostics.push(warning(
+                "invalid-hook",
+                "Hook script has no shebang and no known interpreter extension".to_string(),
+                Some(&display),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn has_interpreter_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext, "js" | "mjs" | "cjs" | "ps1" | "cmd" | "bat"))
+}
+
+fn partial_exists(template_dir: &Path, name: &str) -> bool {
+    let partials = template_dir.join(PARTIALS_DIR);
+    partials.join(name).is_file() || partials.join(format!("{}.hbs", name)).is_file()
+}
+
+fn collect_variables(text: &str, file: &str, used: &mut BTreeMap<String, String>) {
+    for placeholder in placeholders(text) {
+        if let Placeholder::Variable(name) = placeholder {
+            used.entry(name).or_insert_with(|| file.to_string());
+        }
+    }
+}
+
+/// 提取文本中 `{{ ... }}` 形式的占位符
+pub(crate) fn placeholders(text: &str) -> Vec<Placeholder> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        // 转义的 `\{{` 与 raw 块不会被渲染
+        if rest[..start].ends_with('\\') {
+            rest = &rest[start + 2..];
+            continue;
+        }
+        if let Some(block) = rest[start..].strip_prefix(RAW_BLOCK_OPEN)
+            && let Some(end) = block.find(RAW_BLOCK_CLOSE)
+        {
+            rest = &block[end + RAW_BLOCK_CLOSE.len()..];
+            continue;
+        }
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let inner = after[..end].trim_start_matches(['{', '~']).trim();
+        rest = &after[end + 2..];
+
+        if let Some(partial) = inner.strip_prefix('>') {
+            if let Some(name) = partial.split_whitespace().next() {
+                found.push(Placeholder::Partial(name.to_string()));
+            }
+            continue;
+        }
+        if inner.starts_with(['/', '!']) {
+            continue;
+        }
+
+        let inner = inner.trim_end_matches(['}', '~']);
+        let tokens: Vec<&str> = inner.trim_start_matches('#').split_whitespace().collect();
+        // `{{var}}` 本身是变量；`{{#if var}}`、`{{helper var}}` 中参数才是变量
+        let arguments = if tokens.len() > 1 {
+            &tokens[1..]
+        } else {
+            &tokens[..]
+        };
+        for token in arguments {
+            let name = token.split('.').next().unwrap_or(token);
+            if is_variable_name(name)
+                && !HELPER_KEYWORDS.contains(&name)
+                && !template_helpers::is_helper(name)
+            {
+                found.push(Placeholder::Variable(name.to_string()));
+            }
+        }
+    }
+    found
+}
+
+fn is_variable_name(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn error(code: &str, message: String, file: Option<&str>) -> Diagnostic {
+    diagnostic("error", code, message, file)
+}
+
+fn warning(code: &str, message: String, file: Option<&str>) -> Diagnostic {
+    diagnostic("warning", code, message, file)
+}
+
+pub(crate) fn diagnostic(
+    severity: &str,
+    code: &str,
+    message: String,
+    file: Option<&str>,
+) -> Diagnostic {
+    Diagnostic {
+        severity: severity.to_string(),
+        code: code.to_string(),
+        message,
+        file: file.map(str::to_string),
+        fixture: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("{{name}} {{#if useRouter}}x{{/if}} {{> header}} {{upper name}}"),
+            vec![
+                Placeholder::Variable("name".to_string()),
+                Placeholder::Variable("useRouter".to_string()),
+                Placeholder::Partial("header".to_string()),
+                Placeholder::Variable("name".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_template_reports_problems() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "vue", "dependencies": [], "tags": [], "ignore": ["a[z-a]"],
+                "variables": [
+                    {"name": "author", "description": "", "default": null, "required": true, "var_type": "string"},
+                    {"name": "unused", "description": "", "default": "maybe", "required": false, "var_type": "boolean"}
+                ]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("README.md"),
+            "# {{name}} by {{author}} {{license}}\n{{> footer}}\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join(HOOKS_DIR)).unwrap();
+        fs::write(dir.path().join(HOOKS_DIR).join("post"), "").unwrap();
+
+        let codes: Vec<String> = validate_template(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                "invalid-default",
+                "missing-partial",
+                "undeclared-variable",
+                "unused-variable",
+                "invalid-hook",
+                "invalid-ignore",
+            ]
+        );
+
+        // 自带模板应当没有错误
+        let shipped = validate_template(Path::new("templates/node-lib/basic")).unwrap();
+        assert!(
+            shipped.iter().all(|d| d.severity != "error"),
+            "{:?}",
+            shipped
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! 模板元数据模块
+//! template.json 声明的变量、文件规则、特性、helper 与钩子等类型，不依赖进程与网络，可随渲染核心编译到 wasm32
+
+use crate::hooks::Hook;
+use crate::text_format::{BomPolicy, LineEnding};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 模板元数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateMetadata {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub author: String,
+    pub project_type: String,
+    pub variables: Vec<TemplateVariable>,
+    pub dependencies: Vec<String>,
+    pub tags: Vec<String>,
+    /// 忽略规则（gitignore 风格），与模板目录下的 `.genignore` 合并
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// 按条件包含的文件规则
+    #[serde(default)]
+    pub files: Vec<TemplateFileRule>,
+    /// 可选特性片段
+    #[serde(default)]
+    pub features: Vec<TemplateFeature>,
+    /// 额外视为二进制、跳过变量渲染的扩展名（如 `"dat"`）
+    #[serde(default)]
+    pub binary_extensions: Vec<String>,
+    /// 内容原样复制、不做变量渲染的文件（glob，如 `"src/**/*.vue"`）
+    #[serde(default)]
+    pub raw: Vec<String>,
+    /// 按输出文件设置换行符与 BOM，类似 `.gitattributes`，后声明的规则优先
+    #[serde(default)]
+    pub attributes: Vec<TextAttributeRule>,
+    /// 内置 CI 特性（`ci:github`、`ci:gitlab`）追加的任务，与内置任务同名时替换之
+    #[serde(default)]
+    pub ci_jobs: Vec<CiJob>,
+    /// 所需 Java 版本，如 `"17+"`、`"11-17"`、`"1.8"`
+    #[serde(default)]
+    pub required_java_version: Option<String>,
+    /// Node.js 引擎约束，格式同 package.json 的 `engines`（如 `{"node": ">=18"}`）
+    #[serde(default)]
+    pub engines: HashMap<String, String>,
+    /// 支持的操作系统（`linux`、`macos`、`windows`），为空时不限制
+    #[serde(default)]
+    pub os: Vec<String>,
+    /// 支持的 Java 构建工具（`maven`、`gradle`），第一个为默认值，为空时不限制
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub build_tools: Vec<String>,
+    /// 模板自带的声明式 helper，仅在本模板的生成中可用
+    #[serde(default)]
+    pub helpers: Vec<TemplateHelper>,
+    /// 渲染文件内容与路径的模板引擎，默认为 handlebars
+    #[serde(default)]
+    pub engine: TemplateEngine,
+    /// 派生变量，渲染前按声明顺序求值，可引用其他变量与先声明的派生变量
+    #[serde(default)]
+    pub computed: Vec<ComputedVariable>,
+    /// 生成完成后在项目目录中依次执行的钩子，可按平台覆盖命令与解释器
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+    /// 继承的基础模板，如 `"react/basic@^1"`（省略项目类型时与本模板相同）；子模板只需包含覆盖与新增的文件
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// 模板所需的最低生成器版本，如 `"1.4.0"`；运行的生成器更旧时拒绝加载并提示升级
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_generator_version: Option<String>,
+}
+
+/// 模板引擎
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateEngine {
+    /// 内置的 `{{变量}}`、helper 与 partial
+    #[default]
+    Handlebars,
+    /// Jinja 风格语法（`{{ name | upper }}`、`{% if %}`）
+    Tera,
+    /// Liquid 语法
+    Liquid,
+    /// 不渲染，原样复制
+    None,
+}
+
+/// 模板文件规则，例如 `{"path": "src/router/**", "when": "useRouter == true"}`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateFileRule {
+    /// 相对模板根目录的 glob 模式
+    pub path: String,
+    /// 包含条件，为空时始终包含
+    #[serde(default)]
+    pub when: Option<String>,
+}
+
+/// 文本属性规则，例如 `{"path": "*.bat", "eol": "crlf"}`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TextAttributeRule {
+    /// 相对输出目录的 glob 模式，不含 `/` 时匹配任意目录下的文件名
+    pub path: String,
+    /// 换行符，未设置时使用生成选项
+    #[serde(default)]
+    pub eol: Option<LineEnding>,
+    /// UTF-8 BOM 策略，未设置时使用生成选项
+    #[serde(default)]
+    pub bom: Option<BomPolicy>,
+}
+
+/// CI 任务，例如 `{"name": "e2e", "run": ["npm run e2e"]}`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CiJob {
+    pub name: String,
+    /// 依次执行的命令
+    pub run: Vec<String>,
+}
+
+/// 模板特性定义
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateFeature {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// 片段目录（相对模板根目录），默认为 `features/<name>`
+    #[serde(default)]
+    pub path: Option<String>,
+    /// 对基础模板已有文件的补丁
+    #[serde(default)]
+    pub patches: Vec<FeaturePatch>,
+}
+
+/// 特性补丁
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FeaturePatch {
+    /// 目标文件（相对输出目录，支持变量）
+    pub file: String,
+    #[serde(flatten)]
+    pub operation: PatchOperation,
+}
+
+/// 补丁操作
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOperation {
+    /// 深度合并 JSON（如向 package.json 添加脚本和依赖）
+    JsonMerge { value: serde_json::Value },
+    /// 在文件末尾追加内容
+    Append { content: String },
+    /// 文本替换
+    Replace { find: String, replace: String },
+}
+
+/// 派生变量定义，例如 `{"name": "packagePath", "expr": "replace(package, \".\", \"/\")"}`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComputedVariable {
+    pub name: String,
+    /// 表达式：变量、字符串字面量，以及 `replace`、`concat`、`trim`、`default` 和内置 helper 的调用
+    pub expr: String,
+}
+
+/// 模板 helper 定义，例如 `{"name": "packagePath", "type": "replace", "pattern": "\\.", "replacement": "/"}`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateHelper {
+    /// 在模板中以 `{{name value}}` 调用
+    pub name: String,
+    #[serde(flatten)]
+    pub rule: HelperRule,
+}
+
+/// helper 规则
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HelperRule {
+    /// 正则替换，`replacement` 中可用 `$1` 引用捕获组
+    Replace {
+        pattern: String,
+        replacement: String,
+    },
+    /// 字符串映射，未命中时使用 `default`，没有 `default` 时原样输出
+    Map {
+        values: HashMap<String, String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+/// 模板变量定义
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub description: String,
+    pub default: Option<String>,
+    pub required: bool,
+    pub var_type: VariableType,
+    /// 敏感值（如 API Key），不写入日志、报告、锁文件与答案文件，回显时打码
+    #[serde(default)]
+    pub sensitive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    String,
+    Boolean,
+    Number,
+    Choice { options: Vec<String> },
+}
@@ -1,11 +1,39 @@
 //! 模板注册表管理模块
 //! 支持多种模板源：Git、HTTP、npm、本地文件
 
-use crate::{GeneratorError, Result};
+use crate::content_cache::ContentCache;
+use crate::cookiecutter;
+use crate::credentials::{
+    Credential, CredentialStore, EnvCredentials, FileCredentials, KeyringCredentials,
+};
+use crate::git_source::GitFetcher;
+use crate::http_source::HttpFetcher;
+use crate::network::NetworkConfig;
+use crate::npm_source::NpmFetcher;
+use crate::npmrc::Npmrc;
+use crate::pipeline::{self, TEMPLATE_METADATA_FILE};
+use crate::template_index::TemplateIndex;
+use crate::template_version::{self, TemplateSpec};
+use crate::utils;
+use crate::{GeneratorError, PhaseTimeouts, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use tokio::fs;
+use walkdir::WalkDir;
+
+pub use crate::template_metadata::{
+    CiJob, ComputedVariable, FeaturePatch, HelperRule, PatchOperation, TemplateEngine,
+    TemplateFeature, TemplateFileRule, TemplateHelper, TemplateMetadata, TemplateVariable,
+    TextAttributeRule, VariableType,
+};
+
+/// 本地注册表中存放组件子生成器的目录
+pub const COMPONENTS_DIR: &str = "components";
+/// 本地注册表中存放模板覆盖层的目录
+pub const OVERRIDES_DIR: &str = "overrides";
+/// 注册表配置文件名，位于 `~/.generator` 下
+pub const REGISTRIES_FILE: &str = "registries.json";
 
 /// 模板注册表配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +41,24 @@ pub struct TemplateRegistryConfig {
     pub registries: Vec<TemplateRegistry>,
     pub cache_dir: PathBuf,
     pub cache_ttl: u64, // 缓存时间（秒）
+    /// 远程模板缓存的大小上限（MB），超出时按最近使用时间淘汰；为空时不限制
+    #[serde(default = "default_cache_max_size_mb")]
+    pub cache_max_size_mb: Option<u64>,
+    /// 外部操作的默认超时，可被 `GenerateOptions.timeouts` 覆盖
+    #[serde(default)]
+    pub timeouts: PhaseTimeouts,
+    /// 访问网络模板源时使用的代理与 CA 证书
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// 冻结模式：拒绝未固定到不可变修订（git 提交、HTTP 校验和、npm integrity）的远程注册表
+    #[serde(default)]
+    pub frozen: bool,
+    /// 注册表凭据 ID 查找的凭据文件，未设置时为 `~/.generator/credentials.json`
+    #[serde(default)]
+    pub credentials_file: Option<PathBuf>,
+    /// npm 注册表使用的 .npmrc 文件，未设置时合并用户级与当前目录的 .npmrc
+    #[serde(default)]
+    pub npmrc: Option<PathBuf>,
 }
 
 /// 模板注册表
@@ -39,6 +85,9 @@ pub enum TemplateSource {
         branch: Option<String>,
         subfolder: Option<String>,
         auth: Option<GitAuth>,
+        /// 固定的提交（完整 SHA-1），设置后不再跟随分支的最新提交
+        #[serde(default)]
+        commit: Option<String>,
     },
     
     #[serde(rename = "http")]
@@ -53,14 +102,117 @@ pub enum TemplateSource {
         package: String,
         version: String,
         registry: Option<String>,
+        /// 固定的 tarball integrity（如 `sha512-...`），与解析出的版本不一致时拒绝
+        #[serde(default)]
+        integrity: Option<String>,
     },
 }
 
+impl TemplateSource {
+    /// 远程源未固定到不可变修订时内容可能随时变化；本地目录不视为浮动引用
+    pub fn is_floating(&self) -> bool {
+        match self {
+            TemplateSource::Local { .. } => false,
+            TemplateSource::Git { commit, .. } => commit.is_none(),
+            TemplateSource::Http { checksum, .. } => checksum.is_none(),
+            TemplateSource::Npm { integrity, .. } => integrity.is_none(),
+        }
+    }
+
+    /// 解析 degit 风格的简写：`user/repo`、`gh:org/repo`、`gitlab:org/repo/subdir#branch`、`user/repo#branch/subdir`
+    ///
+    /// 前缀 `gh` / `github`、`gl` / `gitlab`、`bb` / `bitbucket` 对应各自的托管平台，省略时为 GitHub。
+    /// `#` 之后的第一段为分支或完整的提交 SHA-1，其余部分是子目录；包含 `/` 的分支名无法用简写表示。
+    /// 不是简写时返回 None。
+    pub fn from_shorthand(spec: &str) -> Option<Self> {
+        let (host, rest) = match spec.split_once(':') {
+            Some((prefix, rest)) => (
+                match prefix {
+                    "gh" | "github" => "github.com",
+                    "gl" | "gitlab" => "gitlab.com",
+                    "bb" | "bitbucket" => "bitbucket.org",
+                    _ => return None,
+                },
+                rest,
+            ),
+            None => ("github.com", spec),
+        };
+        let (path, reference) = match rest.split_once('#') {
+            Some((path, reference)) => (path, Some(reference)),
+            None => (rest, None),
+        };
+        let (reference, reference_path) = match reference.map(|r| r.split_once('/')) {
+            Some(Some((reference, path))) => (Some(reference), path),
+            Some(None) => (reference, ""),
+            None => (None, ""),
+        };
+
+        let is_name = |segment: &str| {
+            !segment.is_empty()
+                && !segment.starts_with('.')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        let mut segments = path.split('/');
+        let (owner, repo) = (segments.next()?, segments.next()?);
+        if !is_name(owner) || !is_name(repo) || reference.is_some_and(str::is_empty) {
+            return None;
+        }
+        let subfolder: Vec<&str> = segments
+            .chain(reference_path.split('/'))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        if subfolder.contains(&"..") {
+            return None;
+        }
+
+        let (branch, commit) = match reference {
+            Some(reference)
+                if reference.len() == 40 && reference.bytes().all(|b| b.is_ascii_hexdigit()) =>
+            {
+                (None, Some(reference.to_string()))
+            }
+            reference => (reference.map(str::to_string), None),
+        };
+        Some(TemplateSource::Git {
+            url: format!(
+                "https://{}/{}/{}.git",
+                host,
+                owner,
+                repo.trim_end_matches(".git")
+            ),
+            branch,
+            subfolder: (!subfolder.is_empty()).then(|| subfolder.join("/")),
+            auth: None,
+            commit,
+        })
+    }
+}
+
 /// Git 认证信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitAuth {
     pub username: Option<String>,
     pub token: Option<String>,
+    /// 凭据存储中的 ID，优先于内联的 token
+    #[serde(default)]
+    pub credential: Option<String>,
+}
+
+impl GitAuth {
+    /// 解析认证凭据：凭据 ID 从凭据存储查找，否则使用内联 token
+    pub fn resolve(&self, store: &CredentialStore) -> Result<Option<Credential>> {
+        if let Some(id) = &self.credential {
+            let mut credential = store.resolve(id)?;
+            credential.username = credential.username.or_else(|| self.username.clone());
+            return Ok(Some(credential));
+        }
+        Ok(self.token.clone().map(|secret| Credential {
+            username: self.username.clone(),
+            secret,
+        }))
+    }
 }
 
 /// HTTP 认证信息
@@ -68,38 +220,43 @@ pub struct GitAuth {
 pub struct HttpAuth {
     pub bearer_token: Option<String>,
     pub basic_auth: Option<(String, String)>,
+    /// 凭据存储中的 ID：带用户名时按 Basic 认证，否则按 Bearer 令牌
+    #[serde(default)]
+    pub credential: Option<String>,
 }
 
-/// 模板元数据
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TemplateMetadata {
-    pub name: String,
-    pub version: String,
-    pub description: String,
-    pub author: String,
-    pub project_type: String,
-    pub variables: Vec<TemplateVariable>,
-    pub dependencies: Vec<String>,
-    pub tags: Vec<String>,
-}
-
-/// 模板变量定义
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TemplateVariable {
-    pub name: String,
-    pub description: String,
-    pub default: Option<String>,
-    pub required: bool,
-    pub var_type: VariableType,
+impl HttpAuth {
+    /// 解析认证凭据：凭据 ID 从凭据存储查找，否则使用内联配置
+    pub fn resolve(&self, store: &CredentialStore) -> Result<Option<Credential>> {
+        if let Some(id) = &self.credential {
+            return store.resolve(id).map(Some);
+        }
+        if let Some((username, password)) = &self.basic_auth {
+            return Ok(Some(Credential {
+                username: Some(username.clone()),
+                secret: password.clone(),
+            }));
+        }
+        Ok(self.bearer_token.clone().map(|secret| Credential {
+            username: None,
+            secret,
+        }))
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum VariableType {
-    String,
-    Boolean,
-    Number,
-    Choice { options: Vec<String> },
+/// 展开继承链后的模板
+#[derive(Debug)]
+pub struct ResolvedTemplate {
+    /// 模板自身的目录
+    pub source_dir: PathBuf,
+    /// 渲染使用的目录：没有继承时即 `source_dir`，否则为按继承链叠加文件后的临时目录
+    pub dir: PathBuf,
+    /// 合并后的元数据
+    pub metadata: TemplateMetadata,
+    /// 来自基础模板或覆盖层的文件（相对模板目录）及其来源层，如 `extends:react/basic@1.2.0`、`override:org`
+    pub provenance: BTreeMap<String, String>,
+    /// 叠加目录，随本结构体一起删除
+    _merged: Option<tempfile::TempDir>,
 }
 
 /// 模板管理器
@@ -161,10 +318,10 @@ impl TemplateManager {
         let cache_key = format!("{}:{}", project_type, template_name);
         
         // 检查缓存
-        if let Some(cached) = self.cache.get(&cache_key) {
-            if !self.is_cache_expired(&cached) {
-                return Ok(cached.path.clone());
-            }
+        if let Some(cached) = self.cache.get(&cache_key)
+            && !self.is_cache_expired(cached)
+        {
+            return Ok(cached.path.clone());
         }
         
         // 从注册表加载
@@ -194,101 +351,89 @@ impl TemplateManager {
 
     /// 从注册表加载模板列表
     async fn load_templates_from_registry(&self, registry: &TemplateRegistry) -> Result<Vec<TemplateMetadata>> {
-        match &registry.source {
-            TemplateSource::Local { path } => {
-                self.load_local_templates(path).await
-            }
-            TemplateSource::Git { url, branch, subfolder, auth } => {
-                self.load_git_templates(url, branch.as_deref(), subfolder.as_deref(), auth).await
-            }
-            TemplateSource::Http { url, checksum, auth } => {
-                self.load_http_templates(url, checksum.as_deref(), auth).await
-            }
-            TemplateSource::Npm { package, version, registry } => {
-                self.load_npm_templates(package, version, registry.as_deref()).await
-            }
+        if let TemplateSource::Local { path } = &registry.source {
+            self.config.check_frozen(registry)?;
+            return self.load_local_templates(path).await;
+        }
+        let dir = self.fetch_registry(registry).await?;
+        match registry.source {
+            TemplateSource::Git { .. } => self.load_git_templates(dir).await,
+            // HTTP 归档与 npm 包本身是单个模板
+            _ => Ok(vec![self.load_template_metadata(&dir).await?]),
         }
     }
 
     /// 从注册表加载特定模板
     async fn load_template_from_registry(
-        &self, 
-        registry: &TemplateRegistry, 
-        project_type: &str, 
-        template_name: &str
+        &self,
+        registry: &TemplateRegistry,
+        project_type: &str,
+        template_name: &str,
     ) -> Result<PathBuf> {
-        // TODO: 实现具体的加载逻辑
-        todo!("实现模板加载逻辑")
-    }
-
-    /// 加载本地模板
-    async fn load_local_templates(&self, _path: &PathBuf) -> Result<Vec<TemplateMetadata>> {
-        // 简化实现：返回硬编码的模板列表
-        // 生产环境中应该扫描目录并读取 template.json 文件
-        Ok(vec![
-            TemplateMetadata {
-                name: "vue-basic".to_string(),
-                version: "1.0.0".to_string(),
-                description: "Basic Vue 3 project".to_string(),
-                author: "Generator Team".to_string(),
-                project_type: "vue".to_string(),
-                variables: vec![],
-                dependencies: vec![],
-                tags: vec!["vue".to_string(), "frontend".to_string()],
-            },
-            TemplateMetadata {
-                name: "react-basic".to_string(),
-                version: "1.0.0".to_string(),
-                description: "Basic React project".to_string(),
-                author: "Generator Team".to_string(),
-                project_type: "react".to_string(),
-                variables: vec![],
-                dependencies: vec![],
-                tags: vec!["react".to_string(), "frontend".to_string()],
-            },
-        ])
-    }
-
-    /// 加载 Git 模板
-    async fn load_git_templates(
-        &self, 
-        url: &str, 
-        branch: Option<&str>, 
-        subfolder: Option<&str>,
-        auth: &Option<GitAuth>
-    ) -> Result<Vec<TemplateMetadata>> {
-        // TODO: 克隆或更新 Git 仓库，加载模板
-        todo!("实现 Git 模板加载")
-    }
-
-    /// 加载 HTTP 模板
-    async fn load_http_templates(
-        &self, 
-        url: &str, 
-        checksum: Option<&str>,
-        auth: &Option<HttpAuth>
-    ) -> Result<Vec<TemplateMetadata>> {
-        // TODO: 下载并解压模板包
-        todo!("实现 HTTP 模板加载")
-    }
-
-    /// 加载 npm 模板
-    async fn load_npm_templates(
-        &self, 
-        package: &str, 
-        version: &str,
-        registry: Option<&str>
-    ) -> Result<Vec<TemplateMetadata>> {
-        // TODO: 从 npm 下载模板包
-        todo!("实现 npm 模板加载")
+        let config = self.config.clone();
+        let (registry, project_type, template_name) = (
+            registry.clone(),
+            project_type.to_string(),
+            template_name.to_string(),
+        );
+        tokio::task::spawn_blocking(move || {
+            config.find_in_registry(&registry, &project_type, &template_name)
+        })
+        .await
+        .map_err(|e| GeneratorError::Unknown(e.to_string()))?
+    }
+
+    /// 在阻塞线程中拉取远程注册表
+    async fn fetch_registry(&self, registry: &TemplateRegistry) -> Result<PathBuf> {
+        let config = self.config.clone();
+        let registry = registry.clone();
+        tokio::task::spawn_blocking(move || config.fetch_registry(&registry))
+            .await
+            .map_err(|e| GeneratorError::Unknown(e.to_string()))?
+    }
+
+    /// 加载本地模板：读取模板索引，只重新解析有变化的模板
+    async fn load_local_templates(&self, path: &PathBuf) -> Result<Vec<TemplateMetadata>> {
+        let index = TemplateIndex::from_config(&self.config);
+        let entries = tokio::task::spawn_blocking(move || index.templates(None))
+            .await
+            .map_err(|e| GeneratorError::Unknown(e.to_string()))??;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| entry.dir.starts_with(path))
+            .map(|entry| entry.metadata)
+            .collect())
+    }
+
+    /// 加载 Git 模板：仓库（或子目录）本身是模板，或按 `<project_type>/<template>` 存放多个模板
+    async fn load_git_templates(&self, dir: PathBuf) -> Result<Vec<TemplateMetadata>> {
+        if pipeline::metadata_file(&dir).is_some() {
+            return Ok(vec![self.load_template_metadata(&dir).await?]);
+        }
+
+        let template_dirs: Vec<PathBuf> = WalkDir::new(&dir)
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| pipeline::metadata_file(entry.path()).is_some())
+            .map(|entry| entry.into_path())
+            .collect();
+        let mut templates = Vec::with_capacity(template_dirs.len());
+        for template_dir in &template_dirs {
+            templates.push(self.load_template_metadata(template_dir).await?);
+        }
+        Ok(templates)
     }
 
     /// 加载模板元数据
-    async fn load_template_metadata(&self, template_path: &PathBuf) -> Result<TemplateMetadata> {
-        let metadata_path = template_path.join("template.json");
+    async fn load_template_metadata(&self, template_path: &Path) -> Result<TemplateMetadata> {
+        if cookiecutter::is_cookiecutter_template(template_path) {
+            return cookiecutter::load_metadata(template_path);
+        }
+        let metadata_path = template_path.join(TEMPLATE_METADATA_FILE);
         let content = fs::read_to_string(metadata_path).await?;
-        let metadata: TemplateMetadata = serde_json::from_str(&content)?;
-        Ok(metadata)
+        pipeline::parse_template_metadata(&content)
     }
 
     /// 检查缓存是否过期
@@ -301,6 +446,511 @@ impl TemplateManager {
     }
 }
 
+/// 生成 template.json 的 JSON Schema，供编辑器校验与自动补全
+pub fn template_metadata_schema() -> Result<String> {
+    let schema = schemars::schema_for!(TemplateMetadata);
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
+
+/// 合并基础模板与子模板的元数据
+///
+/// 子模板的名称、版本等标识字段生效；变量、特性、helper 与派生变量按名称合并，同名时子模板覆盖；
+/// 列表型规则按基础模板在前拼接。子模板未指定引擎（默认 handlebars）时沿用基础模板的引擎。
+pub fn merge_metadata(base: TemplateMetadata, child: TemplateMetadata) -> TemplateMetadata {
+    let mut engines = base.engines;
+    engines.extend(child.engines);
+    TemplateMetadata {
+        variables: merge_by_name(base.variables, child.variables, |v| &v.name),
+        dependencies: merge_by_name(base.dependencies, child.dependencies, String::as_str),
+        tags: merge_by_name(base.tags, child.tags, String::as_str),
+        ignore: [base.ignore, child.ignore].concat(),
+        files: [base.files, child.files].concat(),
+        features: merge_by_name(base.features, child.features, |f| &f.name),
+        binary_extensions: merge_by_name(
+            base.binary_extensions,
+            child.binary_extensions,
+            String::as_str,
+        ),
+        raw: [base.raw, child.raw].concat(),
+        attributes: [base.attributes, child.attributes].concat(),
+        ci_jobs: merge_by_name(base.ci_jobs, child.ci_jobs, |j| &j.name),
+        required_java_version: child.required_java_version.or(base.required_java_version),
+        engines,
+        os: if child.os.is_empty() { base.os } else { child.os },
+        helpers: merge_by_name(base.helpers, child.helpers, |h| &h.name),
+        engine: match child.engine {
+            TemplateEngine::Handlebars => base.engine,
+            engine => engine,
+        },
+        computed: merge_by_name(base.computed, child.computed, |c| &c.name),
+        hooks: [base.hooks, child.hooks].concat(),
+        extends: None,
+        ..child
+    }
+}
+
+/// 将一层模板的文件叠加到 `dest`，记录每个文件来自哪一层；template.json 由调用方合并后写入
+fn overlay(
+    source: &Path,
+    dest: &Path,
+    label: Option<String>,
+    provenance: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    let walker = WalkDir::new(source)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git");
+    for entry in walker {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(dest.join(relative))?;
+            continue;
+        }
+        if relative == Path::new(TEMPLATE_METADATA_FILE) {
+            continue;
+        }
+        utils::copy_file(entry.path(), &dest.join(relative))
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        let key = relative.to_string_lossy().replace('\\', "/");
+        match &label {
+            Some(label) => provenance.insert(key, label.clone()),
+            None => provenance.remove(&key),
+        };
+    }
+    Ok(())
+}
+
+/// 按名称合并列表，同名项原位替换，新项追加在后
+fn merge_by_name<T>(base: Vec<T>, child: Vec<T>, name: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut merged = base;
+    for item in child {
+        match merged.iter().position(|existing| name(existing) == name(&item)) {
+            Some(index) => merged[index] = item,
+            None => merged.push(item),
+        }
+    }
+    merged
+}
+
+impl TemplateRegistryConfig {
+    /// 读取 `dir` 下的注册表配置，文件不存在时使用默认配置
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(REGISTRIES_FILE);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(&std::fs::read_to_string(&path)?).map_err(|e| {
+            GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e))
+        })
+    }
+
+    /// 用户级注册表配置（`~/.generator/registries.json`）
+    pub fn user() -> Result<Self> {
+        Self::load(&utils::generator_home())
+    }
+
+    /// 远程模板（HTTP、npm）共用的内容寻址缓存
+    pub fn content_cache(&self) -> ContentCache {
+        ContentCache::new(
+            self.cache_dir.clone(),
+            self.cache_max_size_mb.map(|mb| mb * 1024 * 1024),
+        )
+    }
+
+    /// 在本地注册表中查找模板目录（`<path>/<project_type>/<template>`）
+    pub fn find_local_template(&self, project_type: &str, template_name: &str) -> Option<PathBuf> {
+        self.find_local(&Path::new(project_type).join(template_name))
+    }
+
+    /// 按版本要求在本地注册表中查找模板
+    ///
+    /// 候选目录为 `<path>/<project_type>/<template>` 与 `<path>/<project_type>/<template>@<version>`，
+    /// 版本以各目录 template.json 中的 `version` 为准，取满足要求的最高版本。
+    pub fn resolve_local_template(
+        &self,
+        project_type: &str,
+        spec: &TemplateSpec,
+    ) -> Option<PathBuf> {
+        if spec.requirement.is_none()
+            && let Some(dir) = self.find_local_template(project_type, &spec.name)
+        {
+            return Some(dir);
+        }
+
+        let versioned_prefix = format!("{}@", spec.name);
+        let candidates = self
+            .local_roots()
+            .into_iter()
+            .filter_map(|root| std::fs::read_dir(root.join(project_type)).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                file_name == spec.name || file_name.starts_with(&versioned_prefix)
+            })
+            .filter_map(|entry| {
+                let dir = entry.path();
+                let metadata = pipeline::load_template_metadata(&dir).ok()?;
+                Some((template_version::parse_version(&metadata.version)?, dir))
+            });
+
+        template_version::select_version(spec, candidates).map(|(_, dir)| dir)
+    }
+
+    /// 在本地注册表中解析 `extends` 指向的基础模板，`project_type` 为子模板的项目类型
+    pub fn resolve_base_template(&self, extends: &str, project_type: &str) -> Result<PathBuf> {
+        let (project_type, template) = extends
+            .split_once('/')
+            .unwrap_or((project_type, extends));
+        self.resolve_local_template(project_type, &TemplateSpec::parse(template)?)
+            .ok_or_else(|| {
+                GeneratorError::TemplateNotFound(format!("Base template '{}' not found", extends))
+            })
+    }
+
+    /// 展开模板的各层：依次叠加基础模板、模板自身与各注册表的覆盖层，并合并元数据
+    pub fn resolve_layers(&self, template_dir: &Path) -> Result<ResolvedTemplate> {
+        // cookiecutter 模板没有继承与覆盖层，只渲染 `{{cookiecutter.*}}` 目录中的文件
+        if cookiecutter::is_cookiecutter_template(template_dir) {
+            return Ok(ResolvedTemplate {
+                source_dir: template_dir.to_path_buf(),
+                dir: cookiecutter::project_dir(template_dir)?,
+                metadata: cookiecutter::load_metadata(template_dir)?,
+                provenance: BTreeMap::new(),
+                _merged: None,
+            });
+        }
+        let metadata = pipeline::load_template_metadata(template_dir)?;
+        let overrides = self.override_layers(&metadata.project_type, &metadata.name);
+        if metadata.extends.is_none() && overrides.is_empty() {
+            return Ok(ResolvedTemplate {
+                source_dir: template_dir.to_path_buf(),
+                dir: template_dir.to_path_buf(),
+                metadata,
+                provenance: BTreeMap::new(),
+                _merged: None,
+            });
+        }
+
+        // 子模板在前，依次追加基础模板
+        let mut chain = vec![(template_dir.to_path_buf(), metadata)];
+        let mut visited = vec![std::fs::canonicalize(template_dir)?];
+        while let Some(extends) = chain.last().and_then(|(_, layer)| layer.extends.clone()) {
+            let project_type = &chain[chain.len() - 1].1.project_type;
+            let base_dir = self.resolve_base_template(&extends, project_type)?;
+            let canonical = std::fs::canonicalize(&base_dir)?;
+            if visited.contains(&canonical) {
+                let mut names: Vec<String> = chain
+                    .iter()
+                    .map(|(_, layer)| format!("{}/{}", layer.project_type, layer.name))
+                    .collect();
+                names.push(extends);
+                return Err(GeneratorError::Configuration(format!(
+                    "Template inheritance cycle: {}",
+                    names.join(" -> ")
+                )));
+            }
+            visited.push(canonical);
+            let base = pipeline::load_template_metadata(&base_dir)?;
+            chain.push((base_dir, base));
+        }
+
+        let merged = tempfile::tempdir()?;
+        let mut provenance = BTreeMap::new();
+        let mut metadata: Option<TemplateMetadata> = None;
+        for (index, (dir, layer)) in chain.into_iter().enumerate().rev() {
+            // 模板自身的文件不记录来源
+            let label = (index > 0).then(|| {
+                format!(
+                    "extends:{}/{}@{}",
+                    layer.project_type, layer.name, layer.version
+                )
+            });
+            overlay(&dir, merged.path(), label, &mut provenance)?;
+            metadata = Some(match metadata {
+                Some(base) => merge_metadata(base, layer),
+                None => layer,
+            });
+        }
+        for (registry, dir) in overrides {
+            let label = Some(format!("override:{}", registry));
+            overlay(&dir, merged.path(), label, &mut provenance)?;
+        }
+        let metadata = metadata.unwrap_or_default();
+        std::fs::write(
+            merged.path().join(TEMPLATE_METADATA_FILE),
+            serde_json::to_string_pretty(&metadata)?,
+        )?;
+
+        Ok(ResolvedTemplate {
+            source_dir: template_dir.to_path_buf(),
+            dir: merged.path().to_path_buf(),
+            metadata,
+            provenance,
+            _merged: Some(merged),
+        })
+    }
+
+    /// 各本地注册表中模板的覆盖层（`<path>/overrides/<project_type>/<template>`）
+    ///
+    /// 按优先级从低到高排列，后叠加的层覆盖先前的同名文件，因此项目级注册表可以再覆盖组织级的修改。
+    pub fn override_layers(
+        &self,
+        project_type: &str,
+        template_name: &str,
+    ) -> Vec<(String, PathBuf)> {
+        let mut registries: Vec<&TemplateRegistry> =
+            self.registries.iter().filter(|r| r.enabled).collect();
+        registries.sort_by_key(|r| std::cmp::Reverse(r.priority));
+
+        registries
+            .into_iter()
+            .filter_map(|registry| match &registry.source {
+                TemplateSource::Local { path } => {
+                    let dir = path.join(OVERRIDES_DIR).join(project_type).join(template_name);
+                    dir.is_dir().then(|| (registry.name.clone(), dir))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 在本地注册表中查找组件子生成器（`<path>/components/<project_type>/<kind>`）
+    pub fn find_local_component(&self, project_type: &str, kind: &str) -> Option<PathBuf> {
+        self.find_local(&Path::new(COMPONENTS_DIR).join(project_type).join(kind))
+    }
+
+    /// 列出本地注册表中项目类型可用的组件子生成器
+    pub fn list_local_components(&self, project_type: &str) -> Vec<String> {
+        let mut kinds: Vec<String> = self
+            .local_roots()
+            .into_iter()
+            .filter_map(|root| std::fs::read_dir(root.join(COMPONENTS_DIR).join(project_type)).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(TEMPLATE_METADATA_FILE).is_file())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        kinds.sort();
+        kinds.dedup();
+        kinds
+    }
+
+    /// 拉取简写引用的 Git 模板（见 [`TemplateSource::from_shorthand`]），无需预先注册；不是简写时返回 None
+    pub fn fetch_shorthand(&self, template: &str) -> Result<Option<PathBuf>> {
+        let Some(TemplateSource::Git {
+            url,
+            branch,
+            subfolder,
+            commit,
+            ..
+        }) = TemplateSource::from_shorthand(template)
+        else {
+            return Ok(None);
+        };
+        if self.frozen && commit.is_none() {
+            return Err(GeneratorError::Configuration(format!(
+                "Template '{}' is not pinned to a commit as frozen mode requires; use {}#<commit>",
+                template, template
+            )));
+        }
+        let fetcher = GitFetcher {
+            network: self.network.clone().resolve(),
+            cache_dir: self.cache_dir.clone(),
+            sparse: true,
+        };
+        let dir = fetcher.fetch(
+            &url,
+            branch.as_deref(),
+            commit.as_deref(),
+            subfolder.as_deref(),
+            None,
+        )?;
+        if pipeline::metadata_file(&dir).is_none() {
+            return Err(GeneratorError::TemplateNotFound(format!(
+                "{} has no {}",
+                template, TEMPLATE_METADATA_FILE
+            )));
+        }
+        Ok(Some(dir))
+    }
+
+    /// 冻结模式下拒绝浮动引用的注册表
+    pub(crate) fn check_frozen(&self, registry: &TemplateRegistry) -> Result<()> {
+        if self.frozen && registry.source.is_floating() {
+            return Err(GeneratorError::Configuration(format!(
+                "Registry '{}' is not pinned to a commit, checksum or integrity \
+                 as frozen mode requires",
+                registry.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// 拉取注册表并返回其目录：本地注册表即其路径，远程注册表拉取到缓存
+    ///
+    /// Git 与 HTTP 注册表的凭据 ID 从凭据存储查找，npm 注册表按 .npmrc（或配置的 `npmrc`）选择 registry 与认证。
+    pub fn fetch_registry(&self, registry: &TemplateRegistry) -> Result<PathBuf> {
+        self.check_frozen(registry)?;
+        match &registry.source {
+            TemplateSource::Local { path } => Ok(path.clone()),
+            TemplateSource::Git {
+                url,
+                branch,
+                subfolder,
+                auth,
+                commit,
+            } => {
+                let credential = match auth {
+                    Some(auth) => auth.resolve(&self.credential_store())?,
+                    None => None,
+                };
+                GitFetcher {
+                    network: self.network.clone().resolve(),
+                    cache_dir: self.cache_dir.clone(),
+                    sparse: true,
+                }
+                .fetch(
+                    url,
+                    branch.as_deref(),
+                    commit.as_deref(),
+                    subfolder.as_deref(),
+                    credential.as_ref(),
+                )
+            }
+            TemplateSource::Http {
+                url,
+                checksum,
+                auth,
+            } => {
+                let credential = match auth {
+                    Some(auth) => auth.resolve(&self.credential_store())?,
+                    None => None,
+                };
+                HttpFetcher {
+                    network: self.network.clone().resolve(),
+                    cache: self.content_cache(),
+                    timeout: PhaseTimeouts::duration(self.timeouts.http_download_secs),
+                    extractor: Default::default(),
+                }
+                .fetch(url, checksum.as_deref(), credential.as_ref())
+            }
+            TemplateSource::Npm {
+                package,
+                version,
+                registry,
+                integrity,
+            } => NpmFetcher {
+                npmrc: match &self.npmrc {
+                    Some(path) => Npmrc::load_file(path)?,
+                    None => Npmrc::load()?,
+                },
+                network: self.network.clone().resolve(),
+                cache: self.content_cache(),
+                timeout: PhaseTimeouts::duration(self.timeouts.npm_pack_secs),
+            }
+            .fetch(package, version, integrity.as_deref(), registry.as_deref()),
+        }
+    }
+
+    /// 解析注册表凭据 ID 的凭据存储：环境变量、系统钥匙串，最后是配置的凭据文件
+    pub fn credential_store(&self) -> CredentialStore {
+        match &self.credentials_file {
+            Some(path) => CredentialStore::new(vec![
+                Box::new(EnvCredentials),
+                Box::new(KeyringCredentials),
+                Box::new(FileCredentials { path: path.clone() }),
+            ]),
+            None => CredentialStore::default(),
+        }
+    }
+
+    /// 在注册表中查找模板：本地与 Git 注册表按 `<project_type>/<template>` 存放多个模板，
+    /// 其余远程源（以及根目录即模板的 Git 仓库）只提供单个模板，名称与项目类型不符时视为未找到
+    pub fn find_in_registry(
+        &self,
+        registry: &TemplateRegistry,
+        project_type: &str,
+        template_name: &str,
+    ) -> Result<PathBuf> {
+        let not_found =
+            || GeneratorError::TemplateNotFound(format!("{}:{}", project_type, template_name));
+        let dir = self.fetch_registry(registry)?;
+        let nested = dir.join(project_type).join(template_name);
+        if matches!(
+            registry.source,
+            TemplateSource::Local { .. } | TemplateSource::Git { .. }
+        ) && pipeline::metadata_file(&nested).is_some()
+        {
+            return Ok(nested);
+        }
+        if matches!(registry.source, TemplateSource::Local { .. })
+            || pipeline::metadata_file(&dir).is_none()
+        {
+            return Err(not_found());
+        }
+        let metadata = pipeline::load_template_metadata(&dir)?;
+        if metadata.project_type == project_type && metadata.name == template_name {
+            Ok(dir)
+        } else {
+            Err(not_found())
+        }
+    }
+
+    /// 按优先级在已启用的远程注册表中查找模板，都没有该模板时返回 None
+    pub fn find_remote_template(
+        &self,
+        project_type: &str,
+        template_name: &str,
+    ) -> Result<Option<PathBuf>> {
+        let mut registries: Vec<&TemplateRegistry> = self
+            .registries
+            .iter()
+            .filter(|r| r.enabled && !matches!(r.source, TemplateSource::Local { .. }))
+            .collect();
+        registries.sort_by_key(|r| r.priority);
+
+        for registry in registries {
+            match self.find_in_registry(registry, project_type, template_name) {
+                Ok(dir) => return Ok(Some(dir)),
+                Err(GeneratorError::TemplateNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// 按优先级排列的已启用本地注册表根目录
+    pub(crate) fn local_roots(&self) -> Vec<&PathBuf> {
+        let mut registries: Vec<&TemplateRegistry> =
+            self.registries.iter().filter(|r| r.enabled).collect();
+        registries.sort_by_key(|r| r.priority);
+
+        registries
+            .into_iter()
+            .filter_map(|registry| match &registry.source {
+                TemplateSource::Local { path } => Some(path),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn find_local(&self, relative: &Path) -> Option<PathBuf> {
+        self.local_roots().into_iter().find_map(|root| {
+            let dir = root.join(relative);
+            dir.join(TEMPLATE_METADATA_FILE).is_file().then_some(dir)
+        })
+    }
+}
+
+fn default_cache_max_size_mb() -> Option<u64> {
+    Some(1024)
+}
+
 /// 默认配置
 impl Default for TemplateRegistryConfig {
     fn default() -> Self {
@@ -317,6 +967,302 @@ impl Default for TemplateRegistryConfig {
             ],
             cache_dir: PathBuf::from("./.template_cache"),
             cache_ttl: 3600, // 1小时
+            cache_max_size_mb: default_cache_max_size_mb(),
+            timeouts: PhaseTimeouts::default(),
+            network: NetworkConfig::default(),
+            frozen: false,
+            credentials_file: None,
+            npmrc: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_template(dir: &Path, metadata: &str, files: &[(&str, &str)]) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(TEMPLATE_METADATA_FILE), metadata).unwrap();
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_override_layers_apply_in_priority_order() {
+        let org = tempdir().unwrap();
+        let team = tempdir().unwrap();
+        let local = |name: &str, path: &Path, priority| TemplateRegistry {
+            name: name.to_string(),
+            source: TemplateSource::Local {
+                path: path.to_path_buf(),
+            },
+            enabled: true,
+            priority,
+        };
+        let config = TemplateRegistryConfig {
+            registries: vec![local("team", team.path(), 0), local("org", org.path(), 10)],
+            ..Default::default()
+        };
+        let template = org.path().join("vue/basic");
+        write_template(
+            &template,
+            r#"{"name": "basic", "version": "1.0.0", "description": "", "author": "", "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+            &[("README.md", "readme"), (".github/workflows/ci.yml", "upstream")],
+        );
+        let overrides = |root: &Path| root.join(OVERRIDES_DIR).join("vue/basic");
+        write_template(
+            &overrides(org.path()),
+            "{}",
+            &[(".github/workflows/ci.yml", "org"), ("LICENSE", "org")],
+        );
+        write_template(
+            &overrides(team.path()),
+            "{}",
+            &[(".github/workflows/ci.yml", "team")],
+        );
+
+        let resolved = config.resolve_layers(&template).unwrap();
+        let read = |path: &str| std::fs::read_to_string(resolved.dir.join(path)).unwrap();
+        assert_eq!(read(".github/workflows/ci.yml"), "team");
+        assert_eq!(read("LICENSE"), "org");
+        assert_eq!(read("README.md"), "readme");
+        // 覆盖层中的 template.json 不替换模板元数据
+        assert_eq!(resolved.metadata.name, "basic");
+        assert_eq!(
+            resolved.provenance,
+            BTreeMap::from([
+                (".github/workflows/ci.yml".to_string(), "override:team".to_string()),
+                ("LICENSE".to_string(), "override:org".to_string()),
+            ])
+        );
+
+        // 没有基础模板和覆盖层时直接使用模板目录
+        std::fs::remove_dir_all(org.path().join(OVERRIDES_DIR)).unwrap();
+        std::fs::remove_dir_all(team.path().join(OVERRIDES_DIR)).unwrap();
+        let resolved = config.resolve_layers(&template).unwrap();
+        assert_eq!(resolved.dir, template);
+        assert!(resolved.provenance.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_layers_overlays_base_template() {
+        let root = tempdir().unwrap();
+        let config = TemplateRegistryConfig {
+            registries: vec![TemplateRegistry {
+                name: "local".to_string(),
+                source: TemplateSource::Local {
+                    path: root.path().to_path_buf(),
+                },
+                enabled: true,
+                priority: 0,
+            }],
+            ..Default::default()
+        };
+        write_template(
+            &root.path().join("react/basic"),
+            r#"{"name": "basic", "version": "1.2.0", "description": "", "author": "", "project_type": "react", "tags": ["react"], "dependencies": [],
+                "variables": [
+                    {"name": "title", "description": "", "default": "App", "required": false, "var_type": "string"},
+                    {"name": "port", "description": "", "default": "3000", "required": false, "var_type": "number"}
+                ]}"#,
+            &[("README.md", "# {{title}}"), ("src/App.tsx", "app")],
+        );
+        let child = root.path().join("custom");
+        write_template(
+            &child,
+            r#"{"name": "admin", "version": "0.1.0", "description": "", "author": "", "project_type": "react", "tags": ["admin"], "dependencies": [],
+                "extends": "react/basic@^1",
+                "variables": [
+                    {"name": "port", "description": "", "default": "8080", "required": false, "var_type": "number"},
+                    {"name": "theme", "description": "", "default": "dark", "required": false, "var_type": "string"}
+                ]}"#,
+            &[("README.md", "# {{title}} admin"), ("src/Admin.tsx", "admin")],
+        );
+
+        let resolved = config.resolve_layers(&child).unwrap();
+        assert_eq!(resolved.source_dir, child);
+        assert_eq!(resolved.metadata.name, "admin");
+        assert_eq!(resolved.metadata.extends, None);
+        assert_eq!(resolved.metadata.tags, vec!["react", "admin"]);
+        let variables: Vec<(&str, Option<&str>)> = resolved
+            .metadata
+            .variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.default.as_deref()))
+            .collect();
+        assert_eq!(
+            variables,
+            vec![
+                ("title", Some("App")),
+                ("port", Some("8080")),
+                ("theme", Some("dark"))
+            ]
+        );
+        let read = |path: &str| std::fs::read_to_string(resolved.dir.join(path)).unwrap();
+        assert_eq!(read("README.md"), "# {{title}} admin");
+        assert_eq!(read("src/App.tsx"), "app");
+        assert_eq!(read("src/Admin.tsx"), "admin");
+        assert_eq!(
+            resolved.provenance,
+            BTreeMap::from([("src/App.tsx".to_string(), "extends:react/basic@1.2.0".to_string())])
+        );
+        assert!(read(TEMPLATE_METADATA_FILE).contains("\"theme\""));
+        let merged_dir = resolved.dir.clone();
+        drop(resolved);
+        assert!(!merged_dir.exists());
+
+        // 版本要求不满足时找不到基础模板
+        write_template(
+            &root.path().join("react/v2"),
+            r#"{"name": "v2", "version": "1.0.0", "description": "", "author": "", "project_type": "react", "variables": [], "dependencies": [], "tags": [],
+                "extends": "basic@^2"}"#,
+            &[],
+        );
+        assert!(matches!(
+            config.resolve_layers(&root.path().join("react/v2")),
+            Err(GeneratorError::TemplateNotFound(_))
+        ));
+
+        // 循环继承
+        for (name, base) in [("a", "b"), ("b", "a")] {
+            write_template(
+                &root.path().join("vue").join(name),
+                &format!(
+                    r#"{{"name": "{}", "version": "1.0.0", "description": "", "author": "", "project_type": "vue", "variables": [], "dependencies": [], "tags": [], "extends": "{}"}}"#,
+                    name, base
+                ),
+                &[],
+            );
+        }
+        let err = config
+            .resolve_layers(&root.path().join("vue/a"))
+            .unwrap_err();
+        assert!(err.to_string().contains("vue/a -> vue/b -> a"));
+    }
+
+    #[test]
+    fn test_template_metadata_schema() {
+        let schema: serde_json::Value =
+            serde_json::from_str(&template_metadata_schema().unwrap()).unwrap();
+        let properties = &schema["properties"];
+        assert!(properties["variables"].is_object());
+        assert!(properties["features"].is_object());
+        assert!(schema["definitions"]["VariableType"].is_object());
+    }
+
+    #[test]
+    fn test_frozen_mode_rejects_floating_registries() {
+        let registries: Vec<TemplateRegistry> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "floating",
+                "source": {"type": "git", "url": "https://example.invalid/t.git", "branch": "main"},
+                "enabled": true,
+                "priority": 0
+            },
+            {
+                "name": "pinned",
+                "source": {"type": "npm", "package": "@acme/t", "version": "1.0.0",
+                           "integrity": "sha512-AAAA"},
+                "enabled": true,
+                "priority": 1
+            }
+        ]))
+        .unwrap();
+        assert!(registries[0].source.is_floating());
+        assert!(!registries[1].source.is_floating());
+
+        let manager = TemplateManager::new(TemplateRegistryConfig {
+            frozen: true,
+            ..Default::default()
+        });
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let err = runtime
+            .block_on(manager.load_templates_from_registry(&registries[0]))
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::Configuration(_)));
+        assert!(manager.config.check_frozen(&registries[1]).is_ok());
+    }
+
+    #[test]
+    fn test_get_template_from_local_registry() {
+        let registry = tempdir().unwrap();
+        write_template(
+            &registry.path().join("vue/basic"),
+            r#"{"name": "basic", "version": "1.0.0", "description": "", "author": "", "project_type": "vue", "variables": [], "dependencies": []}"#,
+            &[],
+        );
+        let mut config = TemplateRegistryConfig::default();
+        config.registries[0].source = TemplateSource::Local {
+            path: registry.path().to_path_buf(),
+        };
+        let mut manager = TemplateManager::new(config);
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        let path = runtime.block_on(manager.get_template("vue", "basic")).unwrap();
+        assert_eq!(path, registry.path().join("vue/basic"));
+        let err = runtime
+            .block_on(manager.get_template("vue", "missing"))
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::TemplateNotFound(_)));
+    }
+
+    #[test]
+    fn test_git_shorthand() {
+        let git = |spec: &str| match TemplateSource::from_shorthand(spec) {
+            Some(TemplateSource::Git {
+                url,
+                branch,
+                subfolder,
+                commit,
+                ..
+            }) => Some((url, branch, subfolder, commit)),
+            _ => None,
+        };
+        let owned = |value: &str| Some(value.to_string());
+
+        assert_eq!(
+            git("user/repo"),
+            Some(("https://github.com/user/repo.git".to_string(), None, None, None))
+        );
+        assert_eq!(
+            git("user/repo#main/templates/vue"),
+            Some((
+                "https://github.com/user/repo.git".to_string(),
+                owned("main"),
+                owned("templates/vue"),
+                None
+            ))
+        );
+        assert_eq!(
+            git("gitlab:org/repo.git/sub#dev"),
+            Some((
+                "https://gitlab.com/org/repo.git".to_string(),
+                owned("dev"),
+                owned("sub"),
+                None
+            ))
+        );
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        assert_eq!(git(&format!("gh:org/repo#{}", sha)).unwrap().3, owned(sha));
+
+        for spec in ["basic", "./a/b", "/abs/path", "C:/dir/x", "npm:pkg/x", "a/b#", "a/b/../c"] {
+            assert_eq!(git(spec), None, "{}", spec);
+        }
+
+        // 冻结模式下浮动的简写在联网前被拒绝
+        let config = TemplateRegistryConfig {
+            frozen: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.fetch_shorthand("gh:org/repo#main"),
+            Err(GeneratorError::Configuration(_))
+        ));
+        assert!(config.fetch_shorthand("basic").unwrap().is_none());
+    }
+}
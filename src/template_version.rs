@@ -0,0 +1,113 @@
+//! 模板版本解析模块
+//! 解析 `template@^2.0` 形式的模板说明，并在候选版本（目录、git 标签、npm 版本）中选出最高的匹配版本
+
+use crate::{GeneratorError, Result};
+use semver::{Version, VersionReq};
+
+/// 模板说明：模板名及可选的版本要求
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateSpec {
+    /// 模板名称或路径
+    pub name: String,
+    /// 版本要求，未指定时取最新版本
+    pub requirement: Option<VersionReq>,
+}
+
+impl TemplateSpec {
+    /// 解析 `name@requirement`；`@scope/pkg` 这类以 `@` 开头的名称不视为版本分隔符
+    pub fn parse(spec: &str) -> Result<Self> {
+        let Some(index) = spec.rfind('@').filter(|&index| index > 0) else {
+            return Ok(Self {
+                name: spec.to_string(),
+                requirement: None,
+            });
+        };
+
+        let (name, requirement) = (&spec[..index], &spec[index + 1..]);
+        let requirement = VersionReq::parse(requirement).map_err(|e| {
+            GeneratorError::Configuration(format!(
+                "Invalid version requirement '{}' in template '{}': {}",
+                requirement, spec, e
+            ))
+        })?;
+
+        Ok(Self {
+            name: name.to_string(),
+            requirement: Some(requirement),
+        })
+    }
+
+    /// 版本是否满足要求
+    pub fn matches(&self, version: &Version) -> bool {
+        self.requirement
+            .as_ref()
+            .is_none_or(|requirement| requirement.matches(version))
+    }
+}
+
+/// 解析版本号，兼容 git 标签常见的 `v` 前缀
+pub fn parse_version(raw: &str) -> Option<Version> {
+    let raw = raw.trim();
+    Version::parse(raw.strip_prefix('v').unwrap_or(raw)).ok()
+}
+
+/// 检查模板要求的最低生成器版本，当前版本过旧时提示升级
+pub fn check_generator_version(template: &str, required: &str) -> Result<()> {
+    let minimum = parse_version(required).ok_or_else(|| {
+        GeneratorError::Configuration(format!(
+            "Invalid min_generator_version '{}' in template '{}'",
+            required, template
+        ))
+    })?;
+    let current = Version::parse(env!("CARGO_PKG_VERSION"))
+        .map_err(|e| GeneratorError::Unknown(e.to_string()))?;
+    if current < minimum {
+        return Err(GeneratorError::Configuration(format!(
+            "Template '{}' requires generator {} or newer, but {} is installed; \
+             upgrade with `npm install -g {}@latest`",
+            template, minimum, current, NPM_PACKAGE
+        )));
+    }
+    Ok(())
+}
+
+/// 生成器的 npm 包名
+const NPM_PACKAGE: &str = "@kdesigner/gen";
+
+/// 从候选中选出满足要求的最高版本；版本相同时保留先出现的候选（优先级更高的注册表）
+pub fn select_version<T>(
+    spec: &TemplateSpec,
+    candidates: impl IntoIterator<Item = (Version, T)>,
+) -> Option<(Version, T)> {
+    candidates
+        .into_iter()
+        .filter(|(version, _)| spec.matches(version))
+        .fold(None, |best, candidate| match best {
+            Some(best) if best.0 >= candidate.0 => Some(best),
+            _ => Some(candidate),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec_and_select_version() {
+        let spec = TemplateSpec::parse("basic@^2.0").unwrap();
+        assert_eq!(spec.name, "basic");
+
+        let scoped = TemplateSpec::parse("@acme/vue-template").unwrap();
+        assert_eq!(scoped.name, "@acme/vue-template");
+        assert!(scoped.requirement.is_none());
+        assert!(TemplateSpec::parse("basic@not-a-version").is_err());
+
+        let tags = ["v1.4.0", "v2.0.0", "v2.3.1", "v3.0.0", "latest"];
+        let selected = select_version(
+            &spec,
+            tags.iter()
+                .filter_map(|tag| parse_version(tag).map(|version| (version, *tag))),
+        );
+        assert_eq!(selected.map(|(_, tag)| tag), Some("v2.3.1"));
+    }
+}
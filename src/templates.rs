@@ -1,33 +1,1032 @@
-//! 模板系统核心模块 - 简化版本
-//! 提供基础的模板操作接口，委托给 template_registry 处理
+//! 模板系统核心模块
+//! 提供基础的模板操作接口，模板解析委托给 template_registry，文件生成委托给 pipeline
 
-use crate::{GenerateOptions, GenerateResult, GeneratorError, Result};
+use crate::android_env;
+use crate::build_wrapper::BuildTool;
+use crate::crud::{self, CrudScaffold};
+use crate::delegate;
+use crate::env_defaults::{env_defaults, volatile_defaults};
+use crate::java_cli::JavaCliConfig;
+use crate::license::{self, License};
+use crate::lockfile::{self, LockSource, ProjectLock};
+use crate::openapi::{OpenApiMode, OpenApiScaffold, OpenApiSpec};
+use crate::pipeline::{self, NEXT_STEPS_FILE, RenderOptions, RenderReport};
+use crate::readme::{self, ProjectSummary};
+use crate::render::TemplateRenderer;
+use crate::report::{GenerationReport, HookRun, PhaseTimer, ReportTemplate, millis};
+use crate::template_index::{IndexEntry, TemplateIndex};
+use crate::template_registry::{
+    COMPONENTS_DIR, ResolvedTemplate, TemplateMetadata, TemplateRegistryConfig,
+};
+use crate::template_version::{self, TemplateSpec};
+use crate::transaction::{CommitMode, MergeSummary, StagedOutput};
+use crate::usage::UsageLog;
+use crate::variables::{redact_sensitive, resolve_variables, without_sensitive};
+use crate::{
+    ComponentOptions, GenerateOptions, GenerateResult, GenerationStats, GeneratorError,
+    PhaseTimeouts, Result, TemplateFeatureInfo, answers, build_wrapper, features, hooks, java_cli,
+    monorepo, node_env, python_env, retry, workspace,
+};
+use chrono::Datelike;
 use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
 
-/// 从模板生成项目 - 简化实现
+/// 从模板生成项目，注册表配置读取自 `~/.generator/registries.json`
 pub fn generate_project_from_template(options: GenerateOptions) -> Result<GenerateResult> {
-    // 暂时返回基础实现，后续集成 TemplateManager
+    generate_project_with_config(options, &TemplateRegistryConfig::user()?)
+}
+
+/// 按给定的注册表配置生成项目
+pub fn generate_project_with_config(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> Result<GenerateResult> {
+    let report_path = options.report.clone();
+    let (result, report) = generate_with_report(options, config)?;
+    if let (Some(path), Some(report)) = (report_path, report) {
+        report.write(Path::new(&path))?;
+    }
+    Ok(result)
+}
+
+/// 生成项目并返回生成报告，不写入报告文件
+///
+/// monorepo 的报告由根项目的生成写入，此处返回 None。
+pub(crate) fn generate_with_report(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> Result<(GenerateResult, Option<GenerationReport>)> {
+    let mut options = answers::apply_answers_file(options)?;
+    // 统一解析为绝对路径，monorepo 子项目与后续阶段不再依赖当前目录
+    let output_dir = workspace::resolve_output_dir(
+        options.output_path.as_deref(),
+        options.workspace_root.as_deref(),
+    )?;
+    options.output_path = Some(output_dir.to_string_lossy().to_string());
+    if options
+        .packages
+        .as_ref()
+        .is_some_and(|packages| !packages.is_empty())
+    {
+        return Ok((monorepo::generate_monorepo(options, config)?, None));
+    }
+    let (result, report) = build_single_project(options, config)?;
+    Ok((result, Some(report)))
+}
+
+/// 从单个模板生成项目，设置了 `report` 时写入生成报告
+pub(crate) fn generate_single_project(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> Result<GenerateResult> {
+    let report_path = options.report.clone();
+    let (result, report) = build_single_project(options, config)?;
+    if let Some(path) = report_path {
+        report.write(Path::new(&path))?;
+    }
+    Ok(result)
+}
+
+/// 项目目录 `<base_dir>/<name>`，名称必须是单个路径段
+pub(crate) fn project_dir(base_dir: &Path, name: &str) -> Result<PathBuf> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(base_dir.join(name)),
+        _ => Err(GeneratorError::InvalidProjectName(format!(
+            "'{}' must not contain path separators or '..'",
+            name
+        ))),
+    }
+}
+
+fn build_single_project(
+    options: GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> Result<(GenerateResult, GenerationReport)> {
+    if delegate::requested(&options) {
+        return delegate::generate(options, config);
+    }
+    let mut timer = PhaseTimer::default();
+    // 丢弃之前操作留下的重试记录，报告只包含本次生成的重试
+    retry::take_events();
+    let template_name = options.template.clone().unwrap_or("basic".to_string());
+    let mut template = timer.time("resolve", || {
+        resolve_template(config, &options.project_type, &template_name)
+    })?;
+
+    // 合并模式直接写入 output_path，否则创建 <output_path>/<name>
+    let into_existing = options.into_existing.unwrap_or(false);
+    let base_dir = workspace::resolve_output_dir(
+        options.output_path.as_deref(),
+        options.workspace_root.as_deref(),
+    )?;
+    let (output_dir, commit_mode) = if into_existing {
+        (
+            base_dir,
+            CommitMode::Merge {
+                force: options.force.unwrap_or(false),
+            },
+        )
+    } else {
+        (project_dir(&base_dir, &options.name)?, CommitMode::Create)
+    };
+    workspace::ensure_writable(&output_dir)?;
+    let source = pinned_source(&template.source_dir, &options, &output_dir)?;
+
+    // 数据库连接信息按敏感变量处理，不进入锁文件与报告
+    let mut provided = build_variables(&options)?;
+    if let Some(database) = &options.database {
+        crud::declare_connection_variables(&mut template.metadata);
+        if database.trim().starts_with("jdbc:") {
+            provided
+                .entry(crud::URL_VARIABLE.to_string())
+                .or_insert_with(|| database.trim().to_string());
+        }
+    }
+    let metadata = &template.metadata;
+    let template_version = metadata.version.clone();
+    let mut variables = resolve_variables(metadata, &provided)?;
+    select_build_tool(&options, metadata, &mut variables)?;
+    let features = options.features.clone().unwrap_or_default();
+    let license = match &options.license {
+        Some(license) => Some((
+            license.parse::<License>()?,
+            license_holder(&options, &variables),
+        )),
+        None => None,
+    };
+    let openapi = match &options.openapi {
+        Some(source) => {
+            let mode = OpenApiMode::resolve(&options.project_type, options.openapi_mode)?;
+            let timeouts = resolve_timeouts(&options, config);
+            let spec = timer.time("openapi-spec", || {
+                OpenApiSpec::load(
+                    source,
+                    &config.network.clone().resolve(),
+                    PhaseTimeouts::duration(timeouts.http_download_secs),
+                )
+            })?;
+            Some(OpenApiScaffold {
+                spec,
+                mode,
+                project_type: options.project_type.clone(),
+                java_cli: JavaCliConfig::from(&timeouts),
+            })
+        }
+        None => None,
+    };
+    let crud = match &options.database {
+        Some(database) => Some(CrudScaffold::new(
+            &options.project_type,
+            database,
+            options.database_tables.clone().unwrap_or_default(),
+            &variables,
+            JavaCliConfig::from(&resolve_timeouts(&options, config)),
+        )?),
+        None => None,
+    };
+
+    // 新建项目时写入锁文件，合并到已有项目时不覆盖其原有锁文件
+    let lock = (commit_mode == CommitMode::Create).then(|| {
+        ProjectLock {
+            name: options.name.clone(),
+            project_type: options.project_type.clone(),
+            template: template_name.clone(),
+            template_version: metadata.version.clone(),
+            variables: without_sensitive(metadata, &variables),
+            features: features.clone(),
+            source: source.clone(),
+            generator_version: String::new(),
+            generated_at: String::new(),
+            openapi: openapi
+                .as_ref()
+                .map(|openapi| openapi.spec.lock(openapi.mode)),
+            files: Default::default(),
+        }
+        .stamp()
+    });
+
+    let plan = GenerationPlan {
+        template,
+        output_dir,
+        commit_mode,
+        keep_partial: options.keep_partial.unwrap_or(false),
+        variables,
+        render_options: build_render_options(&options)?,
+        features,
+        lock,
+        // Android 项目默认生成 Gradle wrapper
+        build_wrapper: options.build_wrapper.unwrap_or(options.project_type == "android"),
+        wrapper_optional: options.build_wrapper.is_none(),
+        android: options.project_type == "android",
+        python: options.project_type == "python",
+        python_venv: options.python_venv.unwrap_or(false),
+        strict: options.strict.unwrap_or(false),
+        license,
+        readme: true,
+        openapi,
+        crud,
+        hook_timeout: PhaseTimeouts::duration(resolve_timeouts(&options, config).hook_secs),
+    };
+    // 以相同选项重复生成且输出未被修改时直接返回，便于声明式流水线重复应用；
+    // 随时间变化的默认变量（如 year）不算输入变化，显式传入的除外
+    let volatile: Vec<String> = volatile_defaults()?
+        .into_iter()
+        .filter(|name| {
+            !options
+                .variables
+                .as_ref()
+                .is_some_and(|variables| variables.contains_key(name))
+        })
+        .collect();
+    let unchanged = plan
+        .lock
+        .as_ref()
+        .and_then(|lock| lock.find_unchanged(&plan.output_dir, &volatile));
+    let outcome = match &unchanged {
+        Some(existing) => GenerationOutcome::unchanged(existing),
+        None => execute_plan(&plan, &mut timer)?,
+    };
+
+    let message = if unchanged.is_some() {
+        format!(
+            "项目 {} 已是最新，无需重新生成 (使用模板: {} {})",
+            options.name, template_name, template_version
+        )
+    } else {
+        format!(
+            "项目 {} 生成成功 (使用模板: {} {}){}",
+            options.name,
+            template_name,
+            template_version,
+            outcome.merge_message()
+        )
+    };
+
+    // 使用记录与下一步说明失败不影响生成结果
+    let mut warnings = outcome.warnings.clone();
+    let message = match render_next_steps(&plan.template, &plan.variables) {
+        Ok(Some(next_steps)) => format!("{}\n\n{}", message, next_steps),
+        Ok(None) => message,
+        Err(e) => {
+            warnings.push(format!("Failed to render {}: {}", NEXT_STEPS_FILE, e));
+            message
+        }
+    };
+    if let Err(e) =
+        UsageLog::default().record(&options.project_type, &template_name, &template_version)
+    {
+        warnings.push(format!("Failed to record template usage: {}", e));
+    }
+
+    let report = GenerationReport {
+        project: options.name.clone(),
+        output_dir: plan.output_dir.display().to_string(),
+        template: ReportTemplate {
+            project_type: options.project_type.clone(),
+            name: template_name,
+            version: template_version.clone(),
+            source,
+        },
+        variables: redact_sensitive(&plan.template.metadata, &plan.variables),
+        features: plan.features.clone(),
+        files: outcome.file_list(None),
+        provenance: plan.template.provenance.clone(),
+        hooks: outcome.hooks.clone(),
+        phases: timer.phases,
+        warnings: warnings.clone(),
+        retries: retry::take_events(),
+        generator_version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let result = GenerateResult {
+        success: true,
+        files: outcome.file_list(Some(&options.name)),
+        message: Some(message),
+        stats: Some(outcome.stats),
+        template_version: Some(template_version),
+        warnings,
+        action: Some(outcome.action.to_string()),
+    };
+    Ok((result, report))
+}
+
+/// 在已有项目中生成单个组件（Vue 组件、React hook、Spring controller 等）
+pub fn generate_component(options: ComponentOptions) -> Result<GenerateResult> {
+    let template_dir = TemplateRegistryConfig::default()
+        .find_local_component(&options.project_type, &options.kind)
+        .ok_or_else(|| {
+            GeneratorError::TemplateNotFound(format!(
+                "{}/{}:{}",
+                COMPONENTS_DIR, options.project_type, options.kind
+            ))
+        })?;
+
+    let mut variables = options.variables.clone().unwrap_or_default();
+    variables
+        .entry("name".to_string())
+        .or_insert_with(|| options.name.clone());
+    variables
+        .entry("project_type".to_string())
+        .or_insert_with(|| options.project_type.clone());
+
+    let template = TemplateRegistryConfig::default().resolve_layers(&template_dir)?;
+    let template_version = template.metadata.version.clone();
+    let plan = GenerationPlan {
+        template,
+        output_dir: workspace::resolve_output_dir(options.project_path.as_deref(), None)?,
+        commit_mode: CommitMode::Merge {
+            force: options.force.unwrap_or(false),
+        },
+        keep_partial: false,
+        variables,
+        render_options: RenderOptions::default(),
+        features: Vec::new(),
+        lock: None,
+        build_wrapper: false,
+        wrapper_optional: false,
+        android: false,
+        python: false,
+        python_venv: false,
+        strict: false,
+        license: None,
+        readme: false,
+        openapi: None,
+        crud: None,
+        hook_timeout: None,
+    };
+    let outcome = execute_plan(&plan, &mut PhaseTimer::default())?;
+
+    let message = format!(
+        "{} {} 生成成功{}",
+        options.kind,
+        options.name,
+        outcome.merge_message()
+    );
+
     Ok(GenerateResult {
         success: true,
-        files: vec![format!("{}/README.md", options.name)],
-        message: Some(format!(
-            "项目 {} 生成成功 (使用模板: {})",
-            options.name,
-            options.template.unwrap_or("basic".to_string())
-        )),
+        files: outcome.file_list(None),
+        message: Some(message),
+        stats: Some(outcome.stats),
+        template_version: Some(template_version),
+        warnings: outcome.warnings,
+        action: Some(outcome.action.to_string()),
+    })
+}
+
+/// 列出项目类型可用的组件子生成器
+pub fn list_components(project_type: &str) -> Result<Vec<String>> {
+    Ok(TemplateRegistryConfig::default().list_local_components(project_type))
+}
+
+/// 一次生成的执行计划
+struct GenerationPlan {
+    template: ResolvedTemplate,
+    output_dir: PathBuf,
+    commit_mode: CommitMode,
+    keep_partial: bool,
+    variables: HashMap<String, String>,
+    render_options: RenderOptions,
+    features: Vec<String>,
+    /// 需要写入项目的锁文件
+    lock: Option<ProjectLock>,
+    /// 生成 Maven / Gradle wrapper
+    build_wrapper: bool,
+    /// wrapper 不是显式要求的（如 Android 项目默认生成），失败时只记为警告
+    wrapper_optional: bool,
+    /// Android 项目：检测 SDK 并写入 local.properties
+    android: bool,
+    /// Python 项目：检查解释器版本
+    python: bool,
+    /// 提交后创建虚拟环境并安装依赖
+    python_venv: bool,
+    /// 残留未解析的占位符时失败
+    strict: bool,
+    /// 写入的许可证及版权持有人
+    license: Option<(License, String)>,
+    /// 模板未提供 README 时生成
+    readme: bool,
+    /// 按 OpenAPI 规范生成的 API 桩代码
+    openapi: Option<OpenApiScaffold>,
+    /// 按数据库表结构生成的 CRUD 代码
+    crud: Option<CrudScaffold>,
+    /// 单个模板钩子的超时时间
+    hook_timeout: Option<Duration>,
+}
+
+/// 生成执行结果
+struct GenerationOutcome {
+    report: RenderReport,
+    merge_summary: Option<MergeSummary>,
+    stats: GenerationStats,
+    warnings: Vec<String>,
+    /// 执行的模板钩子
+    hooks: Vec<HookRun>,
+    /// 执行的操作 (created, merged, unchanged)
+    action: &'static str,
+}
+
+impl GenerationOutcome {
+    /// 输出已与锁文件清单一致，未写入任何文件
+    fn unchanged(existing: &ProjectLock) -> Self {
+        let report = RenderReport {
+            files: existing.files.keys().map(PathBuf::from).collect(),
+            ..Default::default()
+        };
+        let stats = GenerationStats::from_report(&report, Default::default());
+        Self {
+            report,
+            merge_summary: None,
+            stats,
+            warnings: Vec::new(),
+            hooks: Vec::new(),
+            action: "unchanged",
+        }
+    }
+
+    /// 生成的文件列表，`prefix` 为项目目录名
+    fn file_list(&self, prefix: Option<&str>) -> Vec<String> {
+        self.report
+            .files
+            .iter()
+            .map(|path| {
+                let path = path.to_string_lossy().replace('\\', "/");
+                match prefix {
+                    Some(prefix) => format!("{}/{}", prefix, path),
+                    None => path,
+                }
+            })
+            .collect()
+    }
+
+    /// 合并模式下的文件变更摘要
+    fn merge_message(&self) -> String {
+        match &self.merge_summary {
+            Some(summary) => format!(
+                "，新增 {} 个文件，覆盖 {} 个文件，{} 个文件未变化",
+                summary.created.len(),
+                summary.overwritten.len(),
+                summary.unchanged.len()
+            ),
+            None => String::new(),
+        }
+    }
+}
+
+/// 渲染模板及所选特性到指定目录
+pub(crate) fn render_to_dir(
+    template_dir: &Path,
+    metadata: &TemplateMetadata,
+    output_dir: &Path,
+    variables: &HashMap<String, String>,
+    render_options: &RenderOptions,
+    selected_features: &[String],
+) -> Result<RenderReport> {
+    let mut report = pipeline::render_template_dir(
+        template_dir,
+        output_dir,
+        metadata,
+        variables,
+        render_options,
+    )?;
+
+    if !selected_features.is_empty() {
+        report.merge(features::apply_features(
+            template_dir,
+            output_dir,
+            metadata,
+            selected_features,
+            variables,
+            render_options,
+        )?);
+    }
+
+    Ok(report)
+}
+
+/// 执行生成计划：渲染到暂存目录、应用特性，最后提交到输出目录
+fn execute_plan(plan: &GenerationPlan, timer: &mut PhaseTimer) -> Result<GenerationOutcome> {
+    let started = Instant::now();
+
+    let mut warnings = timer.time("environment", || -> Result<Vec<String>> {
+        if let Some(requirement) = &plan.template.metadata.required_java_version {
+            java_cli::ensure_java_version(requirement)
+                .map_err(|e| GeneratorError::JavaEnvironment(e.to_string()))?;
+        }
+
+        Ok(if plan.template.metadata.engines.is_empty() {
+            Vec::new()
+        } else {
+            node_env::check_engines(
+                &plan.template.metadata.engines,
+                &node_env::detect_node_environment(),
+            )
+        })
+    })?;
+
+    // 没有 SDK 与 JDK 也可以生成项目，构建前再安装即可
+    let android_sdk = if plan.android {
+        timer.time("android-sdk", || {
+            if let Err(e) = java_cli::ensure_java_version(android_env::REQUIRED_JAVA_VERSION) {
+                warnings.push(e.to_string());
+            }
+            match android_env::detect_android_sdk() {
+                Ok(sdk) => Some(sdk),
+                Err(e) => {
+                    warnings.push(e.to_string());
+                    None
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // 同样只提示，uv 与 poetry 可以自行下载解释器
+    if plan.python {
+        let required = plan
+            .variables
+            .get(python_env::PYTHON_VERSION_VARIABLE)
+            .map(String::as_str);
+        warnings.extend(timer.time("python", || python_env::interpreter_warning(required)));
+    }
+
+    // 生成到暂存目录，任何一步失败都会回滚暂存目录（或按 keep_partial 保留并在错误中注明位置）
+    let staged = StagedOutput::begin(&plan.output_dir, plan.commit_mode, plan.keep_partial)?;
+    let report = stage_output(
+        plan,
+        staged.path(),
+        android_sdk.as_ref(),
+        timer,
+        &mut warnings,
+    );
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => return Err(staged.fail(e)),
+    };
+
+    let merge_summary = timer.time("commit", || staged.commit())?;
+    // 虚拟环境在钩子之前就绪，钩子可以直接使用 .venv
+    let mut hooks = if plan.python_venv {
+        timer.time("python-venv", || {
+            python_env::bootstrap(&plan.output_dir, plan.hook_timeout)
+        })?
+    } else {
+        Vec::new()
+    };
+    hooks.extend(timer.time("hooks", || run_template_hooks(plan))?);
+    let stats = GenerationStats::from_report(&report, started.elapsed());
+
+    Ok(GenerationOutcome {
+        report,
+        action: match plan.commit_mode {
+            CommitMode::Create => "created",
+            CommitMode::Merge { .. } => "merged",
+        },
+        merge_summary,
+        stats,
+        warnings,
+        hooks,
+    })
+}
+
+/// 在暂存目录中渲染模板并写入各附加步骤的文件，返回渲染报告
+fn stage_output(
+    plan: &GenerationPlan,
+    staging: &Path,
+    android_sdk: Option<&android_env::AndroidSdk>,
+    timer: &mut PhaseTimer,
+    warnings: &mut Vec<String>,
+) -> Result<RenderReport> {
+    let mut report = timer.time("render", || {
+        render_to_dir(
+            &plan.template.dir,
+            &plan.template.metadata,
+            staging,
+            &plan.variables,
+            &plan.render_options,
+            &plan.features,
+        )
+    })?;
+
+    // 残留的占位符多半是变量名拼写错误，严格模式下失败并回滚暂存目录
+    if !report.unresolved.is_empty() {
+        let locations: Vec<String> = report.unresolved.iter().map(ToString::to_string).collect();
+        if plan.strict {
+            return Err(GeneratorError::TemplateProcessing(format!(
+                "Unresolved placeholders: {}",
+                locations.join(", ")
+            )));
+        }
+        warnings.extend(
+            locations
+                .into_iter()
+                .map(|location| format!("Unresolved placeholder {}", location)),
+        );
+    }
+
+    if plan.lock.is_some() {
+        timer.time("lockfile", || {
+            lockfile::write_base_snapshot(staging, &report.files)
+        })?;
+    }
+
+    // wrapper 不属于模板内容，不进入快照，后续更新不会改动它们
+    if plan.build_wrapper {
+        match timer.time("build-wrapper", || build_wrapper::bootstrap(staging)) {
+            Ok(wrapper_files) => report.files.extend(wrapper_files),
+            Err(e) if plan.wrapper_optional => {
+                warnings.push(format!("Build wrapper was not generated: {}", e))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    // local.properties 指向本机 SDK，同样不进入快照
+    if let Some(sdk) = android_sdk {
+        let file = android_env::write_local_properties(staging, sdk)?;
+        if !report.files.contains(&file) {
+            report.files.push(file);
+        }
+    }
+
+    // API 桩代码与 CRUD 代码不属于模板内容，同样不进入快照
+    if let Some(openapi) = &plan.openapi {
+        let files = timer.time("openapi", || openapi.scaffold(staging, &plan.variables))?;
+        for path in files {
+            if !report.files.contains(&path) {
+                report.files.push(path);
+            }
+        }
+    }
+
+    if let Some(crud) = &plan.crud {
+        let files = timer.time("crud", || crud.scaffold(staging, &plan.variables))?;
+        for path in files {
+            if !report.files.contains(&path) {
+                report.files.push(path);
+            }
+        }
+    }
+
+    // 许可证同样在快照之后写入，后续更新会把它当作用户的改动保留
+    if let Some((license, holder)) = &plan.license {
+        let changed = timer.time("license", || {
+            license::apply_license(staging, *license, holder, chrono::Local::now().year())
+        })?;
+        for path in changed {
+            if !report.files.contains(&path) {
+                report.files.push(path);
+            }
+        }
+    }
+
+    // 合并到已有项目时不替换其原有的 README
+    if plan.readme && !plan.output_dir.join(readme::README_FILE).exists() {
+        let variable = |name: &str| plan.variables.get(name).map(String::as_str);
+        let summary = ProjectSummary {
+            name: variable("name").unwrap_or_default(),
+            project_type: variable("project_type").unwrap_or_default(),
+            description: variable("description"),
+            license: plan.license.as_ref().map(|(license, _)| *license),
+        };
+        if let Some(path) = timer.time("readme", || readme::ensure_readme(staging, &summary))? {
+            report.files.push(path);
+        }
+    }
+
+    // 锁文件最后写入，文件清单覆盖所有步骤生成的文件
+    if let Some(lock) = &plan.lock {
+        timer.time("manifest", || -> Result<()> {
+            let mut lock = lock.clone();
+            lock.files = lockfile::file_manifest(staging, &report.files)?;
+            lock.write(staging)
+        })?;
+    }
+
+    Ok(report)
+}
+
+/// 用最终的变量渲染模板的下一步说明，模板没有提供时返回 None
+fn render_next_steps(
+    template: &ResolvedTemplate,
+    variables: &HashMap<String, String>,
+) -> Result<Option<String>> {
+    let path = template.dir.join(NEXT_STEPS_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    let rendered =
+        TemplateRenderer::load(&template.dir, &template.metadata)?.render(&content, variables)?;
+    Ok(Some(rendered.trim().to_string()).filter(|text| !text.is_empty()))
+}
+
+/// 在输出目录中依次执行模板钩子，某个钩子失败时停止
+///
+/// 钩子在提交后执行，能看到合并后的完整项目；失败时已生成的文件保留，便于排查后手动重新执行。
+fn run_template_hooks(plan: &GenerationPlan) -> Result<Vec<HookRun>> {
+    let mut runs = Vec::new();
+    for hook in &plan.template.metadata.hooks {
+        let started = Instant::now();
+        let mut command = hook.command(&plan.output_dir);
+        command.env(hooks::TEMPLATE_DIR_ENV, &plan.template.dir);
+        let output = hook.output(&mut command, plan.hook_timeout);
+        let exit_code = output.as_ref().ok().and_then(|output| output.status.code());
+        let result = output.and_then(|output| hook.check(&output));
+        runs.push(HookRun {
+            command: hook.run.clone(),
+            exit_code,
+            duration_ms: millis(started.elapsed()),
+            error: result.as_ref().err().map(ToString::to_string),
+        });
+        result?;
+    }
+    Ok(runs)
+}
+
+/// 列出模板声明的可选特性
+pub fn list_template_features(
+    project_type: &str,
+    template: &str,
+) -> Result<Vec<TemplateFeatureInfo>> {
+    let config = TemplateRegistryConfig::user()?;
+    let metadata = resolve_template(&config, project_type, template)?.metadata;
+
+    Ok(features::available_features(&metadata))
+}
+
+/// 解析模板目录：支持直接传入模板路径，否则依次在本地注册表、远程注册表（按优先级）中查找，
+/// 最后尝试 Git 简写（如 `gh:org/repo#main`）
+///
+/// `template` 可带版本要求（如 `basic@^2.0`），本地注册表解析为满足要求的最高版本，
+/// 远程注册表与直接传入的路径只提供一个版本，不满足要求时视为未找到。
+pub(crate) fn resolve_template_dir(
+    config: &TemplateRegistryConfig,
+    project_type: &str,
+    template: &str,
+) -> Result<PathBuf> {
+    let spec = TemplateSpec::parse(template)?;
+
+    let direct = Path::new(&spec.name);
+    if pipeline::metadata_file(direct).is_some() {
+        return check_version(direct.to_path_buf(), &spec, template);
+    }
+
+    if let Some(dir) = config.resolve_local_template(project_type, &spec) {
+        return Ok(dir);
+    }
+    if let Some(dir) = config.find_remote_template(project_type, &spec.name)? {
+        return check_version(dir, &spec, template);
+    }
+    // 本地没有同名模板时，`user/repo#branch` 这类简写直接拉取 Git 仓库
+    if spec.requirement.is_none()
+        && let Some(dir) = config.fetch_shorthand(template)?
+    {
+        return Ok(dir);
+    }
+    Err(GeneratorError::TemplateNotFound(format!("{}:{}", project_type, template)))
+}
+
+/// 只有一个版本的模板必须满足 `spec` 的版本要求
+fn check_version(dir: PathBuf, spec: &TemplateSpec, template: &str) -> Result<PathBuf> {
+    if spec.requirement.is_none() {
+        return Ok(dir);
+    }
+    let metadata = pipeline::load_template_metadata(&dir)?;
+    if template_version::parse_version(&metadata.version).is_some_and(|v| spec.matches(&v)) {
+        Ok(dir)
+    } else {
+        Err(GeneratorError::TemplateNotFound(format!(
+            "{} is version {}, which does not satisfy {}",
+            spec.name, metadata.version, template
+        )))
+    }
+}
+
+/// 解析模板目录并展开其继承链
+pub(crate) fn resolve_template(
+    config: &TemplateRegistryConfig,
+    project_type: &str,
+    template: &str,
+) -> Result<ResolvedTemplate> {
+    config.resolve_layers(&resolve_template_dir(config, project_type, template)?)
+}
+
+/// 解析模板来源并核对固定的修订，锁文件中记录的即是解析出的完整修订
+///
+/// 冻结模式下未给出 `template_revision` 时取输出目录中已有锁文件的修订，都没有时视为浮动引用而拒绝。
+fn pinned_source(
+    template_dir: &Path,
+    options: &GenerateOptions,
+    output_dir: &Path,
+) -> Result<LockSource> {
+    let source = LockSource::from_local(template_dir);
+    let frozen = options.frozen.unwrap_or(false);
+    let pin = match &options.template_revision {
+        Some(revision) => Some(revision.clone()),
+        None if frozen => ProjectLock::read(output_dir)
+            .ok()
+            .and_then(|lock| lock.source.revision),
+        None => None,
+    };
+
+    match pin {
+        Some(pin) if source.matches_pin(&pin) => Ok(source),
+        Some(pin) => Err(GeneratorError::Configuration(format!(
+            "Template {} is at revision {}, not the pinned {}",
+            source.location,
+            source.revision.as_deref().unwrap_or("(none)"),
+            pin.trim()
+        ))),
+        None if frozen => Err(GeneratorError::Configuration(format!(
+            "Frozen mode requires a pinned template revision for {}; pass template_revision \
+             or regenerate a project whose lock file records one",
+            source.location
+        ))),
+        None => Ok(source),
+    }
+}
+
+/// 生成选项中的超时设置，未设置的阶段使用注册表配置
+pub fn resolve_timeouts(
+    options: &GenerateOptions,
+    config: &TemplateRegistryConfig,
+) -> PhaseTimeouts {
+    options
+        .timeouts
+        .clone()
+        .unwrap_or_default()
+        .or(config.timeouts.clone())
+}
+
+/// 由生成选项构造渲染选项
+pub(crate) fn build_render_options(options: &GenerateOptions) -> Result<RenderOptions> {
+    Ok(RenderOptions {
+        symlinks: options.symlink_policy.unwrap_or_default(),
+        workers: options.workers.map(|workers| workers.max(1) as usize),
+        line_endings: options.line_endings.unwrap_or_default(),
+        bom: options.bom.unwrap_or_default(),
     })
 }
 
+/// 许可证版权持有人：显式指定优先，其次是 `author` 变量，最后是项目名称
+fn license_holder(options: &GenerateOptions, variables: &HashMap<String, String>) -> String {
+    options
+        .license_holder
+        .clone()
+        .or_else(|| variables.get("author").cloned())
+        .filter(|holder| !holder.trim().is_empty())
+        .unwrap_or_else(|| options.name.clone())
+}
+
+/// 合并用户变量、内置变量（name、project_type）与环境默认变量，显式提供的值优先
+pub(crate) fn build_variables(options: &GenerateOptions) -> Result<HashMap<String, String>> {
+    let mut variables = options.variables.clone().unwrap_or_default();
+    variables
+        .entry("name".to_string())
+        .or_insert_with(|| options.name.clone());
+    variables
+        .entry("project_type".to_string())
+        .or_insert_with(|| options.project_type.clone());
+    for (name, value) in env_defaults()? {
+        variables.entry(name).or_insert(value);
+    }
+    Ok(variables)
+}
+
+/// 传给模板的构建工具变量
+pub const BUILD_TOOL_VARIABLE: &str = "buildTool";
+
+/// 选择 Java 构建工具并写入 `buildTool` 变量：`build_tool` 选项优先于变量，未指定时取模板 `build_tools` 的第一个
+///
+/// 模板声明了 `build_tools` 时，所选工具必须在其中。
+pub(crate) fn select_build_tool(
+    options: &GenerateOptions,
+    metadata: &TemplateMetadata,
+    variables: &mut HashMap<String, String>,
+) -> Result<Option<BuildTool>> {
+    let requested = options
+        .build_tool
+        .map(|tool| tool.name().to_string())
+        .or_else(|| variables.get(BUILD_TOOL_VARIABLE).cloned())
+        .filter(|tool| !tool.trim().is_empty())
+        .or_else(|| metadata.build_tools.first().cloned());
+    let Some(requested) = requested else {
+        return Ok(None);
+    };
+    let tool: BuildTool = requested.parse()?;
+    if !metadata.build_tools.is_empty()
+        && !metadata
+            .build_tools
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(tool.name()))
+    {
+        return Err(GeneratorError::Configuration(format!(
+            "Template '{}' does not support build tool '{}' (supported: {})",
+            metadata.name,
+            tool.name(),
+            metadata.build_tools.join(", ")
+        )));
+    }
+    variables.insert(BUILD_TOOL_VARIABLE.to_string(), tool.name().to_string());
+    Ok(Some(tool))
+}
+
+/// 以模板声明的默认值为基础的变量，`name` 与 `project_type` 总是存在
+pub(crate) fn default_variables(
+    metadata: &TemplateMetadata,
+    name: &str,
+) -> HashMap<String, String> {
+    let mut variables: HashMap<String, String> = metadata
+        .variables
+        .iter()
+        .filter_map(|variable| {
+            variable
+                .default
+                .clone()
+                .map(|default| (variable.name.clone(), default))
+        })
+        .collect();
+    variables.insert("name".to_string(), name.to_string());
+    variables.insert("project_type".to_string(), metadata.project_type.clone());
+    variables
+}
+
 /// 根据项目类型列出可用模板 - 简化实现
 pub fn list_templates_by_type(project_type: &str) -> Result<Vec<String>> {
-    match project_type {
-        "vue" => Ok(vec!["nop".to_string()]),
-        "java" => Ok(vec!["nop".to_string()]),
-        _ => Err(GeneratorError::TemplateNotFound(format!(
+    // 本地注册表中的模板来自索引，只有变化的模板会被重新解析
+    let entries = TemplateIndex::from_config(&TemplateRegistryConfig::user()?)
+        .templates(Some(project_type))?;
+    let templates = with_builtin_templates(project_type, entries);
+
+    if templates.is_empty() {
+        return Err(GeneratorError::TemplateNotFound(format!(
             "Unsupported project type: {}",
             project_type
-        ))),
+        )));
     }
+    Ok(templates)
+}
+
+/// 只读取已保存的模板索引列出模板，不扫描注册表目录也不访问网络；未知的项目类型返回空列表
+pub fn list_templates_cached(project_type: &str) -> Result<Vec<String>> {
+    templates_in_index(
+        &TemplateIndex::from_config(&TemplateRegistryConfig::user()?),
+        project_type,
+    )
+}
+
+fn templates_in_index(index: &TemplateIndex, project_type: &str) -> Result<Vec<String>> {
+    let entries = index.cached(Some(project_type))?;
+    Ok(with_builtin_templates(project_type, entries))
+}
+
+/// 内置模板在前，其后是索引中的模板（同名只保留一个）
+pub(crate) fn with_builtin_templates(project_type: &str, entries: Vec<IndexEntry>) -> Vec<String> {
+    let builtin: &[&str] = match project_type {
+        "vue" | "java" => &["nop"],
+        "node-lib" => &["basic"],
+        "android" => &["compose"],
+        "python" => &["uv", "poetry"],
+        "monorepo" => &["pnpm"],
+        "rust" => &["bin", "lib", "workspace"],
+        _ => &[],
+    };
+    let mut templates: Vec<String> = builtin.iter().map(|name| name.to_string()).collect();
+    for entry in entries {
+        if !templates.contains(&entry.metadata.name) {
+            templates.push(entry.metadata.name);
+        }
+    }
+    templates
+}
+
+/// 从内置说明或已保存的模板索引读取模板信息，不扫描注册表目录；找不到时返回 None
+pub fn get_template_info_cached(project_type: &str, template: &str) -> Result<Option<String>> {
+    template_info_in_index(
+        &TemplateIndex::from_config(&TemplateRegistryConfig::user()?),
+        project_type,
+        template,
+    )
+}
+
+fn template_info_in_index(
+    index: &TemplateIndex,
+    project_type: &str,
+    template: &str,
+) -> Result<Option<String>> {
+    if let Ok(info) = get_template_info(project_type, template) {
+        return Ok(Some(info));
+    }
+    Ok(index
+        .cached(Some(project_type))?
+        .into_iter()
+        .find(|entry| entry.metadata.name == template)
+        .map(|entry| format!("模板信息: {} - {}", template, entry.metadata.description)))
 }
 
 /// 获取模板信息 - 简化实现
@@ -35,6 +1034,14 @@ pub fn get_template_info(project_type: &str, template: &str) -> Result<String> {
     let info = match (project_type, template) {
         ("vue", "nop") => "Vue3 NOP-SITE 基础模板",
         ("java", "nop") => "Java17 NOP 基础模板",
+        ("android", "compose") => "Android 应用（Kotlin + Jetpack Compose，Gradle Kotlin DSL）",
+        ("python", "uv") => "Python 项目（uv 管理依赖，src 布局 + pytest）",
+        ("python", "poetry") => "Python 项目（Poetry 管理依赖，src 布局 + pytest）",
+        ("node-lib", "basic") => "TypeScript 库模板 (tsup + vitest)",
+        ("monorepo", "pnpm") => "pnpm workspace 单仓多包（可选 Turborepo / Nx）",
+        ("rust", "bin") => "Rust 二进制 crate",
+        ("rust", "lib") => "Rust 库 crate（可选 napi-rs 绑定）",
+        ("rust", "workspace") => "Cargo workspace（core 库 + cli 二进制）",
         _ => {
             return Err(GeneratorError::TemplateNotFound(format!(
                 "Template not found: {}:{}",
@@ -46,18 +1053,742 @@ pub fn get_template_info(project_type: &str, template: &str) -> Result<String> {
     Ok(format!("模板信息: {} - {}", template, info))
 }
 
-/// 渲染模板文件 - 基础实现
-pub fn render_template(
-    template_content: &str,
-    variables: &HashMap<String, String>,
-) -> Result<String> {
-    let mut result = template_content.to_string();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::{Credential, FileCredentials};
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use crate::template_registry::{HttpAuth, TemplateRegistry, TemplateSource};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_generate_with_template_partials_and_helpers() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "java", "variables": [], "dependencies": [], "tags": [],
+                "ignore": ["partials/"],
+                "helpers": [{"name": "packagePath", "type": "replace", "pattern": "\\.", "replacement": "/"}]}"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(template_dir.path().join("partials")).unwrap();
+        std::fs::write(
+            template_dir.path().join("partials/header.hbs"),
+            "// {{pascalCase name}}",
+        )
+        .unwrap();
+        std::fs::write(
+            template_dir.path().join("{{packagePath package}}.java"),
+            "{{> header}}\npackage {{package}};",
+        )
+        .unwrap();
+
+        generate_project_from_template(GenerateOptions {
+            name: "demo-app".to_string(),
+            project_type: "java".to_string(),
+            template: Some(template_dir.path().to_string_lossy().to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            variables: Some(HashMap::from([(
+                "package".to_string(),
+                "com.example".to_string(),
+            )])),
+            ..Default::default()
+        })
+        .unwrap();
 
-    // 简单的变量替换
-    for (key, value) in variables {
-        let placeholder = format!("{{{{{}}}}}", key);
-        result = result.replace(&placeholder, value);
+        let project_dir = output_dir.path().join("demo-app");
+        assert_eq!(
+            std::fs::read_to_string(project_dir.join("com/example.java")).unwrap(),
+            "// DemoApp\npackage com.example;"
+        );
+        assert!(!project_dir.join("partials").exists());
     }
 
-    Ok(result)
+    #[test]
+    fn test_generate_renders_next_steps() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "node-lib", "variables": [], "dependencies": [], "tags": [],
+                "ignore": ["next_steps.md.hbs"]}"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.path().join("index.ts"), "").unwrap();
+        std::fs::write(
+            template_dir.path().join(NEXT_STEPS_FILE),
+            "## Next steps\n\n    cd {{name}}\n    pnpm install\n",
+        )
+        .unwrap();
+
+        let result = generate_project_from_template(GenerateOptions {
+            name: "demo-lib".to_string(),
+            project_type: "node-lib".to_string(),
+            template: Some(template_dir.path().to_string_lossy().to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let message = result.message.unwrap();
+        assert!(message.ends_with("## Next steps\n\n    cd demo-lib\n    pnpm install"));
+        assert!(
+            !output_dir
+                .path()
+                .join("demo-lib")
+                .join(NEXT_STEPS_FILE)
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_failed_generation_reports_kept_partial_output() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "node-lib", "variables": [], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.path().join("index.ts"), "// {{titel}}").unwrap();
+        let options = |keep_partial| GenerateOptions {
+            name: "demo-lib".to_string(),
+            project_type: "node-lib".to_string(),
+            template: Some(template_dir.path().to_string_lossy().to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            strict: Some(true),
+            keep_partial: Some(keep_partial),
+            ..Default::default()
+        };
+        let project_dir = output_dir.path().join("demo-lib");
+
+        let error = generate_project_from_template(options(false)).unwrap_err();
+        assert!(matches!(error, GeneratorError::TemplateProcessing(_)));
+        assert!(!project_dir.exists());
+
+        let error = generate_project_from_template(options(true)).unwrap_err();
+        assert_eq!(error.code(), "TEMPLATE_PROCESSING");
+        match error {
+            GeneratorError::PartialOutput { path, source } => {
+                assert_eq!(path, project_dir);
+                assert!(matches!(*source, GeneratorError::TemplateProcessing(_)));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(project_dir.join("index.ts").exists());
+    }
+
+    #[test]
+    fn test_generate_component_into_existing_project() {
+        let project_dir = tempdir().unwrap();
+        std::fs::write(project_dir.path().join("package.json"), "{}").unwrap();
+
+        let result = generate_component(ComponentOptions {
+            project_type: "vue".to_string(),
+            kind: "component".to_string(),
+            name: "UserCard".to_string(),
+            project_path: Some(project_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(result.files, vec!["src/components/UserCard.vue"]);
+        assert!(project_dir.path().join("package.json").exists());
+        assert!(
+            std::fs::read_to_string(project_dir.path().join("src/components/UserCard.vue"))
+                .unwrap()
+                .contains("name: 'UserCard'")
+        );
+        assert!(
+            list_components("vue")
+                .unwrap()
+                .contains(&"component".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generate_resolves_template_version_requirement() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "demo", "version": "2.1.0", "description": "", "author": "",
+                "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.path().join("README.md"), "# {{name}}").unwrap();
+
+        let options = |requirement: &str| GenerateOptions {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: Some(format!("{}@{}", template_dir.path().display(), requirement)),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            generate_project_from_template(options("^1.0")),
+            Err(GeneratorError::TemplateNotFound(_))
+        ));
+
+        let result = generate_project_from_template(options("^2.0")).unwrap();
+        assert_eq!(result.template_version.as_deref(), Some("2.1.0"));
+    }
+
+    #[test]
+    fn test_generate_with_registry_config() {
+        let registry = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let template_dir = registry.path().join("vue/team");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "team", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.join("README.md"), "# {{name}}").unwrap();
+        let config = TemplateRegistryConfig {
+            registries: vec![TemplateRegistry {
+                name: "team".to_string(),
+                source: TemplateSource::Local {
+                    path: registry.path().to_path_buf(),
+                },
+                enabled: true,
+                priority: 0,
+            }],
+            cache_dir: registry.path().join(".cache"),
+            ..Default::default()
+        };
+        let options = GenerateOptions {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: Some("team".to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        // 默认配置中没有该注册表
+        assert!(matches!(
+            generate_with_report(options.clone(), &TemplateRegistryConfig::default()),
+            Err(GeneratorError::TemplateNotFound(_))
+        ));
+        generate_project_with_config(options, &config).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output_dir.path().join("app/README.md")).unwrap(),
+            "# app"
+        );
+    }
+
+    #[test]
+    fn test_cached_listing_reads_only_the_saved_index() {
+        let registry = tempdir().unwrap();
+        let write_template = |name: &str| {
+            let dir = registry.path().join("vue").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join(TEMPLATE_METADATA_FILE),
+                format!(
+                    r#"{{"name": "{}", "version": "1.0.0", "description": "{} template",
+                        "author": "", "project_type": "vue", "variables": [],
+                        "dependencies": [], "tags": []}}"#,
+                    name, name
+                ),
+            )
+            .unwrap();
+        };
+        write_template("team");
+        let config = TemplateRegistryConfig {
+            registries: vec![TemplateRegistry {
+                name: "team".to_string(),
+                source: TemplateSource::Local {
+                    path: registry.path().to_path_buf(),
+                },
+                enabled: true,
+                priority: 0,
+            }],
+            cache_dir: registry.path().join(".cache"),
+            ..Default::default()
+        };
+        let index = TemplateIndex::from_config(&config);
+
+        // 没有索引时先构建，之后只读取已保存的索引
+        assert_eq!(templates_in_index(&index, "vue").unwrap(), ["nop", "team"]);
+        write_template("mobile");
+        assert_eq!(templates_in_index(&index, "vue").unwrap(), ["nop", "team"]);
+        assert_eq!(
+            template_info_in_index(&index, "vue", "team").unwrap(),
+            Some("模板信息: team - team template".to_string())
+        );
+        assert_eq!(
+            template_info_in_index(&index, "vue", "mobile").unwrap(),
+            None
+        );
+        assert!(
+            template_info_in_index(&index, "vue", "nop")
+                .unwrap()
+                .is_some()
+        );
+        assert!(templates_in_index(&index, "unknown").unwrap().is_empty());
+
+        index.refresh(false).unwrap();
+        assert_eq!(
+            templates_in_index(&index, "vue").unwrap(),
+            ["nop", "mobile", "team"]
+        );
+    }
+
+    #[test]
+    fn test_generate_applies_registry_override_layers() {
+        let org = tempdir().unwrap();
+        let team = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let write = |dir: &Path, files: &[(&str, &str)]| {
+            for (path, content) in files {
+                let path = dir.join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, content).unwrap();
+            }
+        };
+        write(
+            &org.path().join("vue/basic"),
+            &[
+                (
+                    TEMPLATE_METADATA_FILE,
+                    r#"{"name": "basic", "version": "1.0.0", "description": "", "author": "",
+                        "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+                ),
+                ("README.md", "# {{name}}"),
+                ("ci.yml", "upstream"),
+            ],
+        );
+        write(
+            &org.path().join("overrides/vue/basic"),
+            &[("ci.yml", "org"), ("LICENSE", "org {{name}}")],
+        );
+        write(
+            &team.path().join("overrides/vue/basic"),
+            &[("ci.yml", "team")],
+        );
+        let local = |name: &str, path: &Path, priority| TemplateRegistry {
+            name: name.to_string(),
+            source: TemplateSource::Local {
+                path: path.to_path_buf(),
+            },
+            enabled: true,
+            priority,
+        };
+        let config = TemplateRegistryConfig {
+            registries: vec![local("team", team.path(), 0), local("org", org.path(), 10)],
+            cache_dir: output_dir.path().join(".cache"),
+            ..Default::default()
+        };
+
+        let (_, report) = generate_with_report(
+            GenerateOptions {
+                name: "app".to_string(),
+                project_type: "vue".to_string(),
+                template: Some("basic".to_string()),
+                output_path: Some(output_dir.path().join("out").to_string_lossy().to_string()),
+                ..Default::default()
+            },
+            &config,
+        )
+        .unwrap();
+
+        let read = |path: &str| {
+            std::fs::read_to_string(output_dir.path().join("out/app").join(path)).unwrap()
+        };
+        assert_eq!(read("ci.yml"), "team");
+        assert_eq!(read("LICENSE"), "org app");
+        assert_eq!(read("README.md"), "# app");
+        assert_eq!(
+            report.unwrap().provenance,
+            std::collections::BTreeMap::from([
+                ("LICENSE".to_string(), "override:org".to_string()),
+                ("ci.yml".to_string(), "override:team".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_generate_from_git_registry() {
+        let upstream = tempdir().unwrap();
+        let cache = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let template_dir = upstream.path().join("vue/remote");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "remote", "version": "1.2.0", "description": "", "author": "",
+                "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.join("README.md"), "# {{name}}").unwrap();
+        let repo = git2::Repository::init(upstream.path()).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+
+        let config = TemplateRegistryConfig {
+            registries: vec![TemplateRegistry {
+                name: "remote".to_string(),
+                source: TemplateSource::Git {
+                    url: upstream.path().to_string_lossy().to_string(),
+                    branch: None,
+                    subfolder: None,
+                    auth: None,
+                    commit: None,
+                },
+                enabled: true,
+                priority: 0,
+            }],
+            cache_dir: cache.path().to_path_buf(),
+            ..Default::default()
+        };
+        let options = |template: &str| GenerateOptions {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: Some(template.to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            generate_project_with_config(options("remote@^2.0"), &config),
+            Err(GeneratorError::TemplateNotFound(_))
+        ));
+        let result = generate_project_with_config(options("remote"), &config).unwrap();
+        assert_eq!(result.template_version.as_deref(), Some("1.2.0"));
+        assert_eq!(
+            std::fs::read_to_string(output_dir.path().join("app/README.md")).unwrap(),
+            "# app"
+        );
+    }
+
+    #[test]
+    fn test_generate_from_http_registry_with_credential() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (path, content) in [
+            (
+                "private-main/template.json",
+                r#"{"name": "private", "version": "1.0.0", "description": "", "author": "",
+                    "project_type": "vue", "variables": [], "dependencies": [], "tags": []}"#,
+            ),
+            ("private-main/README.md", "# {{name}}"),
+        ] {
+            writer
+                .start_file(path, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        let archive = writer.finish().unwrap().into_inner();
+        // 只有带凭据文件中令牌的请求才返回归档
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/private.zip", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let (mut line, mut authorized) = (String::new(), false);
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    authorized |= line.trim() == "Authorization: Bearer s3cret";
+                    line.clear();
+                }
+                let (status, body) = match authorized {
+                    true => ("200 OK", archive.as_slice()),
+                    false => ("401 Unauthorized", &b""[..]),
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(body).unwrap();
+            }
+        });
+
+        let home = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let credentials_file = home.path().join("credentials.json");
+        FileCredentials {
+            path: credentials_file.clone(),
+        }
+        .store(
+            "generator-test-private-registry",
+            &Credential {
+                username: None,
+                secret: "s3cret".to_string(),
+            },
+        )
+        .unwrap();
+        let registry = |credential: &str| TemplateRegistry {
+            name: "private".to_string(),
+            source: TemplateSource::Http {
+                url: url.clone(),
+                checksum: None,
+                auth: Some(HttpAuth {
+                    bearer_token: None,
+                    basic_auth: None,
+                    credential: Some(credential.to_string()),
+                }),
+            },
+            enabled: true,
+            priority: 0,
+        };
+        let config = |credential: &str| TemplateRegistryConfig {
+            registries: vec![registry(credential)],
+            cache_dir: home.path().join("cache"),
+            credentials_file: Some(credentials_file.clone()),
+            ..Default::default()
+        };
+        let options = GenerateOptions {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: Some("private".to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        assert!(
+            generate_project_with_config(
+                options.clone(),
+                &config("generator-test-missing-credential")
+            )
+            .is_err()
+        );
+        generate_project_with_config(options, &config("generator-test-private-registry")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output_dir.path().join("app/README.md")).unwrap(),
+            "# app"
+        );
+    }
+
+    #[test]
+    fn test_generate_node_lib_with_rollup_feature() {
+        let output_dir = tempdir().unwrap();
+
+        let result = generate_project_from_template(GenerateOptions {
+            name: "my-lib".to_string(),
+            project_type: "node-lib".to_string(),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            features: Some(vec!["rollup".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let project_dir = output_dir.path().join("my-lib");
+        let package: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(project_dir.join("package.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(package["name"], "my-lib");
+        assert_eq!(package["scripts"]["build"], "rollup -c");
+        assert!(project_dir.join("rollup.config.mjs").exists());
+        assert!(project_dir.join("tsup.config.ts").exists());
+        assert!(result.files.contains(&"my-lib/src/index.ts".to_string()));
+    }
+
+    #[test]
+    fn test_generate_twice_with_same_options_is_unchanged() {
+        let output_dir = tempdir().unwrap();
+        let options = GenerateOptions {
+            name: "my-lib".to_string(),
+            project_type: "node-lib".to_string(),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+
+        let first = generate_project_from_template(options.clone()).unwrap();
+        assert_eq!(first.action.as_deref(), Some("created"));
+        let lock = ProjectLock::read(&output_dir.path().join("my-lib")).unwrap();
+        assert!(lock.files.contains_key("package.json"));
+
+        let second = generate_project_from_template(options.clone()).unwrap();
+        assert_eq!(second.action.as_deref(), Some("unchanged"));
+        assert_eq!(second.files.len(), lock.files.len());
+
+        // 修改过的输出不会被当作未变化，仍按目录已存在报错
+        std::fs::write(output_dir.path().join("my-lib/package.json"), "{}").unwrap();
+        assert!(generate_project_from_template(options).is_err());
+    }
+
+    #[test]
+    fn test_frozen_generation_requires_pinned_revision() {
+        let write_template = |dir: &Path| {
+            std::fs::write(
+                dir.join(TEMPLATE_METADATA_FILE),
+                r#"{"name": "pinned", "version": "1.0.0", "description": "", "author": "",
+                    "project_type": "node-lib", "variables": [], "dependencies": [], "tags": []}"#,
+            )
+            .unwrap();
+            std::fs::write(dir.join("index.ts"), "export const name = '{{name}}';").unwrap();
+        };
+        // 不在 git 仓库中的模板没有可固定的修订
+        let unpinned = tempdir().unwrap();
+        write_template(unpinned.path());
+        let pinned = tempdir().unwrap();
+        write_template(pinned.path());
+        let repo = git2::Repository::init(pinned.path()).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let revision = repo
+            .commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap()
+            .to_string();
+
+        let output_dir = tempdir().unwrap();
+        let options = |template: &Path| GenerateOptions {
+            name: "pinned-lib".to_string(),
+            project_type: "node-lib".to_string(),
+            template: Some(template.to_string_lossy().to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            frozen: Some(true),
+            ..Default::default()
+        };
+        let project_dir = output_dir.path().join("pinned-lib");
+
+        for template in [unpinned.path(), pinned.path()] {
+            // 既没有固定修订也没有锁文件时视为浮动引用
+            assert!(matches!(
+                generate_project_from_template(options(template)),
+                Err(GeneratorError::Configuration(_))
+            ));
+            assert!(matches!(
+                generate_project_from_template(GenerateOptions {
+                    template_revision: Some("0000000".to_string()),
+                    ..options(template)
+                }),
+                Err(GeneratorError::Configuration(_))
+            ));
+            assert!(!project_dir.exists());
+        }
+
+        generate_project_from_template(GenerateOptions {
+            frozen: None,
+            ..options(unpinned.path())
+        })
+        .unwrap();
+        let lock = ProjectLock::read(&project_dir).unwrap();
+        assert_eq!(lock.source.revision, None);
+        // 锁文件中没有修订，冻结模式仍然拒绝
+        assert!(matches!(
+            generate_project_from_template(options(unpinned.path())),
+            Err(GeneratorError::Configuration(_))
+        ));
+        std::fs::remove_dir_all(&project_dir).unwrap();
+
+        generate_project_from_template(GenerateOptions {
+            frozen: None,
+            ..options(pinned.path())
+        })
+        .unwrap();
+        let lock = ProjectLock::read(&project_dir).unwrap();
+        assert_eq!(lock.source.revision.as_deref(), Some(revision.as_str()));
+
+        // 冻结模式取锁文件中的修订，短前缀同样可以固定
+        let frozen = generate_project_from_template(options(pinned.path())).unwrap();
+        assert_eq!(frozen.action.as_deref(), Some("unchanged"));
+        let by_prefix = generate_project_from_template(GenerateOptions {
+            frozen: None,
+            template_revision: Some(revision[..7].to_string()),
+            ..options(pinned.path())
+        })
+        .unwrap();
+        assert_eq!(by_prefix.action.as_deref(), Some("unchanged"));
+    }
+
+    #[test]
+    fn test_generate_rust_lib_with_napi_feature() {
+        let output_dir = tempdir().unwrap();
+
+        generate_project_from_template(GenerateOptions {
+            name: "demo-crate".to_string(),
+            project_type: "rust".to_string(),
+            template: Some("lib".to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            features: Some(vec!["napi".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let project_dir = output_dir.path().join("demo-crate");
+        let manifest = std::fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"demo-crate\""));
+        assert!(manifest.contains("crate-type = [\"cdylib\", \"rlib\"]"));
+        assert!(manifest.contains("napi-build = \"2\""));
+        assert!(
+            std::fs::read_to_string(project_dir.join("src/lib.rs"))
+                .unwrap()
+                .contains("mod bindings;")
+        );
+        assert!(project_dir.join("build.rs").exists());
+    }
+
+    #[test]
+    fn test_build_tool_selects_files_and_respects_template_support() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        std::fs::write(
+            template_dir.path().join(TEMPLATE_METADATA_FILE),
+            r#"{"name": "service", "version": "1.0.0", "description": "", "author": "",
+                "project_type": "java", "variables": [], "dependencies": [], "tags": [],
+                "build_tools": ["gradle", "maven"],
+                "files": [
+                    {"path": "pom.xml", "when": "buildTool == 'maven'"},
+                    {"path": "build.gradle.kts", "when": "buildTool == 'gradle'"}
+                ]}"#,
+        )
+        .unwrap();
+        std::fs::write(template_dir.path().join("pom.xml"), "<project/>").unwrap();
+        std::fs::write(template_dir.path().join("build.gradle.kts"), "plugins {}").unwrap();
+        let options = |name: &str, build_tool: Option<BuildTool>| GenerateOptions {
+            name: name.to_string(),
+            project_type: "java".to_string(),
+            template: Some(template_dir.path().to_string_lossy().to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            build_tool,
+            ..Default::default()
+        };
+
+        // 未指定时取模板声明的第一个
+        generate_project_from_template(options("default", None)).unwrap();
+        assert!(output_dir.path().join("default/build.gradle.kts").exists());
+        assert!(!output_dir.path().join("default/pom.xml").exists());
+
+        generate_project_from_template(options("maven", Some(BuildTool::Maven))).unwrap();
+        assert!(output_dir.path().join("maven/pom.xml").exists());
+        assert!(!output_dir.path().join("maven/build.gradle.kts").exists());
+
+        let mut metadata = pipeline::load_template_metadata(template_dir.path()).unwrap();
+        metadata.build_tools = vec!["maven".to_string()];
+        let mut variables = HashMap::new();
+        assert!(
+            select_build_tool(
+                &options("gradle", Some(BuildTool::Gradle)),
+                &metadata,
+                &mut variables
+            )
+            .is_err()
+        );
+        // `buildTool` 变量中的未知构建工具同样拒绝
+        let mut variables = HashMap::from([(BUILD_TOOL_VARIABLE.to_string(), "ant".to_string())]);
+        assert!(select_build_tool(&options("ant", None), &metadata, &mut variables).is_err());
+    }
 }
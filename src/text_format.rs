@@ -0,0 +1,252 @@
+//! 文本格式规范化模块
+//! 统一渲染输出的换行符，并确保文本文件以 UTF-8 写出（按需处理 BOM）
+
+use crate::template_metadata::TextAttributeRule;
+use crate::{GeneratorError, Result};
+use globset::{GlobBuilder, GlobMatcher};
+use napi_derive::napi;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// UTF-8 BOM
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// 换行符策略
+#[napi(string_enum)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// 统一为 `\n`
+    #[napi(value = "lf")]
+    Lf,
+    /// 统一为 `\r\n`
+    #[napi(value = "crlf")]
+    Crlf,
+    /// 保持模板中的换行符（默认）
+    #[default]
+    #[napi(value = "keep")]
+    Keep,
+}
+
+impl std::str::FromStr for LineEnding {
+    type Err = GeneratorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::Crlf),
+            "keep" => Ok(LineEnding::Keep),
+            other => Err(GeneratorError::Configuration(format!(
+                "Unknown line ending '{}', expected lf, crlf or keep",
+                other
+            ))),
+        }
+    }
+}
+
+/// UTF-8 BOM 策略
+#[napi(string_enum)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BomPolicy {
+    /// 模板文件带 BOM 时保留（默认）
+    #[default]
+    #[napi(value = "keep")]
+    Keep,
+    /// 去掉 BOM
+    #[napi(value = "strip")]
+    Strip,
+    /// 总是写入 BOM
+    #[napi(value = "add")]
+    Add,
+}
+
+impl std::str::FromStr for BomPolicy {
+    type Err = GeneratorError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "keep" => Ok(BomPolicy::Keep),
+            "strip" => Ok(BomPolicy::Strip),
+            "add" => Ok(BomPolicy::Add),
+            other => Err(GeneratorError::Configuration(format!(
+                "Unknown BOM policy '{}', expected keep, strip or add",
+                other
+            ))),
+        }
+    }
+}
+
+/// 以 BOM 开头的 UTF-16 内容，虽含空字节但仍按文本处理
+pub fn is_utf16_text(head: &[u8]) -> bool {
+    head.starts_with(UTF16_LE_BOM) || head.starts_with(UTF16_BE_BOM)
+}
+
+/// 解码文本内容：去掉 UTF-8 BOM，UTF-16 转为 UTF-8
+///
+/// 返回内容及原文件是否带 BOM；无法解码时原样返回字节
+pub fn decode_text(bytes: Vec<u8>) -> std::result::Result<(String, bool), Vec<u8>> {
+    if let Some(body) = bytes.strip_prefix(UTF8_BOM) {
+        return String::from_utf8(body.to_vec())
+            .map(|content| (content, true))
+            .map_err(|_| bytes);
+    }
+
+    let utf16 = if bytes.starts_with(UTF16_LE_BOM) {
+        Some(u16::from_le_bytes as fn([u8; 2]) -> u16)
+    } else if bytes.starts_with(UTF16_BE_BOM) {
+        Some(u16::from_be_bytes as fn([u8; 2]) -> u16)
+    } else {
+        None
+    };
+    match utf16 {
+        Some(decode) if bytes.len().is_multiple_of(2) => {
+            let units = bytes[2..]
+                .chunks_exact(2)
+                .map(|pair| decode([pair[0], pair[1]]));
+            char::decode_utf16(units)
+                .collect::<std::result::Result<String, _>>()
+                .map(|content| (content, true))
+                .map_err(|_| bytes)
+        }
+        Some(_) => Err(bytes),
+        None => String::from_utf8(bytes)
+            .map(|content| (content, false))
+            .map_err(|e| e.into_bytes()),
+    }
+}
+
+/// 按生成选项与模板声明的 glob 规则规范化文本输出
+pub struct TextFormatter {
+    line_ending: LineEnding,
+    bom: BomPolicy,
+    /// 规则匹配器，后声明的规则优先
+    rules: Vec<(GlobMatcher, bool, TextAttributeRule)>,
+}
+
+impl TextFormatter {
+    pub fn new(
+        rules: &[TextAttributeRule],
+        line_ending: LineEnding,
+        bom: BomPolicy,
+    ) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let matcher = GlobBuilder::new(&rule.path)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| {
+                        GeneratorError::Configuration(format!(
+                            "Invalid text attribute pattern '{}': {}",
+                            rule.path, e
+                        ))
+                    })?
+                    .compile_matcher();
+                // 与 .gitattributes 相同，不含 `/` 的模式匹配任意目录下的文件名
+                let basename = !rule.path.contains('/');
+                Ok((matcher, basename, rule.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            line_ending,
+            bom,
+            rules,
+        })
+    }
+
+    /// 输出文件（相对输出目录）生效的换行符与 BOM 策略
+    fn attributes(&self, relative: &Path) -> (LineEnding, BomPolicy) {
+        let mut line_ending = self.line_ending;
+        let mut bom = self.bom;
+        for (matcher, basename, rule) in &self.rules {
+            let matched = match relative.file_name() {
+                Some(name) if *basename => matcher.is_match(name),
+                _ => matcher.is_match(relative),
+            };
+            if matched {
+                line_ending = rule.eol.unwrap_or(line_ending);
+                bom = rule.bom.unwrap_or(bom);
+            }
+        }
+        (line_ending, bom)
+    }
+
+    /// 生成写入文件的字节，`had_bom` 表示模板文件原本带 BOM
+    pub fn format(&self, relative: &Path, content: &str, had_bom: bool) -> Vec<u8> {
+        let (line_ending, bom) = self.attributes(relative);
+        let content = match line_ending {
+            LineEnding::Keep => content.to_string(),
+            LineEnding::Lf => content.replace("\r\n", "\n"),
+            LineEnding::Crlf => content.replace("\r\n", "\n").replace('\n', "\r\n"),
+        };
+
+        let with_bom = match bom {
+            BomPolicy::Keep => had_bom,
+            BomPolicy::Strip => false,
+            BomPolicy::Add => true,
+        };
+        let mut bytes = Vec::with_capacity(content.len() + UTF8_BOM.len());
+        if with_bom {
+            bytes.extend_from_slice(UTF8_BOM);
+        }
+        bytes.extend_from_slice(content.as_bytes());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(path: &str, eol: Option<LineEnding>, bom: Option<BomPolicy>) -> TextAttributeRule {
+        TextAttributeRule {
+            path: path.to_string(),
+            eol,
+            bom,
+        }
+    }
+
+    #[test]
+    fn test_text_formatter_rules() {
+        let formatter = TextFormatter::new(
+            &[
+                rule("*.bat", Some(LineEnding::Crlf), None),
+                rule(
+                    "scripts/*.ps1",
+                    Some(LineEnding::Crlf),
+                    Some(BomPolicy::Add),
+                ),
+                rule("*.sh", Some(LineEnding::Keep), None),
+            ],
+            LineEnding::Lf,
+            BomPolicy::Strip,
+        )
+        .unwrap();
+
+        let format = |path: &str, content: &str, had_bom: bool| {
+            String::from_utf8(formatter.format(Path::new(path), content, had_bom)).unwrap()
+        };
+        assert_eq!(format("src/main.ts", "a\r\nb\n", true), "a\nb\n");
+        assert_eq!(format("bin/run.bat", "a\r\nb\n", false), "a\r\nb\r\n");
+        assert_eq!(format("scripts/setup.ps1", "a\n", false), "\u{feff}a\r\n");
+        assert_eq!(format("setup.ps1", "a\n", false), "a\n");
+        assert_eq!(format("run.sh", "a\r\nb\n", false), "a\r\nb\n");
+
+        assert_eq!(
+            decode_text(b"\xEF\xBB\xBFhi".to_vec()),
+            Ok(("hi".to_string(), true))
+        );
+        assert_eq!(
+            decode_text(vec![0xFF, 0xFE, b'h', 0, b'i', 0]),
+            Ok(("hi".to_string(), true))
+        );
+        assert_eq!(decode_text(b"hi".to_vec()), Ok(("hi".to_string(), false)));
+        assert!(decode_text(vec![0xC3, 0x28]).is_err());
+        assert!("tabs".parse::<LineEnding>().is_err());
+    }
+}
@@ -0,0 +1,84 @@
+//! 项目构建工具识别模块
+//! 按生成结果中的构建文件（package.json、pom.xml、build.gradle、Cargo.toml）识别构建工具，供 README、CI 等生成器共用
+
+use std::fs;
+use std::path::Path;
+
+/// 未指定 `javaVersion` 变量时使用的 JDK 版本
+pub const DEFAULT_JAVA_VERSION: &str = "17";
+/// 未指定 `nodeVersion` 变量时使用的 Node.js 版本
+pub const DEFAULT_NODE_VERSION: &str = "20";
+
+/// 项目的构建工具
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Toolchain {
+    /// npm、pnpm 或 yarn
+    Node {
+        manager: &'static str,
+    },
+    /// 命令为 `mvn` 或 `./mvnw`
+    Maven {
+        command: &'static str,
+    },
+    /// 命令为 `gradle` 或 `./gradlew`
+    Gradle {
+        command: &'static str,
+    },
+    Cargo,
+}
+
+impl Toolchain {
+    /// 按项目目录中的构建文件识别，没有构建文件时按项目类型推断
+    pub fn detect(project_dir: &Path, project_type: &str) -> Option<Self> {
+        let exists = |name: &str| project_dir.join(name).exists();
+
+        if exists("package.json") {
+            let manager = if exists("pnpm-workspace.yaml") || exists("pnpm-lock.yaml") {
+                "pnpm"
+            } else if exists("yarn.lock") {
+                "yarn"
+            } else {
+                "npm"
+            };
+            return Some(Toolchain::Node { manager });
+        }
+        if exists("pom.xml") {
+            let command = if exists("mvnw") { "./mvnw" } else { "mvn" };
+            return Some(Toolchain::Maven { command });
+        }
+        if exists("build.gradle") || exists("build.gradle.kts") {
+            let command = if exists("gradlew") {
+                "./gradlew"
+            } else {
+                "gradle"
+            };
+            return Some(Toolchain::Gradle { command });
+        }
+        if exists("Cargo.toml") {
+            return Some(Toolchain::Cargo);
+        }
+
+        match project_type {
+            "java" => Some(Toolchain::Maven { command: "mvn" }),
+            "android" => Some(Toolchain::Gradle { command: "gradle" }),
+            "rust" => Some(Toolchain::Cargo),
+            "vue" | "react" | "node-lib" => Some(Toolchain::Node { manager: "npm" }),
+            "monorepo" => Some(Toolchain::Node { manager: "pnpm" }),
+            _ => None,
+        }
+    }
+}
+
+/// package.json 中声明了的脚本（按 `candidates` 顺序），没有 package.json 或 `scripts` 时返回 None
+pub fn node_scripts<'a>(project_dir: &Path, candidates: &[&'a str]) -> Option<Vec<&'a str>> {
+    let content = fs::read_to_string(project_dir.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let scripts = package.get("scripts")?;
+    Some(
+        candidates
+            .iter()
+            .copied()
+            .filter(|script| scripts.get(*script).is_some())
+            .collect(),
+    )
+}
@@ -0,0 +1,361 @@
+//! 事务性输出模块
+//! 先生成到临时暂存目录，成功后整体移动（或合并）到最终位置，失败时自动清理
+
+use crate::utils::{copy_dir_recursive, copy_file, copy_symlink};
+use crate::{GeneratorError, Result, vcs};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 提交方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitMode {
+    /// 创建新目录，目标必须不存在或为空
+    Create,
+    /// 合并到已有目录；`force` 为真时覆盖内容不同的文件
+    Merge { force: bool },
+}
+
+/// 合并结果
+#[derive(Debug, Clone, Default)]
+pub struct MergeSummary {
+    /// 新建的文件
+    pub created: Vec<PathBuf>,
+    /// 被覆盖的文件
+    pub overwritten: Vec<PathBuf>,
+    /// 内容相同而跳过的文件
+    pub unchanged: Vec<PathBuf>,
+}
+
+/// 暂存输出目录
+///
+/// 生成失败时调用 [`StagedOutput::fail`]：默认删除暂存目录，`keep_partial` 为真时
+/// 保留已生成的部分以便排查（新建模式移动到目标位置，合并模式保留暂存目录），
+/// 并在返回的错误中注明保留位置。未提交就被丢弃时同样按失败处理。
+pub struct StagedOutput {
+    staging: PathBuf,
+    target: PathBuf,
+    mode: CommitMode,
+    keep_partial: bool,
+    committed: bool,
+}
+
+impl StagedOutput {
+    /// 在目标目录旁创建暂存目录
+    pub fn begin(target: &Path, mode: CommitMode, keep_partial: bool) -> Result<Self> {
+        if mode == CommitMode::Create && target.exists() && !is_empty_dir(target)? {
+            return Err(GeneratorError::FileOperation(format!(
+                "Output directory already exists and is not empty: {}",
+                target.display()
+            )));
+        }
+
+        // 合并模式下目标可能是当前目录（"."），先规范化以便在其父目录中创建暂存目录
+        let target = match mode {
+            CommitMode::Create => target.to_path_buf(),
+            CommitMode::Merge { .. } => {
+                fs::create_dir_all(target)?;
+                target.canonicalize()?
+            }
+        };
+
+        let parent = match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        fs::create_dir_all(&parent)?;
+
+        let name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string());
+        let staging = parent.join(format!(".{}.generating-{}", name, std::process::id()));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+
+        Ok(Self {
+            staging,
+            target,
+            mode,
+            keep_partial,
+            committed: false,
+        })
+    }
+
+    /// 暂存目录路径，所有文件都应写入这里
+    pub fn path(&self) -> &Path {
+        &self.staging
+    }
+
+    /// 最终输出目录
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    /// 将暂存目录移动或合并到最终位置，合并模式返回合并结果
+    pub fn commit(mut self) -> Result<Option<MergeSummary>> {
+        let summary = match self.mode {
+            CommitMode::Create => move_dir(&self.staging, &self.target).map(|()| None),
+            CommitMode::Merge { force } => {
+                merge_dir(&self.staging, &self.target, force).and_then(|summary| {
+                    fs::remove_dir_all(&self.staging)?;
+                    Ok(Some(summary))
+                })
+            }
+        };
+        match summary {
+            Ok(summary) => {
+                self.committed = true;
+                Ok(summary)
+            }
+            Err(e) => Err(self.fail(e)),
+        }
+    }
+
+    /// 生成失败：回滚暂存目录，保留了部分输出时返回注明保留位置的
+    /// [`GeneratorError::PartialOutput`]，否则原样返回错误
+    pub fn fail(mut self, error: GeneratorError) -> GeneratorError {
+        match self.discard() {
+            Some(path) => GeneratorError::PartialOutput {
+                path,
+                source: Box::new(error),
+            },
+            None => error,
+        }
+    }
+
+    /// 丢弃暂存目录，返回保留的部分输出位置
+    fn discard(&mut self) -> Option<PathBuf> {
+        if self.committed || !self.staging.exists() {
+            return None;
+        }
+        self.committed = true;
+
+        if self.keep_partial {
+            let kept = match self.mode {
+                CommitMode::Create => move_dir(&self.staging, &self.target)
+                    .is_ok()
+                    .then(|| self.target.clone()),
+                CommitMode::Merge { .. } => Some(self.staging.clone()),
+            };
+            if kept.is_some() {
+                return kept;
+            }
+        }
+
+        let _ = fs::remove_dir_all(&self.staging);
+        None
+    }
+}
+
+impl Drop for StagedOutput {
+    fn drop(&mut self) {
+        self.discard();
+    }
+}
+
+/// 移动目录；跨文件系统无法重命名时退化为复制后删除
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        // 只允许覆盖空目录，begin 中已校验
+        fs::remove_dir(to)?;
+    }
+
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    copy_dir_recursive(from, to).map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+    fs::remove_dir_all(from)?;
+    Ok(())
+}
+
+/// 将暂存目录合并到已有目录
+///
+/// 先检查全部冲突再写入：未开启 `force` 时有任何内容不同的文件即失败；
+/// 开启 `force` 时仍拒绝覆盖 git 中存在未提交修改的已跟踪文件。
+fn merge_dir(staging: &Path, target: &Path, force: bool) -> Result<MergeSummary> {
+    let mut summary = MergeSummary::default();
+
+    for entry in WalkDir::new(staging).min_depth(1).sort_by_file_name() {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(staging)
+            .map_err(|e| GeneratorError::FileOperation(e.to_string()))?
+            .to_path_buf();
+        let dest = target.join(&relative);
+
+        if fs::symlink_metadata(&dest).is_err() {
+            summary.created.push(relative);
+        } else if same_content(entry.path(), &dest) {
+            summary.unchanged.push(relative);
+        } else {
+            summary.overwritten.push(relative);
+        }
+    }
+
+    if !summary.overwritten.is_empty() {
+        if !force {
+            return Err(GeneratorError::Conflict(format!(
+                "{} existing file(s) would be overwritten, use force to overwrite: {}",
+                summary.overwritten.len(),
+                display_paths(&summary.overwritten)
+            )));
+        }
+
+        let dirty = vcs::dirty_tracked_files(target, &summary.overwritten)?;
+        if !dirty.is_empty() {
+            return Err(GeneratorError::Conflict(format!(
+                "Refusing to overwrite tracked files with uncommitted changes: {}",
+                display_paths(&dirty)
+            )));
+        }
+    }
+
+    for relative in summary.created.iter().chain(&summary.overwritten) {
+        let src = staging.join(relative);
+        let dest = target.join(relative);
+        if fs::symlink_metadata(&dest).is_ok() {
+            fs::remove_file(&dest)?;
+        }
+
+        let copied = if fs::symlink_metadata(&src)?.file_type().is_symlink() {
+            copy_symlink(&src, &dest)
+        } else {
+            copy_file(&src, &dest)
+        };
+        copied.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+    }
+
+    Ok(summary)
+}
+
+fn same_content(a: &Path, b: &Path) -> bool {
+    match (fs::read(a), fs::read(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn is_empty_dir(path: &Path) -> Result<bool> {
+    Ok(path.is_dir() && fs::read_dir(path)?.next().is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_commit_moves_staging_to_target() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("app");
+
+        let staged = StagedOutput::begin(&target, CommitMode::Create, false).unwrap();
+        fs::write(staged.path().join("README.md"), "hello").unwrap();
+        let staging = staged.path().to_path_buf();
+        staged.commit().unwrap();
+
+        assert!(target.join("README.md").exists());
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn test_drop_without_commit_rolls_back() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("app");
+
+        let staged = StagedOutput::begin(&target, CommitMode::Create, false).unwrap();
+        fs::write(staged.path().join("README.md"), "hello").unwrap();
+        let staging = staged.path().to_path_buf();
+        drop(staged);
+
+        assert!(!target.exists());
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn test_keep_partial_moves_output_on_failure() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("app");
+
+        let staged = StagedOutput::begin(&target, CommitMode::Create, true).unwrap();
+        fs::write(staged.path().join("README.md"), "hello").unwrap();
+        let error = staged.fail(GeneratorError::Cancelled);
+
+        assert!(target.join("README.md").exists());
+        assert!(error.is_cancelled());
+        assert_eq!(error.code(), "CANCELLED");
+        match error {
+            GeneratorError::PartialOutput { path, .. } => assert_eq!(path, target),
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_fail_without_keep_partial_returns_error_unchanged() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("app");
+
+        let staged = StagedOutput::begin(&target, CommitMode::Create, false).unwrap();
+        fs::write(staged.path().join("README.md"), "hello").unwrap();
+        let staging = staged.path().to_path_buf();
+        let error = staged.fail(GeneratorError::Cancelled);
+
+        assert!(matches!(error, GeneratorError::Cancelled));
+        assert!(!target.exists());
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn test_merge_into_existing_detects_conflicts() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("app");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("README.md"), "original").unwrap();
+        fs::write(target.join("same.txt"), "same").unwrap();
+
+        let staged =
+            StagedOutput::begin(&target, CommitMode::Merge { force: false }, false).unwrap();
+        fs::write(staged.path().join("README.md"), "generated").unwrap();
+        fs::write(staged.path().join("same.txt"), "same").unwrap();
+        fs::write(staged.path().join("Dockerfile"), "FROM node").unwrap();
+        let result = staged.commit();
+
+        assert!(matches!(result, Err(GeneratorError::Conflict(_))));
+        assert_eq!(
+            fs::read_to_string(target.join("README.md")).unwrap(),
+            "original"
+        );
+        assert!(!target.join("Dockerfile").exists());
+
+        let staged =
+            StagedOutput::begin(&target, CommitMode::Merge { force: true }, false).unwrap();
+        fs::write(staged.path().join("README.md"), "generated").unwrap();
+        fs::write(staged.path().join("same.txt"), "same").unwrap();
+        fs::write(staged.path().join("Dockerfile"), "FROM node").unwrap();
+        let summary = staged.commit().unwrap().unwrap();
+
+        assert_eq!(summary.created, vec![PathBuf::from("Dockerfile")]);
+        assert_eq!(summary.overwritten, vec![PathBuf::from("README.md")]);
+        assert_eq!(summary.unchanged, vec![PathBuf::from("same.txt")]);
+        assert_eq!(
+            fs::read_to_string(target.join("README.md")).unwrap(),
+            "generated"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! 项目更新模块
+//! 依据 `.generator.lock` 重新渲染模板，与项目现状和原始快照做三方合并
+
+use crate::changelog;
+use crate::lockfile::{LockSource, ProjectLock, base_snapshot_dir, content_hash};
+use crate::pipeline::RenderOptions;
+use crate::template_registry::TemplateRegistryConfig;
+use crate::templates;
+use crate::variables::is_sensitive;
+use crate::{GeneratorError, Result, UpdateOptions, UpdateResult};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 单个文件的合并决定
+#[derive(Debug, PartialEq)]
+enum MergeAction {
+    /// 保持项目中的现状
+    Keep,
+    /// 写入新内容
+    Write(Vec<u8>),
+    /// 删除文件（模板已移除且用户未修改）
+    Delete,
+    /// 双方都有修改且无法自动合并
+    Conflict,
+}
+
+/// 按锁文件重新渲染模板并三方合并到项目中
+pub fn update_project(options: UpdateOptions) -> Result<UpdateResult> {
+    let project_dir = PathBuf::from(&options.project_path);
+    let mut lock = ProjectLock::read(&project_dir)?;
+    if let Some(overrides) = options.variables {
+        lock.variables.extend(overrides);
+    }
+
+    let config = TemplateRegistryConfig::user()?;
+    let template = templates::resolve_template(&config, &lock.project_type, &lock.template)?;
+    let metadata = &template.metadata;
+    let variables: HashMap<String, String> = lock.variables.clone().into_iter().collect();
+
+    let rendered = tempfile::tempdir()?;
+    let report = templates::render_to_dir(
+        &template.dir,
+        metadata,
+        rendered.path(),
+        &variables,
+        &RenderOptions::default(),
+        &lock.features,
+    )?;
+
+    let base_dir = base_snapshot_dir(&project_dir);
+    let mut paths: BTreeSet<PathBuf> = list_files(&base_dir)?.into_iter().collect();
+    paths.extend(report.files.iter().cloned());
+
+    let dry_run = options.dry_run.unwrap_or(false);
+    let mut result = UpdateResult {
+        success: true,
+        updated: vec![],
+        created: vec![],
+        deleted: vec![],
+        conflicts: vec![],
+        template_version: metadata.version.clone(),
+        changelog: changelog::changes_between(
+            &template.source_dir,
+            &lock.template_version,
+            &metadata.version,
+        ),
+        message: None,
+    };
+
+    for relative in &paths {
+        let base = read_optional(&base_dir.join(relative))?;
+        let ours = read_optional(&project_dir.join(relative))?;
+        let theirs = read_optional(&rendered.path().join(relative))?;
+        let display = relative.to_string_lossy().replace('\\', "/");
+
+        let action = decide(base.as_deref(), ours.as_deref(), theirs.as_deref());
+        match &action {
+            MergeAction::Keep => {}
+            MergeAction::Write(_) if ours.is_none() => result.created.push(display.clone()),
+            MergeAction::Write(_) => result.updated.push(display.clone()),
+            MergeAction::Delete => result.deleted.push(display.clone()),
+            MergeAction::Conflict => {
+                result.conflicts.push(display);
+                continue;
+            }
+        }
+
+        if dry_run {
+            continue;
+        }
+
+        let target = project_dir.join(relative);
+        match action {
+            MergeAction::Write(content) => {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&target, content)?;
+            }
+            MergeAction::Delete => fs::remove_file(&target)?,
+            _ => {}
+        }
+
+        // 未冲突的文件以新模板渲染结果作为下一次合并的基线，文件清单同样记录新的渲染结果
+        let snapshot = base_dir.join(relative);
+        match &theirs {
+            Some(content) => lock.files.insert(display, content_hash(content)),
+            None => lock.files.remove(&display),
+        };
+        match theirs {
+            Some(content) => {
+                if let Some(parent) = snapshot.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&snapshot, content)?;
+            }
+            None if snapshot.exists() => fs::remove_file(&snapshot)?,
+            None => {}
+        }
+    }
+
+    if !dry_run {
+        // 通过覆盖值传入的敏感变量同样不写回锁文件
+        lock.variables
+            .retain(|name, _| !is_sensitive(metadata, name));
+        lock.template_version = metadata.version.clone();
+        lock.source = LockSource::from_local(&template.source_dir);
+        lock.stamp().write(&project_dir)?;
+    }
+
+    result.success = result.conflicts.is_empty();
+    result.message = Some(if result.success {
+        format!(
+            "更新完成：修改 {} 个文件，新增 {} 个文件，删除 {} 个文件",
+            result.updated.len(),
+            result.created.len(),
+            result.deleted.len()
+        )
+    } else {
+        format!(
+            "更新存在 {} 个冲突文件，已保留项目中的版本: {}",
+            result.conflicts.len(),
+            result.conflicts.join(", ")
+        )
+    });
+
+    Ok(result)
+}
+
+/// 三方合并决策
+fn decide(base: Option<&[u8]>, ours: Option<&[u8]>, theirs: Option<&[u8]>) -> MergeAction {
+    if ours == theirs {
+        return MergeAction::Keep;
+    }
+    if ours == base {
+        return match theirs {
+            Some(content) => MergeAction::Write(content.to_vec()),
+            None => MergeAction::Delete,
+        };
+    }
+    if theirs == base {
+        return MergeAction::Keep;
+    }
+
+    // 双方都修改过，尝试按行合并文本
+    let (Some(base), Some(ours), Some(theirs)) = (base, ours, theirs) else {
+        return MergeAction::Conflict;
+    };
+    let (Ok(base), Ok(ours), Ok(theirs)) = (
+        std::str::from_utf8(base),
+        std::str::from_utf8(ours),
+        std::str::from_utf8(theirs),
+    ) else {
+        return MergeAction::Conflict;
+    };
+
+    match diffy::merge(base, ours, theirs) {
+        Ok(merged) => MergeAction::Write(merged.into_bytes()),
+        Err(_) => MergeAction::Conflict,
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>> {
+    if path.is_file() {
+        Ok(Some(fs::read(path)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// 列出目录下所有文件的相对路径
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir).min_depth(1) {
+        let entry = entry.map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+        if entry.file_type().is_file() {
+            let relative = entry
+                .path()
+                .strip_prefix(dir)
+                .map_err(|e| GeneratorError::FileOperation(e.to_string()))?;
+            files.push(relative.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateOptions;
+    use crate::pipeline::TEMPLATE_METADATA_FILE;
+    use tempfile::tempdir;
+
+    fn write_template(dir: &Path, version: &str, readme: &str) {
+        fs::write(
+            dir.join(TEMPLATE_METADATA_FILE),
+            format!(
+                r#"{{"name": "demo", "version": "{}", "description": "", "author": "",
+                    "project_type": "vue", "variables": [], "dependencies": [], "tags": []}}"#,
+                version
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("README.md"), readme).unwrap();
+    }
+
+    #[test]
+    fn test_update_project_merges_template_changes() {
+        let template_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_template(
+            template_dir.path(),
+            "1.0.0",
+            "# {{name}}\n\nintro\n\nfooter\n",
+        );
+
+        templates::generate_project_from_template(GenerateOptions {
+            name: "app".to_string(),
+            project_type: "vue".to_string(),
+            template: Some(template_dir.path().to_string_lossy().to_string()),
+            output_path: Some(output_dir.path().to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let project_dir = output_dir.path().join("app");
+        fs::write(
+            project_dir.join("README.md"),
+            "# app\n\nmy intro\n\nfooter\n",
+        )
+        .unwrap();
+        write_template(
+            template_dir.path(),
+            "1.1.0",
+            "# {{name}}\n\nintro\n\nnew footer\n",
+        );
+        fs::write(
+            template_dir.path().join(changelog::CHANGELOG_FILE),
+            "## 1.1.0\n- New footer\n\n## 1.0.0\n- Initial\n",
+        )
+        .unwrap();
+
+        let result = update_project(UpdateOptions {
+            project_path: project_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.updated, vec!["README.md"]);
+        assert_eq!(result.changelog.len(), 1);
+        assert_eq!(result.changelog[0].notes, "- New footer");
+        assert_eq!(
+            fs::read_to_string(project_dir.join("README.md")).unwrap(),
+            "# app\n\nmy intro\n\nnew footer\n"
+        );
+        assert_eq!(
+            ProjectLock::read(&project_dir).unwrap().template_version,
+            "1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_decide_three_way() {
+        let base = b"a\nb\nc\n".as_slice();
+
+        // 用户未改动，直接采用新模板
+        assert_eq!(
+            decide(Some(base), Some(base), Some(b"a\nB\nc\n")),
+            MergeAction::Write(b"a\nB\nc\n".to_vec())
+        );
+        // 模板未改动，保留用户修改
+        assert_eq!(
+            decide(Some(base), Some(b"x\nb\nc\n"), Some(base)),
+            MergeAction::Keep
+        );
+        // 双方修改不同的行，自动合并
+        assert_eq!(
+            decide(Some(base), Some(b"x\nb\nc\n"), Some(b"a\nb\nz\n")),
+            MergeAction::Write(b"x\nb\nz\n".to_vec())
+        );
+        // 双方修改同一行，冲突
+        assert_eq!(
+            decide(Some(base), Some(b"a\nx\nc\n"), Some(b"a\ny\nc\n")),
+            MergeAction::Conflict
+        );
+        // 模板删除且用户未修改
+        assert_eq!(decide(Some(base), Some(base), None), MergeAction::Delete);
+    }
+}
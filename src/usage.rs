@@ -0,0 +1,204 @@
+//! 模板使用统计模块
+//! 默认关闭；开启后将每次生成的模板与版本追加到 `~/.generator/usage.log`，只有显式配置上报地址时才发送网络请求
+
+use crate::network::NetworkConfig;
+use crate::utils::generator_home;
+use crate::{GeneratorError, Result, TemplateUsage, UsageStats};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// 覆盖配置文件中开关的环境变量（`1` / `0`）
+pub const USAGE_ENV: &str = "GENERATOR_USAGE_LOG";
+/// 上报请求的超时，避免拖慢生成
+const REPORT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 使用统计设置（`~/.generator/usage.json`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageConfig {
+    /// 是否记录使用情况
+    #[serde(default)]
+    pub enabled: bool,
+    /// 上报地址，设置后每条记录以 JSON POST 发送
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+/// 一次生成记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageEvent {
+    pub project_type: String,
+    pub template: String,
+    pub version: String,
+    /// RFC 3339 时间
+    pub timestamp: String,
+}
+
+/// 使用统计的存储位置
+#[derive(Debug, Clone)]
+pub struct UsageLog {
+    pub dir: PathBuf,
+}
+
+impl Default for UsageLog {
+    fn default() -> Self {
+        Self {
+            dir: generator_home(),
+        }
+    }
+}
+
+impl UsageLog {
+    fn config_path(&self) -> PathBuf {
+        self.dir.join("usage.json")
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.dir.join("usage.log")
+    }
+
+    /// 读取设置，环境变量优先
+    pub fn config(&self) -> Result<UsageConfig> {
+        let path = self.config_path();
+        let mut config: UsageConfig = if path.is_file() {
+            serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+                GeneratorError::Configuration(format!("Invalid {}: {}", path.display(), e))
+            })?
+        } else {
+            UsageConfig::default()
+        };
+        if let Ok(value) = std::env::var(USAGE_ENV) {
+            config.enabled = matches!(value.as_str(), "1" | "true" | "on");
+        }
+        Ok(config)
+    }
+
+    /// 保存设置
+    pub fn set_config(&self, config: &UsageConfig) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.config_path(), serde_json::to_string_pretty(config)?)?;
+        Ok(())
+    }
+
+    /// 记录一次生成；未开启时不做任何事
+    pub fn record(&self, project_type: &str, template: &str, version: &str) -> Result<()> {
+        let config = self.config()?;
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let event = UsageEvent {
+            project_type: project_type.to_string(),
+            template: template.to_string(),
+            version: version.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        fs::create_dir_all(&self.dir)?;
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path())?;
+        writeln!(log, "{}", serde_json::to_string(&event)?)?;
+
+        if let Some(endpoint) = &config.endpoint {
+            report(endpoint, &event)?;
+        }
+        Ok(())
+    }
+
+    /// 按模板与版本汇总使用次数，按次数降序排列
+    pub fn stats(&self) -> Result<UsageStats> {
+        let path = self.log_path();
+        let content = if path.is_file() {
+            fs::read_to_string(&path)?
+        } else {
+            String::new()
+        };
+
+        let mut grouped: BTreeMap<(String, String, String), TemplateUsage> = BTreeMap::new();
+        let mut total = 0;
+        // 跳过损坏的行（如写入中断），不影响其余统计
+        for event in content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<UsageEvent>(line).ok())
+        {
+            total += 1;
+            let usage = grouped
+                .entry((
+                    event.project_type.clone(),
+                    event.template.clone(),
+                    event.version.clone(),
+                ))
+                .or_insert_with(|| TemplateUsage {
+                    project_type: event.project_type,
+                    template: event.template,
+                    version: event.version,
+                    count: 0,
+                    last_used: String::new(),
+                });
+            usage.count += 1;
+            usage.last_used = usage.last_used.clone().max(event.timestamp);
+        }
+
+        let mut templates: Vec<TemplateUsage> = grouped.into_values().collect();
+        templates.sort_by_key(|usage| std::cmp::Reverse(usage.count));
+        Ok(UsageStats {
+            enabled: self.config()?.enabled,
+            total,
+            templates,
+        })
+    }
+}
+
+/// 发送到上报地址
+fn report(endpoint: &str, event: &UsageEvent) -> Result<()> {
+    NetworkConfig::default()
+        .resolve()
+        .http_agent(endpoint, Some(REPORT_TIMEOUT))?
+        .post(endpoint)
+        .set("Content-Type", "application/json")
+        .send_string(&serde_json::to_string(event)?)
+        .map_err(|e| {
+            GeneratorError::ExternalCommand(format!(
+                "Failed to report usage to {}: {}",
+                endpoint, e
+            ))
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_usage_is_opt_in_and_aggregated() {
+        let dir = tempdir().unwrap();
+        let log = UsageLog {
+            dir: dir.path().to_path_buf(),
+        };
+
+        log.record("vue", "basic", "1.0.0").unwrap();
+        assert!(!log.log_path().exists());
+
+        log.set_config(&UsageConfig {
+            enabled: true,
+            endpoint: None,
+        })
+        .unwrap();
+        log.record("vue", "basic", "1.0.0").unwrap();
+        log.record("vue", "basic", "1.0.0").unwrap();
+        log.record("java", "spring", "2.0.0").unwrap();
+
+        let stats = log.stats().unwrap();
+        assert!(stats.enabled);
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.templates[0].template, "basic");
+        assert_eq!(stats.templates[0].count, 2);
+        assert_eq!(stats.templates[1].project_type, "java");
+    }
+}
@@ -3,7 +3,9 @@
 
 use std::path::{Path, PathBuf};
 use std::fs;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
 
 /// 跨平台路径规范化
 pub fn normalize_path(path: &str) -> PathBuf {
@@ -29,20 +31,106 @@ pub fn copy_file(src: &Path, dest: &Path) -> Result<()> {
         ensure_dir_exists(parent)?;
     }
     fs::copy(src, dest)?;
+    copy_permissions(src, dest)?;
     Ok(())
 }
 
+/// 复制文件权限（Unix 下保留可执行位等模式位）
+/// Windows 仅有只读标记，为避免生成只读文件不做处理
+pub fn copy_permissions(src: &Path, dest: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(src)?.permissions().mode();
+        fs::set_permissions(dest, fs::Permissions::from_mode(mode))?;
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (src, dest);
+    }
+
+    Ok(())
+}
+
+/// 符号链接处理策略
+#[napi(string_enum)]
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkPolicy {
+    /// 复制链接指向的内容（默认）
+    #[default]
+    #[napi(value = "follow")]
+    Follow,
+    /// 在目标位置重建链接
+    #[napi(value = "preserve")]
+    Preserve,
+    /// 跳过链接
+    #[napi(value = "skip")]
+    Skip,
+}
+
+impl std::str::FromStr for SymlinkPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "follow" => Ok(SymlinkPolicy::Follow),
+            "preserve" => Ok(SymlinkPolicy::Preserve),
+            "skip" => Ok(SymlinkPolicy::Skip),
+            other => Err(anyhow!(
+                "Unknown symlink policy '{}', expected follow, preserve or skip",
+                other
+            )),
+        }
+    }
+}
+
 /// 递归复制目录
 pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<()> {
+    copy_dir_recursive_with(src, dest, SymlinkPolicy::default())
+}
+
+/// 按指定的符号链接策略递归复制目录
+pub fn copy_dir_recursive_with(src: &Path, dest: &Path, policy: SymlinkPolicy) -> Result<()> {
+    let mut ancestors = vec![fs::canonicalize(src)?];
+    copy_dir_inner(src, dest, policy, &mut ancestors)
+}
+
+fn copy_dir_inner(
+    src: &Path,
+    dest: &Path,
+    policy: SymlinkPolicy,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<()> {
     ensure_dir_exists(dest)?;
     
     for entry in fs::read_dir(src)? {
         let entry = entry?;
         let src_path = entry.path();
         let dest_path = dest.join(entry.file_name());
-        
+
+        if entry.file_type()?.is_symlink() {
+            match policy {
+                SymlinkPolicy::Skip => continue,
+                SymlinkPolicy::Preserve => {
+                    copy_symlink(&src_path, &dest_path)?;
+                    continue;
+                }
+                SymlinkPolicy::Follow => {}
+            }
+        }
+
         if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dest_path)?;
+            // 跟随链接时检测目录环
+            let canonical = fs::canonicalize(&src_path)?;
+            if ancestors.contains(&canonical) {
+                return Err(anyhow!("Symlink cycle detected at {}", src_path.display()));
+            }
+            ancestors.push(canonical);
+            copy_dir_inner(&src_path, &dest_path, policy, ancestors)?;
+            ancestors.pop();
         } else {
             copy_file(&src_path, &dest_path)?;
         }
@@ -51,6 +139,36 @@ pub fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<()> {
     Ok(())
 }
 
+/// 在目标位置重建符号链接，保持原始链接目标不变
+pub fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
+    let target = fs::read_link(src)?;
+    if let Some(parent) = dest.parent() {
+        ensure_dir_exists(parent)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target, dest)?;
+
+    #[cfg(windows)]
+    {
+        // Windows 创建链接可能需要开发者模式，失败时退化为复制内容
+        let created = if src.is_dir() {
+            std::os::windows::fs::symlink_dir(&target, dest)
+        } else {
+            std::os::windows::fs::symlink_file(&target, dest)
+        };
+        if created.is_err() {
+            if src.is_dir() {
+                copy_dir_recursive(src, dest)?;
+            } else {
+                copy_file(src, dest)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// 替换文件中的变量
 pub fn replace_variables_in_file(file_path: &Path, variables: &std::collections::HashMap<String, String>) -> Result<()> {
     let content = fs::read_to_string(file_path)?;
@@ -65,6 +183,15 @@ pub fn replace_variables_in_file(file_path: &Path, variables: &std::collections:
     Ok(())
 }
 
+/// 用户级数据目录 `~/.generator`（缓存、凭据文件），取不到主目录时位于临时目录
+pub fn generator_home() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(".generator")
+}
+
 /// 获取当前可执行文件的目录
 pub fn get_exe_dir() -> Result<PathBuf> {
     let exe_path = std::env::current_exe()?;
@@ -97,4 +224,60 @@ mod tests {
         ensure_dir_exists(&test_dir).unwrap();
         assert!(test_dir.exists());
     }
-}
\ No newline at end of file
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_recursive_preserves_executable_bit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        let script = src_dir.path().join("gradlew");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        copy_dir_recursive(src_dir.path(), &dest_dir.path().join("out")).unwrap();
+
+        let mode = fs::metadata(dest_dir.path().join("out/gradlew"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_symlink_policies() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        fs::write(src_dir.path().join("target.txt"), "content").unwrap();
+        std::os::unix::fs::symlink("target.txt", src_dir.path().join("link.txt")).unwrap();
+
+        let preserved = dest_dir.path().join("preserve");
+        copy_dir_recursive_with(src_dir.path(), &preserved, SymlinkPolicy::Preserve).unwrap();
+        assert_eq!(
+            fs::read_link(preserved.join("link.txt")).unwrap(),
+            PathBuf::from("target.txt")
+        );
+
+        let skipped = dest_dir.path().join("skip");
+        copy_dir_recursive_with(src_dir.path(), &skipped, SymlinkPolicy::Skip).unwrap();
+        assert!(!skipped.join("link.txt").exists());
+
+        let followed = dest_dir.path().join("follow");
+        copy_dir_recursive_with(src_dir.path(), &followed, SymlinkPolicy::Follow).unwrap();
+        assert!(!fs::symlink_metadata(followed.join("link.txt")).unwrap().file_type().is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_detects_symlink_cycle() {
+        let src_dir = tempdir().unwrap();
+        let dest_dir = tempdir().unwrap();
+        fs::create_dir(src_dir.path().join("nested")).unwrap();
+        std::os::unix::fs::symlink("..", src_dir.path().join("nested/loop")).unwrap();
+
+        let result = copy_dir_recursive_with(src_dir.path(), &dest_dir.path().join("out"), SymlinkPolicy::Follow);
+        assert!(result.is_err());
+    }
+}
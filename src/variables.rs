@@ -0,0 +1,339 @@
+//! 变量校验模块
+//! 渲染前按 template.json 声明的类型转换并校验变量：补全默认值、规范布尔值、检查数字与可选值，一次返回全部问题
+
+use crate::computed::apply_computed;
+use crate::events;
+use crate::render::TemplateRenderer;
+use crate::template_helpers::TemplateHelpers;
+use crate::template_metadata::{TemplateEngine, TemplateMetadata, TemplateVariable, VariableType};
+use crate::{GeneratorError, Result, VariableViolation};
+use std::collections::{BTreeMap, HashMap};
+
+/// 按声明补全与规范化变量，存在问题时返回包含全部问题的 `InvalidVariables`
+///
+/// 未声明的变量原样保留；布尔值接受 `yes/no`、`on/off`、`1/0` 并统一为 `true/false`，
+/// 可选值不区分大小写并统一为声明中的写法。校验通过后再求值 template.json 中的派生变量。
+/// 缺少的必填变量先向事件监听者询问，见 [`events::prompt`]。
+pub fn resolve_variables(
+    metadata: &TemplateMetadata,
+    provided: &HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    let mut provided = provided.clone();
+    for variable in &metadata.variables {
+        if variable.required
+            && variable.default.is_none()
+            && !provided.contains_key(&variable.name)
+            && let Some(answer) = events::prompt(variable)?
+        {
+            provided.insert(variable.name.clone(), answer);
+        }
+    }
+    let mut variables = provided.clone();
+    let mut violations = Vec::new();
+
+    for variable in &metadata.variables {
+        let raw = match provided.get(&variable.name) {
+            Some(value) => Some(value.clone()),
+            None => default_value(metadata, variable, &variables)?,
+        };
+        let Some(raw) = raw else {
+            if variable.required {
+                violations.push(violation(
+                    &variable.name,
+                    "missing",
+                    format!("Required variable '{}' has no value", variable.name),
+                ));
+            }
+            continue;
+        };
+
+        match coerce(&variable.var_type, &raw) {
+            Ok(value) => {
+                variables.insert(variable.name.clone(), value.to_string_value());
+            }
+            Err((kind, expected)) => violations.push(violation(
+                &variable.name,
+                kind,
+                format!(
+                    "Variable '{}' must be {}, got '{}'",
+                    variable.name,
+                    expected,
+                    if variable.sensitive { REDACTED } else { &raw }
+                ),
+            )),
+        }
+    }
+
+    if !violations.is_empty() {
+        return Err(GeneratorError::InvalidVariables(violations));
+    }
+    apply_computed(metadata, &mut variables)?;
+    Ok(variables)
+}
+
+/// 变量的默认值；Tera 引擎下含表达式的默认值（如 `{{ project_name | lower }}`）按先声明的变量渲染
+fn default_value(
+    metadata: &TemplateMetadata,
+    variable: &TemplateVariable,
+    variables: &HashMap<String, String>,
+) -> Result<Option<String>> {
+    match &variable.default {
+        Some(default)
+            if metadata.engine == TemplateEngine::Tera
+                && (default.contains("{{") || default.contains("{%")) =>
+        {
+            TemplateRenderer::new(metadata, TemplateHelpers::default())
+                .render(default, variables)
+                .map(Some)
+        }
+        default => Ok(default.clone()),
+    }
+}
+
+/// 敏感变量回显时的替代文本
+pub const REDACTED: &str = "******";
+
+/// 是否为声明了 `sensitive` 的变量
+pub fn is_sensitive(metadata: &TemplateMetadata, name: &str) -> bool {
+    metadata
+        .variables
+        .iter()
+        .any(|variable| variable.sensitive && variable.name == name)
+}
+
+/// 敏感变量的值替换为 [`REDACTED`]，用于报告与回显
+pub fn redact_sensitive(
+    metadata: &TemplateMetadata,
+    variables: &HashMap<String, String>,
+) -> BTreeMap<String, String> {
+    variables
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive(metadata, name) {
+                REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// 去掉敏感变量，用于需要原样读回的锁文件与答案文件
+pub fn without_sensitive(
+    metadata: &TemplateMetadata,
+    variables: &HashMap<String, String>,
+) -> BTreeMap<String, String> {
+    variables
+        .iter()
+        .filter(|(name, _)| !is_sensitive(metadata, name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// 按类型转换后的变量值
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue {
+    String(String),
+    Boolean(bool),
+    Number(f64),
+}
+
+impl TypedValue {
+    /// 转为模板引擎使用的 JSON 值
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            TypedValue::String(value) => serde_json::Value::String(value.clone()),
+            TypedValue::Boolean(value) => serde_json::Value::Bool(*value),
+            TypedValue::Number(value) => serde_json::Number::from_f64(*value)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+        }
+    }
+
+    fn to_string_value(&self) -> String {
+        match self {
+            TypedValue::String(value) => value.clone(),
+            TypedValue::Boolean(value) => value.to_string(),
+            TypedValue::Number(value) => value.to_string(),
+        }
+    }
+}
+
+/// 将字符串转换为声明的类型，失败时返回问题类型与期望的描述
+pub fn coerce(
+    var_type: &VariableType,
+    raw: &str,
+) -> std::result::Result<TypedValue, (&'static str, String)> {
+    let trimmed = raw.trim();
+    match var_type {
+        VariableType::String => Ok(TypedValue::String(raw.to_string())),
+        VariableType::Boolean => match trimmed.to_lowercase().as_str() {
+            "true" | "yes" | "y" | "on" | "1" => Ok(TypedValue::Boolean(true)),
+            "false" | "no" | "n" | "off" | "0" => Ok(TypedValue::Boolean(false)),
+            _ => Err(("invalid-type", "a boolean (true/false)".to_string())),
+        },
+        VariableType::Number => trimmed
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(TypedValue::Number)
+            .ok_or(("invalid-type", "a number".to_string())),
+        VariableType::Choice { options } => options
+            .iter()
+            .find(|option| option.eq_ignore_ascii_case(trimmed))
+            .map(|option| TypedValue::String(option.clone()))
+            .ok_or(("invalid-choice", format!("one of [{}]", options.join(", ")))),
+    }
+}
+
+/// 模板变量的 JSON Schema，供表单按类型、默认值与选项渲染输入项
+///
+/// 有默认值的必填变量不列入 `required`；敏感变量标记为 `writeOnly`。
+pub fn variables_schema(metadata: &TemplateMetadata) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for variable in &metadata.variables {
+        let mut property = match &variable.var_type {
+            VariableType::String => serde_json::json!({ "type": "string" }),
+            VariableType::Boolean => serde_json::json!({ "type": "boolean" }),
+            VariableType::Number => serde_json::json!({ "type": "number" }),
+            VariableType::Choice { options } => {
+                serde_json::json!({ "type": "string", "enum": options })
+            }
+        };
+        if !variable.description.is_empty() {
+            property["description"] = variable.description.clone().into();
+        }
+        if let Some(default) = variable.default.as_deref() {
+            if let Ok(value) = coerce(&variable.var_type, default) {
+                property["default"] = value.to_json();
+            }
+        } else if variable.required {
+            required.push(variable.name.clone());
+        }
+        if variable.sensitive {
+            property["writeOnly"] = true.into();
+        }
+        properties.insert(variable.name.clone(), property);
+    }
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": metadata.name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn violation(variable: &str, kind: &str, message: String) -> VariableViolation {
+    VariableViolation {
+        variable: variable.to_string(),
+        kind: kind.to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> TemplateMetadata {
+        serde_json::from_value(serde_json::json!({
+            "name": "demo", "version": "1.0.0", "description": "", "author": "",
+            "project_type": "vue", "dependencies": [], "tags": [],
+            "variables": [
+                {"name": "useRouter", "description": "", "default": "false", "required": false, "var_type": "boolean"},
+                {"name": "port", "description": "", "default": null, "required": false, "var_type": "number"},
+                {"name": "style", "description": "", "default": null, "required": false,
+                 "var_type": {"choice": {"options": ["css", "scss"]}}},
+                {"name": "author", "description": "", "default": null, "required": true, "var_type": "string"},
+                {"name": "apiKey", "description": "", "default": null, "required": false, "var_type": "number", "sensitive": true}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_variables_coerces_and_fills_defaults() {
+        let provided = HashMap::from([
+            ("author".to_string(), "me".to_string()),
+            ("port".to_string(), " 8080 ".to_string()),
+            ("style".to_string(), "SCSS".to_string()),
+            ("extra".to_string(), "kept".to_string()),
+        ]);
+        let variables = resolve_variables(&metadata(), &provided).unwrap();
+        assert_eq!(variables["useRouter"], "false");
+        assert_eq!(variables["port"], "8080");
+        assert_eq!(variables["style"], "scss");
+        assert_eq!(variables["extra"], "kept");
+    }
+
+    #[test]
+    fn test_resolve_variables_reports_all_violations() {
+        let provided = HashMap::from([
+            ("useRouter".to_string(), "maybe".to_string()),
+            ("port".to_string(), "eighty".to_string()),
+            ("style".to_string(), "less".to_string()),
+            ("apiKey".to_string(), "sk-secret".to_string()),
+        ]);
+        let Err(GeneratorError::InvalidVariables(violations)) =
+            resolve_variables(&metadata(), &provided)
+        else {
+            panic!("expected InvalidVariables");
+        };
+        let kinds: Vec<(&str, &str)> = violations
+            .iter()
+            .map(|violation| (violation.variable.as_str(), violation.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("useRouter", "invalid-type"),
+                ("port", "invalid-type"),
+                ("style", "invalid-choice"),
+                ("author", "missing"),
+                ("apiKey", "invalid-type"),
+            ]
+        );
+        assert!(
+            violations
+                .iter()
+                .all(|violation| !violation.message.contains("sk-secret"))
+        );
+
+        let details = GeneratorError::InvalidVariables(violations).details(None, None);
+        assert_eq!(details.code, "INVALID_VARIABLES");
+        assert_eq!(details.violations.len(), 5);
+    }
+
+    #[test]
+    fn test_variables_schema() {
+        let schema = variables_schema(&metadata());
+        assert_eq!(schema["title"], "demo");
+        assert_eq!(schema["required"], serde_json::json!(["author"]));
+        let properties = &schema["properties"];
+        assert_eq!(
+            properties["useRouter"],
+            serde_json::json!({ "type": "boolean", "default": false })
+        );
+        assert_eq!(
+            properties["style"]["enum"],
+            serde_json::json!(["css", "scss"])
+        );
+        assert_eq!(properties["apiKey"]["writeOnly"], true);
+    }
+
+    #[test]
+    fn test_sensitive_variables_are_redacted() {
+        let variables = HashMap::from([
+            ("apiKey".to_string(), "sk-secret".to_string()),
+            ("port".to_string(), "8080".to_string()),
+        ]);
+        let redacted = redact_sensitive(&metadata(), &variables);
+        assert_eq!(redacted["apiKey"], REDACTED);
+        assert_eq!(redacted["port"], "8080");
+        let kept = without_sensitive(&metadata(), &variables);
+        assert_eq!(kept.keys().collect::<Vec<_>>(), vec!["port"]);
+    }
+}
@@ -0,0 +1,75 @@
+//! 版本控制辅助模块
+//! 基于 git2 检查目标目录中被跟踪文件的工作区状态
+
+use crate::{GeneratorError, Result};
+use git2::{Repository, Status};
+use std::path::{Path, PathBuf};
+
+/// 仓库 HEAD 信息
+#[derive(Debug, Clone)]
+pub struct HeadInfo {
+    /// origin 远程地址
+    pub remote_url: Option<String>,
+    /// HEAD 提交 ID
+    pub commit: String,
+}
+
+/// 读取目录所在 git 仓库的 HEAD 提交与 origin 地址，不在仓库中时返回 None
+pub fn head_info(dir: &Path) -> Option<HeadInfo> {
+    let repo = Repository::discover(dir).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?.id().to_string();
+    let remote_url = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|remote| remote.url().map(str::to_string));
+
+    Some(HeadInfo { remote_url, commit })
+}
+
+/// 返回 `candidates` 中被 git 跟踪且存在未提交修改的文件（相对 `dir`）
+///
+/// `dir` 不在 git 仓库中时返回空列表。
+pub fn dirty_tracked_files(dir: &Path, candidates: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let repo = match Repository::discover(dir) {
+        Ok(repo) => repo,
+        Err(_) => return Ok(Vec::new()),
+    };
+    let Some(workdir) = repo.workdir() else {
+        return Ok(Vec::new());
+    };
+
+    let workdir = workdir.canonicalize()?;
+    let dir = dir.canonicalize()?;
+    let prefix = dir.strip_prefix(&workdir).map_err(|e| {
+        GeneratorError::FileOperation(format!("{} is outside git workdir: {}", dir.display(), e))
+    })?;
+
+    let dirty_flags = Status::INDEX_MODIFIED
+        | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED
+        | Status::INDEX_TYPECHANGE
+        | Status::WT_MODIFIED
+        | Status::WT_DELETED
+        | Status::WT_RENAMED
+        | Status::WT_TYPECHANGE;
+
+    let mut dirty = Vec::new();
+    for candidate in candidates {
+        let repo_path = prefix.join(candidate);
+        match repo.status_file(&repo_path) {
+            Ok(status) if status.intersects(dirty_flags) => dirty.push(candidate.clone()),
+            Ok(_) => {}
+            // 未被跟踪或被忽略的文件不受保护
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {}
+            Err(e) => {
+                return Err(GeneratorError::ExternalCommand(format!(
+                    "git status failed for {}: {}",
+                    candidate.display(),
+                    e
+                )));
+            }
+        }
+    }
+
+    Ok(dirty)
+}
@@ -0,0 +1,20 @@
+//! 浏览器绑定模块
+//! `wasm` 特性下用 wasm-bindgen 导出渲染核心，供文档站点实时预览模板
+
+use crate::render::render_files;
+use crate::template_metadata::TemplateMetadata;
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen::prelude::*;
+
+/// 预览模板的渲染结果
+///
+/// 参数与返回值均为 JSON：`metadata` 为 template.json 的内容，`files` 与 `variables` 为
+/// 相对路径 → 内容、变量名 → 值的对象；返回渲染后的相对路径 → 内容。
+#[wasm_bindgen(js_name = renderPreview)]
+pub fn render_preview(metadata: &str, files: &str, variables: &str) -> Result<String, JsError> {
+    let metadata: TemplateMetadata = serde_json::from_str(metadata)?;
+    let files: BTreeMap<String, String> = serde_json::from_str(files)?;
+    let variables: HashMap<String, String> = serde_json::from_str(variables)?;
+    let rendered = render_files(&metadata, &files, &variables)?;
+    Ok(serde_json::to_string(&rendered)?)
+}
@@ -0,0 +1,151 @@
+//! 输出路径解析模块
+//! 相对输出路径基于 workspace 根目录（选项、`GENERATOR_WORKSPACE` 或当前目录）解析，展开 `~`，生成前检查可写并阻止模板路径越出输出目录
+
+use crate::{GeneratorError, Result};
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+/// 未在选项中指定 workspace 根目录时使用的环境变量
+pub const WORKSPACE_ENV: &str = "GENERATOR_WORKSPACE";
+
+/// 展开开头的 `~` 为用户主目录
+pub fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some("") => Some(""),
+        Some(rest) if rest.starts_with(['/', '\\']) => Some(&rest[1..]),
+        _ => None,
+    };
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    match (rest, home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// 相对路径的基准目录：显式配置 > `GENERATOR_WORKSPACE` > 当前目录
+pub fn workspace_root(configured: Option<&str>) -> Result<PathBuf> {
+    let configured = configured
+        .map(str::to_string)
+        .or_else(|| env::var(WORKSPACE_ENV).ok().filter(|root| !root.is_empty()));
+    let cwd = env::current_dir()?;
+    Ok(match configured {
+        Some(root) => normalize(&cwd.join(expand_home(&root))),
+        None => cwd,
+    })
+}
+
+/// 解析为绝对输出目录；未指定时为 workspace 根目录本身
+pub fn resolve_output_dir(
+    output_path: Option<&str>,
+    workspace_root: Option<&str>,
+) -> Result<PathBuf> {
+    let root = self::workspace_root(workspace_root)?;
+    let path = expand_home(output_path.unwrap_or("."));
+    Ok(normalize(&root.join(path)))
+}
+
+/// 检查目录可写；目录尚不存在时检查最近的已有上级目录
+pub fn ensure_writable(dir: &Path) -> Result<()> {
+    let existing = dir
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(dir);
+    if !existing.is_dir() {
+        return Err(GeneratorError::FileOperation(format!(
+            "Output path {} is blocked by a file at {}",
+            dir.display(),
+            existing.display()
+        )));
+    }
+    tempfile::tempfile_in(existing).map_err(|e| {
+        GeneratorError::FileOperation(format!(
+            "Output directory {} is not writable: {}",
+            existing.display(),
+            e
+        ))
+    })?;
+    Ok(())
+}
+
+/// 将模板给出的相对路径拼接到 `root` 下，绝对路径或越出 `root` 的 `..` 报错
+pub fn safe_join(root: &Path, relative: &Path) -> Result<PathBuf> {
+    let mut joined = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => joined.push(part),
+            Component::CurDir => {}
+            Component::ParentDir if joined.pop() => {}
+            _ => {
+                return Err(GeneratorError::TemplateProcessing(format!(
+                    "Path '{}' escapes the output directory",
+                    relative.display()
+                )));
+            }
+        }
+    }
+    Ok(root.join(joined))
+}
+
+/// 按字面消除 `.` 与 `..`，不访问文件系统（目标可能尚不存在）
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resolve_output_dir_against_workspace_root() {
+        let root = tempdir().unwrap();
+        let root_str = root.path().to_str().unwrap();
+
+        assert_eq!(
+            resolve_output_dir(Some("apps/../packages/./web"), Some(root_str)).unwrap(),
+            root.path().join("packages/web")
+        );
+        assert_eq!(
+            resolve_output_dir(None, Some(root_str)).unwrap(),
+            root.path()
+        );
+        let absolute = root.path().join("elsewhere");
+        assert_eq!(
+            resolve_output_dir(absolute.to_str(), Some("/ignored")).unwrap(),
+            absolute
+        );
+        if let Some(home) = env::var_os("HOME") {
+            assert_eq!(
+                expand_home("~/projects"),
+                PathBuf::from(home).join("projects")
+            );
+        }
+        assert_eq!(expand_home("~user/x"), PathBuf::from("~user/x"));
+
+        ensure_writable(&root.path().join("not/yet/created")).unwrap();
+        std::fs::write(root.path().join("file"), "").unwrap();
+        assert!(ensure_writable(&root.path().join("file/child")).is_err());
+    }
+
+    #[test]
+    fn test_safe_join_rejects_traversal() {
+        let root = Path::new("/out");
+        assert_eq!(
+            safe_join(root, Path::new("src/../lib/./mod.rs")).unwrap(),
+            root.join("lib/mod.rs")
+        );
+        assert!(safe_join(root, Path::new("../secret")).is_err());
+        assert!(safe_join(root, Path::new("src/../../secret")).is_err());
+        assert!(safe_join(root, Path::new("/etc/passwd")).is_err());
+    }
+}
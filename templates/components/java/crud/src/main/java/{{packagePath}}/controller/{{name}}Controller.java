@@ -0,0 +1,16 @@
+package {{package}}.controller;
+
+import {{package}}.service.{{name}}Service;
+import org.springframework.web.bind.annotation.RequestMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+@RequestMapping("/{{name}}")
+public class {{name}}Controller {
+
+    private final {{name}}Service service;
+
+    public {{name}}Controller({{name}}Service service) {
+        this.service = service;
+    }
+}
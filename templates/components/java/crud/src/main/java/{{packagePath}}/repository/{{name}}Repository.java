@@ -0,0 +1,7 @@
+package {{package}}.repository;
+
+import org.springframework.stereotype.Repository;
+
+@Repository
+public class {{name}}Repository {
+}
@@ -0,0 +1,14 @@
+package {{package}}.service;
+
+import {{package}}.repository.{{name}}Repository;
+import org.springframework.stereotype.Service;
+
+@Service
+public class {{name}}Service {
+
+    private final {{name}}Repository repository;
+
+    public {{name}}Service({{name}}Repository repository) {
+        this.repository = repository;
+    }
+}
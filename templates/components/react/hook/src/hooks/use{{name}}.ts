@@ -0,0 +1,7 @@
+import { useState } from 'react'
+
+export function use{{name}}() {
+  const [state, setState] = useState<unknown>(null)
+
+  return { state, setState }
+}
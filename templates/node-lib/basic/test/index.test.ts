@@ -0,0 +1,8 @@
+import { describe, expect, it } from 'vitest'
+import { greet } from '../src/index'
+
+describe('greet', () => {
+  it('greets by name', () => {
+    expect(greet('{{name}}')).toBe('Hello, {{name}}!')
+  })
+})
@@ -0,0 +1,5 @@
+from {{packageName}} import greet
+
+
+def test_greet() -> None:
+    assert greet("world") == "Hello, world!"
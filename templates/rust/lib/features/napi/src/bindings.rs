@@ -0,0 +1,7 @@
+use napi_derive::napi;
+
+/// Adds two numbers, exposed to Node.js.
+#[napi]
+pub fn add(left: u32, right: u32) -> u32 {
+    crate::add(left.into(), right.into()) as u32
+}
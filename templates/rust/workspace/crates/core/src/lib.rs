@@ -0,0 +1,16 @@
+//! Core library for {{name}}.
+
+/// Builds the greeting shown by the CLI.
+pub fn greeting(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_by_name() {
+        assert_eq!(greeting("{{name}}"), "Hello, {{name}}!");
+    }
+}